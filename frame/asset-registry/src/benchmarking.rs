@@ -18,11 +18,14 @@
 
 use super::*;
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::Get;
 use frame_system::{self, RawOrigin};
+use sp_std::vec;
 use tidefi_primitives::CurrencyId;
 
 const SEED: u32 = 0;
 const ADMIN_ID: u32 = 1;
+const ASSET_ID: u32 = 200;
 
 fn _assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
   frame_system::Pallet::<T>::assert_last_event(generic_event.into());
@@ -34,10 +37,43 @@ fn pre_set_auth<T: Config>() -> T::AccountId {
   user
 }
 
+fn max_length_asset_info<T: Config>() -> AssetInfo<Vec<u8>> {
+  AssetInfo {
+    base_chain: Some(CurrencyId::Tdfy),
+    contract_address: Some(vec![
+      0;
+      <T as pallet_assets::Config>::StringLimit::get()
+        as usize
+    ]),
+    required_confirmations: 12,
+    deposit_minimum: 1_000,
+    withdrawal_minimum: 1_000,
+    gas_refund: true,
+  }
+}
+
 benchmarks! {
    set_status {
     let user = pre_set_auth::<T>();
    }: _(RawOrigin::Signed(user), CurrencyId::Tdfy, true)
+   set_asset_info {
+    let user = pre_set_auth::<T>();
+    Pallet::<T>::register(
+      RawOrigin::Signed(user.clone()).into(),
+      CurrencyId::Wrapped(ASSET_ID),
+      "Benchmark".into(),
+      "BNC".into(),
+      8,
+      1,
+    )?;
+    let info = max_length_asset_info::<T>();
+   }: _(RawOrigin::Signed(user), CurrencyId::Wrapped(ASSET_ID), info.clone())
+   verify {
+    assert_eq!(
+      AssetInfos::<T>::get(CurrencyId::Wrapped(ASSET_ID)).map(AssetInfo::into_info),
+      Some(info)
+    );
+   }
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
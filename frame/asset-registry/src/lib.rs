@@ -24,9 +24,26 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod types;
 pub mod weights;
+pub use types::*;
 pub use weights::*;
 
+use frame_support::inherent::Vec;
+use tidefi_primitives::CurrencyId;
+
+/// Maximum number of decimals of an asset, `10^38` is the largest power of ten in a `u128` balance.
+pub const MAX_DECIMALS: u8 = 38;
+
+/// Read the on-chain properties of the listed assets.
+pub trait AssetInfoProvider {
+  /// Properties of the asset, `None` when the asset has no record.
+  fn asset_info(currency_id: CurrencyId) -> Option<AssetInfo<Vec<u8>>>;
+  /// Number of decimals of the asset, `None` when the asset isn't registered.
+  fn decimals(currency_id: CurrencyId) -> Option<u8>;
+}
+
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
@@ -50,7 +67,11 @@ pub mod pallet {
     CurrencyMetadata,
   };
 
+  /// The current storage version.
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
   type CurrenciesMetadata = (CurrencyId, CurrencyMetadata<Vec<u8>>);
+  pub type AssetInfoOf<T> = AssetInfo<BoundedVec<u8, <T as pallet_assets::Config>::StringLimit>>;
   type AssetGenesis<T> = (
     CurrencyId,
     Vec<u8>,
@@ -86,6 +107,7 @@ pub mod pallet {
 
   #[pallet::pallet]
   #[pallet::generate_store(pub (super) trait Store)]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
   /// Assets Account ID owner
@@ -93,6 +115,11 @@ pub mod pallet {
   #[pallet::getter(fn account_id)]
   pub type AssetRegistryAccountId<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
+  /// Properties of the listed assets
+  #[pallet::storage]
+  #[pallet::getter(fn asset_info)]
+  pub type AssetInfos<T: Config> = StorageMap<_, Blake2_128Concat, CurrencyId, AssetInfoOf<T>>;
+
   /// Genesis configuration
   #[pallet::genesis_config]
   pub struct GenesisConfig<T: Config> {
@@ -102,6 +129,9 @@ pub mod pallet {
     /// Assets owner
     /// Only this account can modify storage on this pallet.
     pub account: T::AccountId,
+    /// Properties of the assets created on initialization
+    /// \[currency_id, info\]
+    pub infos: Vec<(CurrencyId, AssetInfo<Vec<u8>>)>,
   }
 
   #[cfg(feature = "std")]
@@ -113,6 +143,8 @@ pub mod pallet {
         // We use pallet account ID by default,
         // but should always be set in the genesis config.
         account: T::AssetRegistryPalletId::get().into_account_truncating(),
+        // no asset properties by default
+        infos: Vec::new(),
       }
     }
   }
@@ -136,6 +168,12 @@ pub mod pallet {
             .expect("Unable to mint asset");
         }
       }
+
+      // 3. Save the assets properties
+      for (currency_id, info) in self.infos.clone() {
+        let info = Pallet::<T>::bounded_asset_info(info).expect("Invalid asset info");
+        AssetInfos::<T>::insert(currency_id, info);
+      }
     }
   }
 
@@ -146,6 +184,8 @@ pub mod pallet {
     Registered(CurrencyId),
     /// Asset was updated. \[currency_id, is_enabled\]
     StatusChanged(CurrencyId, bool),
+    /// Asset properties were updated. \[currency_id\]
+    AssetInfoUpdated(CurrencyId),
   }
 
   // Errors inform users that something went wrong.
@@ -161,6 +201,10 @@ pub mod pallet {
     AssetAlreadyRegistered,
     /// Invalid Currency Id
     CurrencyIdNotValid,
+    /// The contract address is longer than the assets string limit.
+    ContractAddressTooLong,
+    /// The decimals of the asset are above `MAX_DECIMALS`, its unit doesn't fit in a balance.
+    InvalidDecimals,
   }
 
   #[pallet::call]
@@ -170,7 +214,7 @@ pub mod pallet {
     /// - `currency_id`: The currency ID to register
     /// - `name`: Currency name. Ex: `Bitcoin`
     /// - `symbol`: Currency symbol. Ex: `BTC`
-    /// - `decimals`: Number of decimals for the asset. Ex: `8`
+    /// - `decimals`: Number of decimals for the asset, up to `MAX_DECIMALS`. Ex: `8`
     /// - `existential_deposit`: Number of token required to keep the balance alive. Ex: `1`
     ///
     /// Emits `Registered` event when successful.
@@ -198,7 +242,10 @@ pub mod pallet {
         Error::<T>::AssetAlreadyRegistered
       );
 
-      // 3. If it's a wrapped token, let's register it with pallet_assets
+      // 3. Make sure one unit of the asset fits in a balance
+      ensure!(decimals <= MAX_DECIMALS, Error::<T>::InvalidDecimals);
+
+      // 4. If it's a wrapped token, let's register it with pallet_assets
       if let CurrencyId::Wrapped(asset_id) = currency_id {
         Self::register_asset(asset_id, name, symbol, decimals, existential_deposit)?;
       }
@@ -262,6 +309,43 @@ pub mod pallet {
 
       Ok(())
     }
+
+    /// Update asset properties.
+    ///
+    /// - `currency_id`: The currency ID to update
+    /// - `info`: Base chain, contract address, required confirmations, deposit and withdrawal
+    /// minimums, and gas refund eligibility of the asset
+    ///
+    /// Emits `AssetInfoUpdated` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::call_index(2)]
+    #[pallet::weight(<T as Config>::WeightInfo::set_asset_info())]
+    pub fn set_asset_info(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      info: AssetInfo<Vec<u8>>,
+    ) -> DispatchResult {
+      // 1. Make sure it's signed from the asset-registry owner
+      ensure!(
+        Some(ensure_signed(origin)?) == Self::account_id(),
+        Error::<T>::AccessDenied
+      );
+
+      // 2. Make sure the currency is already registered
+      ensure!(
+        Self::is_currency_exist(currency_id),
+        Error::<T>::AssetNotRegistered
+      );
+
+      // 3. Save the asset properties
+      AssetInfos::<T>::insert(currency_id, Self::bounded_asset_info(info)?);
+
+      // 4. Emit asset properties update
+      Self::deposit_event(<Event<T>>::AssetInfoUpdated(currency_id));
+
+      Ok(())
+    }
   }
 
   impl<T: Config> Pallet<T> {
@@ -295,6 +379,26 @@ pub mod pallet {
       Ok(())
     }
 
+    fn bounded_asset_info(info: AssetInfo<Vec<u8>>) -> Result<AssetInfoOf<T>, DispatchError> {
+      let contract_address = info
+        .contract_address
+        .map(|contract_address| {
+          contract_address
+            .try_into()
+            .map_err(|_| Error::<T>::ContractAddressTooLong)
+        })
+        .transpose()?;
+
+      Ok(AssetInfo {
+        base_chain: info.base_chain,
+        contract_address,
+        required_confirmations: info.required_confirmations,
+        deposit_minimum: info.deposit_minimum,
+        withdrawal_minimum: info.withdrawal_minimum,
+        gas_refund: info.gas_refund,
+      })
+    }
+
     pub fn is_currency_exist(currency_id: CurrencyId) -> bool {
       match currency_id {
         // TDFY always exist
@@ -355,6 +459,13 @@ pub mod pallet {
       Ok(final_assets)
     }
 
+    /// Properties of all the listed assets, serialized for quick RPC call
+    pub fn get_asset_infos() -> Vec<(CurrencyId, AssetInfo<Vec<u8>>)> {
+      AssetInfos::<T>::iter()
+        .map(|(currency_id, info)| (currency_id, info.into_info()))
+        .collect()
+    }
+
     pub fn get_account_balances(
       account_id: &T::AccountId,
     ) -> Result<Vec<(CurrencyId, CurrencyBalance<BalanceInfo>)>, DispatchError> {
@@ -393,4 +504,19 @@ pub mod pallet {
       }
     }
   }
+
+  impl<T: Config> AssetInfoProvider for Pallet<T> {
+    fn asset_info(currency_id: CurrencyId) -> Option<AssetInfo<Vec<u8>>> {
+      AssetInfos::<T>::get(currency_id).map(AssetInfo::into_info)
+    }
+
+    fn decimals(currency_id: CurrencyId) -> Option<u8> {
+      match currency_id {
+        CurrencyId::Tdfy => Some(12),
+        CurrencyId::Wrapped(asset_id) => pallet_assets::Metadata::<T>::try_get(asset_id)
+          .ok()
+          .map(|metadata| metadata.decimals),
+      }
+    }
+  }
 }
@@ -0,0 +1,112 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use crate::types::AssetInfo;
+use frame_support::{
+  log,
+  pallet_prelude::*,
+  traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::traits::Saturating;
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+use tidefi_primitives::assets::Asset;
+
+const LOG_TARGET: &str = "tidefi::asset-registry";
+
+pub mod v1 {
+  use super::*;
+
+  /// Seed the properties of the registered assets from the compiled `Asset` enum.
+  ///
+  /// The records keep the previous behaviour, no minimums, the confirmations required by the
+  /// external chain relay only, and the gas refunds for the assets deposited from an external
+  /// chain, all but TDFY.
+  ///
+  /// The assets unknown to the enum are left without record, they have to be set with
+  /// `set_asset_info`.
+  pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+  impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+    fn on_runtime_upgrade() -> Weight {
+      let onchain_version = Pallet::<T>::on_chain_storage_version();
+      if onchain_version != 0 {
+        log::warn!(
+          target: LOG_TARGET,
+          "skipping v1 migration, on-chain storage version is {:?}",
+          onchain_version
+        );
+        return T::DbWeight::get().reads(1);
+      }
+
+      let mut reads: u64 = 1;
+      let mut writes: u64 = 0;
+
+      let currencies = sp_std::iter::once(CurrencyId::Tdfy)
+        .chain(pallet_assets::Metadata::<T>::iter_keys().map(CurrencyId::Wrapped));
+      for currency_id in currencies {
+        reads.saturating_inc();
+        let asset: Asset = match currency_id.try_into() {
+          Ok(asset) => asset,
+          Err(_) => continue,
+        };
+        AssetInfos::<T>::insert(
+          currency_id,
+          AssetInfo {
+            base_chain: asset
+              .base_chain()
+              .map(|base_chain| base_chain.currency_id()),
+            contract_address: None,
+            required_confirmations: 0,
+            deposit_minimum: 0,
+            withdrawal_minimum: 0,
+            gas_refund: currency_id != CurrencyId::Tdfy,
+          },
+        );
+        writes.saturating_inc();
+      }
+
+      StorageVersion::new(1).put::<Pallet<T>>();
+      writes.saturating_inc();
+
+      log::info!(target: LOG_TARGET, "migrated asset registry to v1");
+
+      T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+      ensure!(
+        AssetInfos::<T>::iter_keys().next().is_none(),
+        "asset infos must be empty before v1"
+      );
+      Ok(Vec::new())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+      ensure!(
+        AssetInfos::<T>::contains_key(CurrencyId::Tdfy),
+        "TDFY must have a record"
+      );
+      ensure!(
+        Pallet::<T>::on_chain_storage_version() == 1,
+        "storage version must be 1"
+      );
+      Ok(())
+    }
+  }
+}
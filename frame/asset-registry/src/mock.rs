@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::pallet as pallet_asset_registry;
+use crate::{pallet as pallet_asset_registry, types::AssetInfo};
 use frame_support::{
   traits::{AsEnsureOriginWithArg, EnsureOrigin},
   PalletId,
@@ -25,6 +25,9 @@ use sp_runtime::traits::AccountIdConversion;
 use system::RawOrigin;
 use tidefi_primitives::CurrencyId;
 
+pub const BTC: CurrencyId = CurrencyId::Wrapped(100);
+pub const ETH: CurrencyId = CurrencyId::Wrapped(1000);
+
 construct_mock_runtime!({
   Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
   AssetRegistry: pallet_asset_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
@@ -112,22 +115,21 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
   pallet_asset_registry::GenesisConfig::<Test> {
     assets: vec![
-      (
-        CurrencyId::Wrapped(100),
-        "Bitcoin".into(),
-        "BTC".into(),
-        8,
-        Vec::new(),
-      ),
-      (
-        CurrencyId::Wrapped(1000),
-        "Ethereum".into(),
-        "ETH".into(),
-        18,
-        Vec::new(),
-      ),
+      (BTC, "Bitcoin".into(), "BTC".into(), 8, Vec::new()),
+      (ETH, "Ethereum".into(), "ETH".into(), 18, Vec::new()),
     ],
     account: 0_u64.into(),
+    infos: vec![(
+      BTC,
+      AssetInfo {
+        base_chain: None,
+        contract_address: None,
+        required_confirmations: 3,
+        deposit_minimum: 10_000,
+        withdrawal_minimum: 50_000,
+        gas_refund: true,
+      },
+    )],
   }
   .assimilate_storage(&mut t)
   .unwrap();
@@ -14,7 +14,29 @@
 // You should have received a copy of the GNU General Public License
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::mock::{new_test_ext, AssetRegistry};
+use crate::{
+  mock::{
+    new_test_ext, AssetRegistry, RuntimeEvent as MockEvent, RuntimeOrigin, System, Test, BTC, ETH,
+  },
+  types::AssetInfo,
+  AssetInfoProvider, Error, Event, MAX_DECIMALS,
+};
+use frame_support::{assert_noop, assert_ok};
+use tidefi_primitives::CurrencyId;
+
+const OWNER_ACCOUNT_ID: u64 = 0;
+const ALICE_ACCOUNT_ID: u64 = 1;
+
+fn usdt_info() -> AssetInfo<Vec<u8>> {
+  AssetInfo {
+    base_chain: Some(ETH),
+    contract_address: Some(b"0xdac17f958d2ee523a2206206994597c13d831ec7".to_vec()),
+    required_confirmations: 12,
+    deposit_minimum: 1_000_000,
+    withdrawal_minimum: 5_000_000,
+    gas_refund: false,
+  }
+}
 
 #[test]
 pub fn check_genesis_config() {
@@ -25,6 +47,108 @@ pub fn check_genesis_config() {
         .expect("Unable to get results")
         .len(),
       3
-    )
+    );
+
+    // only BTC has properties
+    assert_eq!(AssetRegistry::get_asset_infos().len(), 1);
+    assert_eq!(
+      <AssetRegistry as AssetInfoProvider>::asset_info(BTC).map(|info| info.withdrawal_minimum),
+      Some(50_000)
+    );
+  });
+}
+
+#[test]
+pub fn decimals_are_read_from_the_registry() {
+  new_test_ext().execute_with(|| {
+    assert_eq!(AssetRegistry::decimals(CurrencyId::Tdfy), Some(12));
+    assert_eq!(AssetRegistry::decimals(BTC), Some(8));
+    assert_eq!(AssetRegistry::decimals(ETH), Some(18));
+    assert_eq!(AssetRegistry::decimals(CurrencyId::Wrapped(4)), None);
   });
 }
+
+mod register {
+  use super::*;
+
+  #[test]
+  fn fails_when_decimals_are_too_large() {
+    new_test_ext().execute_with(|| {
+      assert_noop!(
+        AssetRegistry::register(
+          RuntimeOrigin::signed(OWNER_ACCOUNT_ID),
+          CurrencyId::Wrapped(4),
+          b"Too Many Decimals".to_vec(),
+          b"TMD".to_vec(),
+          MAX_DECIMALS + 1,
+          1
+        ),
+        Error::<Test>::InvalidDecimals
+      );
+    });
+  }
+}
+
+mod set_asset_info {
+  use super::*;
+
+  #[test]
+  fn succeed() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+
+      assert_ok!(AssetRegistry::set_asset_info(
+        RuntimeOrigin::signed(OWNER_ACCOUNT_ID),
+        ETH,
+        usdt_info(),
+      ));
+
+      assert_eq!(
+        AssetRegistry::asset_info(ETH).map(AssetInfo::into_info),
+        Some(usdt_info())
+      );
+      System::assert_last_event(MockEvent::AssetRegistry(Event::AssetInfoUpdated(ETH)));
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_called_by_the_owner() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          AssetRegistry::set_asset_info(RuntimeOrigin::signed(ALICE_ACCOUNT_ID), ETH, usdt_info()),
+          Error::<Test>::AccessDenied
+        );
+      });
+    }
+
+    #[test]
+    fn asset_is_not_registered() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          AssetRegistry::set_asset_info(
+            RuntimeOrigin::signed(OWNER_ACCOUNT_ID),
+            CurrencyId::Wrapped(4),
+            usdt_info()
+          ),
+          Error::<Test>::AssetNotRegistered
+        );
+      });
+    }
+
+    #[test]
+    fn contract_address_is_too_long() {
+      new_test_ext().execute_with(|| {
+        let mut info = usdt_info();
+        info.contract_address = Some(vec![0; 51]);
+
+        assert_noop!(
+          AssetRegistry::set_asset_info(RuntimeOrigin::signed(OWNER_ACCOUNT_ID), ETH, info),
+          Error::<Test>::ContractAddressTooLong
+        );
+      });
+    }
+  }
+}
@@ -0,0 +1,60 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! On-chain asset records.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{inherent::Vec, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use tidefi_primitives::{Balance, CurrencyId};
+
+/// Properties of a listed asset, used by the quorum, sunrise and oracle pallets.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AssetInfo<Address> {
+  /// Currency paying the gas of the asset on its external chain, for example `ETH` for `USDT`,
+  /// `None` when the asset is the native currency of its chain
+  pub base_chain: Option<CurrencyId>,
+  /// Contract address or token id of the asset on its external chain
+  pub contract_address: Option<Address>,
+  /// Number of external chain confirmations required before a deposit is minted, checked with
+  /// the inclusion proof of the deposit, the external chain relay minimum is always required
+  pub required_confirmations: u32,
+  /// Minimum amount of a deposit to be minted
+  pub deposit_minimum: Balance,
+  /// Minimum amount of a withdrawal to be burned
+  pub withdrawal_minimum: Balance,
+  /// The gas paid for the deposits of this asset can be refunded by sunrise
+  pub gas_refund: bool,
+}
+
+impl<Address: AsRef<[u8]>> AssetInfo<Address> {
+  /// Convert the contract address, serialized for quick RPC call
+  pub fn into_info(self) -> AssetInfo<Vec<u8>> {
+    AssetInfo {
+      base_chain: self.base_chain,
+      contract_address: self
+        .contract_address
+        .map(|contract_address| contract_address.as_ref().to_vec()),
+      required_confirmations: self.required_confirmations,
+      deposit_minimum: self.deposit_minimum,
+      withdrawal_minimum: self.withdrawal_minimum,
+      gas_refund: self.gas_refund,
+    }
+  }
+}
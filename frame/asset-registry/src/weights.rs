@@ -24,6 +24,7 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for `pallet_tidefi`.
 pub trait WeightInfo {
    fn set_status() -> Weight;
+   fn set_asset_info() -> Weight;
 }
 
 /// Weights for `pallet_tidefi` using the Substrate node and recommended hardware.
@@ -34,4 +35,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
           .saturating_add(T::DbWeight::get().reads(6_u64))
           .saturating_add(T::DbWeight::get().writes(5_u64))
   }
+   fn set_asset_info() -> Weight {
+      Weight::from_ref_time(24_000_000)
+          .saturating_add(T::DbWeight::get().reads(2_u64))
+          .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
 }
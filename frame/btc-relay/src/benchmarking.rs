@@ -0,0 +1,42 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use crate::{fixtures::*, Call, Config, Pallet};
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, BenchmarkError};
+use frame_support::{
+  assert_ok,
+  traits::{EnsureOrigin, UnfilteredDispatchable},
+};
+use frame_system::RawOrigin;
+use sp_std::vec;
+
+benchmarks! {
+   where_clause { where T: pallet_timestamp::Config }
+
+   initialize {
+   }: _(RawOrigin::Root, MAINNET_GENESIS, 0)
+   submit_block_header {
+      let origin = T::RelayerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+      // 2023-01-01, the recorded headers are not in the future
+      pallet_timestamp::Now::<T>::put(T::Moment::from(1_672_531_u32) * T::Moment::from(1_000_000_u32));
+      assert_ok!(Pallet::<T>::initialize(RawOrigin::Root.into(), MAINNET_GENESIS, 0));
+      let call = Call::<T>::submit_block_headers { headers: vec![MAINNET_BLOCK_1] };
+   }: { call.dispatch_bypass_filter(origin)? }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
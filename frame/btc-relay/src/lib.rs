@@ -0,0 +1,591 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+mod fixtures;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod types;
+pub use types::*;
+
+pub mod weights;
+pub use weights::*;
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+  use super::*;
+  use frame_support::{pallet_prelude::*, traits::UnixTime};
+  use frame_system::pallet_prelude::*;
+  use sp_core::H256;
+  use sp_std::vec::Vec;
+
+  /// Bitcoin relay configuration
+  #[pallet::config]
+  pub trait Config: frame_system::Config {
+    /// Events
+    type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+    /// Weights
+    type WeightInfo: WeightInfo;
+
+    /// Bitcoin network followed by the relay
+    #[pallet::constant]
+    type Network: Get<Network>;
+
+    /// Number of blocks (including the block itself) required in the main chain
+    /// before a transaction is considered final
+    #[pallet::constant]
+    type ConfirmationDepth: Get<u32>;
+
+    /// Maximum number of blocks the main chain can be reorganized
+    #[pallet::constant]
+    type MaxReorgDepth: Get<u32>;
+
+    /// Maximum number of headers submitted in a single extrinsic
+    #[pallet::constant]
+    type MaxHeadersPerSubmission: Get<u32>;
+
+    /// Number of blocks kept below the best block, the deeper headers are pruned
+    #[pallet::constant]
+    type PruningDepth: Get<u32>;
+
+    /// Current time, used to reject the block headers too far in the future
+    type UnixTime: UnixTime;
+
+    /// The origin which may initialize the relay
+    type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// The origin which may submit block headers
+    type RelayerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+  }
+
+  #[pallet::pallet]
+  #[pallet::generate_store(pub (super) trait Store)]
+  pub struct Pallet<T>(_);
+
+  /// All block headers known by the relay, including forks
+  #[pallet::storage]
+  #[pallet::getter(fn block_headers)]
+  pub type BlockHeaders<T: Config> = StorageMap<_, Identity, H256, StoredBlockHeader>;
+
+  /// Block hash of the main chain for each height
+  #[pallet::storage]
+  #[pallet::getter(fn main_chain)]
+  pub type MainChain<T: Config> = StorageMap<_, Twox64Concat, u32, H256>;
+
+  /// Tip of the chain with the most accumulated work
+  #[pallet::storage]
+  #[pallet::getter(fn best_block)]
+  pub type BestBlock<T: Config> = StorageValue<_, H256>;
+
+  /// Height of the best block
+  #[pallet::storage]
+  #[pallet::getter(fn best_block_height)]
+  pub type BestBlockHeight<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// Hashes of all block headers known by the relay for each height, used to prune the forks
+  #[pallet::storage]
+  pub type HeadersByHeight<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, u32, Identity, H256, (), OptionQuery>;
+
+  /// Height of the oldest block header kept by the relay
+  #[pallet::storage]
+  #[pallet::getter(fn lowest_height)]
+  pub type LowestHeight<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// Height and merkle root of the pruned blocks of the main chain, the transactions included in
+  /// these blocks can still be verified
+  #[pallet::storage]
+  #[pallet::getter(fn pruned_merkle_roots)]
+  pub type PrunedMerkleRoots<T: Config> = StorageMap<_, Identity, H256, (u32, H256)>;
+
+  #[pallet::event]
+  #[pallet::generate_deposit(pub (super) fn deposit_event)]
+  pub enum Event<T: Config> {
+    /// The relay has been initialized with a starting block
+    Initialized { block_hash: H256, height: u32 },
+    /// A new block header has been stored
+    BlockHeaderStored { block_hash: H256, height: u32 },
+    /// The main chain switched to a fork with more accumulated work
+    ChainReorganized {
+      best_block_hash: H256,
+      best_block_height: u32,
+      fork_height: u32,
+    },
+  }
+
+  // Errors inform users that something went wrong.
+  #[pallet::error]
+  pub enum Error<T> {
+    /// The relay is already initialized
+    AlreadyInitialized,
+    /// The relay is not initialized
+    NotInitialized,
+    /// The starting block must be the first block of a difficulty period
+    InvalidStartHeight,
+    /// Too many headers submitted at once
+    TooManyHeaders,
+    /// The block header is already stored
+    DuplicateBlockHeader,
+    /// The parent block is unknown
+    UnknownParentBlock,
+    /// The block hash is above the target
+    InvalidProofOfWork,
+    /// The block target doesn't match the network difficulty
+    InvalidDifficulty,
+    /// The fork is deeper than the maximum reorganization depth
+    ForkTooDeep,
+    /// The block is unknown
+    UnknownBlock,
+    /// The block is not part of the main chain
+    BlockNotInMainChain,
+    /// The block doesn't have enough confirmations yet
+    InsufficientConfirmations,
+    /// The transaction id can't be decoded
+    InvalidTransactionId,
+    /// The block timestamp is not above the median time of the previous blocks
+    TimestampTooOld,
+    /// The block timestamp is too far in the future
+    TimestampInFuture,
+    /// The merkle proof doesn't match the block merkle root
+    InvalidMerkleProof,
+    /// The transaction can't be decoded
+    InvalidTransaction,
+    /// The transaction doesn't match the transaction id
+    TransactionIdMismatch,
+    /// The transaction output doesn't exist
+    UnknownOutput,
+    /// The transaction output doesn't pay the expected amount to the expected script
+    OutputMismatch,
+  }
+
+  #[pallet::hooks]
+  impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+    fn integrity_test() {
+      // the parent of a fork and the blocks used for its median time past must be kept
+      assert!(
+        T::PruningDepth::get() >= T::MaxReorgDepth::get().saturating_add(MEDIAN_TIME_SPAN),
+        "The pruning depth must cover the maximum reorganization depth and the median time span"
+      );
+      // the pruned blocks of the main chain are considered final
+      assert!(
+        T::PruningDepth::get() >= T::ConfirmationDepth::get(),
+        "The pruning depth must cover the confirmation depth"
+      );
+    }
+  }
+
+  #[pallet::call]
+  impl<T: Config> Pallet<T> {
+    /// Initialize the relay with a trusted starting block.
+    ///
+    /// - `header`: Serialized block header
+    /// - `height`: Block height, must be the first block of a difficulty period
+    ///
+    /// Emits `Initialized` event when successful.
+    #[pallet::call_index(0)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::initialize())]
+    pub fn initialize(
+      origin: OriginFor<T>,
+      header: RawBlockHeader,
+      height: u32,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is coming from the force origin
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure the relay is not already initialized
+      ensure!(!Self::is_initialized(), Error::<T>::AlreadyInitialized);
+
+      // 3. We need the first block of the period to validate the next retarget
+      ensure!(
+        height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0,
+        Error::<T>::InvalidStartHeight
+      );
+
+      let header = BlockHeader::parse(&header);
+      ensure!(
+        header.has_valid_proof_of_work(T::Network::get()),
+        Error::<T>::InvalidProofOfWork
+      );
+
+      // 4. Store the starting block
+      let block_hash = header.hash();
+      BlockHeaders::<T>::insert(
+        block_hash,
+        StoredBlockHeader {
+          height,
+          chain_work: block_work(header.bits),
+          period_start_time: header.time,
+          last_regular_bits: header.bits,
+          header,
+        },
+      );
+      HeadersByHeight::<T>::insert(height, block_hash, ());
+      MainChain::<T>::insert(height, block_hash);
+      BestBlock::<T>::put(block_hash);
+      BestBlockHeight::<T>::put(height);
+      LowestHeight::<T>::put(height);
+
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::Initialized { block_hash, height });
+
+      Ok(().into())
+    }
+
+    /// Submit new block headers to the relay.
+    ///
+    /// Headers must be ordered, each parent must be already known by the relay. The headers
+    /// deeper than the pruning depth are removed once the best block moves forward.
+    ///
+    /// - `headers`: Serialized block headers
+    ///
+    /// Emits `BlockHeaderStored` event for each header and `ChainReorganized` when the
+    /// main chain switched to a fork.
+    ///
+    /// Weight: `O(n)` where `n` is the number of headers
+    #[pallet::call_index(1)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_block_header().saturating_mul(headers.len() as u64))]
+    pub fn submit_block_headers(
+      origin: OriginFor<T>,
+      headers: Vec<RawBlockHeader>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is coming from a relayer
+      T::RelayerOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure the relay is initialized
+      ensure!(Self::is_initialized(), Error::<T>::NotInitialized);
+
+      // 3. Make sure the batch is not too large
+      ensure!(
+        headers.len() as u32 <= T::MaxHeadersPerSubmission::get(),
+        Error::<T>::TooManyHeaders
+      );
+
+      // 4. Validate and store all headers
+      for header in headers.iter() {
+        Self::store_block_header(BlockHeader::parse(header))?;
+      }
+
+      Ok(().into())
+    }
+  }
+
+  // helper functions (not dispatchable)
+  impl<T: Config> Pallet<T> {
+    /// The relay has a starting block and is following the chain.
+    pub fn is_initialized() -> bool {
+      BestBlock::<T>::exists()
+    }
+
+    /// Number of confirmations of a block in the main chain.
+    pub fn confirmations(block_hash: H256) -> Option<u32> {
+      let stored_header = Self::block_headers(block_hash)?;
+      if Self::main_chain(stored_header.height) != Some(block_hash) {
+        return None;
+      }
+      Some(
+        Self::best_block_height()
+          .saturating_sub(stored_header.height)
+          .saturating_add(1),
+      )
+    }
+
+    /// Make sure the transaction is included in a block of the main chain with
+    /// enough confirmations.
+    ///
+    /// - `transaction_id`: Transaction id as displayed by the explorers
+    /// - `proof`: Merkle proof of the transaction inclusion
+    /// - `required_confirmations`: Confirmations required for the asset, the `ConfirmationDepth`
+    ///   is always required
+    pub fn verify_transaction_inclusion(
+      transaction_id: &[u8],
+      proof: &MerkleProof,
+      required_confirmations: u32,
+    ) -> Result<(), DispatchError> {
+      let transaction_id =
+        parse_transaction_id(transaction_id).ok_or(Error::<T>::InvalidTransactionId)?;
+
+      let (merkle_root, confirmations) = match Self::block_headers(proof.block_hash) {
+        Some(stored_header) => (
+          stored_header.header.merkle_root,
+          Self::confirmations(proof.block_hash).ok_or(Error::<T>::BlockNotInMainChain)?,
+        ),
+        // the pruned blocks are part of the main chain
+        None => {
+          let (height, merkle_root) =
+            Self::pruned_merkle_roots(proof.block_hash).ok_or(Error::<T>::UnknownBlock)?;
+          (
+            merkle_root,
+            Self::best_block_height()
+              .saturating_sub(height)
+              .saturating_add(1),
+          )
+        }
+      };
+      ensure!(
+        confirmations >= T::ConfirmationDepth::get().max(required_confirmations),
+        Error::<T>::InsufficientConfirmations
+      );
+
+      ensure!(
+        proof.compute_merkle_root(transaction_id) == Some(merkle_root),
+        Error::<T>::InvalidMerkleProof
+      );
+
+      Ok(())
+    }
+
+    /// Make sure the deposit transaction is included in the main chain and its output
+    /// pays `amount` to `script_pubkey`.
+    ///
+    /// - `transaction_id`: Transaction id as displayed by the explorers
+    /// - `proof`: Deposit transaction and its inclusion proof
+    /// - `amount`: Expected amount, in satoshis
+    /// - `script_pubkey`: Expected locking script of the deposit output
+    /// - `required_confirmations`: Confirmations required for the asset, the `ConfirmationDepth`
+    ///   is always required
+    pub fn verify_deposit(
+      transaction_id: &[u8],
+      proof: &DepositProof,
+      amount: u64,
+      script_pubkey: &[u8],
+      required_confirmations: u32,
+    ) -> Result<(), DispatchError> {
+      // 1. Make sure the transaction match the transaction id
+      let transaction =
+        Transaction::parse(&proof.raw_transaction).ok_or(Error::<T>::InvalidTransaction)?;
+      ensure!(
+        Some(transaction.transaction_id) == parse_transaction_id(transaction_id),
+        Error::<T>::TransactionIdMismatch
+      );
+
+      // 2. Make sure the transaction is included in the main chain
+      Self::verify_transaction_inclusion(
+        transaction_id,
+        &proof.merkle_proof,
+        required_confirmations,
+      )?;
+
+      // 3. Make sure the output pays the deposit
+      let output = transaction
+        .outputs
+        .get(proof.output_index as usize)
+        .ok_or(Error::<T>::UnknownOutput)?;
+      ensure!(
+        output.value == amount && output.script_pubkey == script_pubkey,
+        Error::<T>::OutputMismatch
+      );
+
+      Ok(())
+    }
+
+    /// Median timestamp of the block and its ancestors, up to `MEDIAN_TIME_SPAN` blocks.
+    pub fn median_time_past(block_hash: H256) -> u32 {
+      let mut times = Vec::with_capacity(MEDIAN_TIME_SPAN as usize);
+      let mut block_hash = block_hash;
+      while times.len() < MEDIAN_TIME_SPAN as usize {
+        match Self::block_headers(block_hash) {
+          Some(stored_header) => {
+            times.push(stored_header.header.time);
+            block_hash = stored_header.header.prev_block_hash;
+          }
+          // the relay starting block has no known ancestors
+          None => break,
+        }
+      }
+      times.sort_unstable();
+      times.get(times.len() / 2).copied().unwrap_or_default()
+    }
+
+    /// Bits required for the block following `parent`.
+    pub fn expected_bits(parent: &StoredBlockHeader, header: &BlockHeader) -> u32 {
+      let network = T::Network::get();
+      let height = parent.height.saturating_add(1);
+
+      match network {
+        Network::Regtest => parent.header.bits,
+        _ if height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0 => retarget_bits(
+          network,
+          parent.period_start_time,
+          parent.header.time,
+          parent.header.bits,
+        ),
+        Network::Testnet if header.time > parent.header.time.saturating_add(TARGET_SPACING * 2) => {
+          network.pow_limit_bits()
+        }
+        Network::Testnet => parent.last_regular_bits,
+        Network::Mainnet => parent.header.bits,
+      }
+    }
+
+    // Validate a block header and store it, the main chain is updated if the header
+    // is the new tip with the most accumulated work
+    fn store_block_header(header: BlockHeader) -> Result<(), DispatchError> {
+      let network = T::Network::get();
+      let block_hash = header.hash();
+
+      // 1. Make sure we do not know this block yet
+      ensure!(
+        !BlockHeaders::<T>::contains_key(block_hash),
+        Error::<T>::DuplicateBlockHeader
+      );
+
+      // 2. The parent must be known and not too deep
+      let parent =
+        Self::block_headers(header.prev_block_hash).ok_or(Error::<T>::UnknownParentBlock)?;
+      ensure!(
+        parent.height.saturating_add(T::MaxReorgDepth::get()) >= Self::best_block_height(),
+        Error::<T>::ForkTooDeep
+      );
+
+      // 3. Validate the proof of work and difficulty
+      ensure!(
+        header.has_valid_proof_of_work(network),
+        Error::<T>::InvalidProofOfWork
+      );
+      ensure!(
+        header.bits == Self::expected_bits(&parent, &header),
+        Error::<T>::InvalidDifficulty
+      );
+
+      // 4. Validate the timestamp, above the median time past and not too far in the future
+      ensure!(
+        header.time > Self::median_time_past(header.prev_block_hash),
+        Error::<T>::TimestampTooOld
+      );
+      ensure!(
+        u64::from(header.time)
+          <= T::UnixTime::now()
+            .as_secs()
+            .saturating_add(MAX_FUTURE_BLOCK_TIME.into()),
+        Error::<T>::TimestampInFuture
+      );
+
+      // 5. Store the header
+      let height = parent.height.saturating_add(1);
+      let is_period_start = height % DIFFICULTY_ADJUSTMENT_INTERVAL == 0;
+      let stored_header = StoredBlockHeader {
+        height,
+        chain_work: parent.chain_work.saturating_add(block_work(header.bits)),
+        period_start_time: if is_period_start {
+          header.time
+        } else {
+          parent.period_start_time
+        },
+        last_regular_bits: if is_period_start || header.bits != network.pow_limit_bits() {
+          header.bits
+        } else {
+          parent.last_regular_bits
+        },
+        header,
+      };
+      BlockHeaders::<T>::insert(block_hash, stored_header.clone());
+      HeadersByHeight::<T>::insert(height, block_hash, ());
+
+      Self::deposit_event(Event::<T>::BlockHeaderStored { block_hash, height });
+
+      // 6. Update the main chain if needed
+      let best_block = Self::best_block()
+        .and_then(Self::block_headers)
+        .ok_or(Error::<T>::NotInitialized)?;
+      if stored_header.chain_work > best_block.chain_work {
+        Self::update_main_chain(block_hash, &stored_header)?;
+      }
+
+      Ok(())
+    }
+
+    // Set the new best block and update the main chain index up to the fork point
+    fn update_main_chain(
+      best_block_hash: H256,
+      best_block: &StoredBlockHeader,
+    ) -> Result<(), DispatchError> {
+      let previous_best_height = Self::best_block_height();
+      let mut block_hash = best_block_hash;
+      let mut stored_header = best_block.clone();
+      let mut depth = 0_u32;
+
+      // 1. Walk back until we reach a block of the current main chain
+      while Self::main_chain(stored_header.height) != Some(block_hash) {
+        ensure!(depth <= T::MaxReorgDepth::get(), Error::<T>::ForkTooDeep);
+        MainChain::<T>::insert(stored_header.height, block_hash);
+        block_hash = stored_header.header.prev_block_hash;
+        stored_header = Self::block_headers(block_hash).ok_or(Error::<T>::UnknownParentBlock)?;
+        depth = depth.saturating_add(1);
+      }
+
+      // 2. Remove the stale blocks above the new tip
+      for height in best_block.height.saturating_add(1)..=previous_best_height {
+        MainChain::<T>::remove(height);
+      }
+
+      BestBlock::<T>::put(best_block_hash);
+      BestBlockHeight::<T>::put(best_block.height);
+      Self::prune_block_headers(best_block.height);
+
+      // 3. More than one block replaced in the main chain, this is a reorganization
+      let fork_height = stored_header.height;
+      if fork_height < previous_best_height {
+        Self::deposit_event(Event::<T>::ChainReorganized {
+          best_block_hash,
+          best_block_height: best_block.height,
+          fork_height,
+        });
+      }
+
+      Ok(())
+    }
+
+    // Remove the block headers deeper than the pruning depth, including the forks, they
+    // can't be part of a reorganization anymore. The merkle root of the main chain blocks is
+    // kept to verify the transactions proven late.
+    fn prune_block_headers(best_block_height: u32) {
+      let prune_below = best_block_height.saturating_sub(T::PruningDepth::get());
+      let lowest_height = Self::lowest_height();
+      if lowest_height >= prune_below {
+        return;
+      }
+
+      for height in lowest_height..prune_below {
+        let main_chain_hash = MainChain::<T>::take(height);
+        for (block_hash, _) in HeadersByHeight::<T>::drain_prefix(height) {
+          if let Some(stored_header) = BlockHeaders::<T>::take(block_hash) {
+            if main_chain_hash == Some(block_hash) {
+              PrunedMerkleRoots::<T>::insert(
+                block_hash,
+                (stored_header.height, stored_header.header.merkle_root),
+              );
+            }
+          }
+        }
+      }
+      LowestHeight::<T>::put(prune_below);
+    }
+  }
+}
@@ -0,0 +1,95 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{pallet as pallet_btc_relay, Network};
+use frame_support::{ord_parameter_types, traits::AsEnsureOriginWithArg};
+use frame_system as system;
+use frame_utils::construct_mock_runtime;
+use system::{EnsureRoot, EnsureSignedBy};
+use tidefi_primitives::CurrencyId;
+
+construct_mock_runtime!({
+  BtcRelay: pallet_btc_relay::{Pallet, Call, Storage, Event<T>},
+  Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
+}, {
+  pub const AssetDeposit: u64 = 1;
+  pub const ApprovalDeposit: u64 = 1;
+  pub const StringLimit: u32 = 50;
+  pub const MetadataDepositBase: u64 = 1;
+  pub const MetadataDepositPerByte: u64 = 1;
+  pub static BitcoinNetwork: Network = Network::Mainnet;
+  pub const ConfirmationDepth: u32 = 2;
+  pub const MaxReorgDepth: u32 = 3;
+  pub const MaxHeadersPerSubmission: u32 = 10;
+  pub const PruningDepth: u32 = 14;
+});
+
+ord_parameter_types! {
+  pub const Relayer: AccountId = AccountId(1);
+}
+
+/// 2023-01-01, after all the recorded and mined block headers
+pub const NOW_IN_MILLIS: u64 = 1_672_531_200_000;
+
+impl pallet_assets::Config for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type Balance = Balance;
+  type AssetId = u32;
+  type AssetIdParameter = u32;
+  type Currency = Balances;
+  type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+  type ForceOrigin = EnsureRoot<AccountId>;
+  type AssetDeposit = AssetDeposit;
+  type AssetAccountDeposit = AssetDeposit;
+  type MetadataDepositBase = MetadataDepositBase;
+  type MetadataDepositPerByte = MetadataDepositPerByte;
+  type ApprovalDeposit = ApprovalDeposit;
+  type StringLimit = StringLimit;
+  type Freezer = ();
+  type WeightInfo = ();
+  type CallbackHandle = ();
+  type Extra = ();
+  type RemoveItemsLimit = ConstU32<5>;
+  #[cfg(feature = "runtime-benchmarks")]
+  type BenchmarkHelper = ();
+}
+
+impl pallet_btc_relay::Config for Test {
+  type RuntimeEvent = RuntimeEvent;
+  type WeightInfo = crate::weights::SubstrateWeight<Test>;
+  type Network = BitcoinNetwork;
+  type ConfirmationDepth = ConfirmationDepth;
+  type MaxReorgDepth = MaxReorgDepth;
+  type MaxHeadersPerSubmission = MaxHeadersPerSubmission;
+  type PruningDepth = PruningDepth;
+  type UnixTime = Timestamp;
+  type ForceOrigin = EnsureRoot<AccountId>;
+  type RelayerOrigin = EnsureSignedBy<Relayer, AccountId>;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+  let t = system::GenesisConfig::default()
+    .build_storage::<Test>()
+    .unwrap();
+
+  let mut ext = sp_io::TestExternalities::new(t);
+  ext.execute_with(|| {
+    System::set_block_number(1);
+    Timestamp::set_timestamp(NOW_IN_MILLIS);
+  });
+  ext
+}
@@ -0,0 +1,940 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+  fixtures::*,
+  mock::{
+    new_test_ext, AccountId, BitcoinNetwork, BtcRelay, PruningDepth, RuntimeEvent as MockEvent,
+    RuntimeOrigin, System, Test, NOW_IN_MILLIS,
+  },
+  pallet::*,
+  types::*,
+};
+use frame_support::{assert_noop, assert_ok, error::BadOrigin, traits::Get, BoundedVec};
+use sp_core::H256;
+
+const ALICE_ACCOUNT_ID: AccountId = AccountId(1);
+const BOB_ACCOUNT_ID: AccountId = AccountId(2);
+const REGTEST_BITS: u32 = 0x207f_ffff;
+const REGTEST_GENESIS_TIME: u32 = 1_296_688_602;
+
+// Mine a regtest block on top of `parent`
+fn mine_block(parent: &RawBlockHeader, merkle_root: H256) -> RawBlockHeader {
+  mine_block_at(
+    parent,
+    merkle_root,
+    BlockHeader::parse(parent).time + TARGET_SPACING,
+  )
+}
+
+// Mine a regtest block on top of `parent` with the timestamp `time`
+fn mine_block_at(parent: &RawBlockHeader, merkle_root: H256, time: u32) -> RawBlockHeader {
+  let parent = BlockHeader::parse(parent);
+  let mut header = BlockHeader {
+    version: 0x2000_0000,
+    prev_block_hash: parent.hash(),
+    merkle_root,
+    time,
+    bits: REGTEST_BITS,
+    nonce: 0,
+  };
+  while !header.has_valid_proof_of_work(Network::Regtest) {
+    header.nonce += 1;
+  }
+  header.serialize()
+}
+
+// Mine `length` regtest blocks on top of `parent`, `seed` is used to create distinct forks
+fn mine_chain(parent: &RawBlockHeader, length: u8, seed: u8) -> Vec<RawBlockHeader> {
+  let mut chain: Vec<RawBlockHeader> = Vec::new();
+  for i in 0..length {
+    let previous = *chain.last().unwrap_or(parent);
+    chain.push(mine_block(
+      &previous,
+      H256::repeat_byte(seed.wrapping_add(i)),
+    ));
+  }
+  chain
+}
+
+fn regtest_genesis() -> RawBlockHeader {
+  let mut header = BlockHeader {
+    version: 1,
+    prev_block_hash: H256::zero(),
+    merkle_root: H256::zero(),
+    time: REGTEST_GENESIS_TIME,
+    bits: REGTEST_BITS,
+    nonce: 0,
+  };
+  while !header.has_valid_proof_of_work(Network::Regtest) {
+    header.nonce += 1;
+  }
+  header.serialize()
+}
+
+fn hash_of(header: &RawBlockHeader) -> H256 {
+  BlockHeader::parse(header).hash()
+}
+
+fn initialize_mainnet() {
+  BitcoinNetwork::set(Network::Mainnet);
+  assert_ok!(BtcRelay::initialize(
+    RuntimeOrigin::root(),
+    MAINNET_GENESIS,
+    0
+  ));
+}
+
+fn initialize_regtest() -> RawBlockHeader {
+  BitcoinNetwork::set(Network::Regtest);
+  let genesis = regtest_genesis();
+  assert_ok!(BtcRelay::initialize(RuntimeOrigin::root(), genesis, 0));
+  genesis
+}
+
+fn submit(headers: Vec<RawBlockHeader>) -> frame_support::dispatch::DispatchResultWithPostInfo {
+  BtcRelay::submit_block_headers(RuntimeOrigin::signed(ALICE_ACCOUNT_ID), headers)
+}
+
+#[test]
+pub fn recorded_headers_are_valid() {
+  assert_eq!(
+    hash_of(&MAINNET_BLOCK_1),
+    BlockHeader::parse(&MAINNET_BLOCK_2).prev_block_hash
+  );
+  assert_eq!(
+    hash_of(&MAINNET_GENESIS),
+    BlockHeader::parse(&MAINNET_BLOCK_1).prev_block_hash
+  );
+  for header in [
+    MAINNET_GENESIS,
+    MAINNET_BLOCK_1,
+    MAINNET_BLOCK_2,
+    TESTNET_GENESIS,
+  ] {
+    assert!(BlockHeader::parse(&header).has_valid_proof_of_work(Network::Mainnet));
+    assert_eq!(BlockHeader::parse(&header).serialize(), header);
+  }
+}
+
+#[test]
+pub fn difficulty_retargeting() {
+  // mainnet blocks #30240 -> #32255
+  assert_eq!(
+    retarget_bits(Network::Mainnet, 1_261_130_161, 1_262_152_739, 0x1d00_ffff),
+    0x1d00_d86a
+  );
+  // mainnet blocks #0 -> #2015, can't go above the pow limit
+  assert_eq!(
+    retarget_bits(Network::Mainnet, 1_231_006_505, 1_233_061_996, 0x1d00_ffff),
+    0x1d00_ffff
+  );
+  // mainnet blocks #66528 -> #68543, timespan is limited to a quarter
+  assert_eq!(
+    retarget_bits(Network::Mainnet, 1_279_008_237, 1_279_297_671, 0x1c05_a3f4),
+    0x1c01_68fd
+  );
+  // mainnet blocks #46368 -> #48383, timespan is limited to four times
+  assert_eq!(
+    retarget_bits(Network::Mainnet, 1_263_163_443, 1_269_211_443, 0x1c38_7f6f),
+    0x1d00_e1fd
+  );
+}
+
+#[test]
+pub fn compact_target_encoding() {
+  for bits in [0x1d00_ffff, 0x1c05_a3f4, 0x207f_ffff, 0x1703_a30c] {
+    assert_eq!(bits_from_target(target_from_bits(bits).unwrap()), bits);
+  }
+  // negative target
+  assert_eq!(target_from_bits(0x0480_0001), None);
+  // overflow
+  assert_eq!(target_from_bits(0xff12_3456), None);
+}
+
+#[test]
+pub fn testnet_minimum_difficulty_blocks() {
+  new_test_ext().execute_with(|| {
+    BitcoinNetwork::set(Network::Testnet);
+    let parent = StoredBlockHeader {
+      header: BlockHeader::parse(&TESTNET_GENESIS),
+      height: 100,
+      chain_work: Default::default(),
+      period_start_time: 0,
+      last_regular_bits: 0x1c00_ffff,
+    };
+    let mut header = BlockHeader::parse(&TESTNET_GENESIS);
+
+    // more than 20 minutes since the last block
+    header.time = parent.header.time + 2 * TARGET_SPACING + 1;
+    assert_eq!(BtcRelay::expected_bits(&parent, &header), 0x1d00_ffff);
+
+    // otherwise the last regular difficulty applies
+    header.time = parent.header.time + TARGET_SPACING;
+    assert_eq!(BtcRelay::expected_bits(&parent, &header), 0x1c00_ffff);
+
+    // mainnet doesn't allow minimum difficulty blocks
+    BitcoinNetwork::set(Network::Mainnet);
+    header.time = parent.header.time + 2 * TARGET_SPACING + 1;
+    assert_eq!(
+      BtcRelay::expected_bits(&parent, &header),
+      parent.header.bits
+    );
+  });
+}
+
+mod initialize {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      initialize_mainnet();
+
+      let block_hash = hash_of(&MAINNET_GENESIS);
+      assert!(BtcRelay::is_initialized());
+      assert_eq!(BtcRelay::best_block(), Some(block_hash));
+      assert_eq!(BtcRelay::best_block_height(), 0);
+      assert_eq!(BtcRelay::main_chain(0), Some(block_hash));
+      System::assert_has_event(MockEvent::BtcRelay(Event::Initialized {
+        block_hash,
+        height: 0,
+      }));
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_root() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          BtcRelay::initialize(RuntimeOrigin::signed(ALICE_ACCOUNT_ID), MAINNET_GENESIS, 0),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn already_initialized() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_noop!(
+          BtcRelay::initialize(RuntimeOrigin::root(), MAINNET_GENESIS, 0),
+          Error::<Test>::AlreadyInitialized
+        );
+      });
+    }
+
+    #[test]
+    fn height_is_not_a_difficulty_period_start() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          BtcRelay::initialize(RuntimeOrigin::root(), MAINNET_BLOCK_1, 1),
+          Error::<Test>::InvalidStartHeight
+        );
+      });
+    }
+
+    #[test]
+    fn proof_of_work_is_invalid() {
+      new_test_ext().execute_with(|| {
+        let mut header = MAINNET_GENESIS;
+        header[79] ^= 0xff;
+        assert_noop!(
+          BtcRelay::initialize(RuntimeOrigin::root(), header, 0),
+          Error::<Test>::InvalidProofOfWork
+        );
+      });
+    }
+  }
+}
+
+mod submit_block_headers {
+  use super::*;
+
+  mod succeeds {
+    use super::*;
+
+    #[test]
+    fn with_recorded_mainnet_headers() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+
+        assert_eq!(BtcRelay::best_block(), Some(hash_of(&MAINNET_BLOCK_2)));
+        assert_eq!(BtcRelay::best_block_height(), 2);
+        assert_eq!(BtcRelay::confirmations(hash_of(&MAINNET_GENESIS)), Some(3));
+        assert_eq!(BtcRelay::confirmations(hash_of(&MAINNET_BLOCK_2)), Some(1));
+        System::assert_has_event(MockEvent::BtcRelay(Event::BlockHeaderStored {
+          block_hash: hash_of(&MAINNET_BLOCK_2),
+          height: 2,
+        }));
+      });
+    }
+
+    #[test]
+    fn fork_with_less_work_is_not_the_main_chain() {
+      new_test_ext().execute_with(|| {
+        let genesis = initialize_regtest();
+        let chain = mine_chain(&genesis, 3, 1);
+        let fork = mine_chain(&chain[0], 1, 100);
+        assert_ok!(submit(chain.clone()));
+        assert_ok!(submit(fork.clone()));
+
+        assert_eq!(BtcRelay::best_block(), Some(hash_of(&chain[2])));
+        assert_eq!(BtcRelay::confirmations(hash_of(&fork[0])), None);
+        assert!(BtcRelay::block_headers(hash_of(&fork[0])).is_some());
+      });
+    }
+
+    #[test]
+    fn fork_with_more_work_reorganize_the_main_chain() {
+      new_test_ext().execute_with(|| {
+        let genesis = initialize_regtest();
+        let chain = mine_chain(&genesis, 3, 1);
+        let fork = mine_chain(&chain[0], 3, 100);
+        assert_ok!(submit(chain.clone()));
+        assert_ok!(submit(fork.clone()));
+
+        assert_eq!(BtcRelay::best_block(), Some(hash_of(&fork[2])));
+        assert_eq!(BtcRelay::best_block_height(), 4);
+        assert_eq!(BtcRelay::main_chain(1), Some(hash_of(&chain[0])));
+        assert_eq!(BtcRelay::main_chain(2), Some(hash_of(&fork[0])));
+        assert_eq!(BtcRelay::main_chain(3), Some(hash_of(&fork[1])));
+        assert_eq!(BtcRelay::confirmations(hash_of(&chain[1])), None);
+        System::assert_has_event(MockEvent::BtcRelay(Event::ChainReorganized {
+          best_block_hash: hash_of(&fork[2]),
+          best_block_height: 4,
+          fork_height: 1,
+        }));
+      });
+    }
+
+    #[test]
+    fn deep_headers_are_pruned() {
+      new_test_ext().execute_with(|| {
+        let genesis = initialize_regtest();
+        let chain = mine_chain(&genesis, 20, 1);
+        let fork = mine_chain(&chain[0], 1, 100);
+        assert_ok!(submit(chain[..2].to_vec()));
+        assert_ok!(submit(fork.clone()));
+        assert_ok!(submit(chain[2..12].to_vec()));
+        assert_ok!(submit(chain[12..].to_vec()));
+
+        let lowest_height = 20 - PruningDepth::get();
+        assert_eq!(BtcRelay::lowest_height(), lowest_height);
+        assert_eq!(BtcRelay::block_headers(hash_of(&genesis)), None);
+        assert_eq!(BtcRelay::block_headers(hash_of(&fork[0])), None);
+        assert_eq!(BtcRelay::main_chain(lowest_height - 1), None);
+        assert_eq!(
+          BtcRelay::main_chain(lowest_height),
+          Some(hash_of(&chain[lowest_height as usize - 1]))
+        );
+        assert_eq!(HeadersByHeight::<Test>::iter_prefix(2).count(), 0);
+        assert_eq!(BtcRelay::best_block_height(), 20);
+
+        // the merkle root of the pruned main chain blocks is kept, not the forks
+        assert_eq!(
+          BtcRelay::pruned_merkle_roots(hash_of(&chain[0])),
+          Some((1, BlockHeader::parse(&chain[0]).merkle_root))
+        );
+        assert_eq!(BtcRelay::pruned_merkle_roots(hash_of(&fork[0])), None);
+      });
+    }
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_signed() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_noop!(
+          BtcRelay::submit_block_headers(RuntimeOrigin::none(), vec![MAINNET_BLOCK_1]),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn not_a_relayer() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_noop!(
+          BtcRelay::submit_block_headers(
+            RuntimeOrigin::signed(BOB_ACCOUNT_ID),
+            vec![MAINNET_BLOCK_1]
+          ),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn relay_is_not_initialized() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(submit(vec![MAINNET_BLOCK_1]), Error::<Test>::NotInitialized);
+      });
+    }
+
+    #[test]
+    fn too_many_headers() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_noop!(
+          submit(vec![MAINNET_BLOCK_1; 11]),
+          Error::<Test>::TooManyHeaders
+        );
+      });
+    }
+
+    #[test]
+    fn header_is_already_stored() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1]));
+        assert_noop!(
+          submit(vec![MAINNET_BLOCK_1]),
+          Error::<Test>::DuplicateBlockHeader
+        );
+      });
+    }
+
+    #[test]
+    fn parent_is_unknown() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_noop!(
+          submit(vec![MAINNET_BLOCK_2]),
+          Error::<Test>::UnknownParentBlock
+        );
+      });
+    }
+
+    #[test]
+    fn proof_of_work_is_invalid() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        let mut header = MAINNET_BLOCK_1;
+        header[79] ^= 0xff;
+        assert_noop!(submit(vec![header]), Error::<Test>::InvalidProofOfWork);
+      });
+    }
+
+    #[test]
+    fn difficulty_is_invalid() {
+      new_test_ext().execute_with(|| {
+        let genesis = initialize_regtest();
+        let mut header = BlockHeader::parse(&mine_block(&genesis, H256::zero()));
+        header.bits = 0x207f_fffe;
+        while !header.has_valid_proof_of_work(Network::Regtest) {
+          header.nonce += 1;
+        }
+        assert_noop!(
+          submit(vec![header.serialize()]),
+          Error::<Test>::InvalidDifficulty
+        );
+      });
+    }
+
+    #[test]
+    fn timestamp_is_not_above_the_median_time_past() {
+      new_test_ext().execute_with(|| {
+        let genesis = initialize_regtest();
+        let chain = mine_chain(&genesis, 2, 1);
+        assert_ok!(submit(chain.clone()));
+
+        // median of the genesis and the two blocks is the first block time
+        let median_time_past = BlockHeader::parse(&chain[0]).time;
+        assert_eq!(
+          BtcRelay::median_time_past(hash_of(&chain[1])),
+          median_time_past
+        );
+        assert_noop!(
+          submit(vec![mine_block_at(
+            &chain[1],
+            H256::zero(),
+            median_time_past
+          )]),
+          Error::<Test>::TimestampTooOld
+        );
+        assert_ok!(submit(vec![mine_block_at(
+          &chain[1],
+          H256::zero(),
+          median_time_past + 1
+        )]));
+      });
+    }
+
+    #[test]
+    fn timestamp_is_too_far_in_the_future() {
+      new_test_ext().execute_with(|| {
+        let genesis = initialize_regtest();
+        let now = (NOW_IN_MILLIS / 1_000) as u32;
+        assert_noop!(
+          submit(vec![mine_block_at(
+            &genesis,
+            H256::zero(),
+            now + MAX_FUTURE_BLOCK_TIME + 1
+          )]),
+          Error::<Test>::TimestampInFuture
+        );
+        assert_ok!(submit(vec![mine_block_at(
+          &genesis,
+          H256::zero(),
+          now + MAX_FUTURE_BLOCK_TIME
+        )]));
+      });
+    }
+
+    #[test]
+    fn fork_is_too_deep() {
+      new_test_ext().execute_with(|| {
+        let genesis = initialize_regtest();
+        assert_ok!(submit(mine_chain(&genesis, 5, 1)));
+        assert_noop!(
+          submit(mine_chain(&genesis, 1, 100)),
+          Error::<Test>::ForkTooDeep
+        );
+      });
+    }
+  }
+}
+
+mod verify_transaction_inclusion {
+  use super::*;
+
+  fn display(transaction_id: H256) -> Vec<u8> {
+    let mut bytes = transaction_id.0;
+    bytes.reverse();
+    bytes.to_vec()
+  }
+
+  fn proof_for(
+    block: &RawBlockHeader,
+    transaction_count: u32,
+    transaction_index: u32,
+    merkle_path: Vec<H256>,
+  ) -> MerkleProof {
+    MerkleProof {
+      block_hash: hash_of(block),
+      transaction_count,
+      transaction_index,
+      merkle_path: BoundedVec::try_from(merkle_path).unwrap(),
+    }
+  }
+
+  #[test]
+  fn succeeds_with_recorded_mainnet_coinbase() {
+    new_test_ext().execute_with(|| {
+      initialize_mainnet();
+      assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+
+      assert_ok!(BtcRelay::verify_transaction_inclusion(
+        MAINNET_BLOCK_1_COINBASE,
+        &proof_for(&MAINNET_BLOCK_1, 1, 0, Vec::new()),
+        0
+      ));
+    });
+  }
+
+  #[test]
+  fn succeeds_with_merkle_path() {
+    new_test_ext().execute_with(|| {
+      let genesis = initialize_regtest();
+      let transactions: Vec<H256> = (0..4).map(H256::repeat_byte).collect();
+      let left = sha256d(&[transactions[0].as_bytes(), transactions[1].as_bytes()].concat());
+      let right = sha256d(&[transactions[2].as_bytes(), transactions[3].as_bytes()].concat());
+      let merkle_root = sha256d(&[left.as_bytes(), right.as_bytes()].concat());
+
+      let block = mine_block(&genesis, merkle_root);
+      let confirmation = mine_block(&block, H256::zero());
+      assert_ok!(submit(vec![block, confirmation]));
+
+      assert_ok!(BtcRelay::verify_transaction_inclusion(
+        &display(transactions[2]),
+        &proof_for(&block, 4, 2, vec![transactions[3], left]),
+        0
+      ));
+      assert_ok!(BtcRelay::verify_transaction_inclusion(
+        &display(transactions[1]),
+        &proof_for(&block, 4, 1, vec![transactions[0], right]),
+        0
+      ));
+    });
+  }
+
+  #[test]
+  fn succeeds_in_a_pruned_block() {
+    new_test_ext().execute_with(|| {
+      let genesis = initialize_regtest();
+      let chain = mine_chain(&genesis, 20, 1);
+      assert_ok!(submit(chain[..10].to_vec()));
+      assert_ok!(submit(chain[10..].to_vec()));
+      assert_eq!(BtcRelay::block_headers(hash_of(&chain[0])), None);
+
+      // a single transaction, its id is the merkle root
+      assert_ok!(BtcRelay::verify_transaction_inclusion(
+        &display(H256::repeat_byte(1)),
+        &proof_for(&chain[0], 1, 0, Vec::new()),
+        0
+      ));
+      assert_noop!(
+        BtcRelay::verify_transaction_inclusion(
+          &display(H256::repeat_byte(2)),
+          &proof_for(&chain[0], 1, 0, Vec::new()),
+          0
+        ),
+        Error::<Test>::InvalidMerkleProof
+      );
+    });
+  }
+
+  #[test]
+  fn merkle_tree_depth() {
+    assert_eq!(MerkleProof::tree_depth(1), 0);
+    assert_eq!(MerkleProof::tree_depth(2), 1);
+    assert_eq!(MerkleProof::tree_depth(3), 2);
+    assert_eq!(MerkleProof::tree_depth(4), 2);
+    assert_eq!(MerkleProof::tree_depth(5), 3);
+    assert_eq!(MerkleProof::tree_depth(u32::MAX), MAX_MERKLE_PROOF_DEPTH);
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn transaction_id_is_invalid() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_noop!(
+          BtcRelay::verify_transaction_inclusion(
+            b"not an id",
+            &proof_for(&MAINNET_GENESIS, 1, 0, Vec::new()),
+            0
+          ),
+          Error::<Test>::InvalidTransactionId
+        );
+      });
+    }
+
+    #[test]
+    fn block_is_unknown() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_noop!(
+          BtcRelay::verify_transaction_inclusion(
+            MAINNET_BLOCK_1_COINBASE,
+            &proof_for(&MAINNET_BLOCK_1, 1, 0, Vec::new()),
+            0
+          ),
+          Error::<Test>::UnknownBlock
+        );
+      });
+    }
+
+    #[test]
+    fn block_is_not_in_main_chain() {
+      new_test_ext().execute_with(|| {
+        let genesis = initialize_regtest();
+        let chain = mine_chain(&genesis, 3, 1);
+        let fork = mine_chain(&genesis, 1, 100);
+        assert_ok!(submit(chain));
+        assert_ok!(submit(fork.clone()));
+
+        assert_noop!(
+          BtcRelay::verify_transaction_inclusion(
+            &display(H256::repeat_byte(100)),
+            &proof_for(&fork[0], 1, 0, Vec::new()),
+            0
+          ),
+          Error::<Test>::BlockNotInMainChain
+        );
+      });
+    }
+
+    #[test]
+    fn not_enough_confirmations() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1]));
+        assert_noop!(
+          BtcRelay::verify_transaction_inclusion(
+            MAINNET_BLOCK_1_COINBASE,
+            &proof_for(&MAINNET_BLOCK_1, 1, 0, Vec::new()),
+            0
+          ),
+          Error::<Test>::InsufficientConfirmations
+        );
+      });
+    }
+
+    #[test]
+    fn not_enough_confirmations_for_the_asset() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+        assert_noop!(
+          BtcRelay::verify_transaction_inclusion(
+            MAINNET_BLOCK_1_COINBASE,
+            &proof_for(&MAINNET_BLOCK_1, 1, 0, Vec::new()),
+            3
+          ),
+          Error::<Test>::InsufficientConfirmations
+        );
+      });
+    }
+
+    #[test]
+    fn merkle_proof_is_invalid() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+        assert_noop!(
+          BtcRelay::verify_transaction_inclusion(
+            MAINNET_BLOCK_1_COINBASE,
+            &proof_for(&MAINNET_BLOCK_1, 1, 0, vec![H256::zero()]),
+            0
+          ),
+          Error::<Test>::InvalidMerkleProof
+        );
+        assert_noop!(
+          BtcRelay::verify_transaction_inclusion(
+            MAINNET_BLOCK_1_COINBASE,
+            &proof_for(&MAINNET_BLOCK_1, 1, 1, Vec::new()),
+            0
+          ),
+          Error::<Test>::InvalidMerkleProof
+        );
+      });
+    }
+
+    #[test]
+    fn merkle_path_does_not_match_the_transaction_count() {
+      new_test_ext().execute_with(|| {
+        let genesis = initialize_regtest();
+        let transactions: Vec<H256> = (0..4).map(H256::repeat_byte).collect();
+        let left = sha256d(&[transactions[0].as_bytes(), transactions[1].as_bytes()].concat());
+        let right = sha256d(&[transactions[2].as_bytes(), transactions[3].as_bytes()].concat());
+        let merkle_root = sha256d(&[left.as_bytes(), right.as_bytes()].concat());
+
+        let block = mine_block(&genesis, merkle_root);
+        let confirmation = mine_block(&block, H256::zero());
+        assert_ok!(submit(vec![block, confirmation]));
+
+        // an inner node can't be proven as a transaction
+        assert_noop!(
+          BtcRelay::verify_transaction_inclusion(
+            &display(left),
+            &proof_for(&block, 4, 0, vec![right]),
+            0
+          ),
+          Error::<Test>::InvalidMerkleProof
+        );
+        assert_noop!(
+          BtcRelay::verify_transaction_inclusion(
+            &display(transactions[2]),
+            &proof_for(&block, 8, 2, vec![transactions[3], left]),
+            0
+          ),
+          Error::<Test>::InvalidMerkleProof
+        );
+      });
+    }
+  }
+}
+
+#[test]
+pub fn transaction_parsing() {
+  let transaction = Transaction::parse(MAINNET_BLOCK_1_COINBASE_TRANSACTION).unwrap();
+  assert_eq!(
+    transaction.transaction_id,
+    parse_transaction_id(MAINNET_BLOCK_1_COINBASE).unwrap()
+  );
+  assert_eq!(transaction.outputs.len(), 1);
+  assert_eq!(transaction.outputs[0].value, 5_000_000_000);
+  assert_eq!(
+    transaction.outputs[0].script_pubkey,
+    MAINNET_BLOCK_1_COINBASE_TRANSACTION[MAINNET_BLOCK_1_COINBASE_TRANSACTION.len() - 71
+      ..MAINNET_BLOCK_1_COINBASE_TRANSACTION.len() - 4]
+  );
+
+  // the witness is not part of the transaction id
+  let legacy = MAINNET_BLOCK_1_COINBASE_TRANSACTION;
+  let (version, rest) = legacy.split_at(4);
+  let (inputs_and_outputs, lock_time) = rest.split_at(rest.len() - 4);
+  let witness = [&[0x01, 0x20][..], &[0xab; 32]].concat();
+  let segwit = [
+    version,
+    &[0x00, 0x01],
+    inputs_and_outputs,
+    &witness,
+    lock_time,
+  ]
+  .concat();
+  assert_eq!(Transaction::parse(&segwit), Some(transaction));
+
+  // truncated or with trailing bytes
+  assert_eq!(Transaction::parse(&legacy[..legacy.len() - 1]), None);
+  assert_eq!(Transaction::parse(&[legacy, &[0]].concat()), None);
+  assert_eq!(Transaction::parse(&[]), None);
+}
+
+mod verify_deposit {
+  use super::*;
+
+  // P2PK script of the mainnet block #1 coinbase
+  fn script_pubkey() -> Vec<u8> {
+    Transaction::parse(MAINNET_BLOCK_1_COINBASE_TRANSACTION)
+      .unwrap()
+      .outputs[0]
+      .script_pubkey
+      .clone()
+  }
+
+  fn deposit_proof(raw_transaction: &[u8], output_index: u32) -> DepositProof {
+    DepositProof {
+      merkle_proof: MerkleProof {
+        block_hash: hash_of(&MAINNET_BLOCK_1),
+        transaction_count: 1,
+        transaction_index: 0,
+        merkle_path: BoundedVec::default(),
+      },
+      raw_transaction: BoundedVec::try_from(raw_transaction.to_vec()).unwrap(),
+      output_index,
+    }
+  }
+
+  #[test]
+  fn succeeds_with_recorded_mainnet_coinbase() {
+    new_test_ext().execute_with(|| {
+      initialize_mainnet();
+      assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+
+      assert_ok!(BtcRelay::verify_deposit(
+        MAINNET_BLOCK_1_COINBASE,
+        &deposit_proof(MAINNET_BLOCK_1_COINBASE_TRANSACTION, 0),
+        5_000_000_000,
+        &script_pubkey(),
+        0
+      ));
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn transaction_is_invalid() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+        assert_noop!(
+          BtcRelay::verify_deposit(
+            MAINNET_BLOCK_1_COINBASE,
+            &deposit_proof(
+              &MAINNET_BLOCK_1_COINBASE_TRANSACTION
+                [..MAINNET_BLOCK_1_COINBASE_TRANSACTION.len() - 1],
+              0
+            ),
+            5_000_000_000,
+            &script_pubkey(),
+            0
+          ),
+          Error::<Test>::InvalidTransaction
+        );
+      });
+    }
+
+    #[test]
+    fn transaction_does_not_match_the_transaction_id() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+        let mut raw_transaction = MAINNET_BLOCK_1_COINBASE_TRANSACTION.to_vec();
+        // lock time
+        raw_transaction[MAINNET_BLOCK_1_COINBASE_TRANSACTION.len() - 1] = 1;
+        assert_noop!(
+          BtcRelay::verify_deposit(
+            MAINNET_BLOCK_1_COINBASE,
+            &deposit_proof(&raw_transaction, 0),
+            5_000_000_000,
+            &script_pubkey(),
+            0
+          ),
+          Error::<Test>::TransactionIdMismatch
+        );
+      });
+    }
+
+    #[test]
+    fn output_does_not_exist() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+        assert_noop!(
+          BtcRelay::verify_deposit(
+            MAINNET_BLOCK_1_COINBASE,
+            &deposit_proof(MAINNET_BLOCK_1_COINBASE_TRANSACTION, 1),
+            5_000_000_000,
+            &script_pubkey(),
+            0
+          ),
+          Error::<Test>::UnknownOutput
+        );
+      });
+    }
+
+    #[test]
+    fn output_amount_does_not_match() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+        assert_noop!(
+          BtcRelay::verify_deposit(
+            MAINNET_BLOCK_1_COINBASE,
+            &deposit_proof(MAINNET_BLOCK_1_COINBASE_TRANSACTION, 0),
+            5_000_000_001,
+            &script_pubkey(),
+            0
+          ),
+          Error::<Test>::OutputMismatch
+        );
+      });
+    }
+
+    #[test]
+    fn output_script_does_not_match() {
+      new_test_ext().execute_with(|| {
+        initialize_mainnet();
+        assert_ok!(submit(vec![MAINNET_BLOCK_1, MAINNET_BLOCK_2]));
+        assert_noop!(
+          BtcRelay::verify_deposit(
+            MAINNET_BLOCK_1_COINBASE,
+            &deposit_proof(MAINNET_BLOCK_1_COINBASE_TRANSACTION, 0),
+            5_000_000_000,
+            b"another script",
+            0
+          ),
+          Error::<Test>::OutputMismatch
+        );
+      });
+    }
+  }
+}
@@ -0,0 +1,414 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Bitcoin consensus types and helpers used by the relay.
+//!
+//! All hashes are kept in the internal (little-endian) byte order, as serialized
+//! in the block headers. Block explorers display them reversed.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+use sha2::{Digest, Sha256};
+use sp_core::{H256, U256};
+use sp_std::vec::Vec;
+
+/// Serialized bitcoin block header.
+pub type RawBlockHeader = [u8; 80];
+
+/// Number of blocks between two difficulty adjustments.
+pub const DIFFICULTY_ADJUSTMENT_INTERVAL: u32 = 2016;
+/// Expected duration of a difficulty period (two weeks).
+pub const TARGET_TIMESPAN: u32 = 14 * 24 * 60 * 60;
+/// Expected time between two blocks.
+pub const TARGET_SPACING: u32 = 10 * 60;
+/// Maximum depth of a merkle proof (enough for 2^32 transactions).
+pub const MAX_MERKLE_PROOF_DEPTH: u32 = 32;
+
+/// Number of previous blocks used to compute the median time past.
+pub const MEDIAN_TIME_SPAN: u32 = 11;
+/// Maximum time a block header can be ahead of the current time (two hours).
+pub const MAX_FUTURE_BLOCK_TIME: u32 = 2 * 60 * 60;
+
+/// Maximum size of a serialized transaction in a deposit proof.
+pub const MAX_TRANSACTION_SIZE: u32 = 10_000;
+
+/// Serialized bitcoin transaction, with or without the witness data.
+pub type RawTransaction = BoundedVec<u8, ConstU32<MAX_TRANSACTION_SIZE>>;
+
+/// Bitcoin network the relay is following.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Network {
+  /// Bitcoin mainnet
+  Mainnet,
+  /// Bitcoin testnet3, allow minimum difficulty blocks after 20 minutes without block
+  Testnet,
+  /// Local regression test network, no difficulty adjustment
+  Regtest,
+}
+
+impl Network {
+  /// Highest target allowed by the network, in compact format.
+  pub fn pow_limit_bits(&self) -> u32 {
+    match self {
+      Network::Mainnet | Network::Testnet => 0x1d00_ffff,
+      Network::Regtest => 0x207f_ffff,
+    }
+  }
+
+  /// Highest target allowed by the network.
+  pub fn pow_limit(&self) -> U256 {
+    target_from_bits(self.pow_limit_bits()).unwrap_or_default()
+  }
+}
+
+/// Bitcoin block header.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct BlockHeader {
+  pub version: i32,
+  pub prev_block_hash: H256,
+  pub merkle_root: H256,
+  pub time: u32,
+  pub bits: u32,
+  pub nonce: u32,
+}
+
+impl BlockHeader {
+  /// Decode a serialized block header.
+  pub fn parse(raw: &RawBlockHeader) -> Self {
+    let u32_at = |offset: usize| {
+      let mut bytes = [0_u8; 4];
+      bytes.copy_from_slice(&raw[offset..offset + 4]);
+      u32::from_le_bytes(bytes)
+    };
+
+    Self {
+      version: u32_at(0) as i32,
+      prev_block_hash: H256::from_slice(&raw[4..36]),
+      merkle_root: H256::from_slice(&raw[36..68]),
+      time: u32_at(68),
+      bits: u32_at(72),
+      nonce: u32_at(76),
+    }
+  }
+
+  /// Serialize the block header.
+  pub fn serialize(&self) -> RawBlockHeader {
+    let mut raw = [0_u8; 80];
+    raw[0..4].copy_from_slice(&self.version.to_le_bytes());
+    raw[4..36].copy_from_slice(self.prev_block_hash.as_bytes());
+    raw[36..68].copy_from_slice(self.merkle_root.as_bytes());
+    raw[68..72].copy_from_slice(&self.time.to_le_bytes());
+    raw[72..76].copy_from_slice(&self.bits.to_le_bytes());
+    raw[76..80].copy_from_slice(&self.nonce.to_le_bytes());
+    raw
+  }
+
+  /// Block hash, in internal byte order.
+  pub fn hash(&self) -> H256 {
+    sha256d(&self.serialize())
+  }
+
+  /// Make sure the block hash is below the target encoded in `bits`.
+  pub fn has_valid_proof_of_work(&self, network: Network) -> bool {
+    match target_from_bits(self.bits) {
+      Some(target) if !target.is_zero() && target <= network.pow_limit() => {
+        U256::from_little_endian(self.hash().as_bytes()) <= target
+      }
+      _ => false,
+    }
+  }
+}
+
+/// Block header stored by the relay, with its position in the chain.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct StoredBlockHeader {
+  /// Block header
+  pub header: BlockHeader,
+  /// Block height
+  pub height: u32,
+  /// Accumulated proof of work up to this block (included)
+  pub chain_work: U256,
+  /// Timestamp of the first block of the difficulty period
+  pub period_start_time: u32,
+  /// Bits of the last block which is not a testnet minimum difficulty block
+  pub last_regular_bits: u32,
+}
+
+/// Merkle proof of a transaction inclusion in a bitcoin block.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct MerkleProof {
+  /// Hash of the block including the transaction
+  pub block_hash: H256,
+  /// Number of transactions in the block
+  pub transaction_count: u32,
+  /// Position of the transaction in the block
+  pub transaction_index: u32,
+  /// Sibling hashes from the transaction up to the merkle root
+  pub merkle_path: BoundedVec<H256, ConstU32<MAX_MERKLE_PROOF_DEPTH>>,
+}
+
+impl MerkleProof {
+  /// Depth of the merkle tree of a block with `transaction_count` transactions.
+  pub fn tree_depth(transaction_count: u32) -> u32 {
+    // ceil(log2(n))
+    u32::BITS - transaction_count.saturating_sub(1).leading_zeros()
+  }
+
+  /// Compute the merkle root for `transaction_id` following the merkle path.
+  ///
+  /// The path length must match the depth of the tree, so an inner node can't be
+  /// proven as a transaction.
+  pub fn compute_merkle_root(&self, transaction_id: H256) -> Option<H256> {
+    if self.transaction_index >= self.transaction_count
+      || self.merkle_path.len() as u32 != Self::tree_depth(self.transaction_count)
+    {
+      return None;
+    }
+
+    let mut index = self.transaction_index;
+    let mut current = transaction_id;
+    for sibling in self.merkle_path.iter() {
+      current = if index & 1 == 0 {
+        sha256d(&[current.as_bytes(), sibling.as_bytes()].concat())
+      } else {
+        sha256d(&[sibling.as_bytes(), current.as_bytes()].concat())
+      };
+      index >>= 1;
+    }
+    Some(current)
+  }
+}
+
+/// Proof of a deposit, the transaction is provided to link the mint with its outputs.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct DepositProof {
+  /// Merkle proof of the transaction inclusion
+  pub merkle_proof: MerkleProof,
+  /// Serialized deposit transaction
+  pub raw_transaction: RawTransaction,
+  /// Index of the deposit output in the transaction
+  pub output_index: u32,
+}
+
+/// Bitcoin transaction output.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct TransactionOutput {
+  /// Amount in satoshis
+  pub value: u64,
+  /// Locking script of the output
+  pub script_pubkey: Vec<u8>,
+}
+
+/// Bitcoin transaction decoded from its serialization, only the outputs are kept.
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub struct Transaction {
+  /// Transaction id (hash of the serialization without witness), in internal byte order
+  pub transaction_id: H256,
+  /// Transaction outputs
+  pub outputs: Vec<TransactionOutput>,
+}
+
+impl Transaction {
+  /// Decode a serialized transaction, returns `None` when malformed or when some
+  /// bytes are left after the lock time.
+  pub fn parse(raw: &[u8]) -> Option<Self> {
+    let mut reader = Reader { raw, position: 0 };
+
+    let version = reader.read(4)?;
+    // segwit serialization has a `0x00` marker and a `0x01` flag after the version
+    let has_witness = raw.get(4..6) == Some(&[0x00, 0x01]);
+    if has_witness {
+      reader.read(2)?;
+    }
+
+    let inputs_start = reader.position;
+    let input_count = reader.read_compact_size()?;
+    for _ in 0..input_count {
+      // previous output (txid + index)
+      reader.read(36)?;
+      let script_length = reader.read_compact_size()?;
+      reader.read(script_length)?;
+      // sequence
+      reader.read(4)?;
+    }
+
+    let output_count = reader.read_compact_size()?;
+    let mut outputs = Vec::new();
+    for _ in 0..output_count {
+      let mut value = [0_u8; 8];
+      value.copy_from_slice(reader.read(8)?);
+      let script_length = reader.read_compact_size()?;
+      outputs.push(TransactionOutput {
+        value: u64::from_le_bytes(value),
+        script_pubkey: reader.read(script_length)?.to_vec(),
+      });
+    }
+    let inputs_and_outputs = &raw[inputs_start..reader.position];
+
+    if has_witness {
+      for _ in 0..input_count {
+        let item_count = reader.read_compact_size()?;
+        for _ in 0..item_count {
+          let item_length = reader.read_compact_size()?;
+          reader.read(item_length)?;
+        }
+      }
+    }
+
+    let lock_time = reader.read(4)?;
+    if reader.position != raw.len() || input_count == 0 || outputs.is_empty() {
+      return None;
+    }
+
+    Some(Self {
+      transaction_id: sha256d(&[version, inputs_and_outputs, lock_time].concat()),
+      outputs,
+    })
+  }
+}
+
+// Cursor over a serialized transaction
+struct Reader<'a> {
+  raw: &'a [u8],
+  position: usize,
+}
+
+impl<'a> Reader<'a> {
+  fn read(&mut self, length: usize) -> Option<&'a [u8]> {
+    let end = self.position.checked_add(length)?;
+    let bytes = self.raw.get(self.position..end)?;
+    self.position = end;
+    Some(bytes)
+  }
+
+  fn read_compact_size(&mut self) -> Option<usize> {
+    let size = match self.read(1)?[0] {
+      0xfd => {
+        let mut bytes = [0_u8; 2];
+        bytes.copy_from_slice(self.read(2)?);
+        u16::from_le_bytes(bytes) as u64
+      }
+      0xfe => {
+        let mut bytes = [0_u8; 4];
+        bytes.copy_from_slice(self.read(4)?);
+        u32::from_le_bytes(bytes) as u64
+      }
+      0xff => {
+        let mut bytes = [0_u8; 8];
+        bytes.copy_from_slice(self.read(8)?);
+        u64::from_le_bytes(bytes)
+      }
+      size => size as u64,
+    };
+    // nothing can be larger than the transaction itself
+    if size > self.raw.len() as u64 {
+      return None;
+    }
+    Some(size as usize)
+  }
+}
+
+/// Double SHA-256, used for block and transaction hashes.
+pub fn sha256d(data: &[u8]) -> H256 {
+  let mut result = [0; 32];
+  result.copy_from_slice(&Sha256::digest(Sha256::digest(data))[..]);
+  H256(result)
+}
+
+/// Decode a transaction id as displayed by the explorers (hex, or raw bytes) into
+/// the internal byte order.
+pub fn parse_transaction_id(transaction_id: &[u8]) -> Option<H256> {
+  let mut hash = [0_u8; 32];
+  match transaction_id.len() {
+    32 => hash.copy_from_slice(transaction_id),
+    64 => {
+      for (i, pair) in transaction_id.chunks(2).enumerate() {
+        let high = (pair[0] as char).to_digit(16)?;
+        let low = (pair[1] as char).to_digit(16)?;
+        hash[i] = (high << 4 | low) as u8;
+      }
+    }
+    _ => return None,
+  }
+  hash.reverse();
+  Some(H256(hash))
+}
+
+/// Decode a compact target (`bits`), returns `None` for negative or overflowing targets.
+pub fn target_from_bits(bits: u32) -> Option<U256> {
+  let exponent = bits >> 24;
+  let mantissa = bits & 0x007f_ffff;
+
+  if bits & 0x0080_0000 != 0 && mantissa != 0 {
+    return None;
+  }
+
+  if exponent <= 3 {
+    return Some(U256::from(mantissa >> (8 * (3 - exponent))));
+  }
+
+  if mantissa != 0
+    && (exponent > 34 || (mantissa > 0xff && exponent > 33) || (mantissa > 0xffff && exponent > 32))
+  {
+    return None;
+  }
+
+  Some(U256::from(mantissa) << (8 * (exponent - 3)))
+}
+
+/// Encode a target into the compact format (`bits`).
+pub fn bits_from_target(target: U256) -> u32 {
+  let mut size = (target.bits() + 7) / 8;
+  let mut compact = if size <= 3 {
+    (target.low_u64() << (8 * (3 - size))) as u32
+  } else {
+    (target >> (8 * (size - 3))).low_u32()
+  };
+
+  // the mantissa sign bit can't be set
+  if compact & 0x0080_0000 != 0 {
+    compact >>= 8;
+    size += 1;
+  }
+
+  compact | (size as u32) << 24
+}
+
+/// Expected amount of work to produce a block with the `bits` target.
+pub fn block_work(bits: u32) -> U256 {
+  match target_from_bits(bits) {
+    Some(target) if !target.is_zero() => (!target / (target + 1)) + 1,
+    _ => U256::zero(),
+  }
+}
+
+/// Compute the bits required after a difficulty period.
+pub fn retarget_bits(
+  network: Network,
+  period_start_time: u32,
+  last_block_time: u32,
+  last_block_bits: u32,
+) -> u32 {
+  let actual_timespan = last_block_time
+    .saturating_sub(period_start_time)
+    .clamp(TARGET_TIMESPAN / 4, TARGET_TIMESPAN * 4);
+
+  let target = target_from_bits(last_block_bits).unwrap_or_default();
+  let new_target = target.saturating_mul(actual_timespan.into()) / TARGET_TIMESPAN;
+
+  bits_from_target(new_target.min(network.pow_limit()))
+}
@@ -19,15 +19,23 @@ use crate::*;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
 use frame_support::{
   assert_ok,
-  traits::{fungibles::Mutate, Get, Hooks},
+  traits::{
+    fungibles::{Inspect, Mutate},
+    ConstU32, Get, Hooks,
+  },
+  weights::Weight,
+  BoundedVec,
 };
 use frame_system::RawOrigin;
 use scale_info::prelude::format;
-use sp_runtime::traits::{Saturating, StaticLookup};
+use sp_runtime::{
+  traits::{Saturating, StaticLookup, Zero},
+  Permill,
+};
 use sp_std::prelude::*;
 use tidefi_primitives::{
   pallet::{FeesExt, StakingExt},
-  CurrencyId, Fee,
+  ActiveEraInfo, CurrencyId, EraIndex, Fee,
 };
 
 const MAX_ASSET_ID_IN_PRIMITIVES: u32 = 6;
@@ -94,6 +102,37 @@ where
     .collect()
 }
 
+fn set_fee_distribution_batch<T: Config>(batch_size: u32) {
+  (0..batch_size).for_each(|i| {
+    FeeDistributions::<T>::insert(
+      CurrencyId::Wrapped(i),
+      FeeDistribution {
+        stakers: Permill::from_percent(50),
+        operator: Permill::from_percent(20),
+        treasury: Permill::from_percent(20),
+        burn: Permill::from_percent(10),
+      },
+    );
+  });
+}
+
+fn create_account_fees_batch<T: Config>(era_index: EraIndex, batch_size: u32) {
+  (0..batch_size).for_each(|i| {
+    let account = frame_benchmarking::account::<T::AccountId>("swap", i, USER_SEED);
+    let account_fees: BoundedVec<(CurrencyId, Fee), ConstU32<1_000>> = vec![(
+      CurrencyId::Wrapped(TEST_TOKEN),
+      Fee {
+        amount: USER_FEE.saturating_mul(100),
+        fee: USER_FEE,
+        fee_tdfy: 100_000_000_000,
+      },
+    )]
+    .try_into()
+    .expect("one currency");
+    AccountFees::<T>::insert(era_index, account, account_fees);
+  });
+}
+
 fn create_stake_batch<T: Config>(batch_size: u32) -> Vec<T::AccountId>
 where
   T: pallet_tidefi_stake::Config,
@@ -121,7 +160,8 @@ where
     RawOrigin::Signed(account.clone()).into(),
     CurrencyId::Wrapped(TEST_TOKEN),
     INITIAL_AMOUNT,
-    FIFTEEN_DAYS.into()
+    FIFTEEN_DAYS.into(),
+    false
   ));
 }
 
@@ -155,6 +195,9 @@ benchmarks! {
     create_swap_fees_batch::<T>(b);
     assert_eq!(SessionTotalFees::<T>::iter_prefix(CurrentSession::<T>::get()).count() as u32, b);
 
+    // split each currency fees between the stakers, the operator, the treasury and the burn
+    set_fee_distribution_batch::<T>(b);
+
     // jump to expected end of session
     let expected_block = T::BlocksPerSession::get().saturating_add(1_u32.into());
     pallet_security::CurrentBlockCount::<T>::put(expected_block);
@@ -164,9 +207,59 @@ benchmarks! {
   }
   verify {
     assert_eq!(
-      pallet_tidefi_stake::QueueCompound::<T>::count(),
-      a
-    )
+      pallet_tidefi_stake::InterestCompoundLastSession::<T>::get(),
+      1
+    );
+    let treasury_balance = <T as Config>::CurrencyTidefi::balance(
+      CurrencyId::Wrapped(0),
+      &Pallet::<T>::treasury_account_id(),
+    );
+    assert!(!treasury_balance.is_zero());
+  }
+
+  set_fee_distribution {
+    let distribution = FeeDistribution {
+      stakers: Permill::from_percent(50),
+      operator: Permill::from_percent(20),
+      treasury: Permill::from_percent(20),
+      burn: Permill::from_percent(10),
+    };
+  }: _(RawOrigin::Root, CurrencyId::Tdfy, Some(distribution))
+  verify {
+    assert_eq!(FeeDistributions::<T>::get(CurrencyId::Tdfy), Some(distribution));
+  }
+
+  set_session_duration {
+    pallet_security::CurrentBlockCount::<T>::put::<T::BlockNumber>(1_u32.into());
+    // start fees era
+    Pallet::<T>::on_finalize(1_u32.into());
+  }: _(RawOrigin::Root, None)
+  verify {
+    assert_eq!(SessionDuration::<T>::get(), None);
+    assert_eq!(
+      ActiveEra::<T>::get().and_then(|active_era| active_era.last_session_block),
+      Some(1_u32.into())
+    );
+  }
+
+  on_idle_account_fees_pruning {
+    let b in 1 .. T::BatchSize::get();
+
+    // the oldest era is out of the archive
+    ActiveEra::<T>::put(ActiveEraInfo::<T::BlockNumber> {
+      index: T::ErasArchive::get().saturating_add(1),
+      start_block: None,
+      start_session_index: None,
+      last_session_block: None,
+      start: None,
+    });
+    create_account_fees_batch::<T>(0, b);
+  }
+  : {
+    Pallet::<T>::do_on_idle_account_fees_pruning(Weight::MAX);
+  }
+  verify {
+    assert_eq!(AccountFees::<T>::iter_prefix(0).count(), 0);
   }
 
 }
@@ -22,7 +22,9 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod types;
 pub mod weights;
+pub use types::*;
 pub use weights::*;
 
 #[cfg(feature = "runtime-benchmarks")]
@@ -50,15 +52,17 @@ pub mod pallet {
   use frame_support::{
     inherent::Vec,
     log,
-    pallet_prelude::*,
+    pallet_prelude::{DispatchResultWithPostInfo, *},
     traits::{
       tokens::fungibles::{Inspect, Mutate, Transfer},
       StorageVersion, UnixTime,
     },
     PalletId,
   };
+  use frame_system::pallet_prelude::*;
+  use pallet_tidefi_stake::StakingOperator;
   use sp_runtime::{
-    traits::{AccountIdConversion, Saturating},
+    traits::{AccountIdConversion, Saturating, Zero},
     Percent, Permill, SaturatedConversion,
   };
   use sp_std::vec;
@@ -96,10 +100,28 @@ pub mod pallet {
     #[pallet::constant]
     type SessionsArchive: Get<SessionIndex>;
 
-    /// Number of block per session
+    /// Number of past eras to keep the account fees of, older eras are pruned when the chain is
+    /// idle
+    #[pallet::constant]
+    type ErasArchive: Get<EraIndex>;
+
+    /// Maximum number of account fees pruned in a single block
+    #[pallet::constant]
+    type BatchSize: Get<u32>;
+
+    /// Number of block per session, when the sessions don't follow the wall clock
     #[pallet::constant]
     type BlocksPerSession: Get<Self::BlockNumber>;
 
+    /// Expected time between two blocks in milliseconds, estimates the length of the eras when
+    /// the sessions follow the wall clock
+    #[pallet::constant]
+    type ExpectedBlockTime: Get<u64>;
+
+    /// Maximum number of sessions ended in a single block when catching up with the wall clock
+    #[pallet::constant]
+    type MaximumSessionsCatchUp: Get<u32>;
+
     /// Retail user swap fee
     #[pallet::constant]
     type FeeAmount: Get<Permill>;
@@ -112,14 +134,20 @@ pub mod pallet {
     #[pallet::constant]
     type MarketMakerLimitFeeAmount: Get<Permill>;
 
+    /// Treasury pallet ID, receiving the treasury share of the session fees
+    #[pallet::constant]
+    type TreasuryPalletId: Get<PalletId>;
+
     /// Security traits
     type Security: SecurityExt<Self::AccountId, Self::BlockNumber>;
 
     /// Tidefi stake traits
-    type Staking: StakingExt<Self::AccountId>;
+    type Staking: StakingExt<Self::AccountId> + StakingOperator<Self::AccountId>;
 
     /// Tidefi sunrise traits
-    type Sunrise: SunriseExt<Self::AccountId, Self::BlockNumber>;
+    type Sunrise: SunriseExt<Self::AccountId, Self::BlockNumber>
+      + pallet_sunrise::SunriseRewards<Self::AccountId, Self::BlockNumber>
+      + pallet_sunrise::SunriseReferral<Self::AccountId>;
 
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
@@ -188,6 +216,38 @@ pub mod pallet {
     ValueQuery,
   >;
 
+  /// Oldest era of the account fees not pruned yet
+  #[pallet::storage]
+  #[pallet::getter(fn oldest_account_fees_era)]
+  pub type OldestAccountFeesEra<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
+
+  /// Duration of the sessions in milliseconds set by governance, the sessions end on the multiples
+  /// of the duration since the unix epoch.
+  ///
+  /// Without duration, the sessions last `T::BlocksPerSession` blocks.
+  #[pallet::storage]
+  #[pallet::getter(fn session_duration)]
+  pub type SessionDuration<T: Config> = StorageValue<_, u64>;
+
+  /// Unix time in milliseconds at which the current session ends, when the sessions follow the
+  /// wall clock
+  #[pallet::storage]
+  #[pallet::getter(fn session_end)]
+  pub type SessionEnd<T: Config> = StorageValue<_, u64>;
+
+  /// Number of sessions ended by the wall clock, counted in `on_initialize` and closed in
+  /// `on_finalize`
+  #[pallet::storage]
+  pub type SessionsToEnd<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// Distribution of the session fees set by governance, by currency
+  ///
+  /// The currencies without distribution use `FeeDistribution::default_for`.
+  #[pallet::storage]
+  #[pallet::getter(fn fee_distribution)]
+  pub type FeeDistributions<T: Config> =
+    StorageMap<_, Blake2_128Concat, CurrencyId, FeeDistribution>;
+
   /// Genesis configuration
   #[pallet::genesis_config]
   pub struct GenesisConfig<T: Config> {
@@ -239,6 +299,17 @@ pub mod pallet {
       era_index: EraIndex,
       session_index: SessionIndex,
       session_fees_by_currency: Vec<(CurrencyId, Balance)>,
+      /// Session fees distributed to the stakers, operator, treasury and burned, by currency
+      session_fees_split: Vec<(CurrencyId, SessionFeesSplit)>,
+    },
+    /// Distribution of the session fees of the currency updated, `None` restores the default
+    FeeDistributionUpdated {
+      currency_id: CurrencyId,
+      distribution: Option<FeeDistribution>,
+    },
+    /// Duration of the sessions updated, `None` restores the sessions of `BlocksPerSession` blocks
+    SessionDurationUpdated {
+      duration: Option<u64>,
     },
     EraStarted {
       era_index: EraIndex,
@@ -257,23 +328,40 @@ pub mod pallet {
     BalanceOverflow,
     /// Invalid TDFY value in the order book
     InvalidTdfyValue,
+    /// The parts of the fee distribution don't sum up to 100%
+    InvalidFeeDistribution,
+    /// The session duration can't be zero
+    InvalidSessionDuration,
   }
 
   // hooks
   #[pallet::hooks]
   impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
     fn on_initialize(_now: T::BlockNumber) -> Weight {
-      if Self::should_finalize_current_session() {
+      let sessions_to_end = Self::sessions_to_end();
+      if sessions_to_end > 0 {
+        // the wall clock sessions are counted with the time of the previous block, the same
+        // sessions are closed in `on_finalize`
+        if Self::session_duration().is_some() {
+          SessionsToEnd::<T>::put(sessions_to_end);
+        }
+
         <T as Config>::WeightInfo::on_finalize(
           // predict the size of the new queue to be created in `pallet_tidefi_staking`
           T::Staking::account_stakes_size() as u32,
           // predict the size of the `session_fees_by_currency`
           SessionTotalFees::<T>::iter_prefix(CurrentSession::<T>::get()).count() as u32,
         )
-        .saturating_add(T::DbWeight::get().reads(6))
+        // the sessions skipped since the last block don't have fees
+        .saturating_add(
+          <T as Config>::WeightInfo::on_finalize(0, 0)
+            .saturating_mul(sessions_to_end.saturating_sub(1).into()),
+        )
+        .saturating_add(T::DbWeight::get().reads(9))
+        .saturating_add(T::DbWeight::get().writes(2))
       } else {
-        // just return the weight of the `should_finalize_current_session`
-        T::DbWeight::get().reads(4)
+        // just return the weight of the `sessions_to_end`
+        T::DbWeight::get().reads(6)
       }
     }
 
@@ -281,70 +369,36 @@ pub mod pallet {
       if let Some(mut active_era) = Self::current_era() {
         let real_block = T::Security::get_current_block_count();
         match active_era.start_block {
-          Some(start_block) => {
-            // determine when the session
-            let session_start_block = active_era.last_session_block.unwrap_or(start_block);
-            let expected_end_block_for_session =
-              session_start_block.saturating_add(T::BlocksPerSession::get());
-
-            // end of session
-            if real_block == expected_end_block_for_session {
-              let current_session = CurrentSession::<T>::get();
-
-              let expected_end_session_for_era = match active_era.start_session_index {
-                Some(start_session_index) => start_session_index,
-                None => current_session,
-              }
-              .saturating_add(T::SessionsPerEra::get());
-
-              log!(
-                debug,
-                "Fees compound session #{} started in block #{:?}, and is now expired.",
-                current_session,
-                start_block
-              );
-
-              // get current session total trade / currency
-              let session_fees_by_currency: Vec<(CurrencyId, Balance)> =
-                SessionTotalFees::<T>::iter_prefix(current_session)
-                  .map(|(currency_id, fee)| (currency_id, fee.fee))
-                  .collect();
-
-              // notify the staking pallet that we are done with this session
-              // the compute can be done for all stakers
-              if let Err(err) = T::Staking::on_session_end(
-                current_session,
-                session_fees_by_currency.clone(),
-                Self::account_id(),
-              ) {
-                log!(error, "Can't notify staking pallet {:?}", err);
+          Some(_) => {
+            let sessions_to_end = match Self::session_duration() {
+              Some(duration) => {
+                // the first session following the wall clock ends on the next multiple of the
+                // duration
+                if SessionEnd::<T>::get().is_none() {
+                  let now_as_millis_u64 = T::UnixTime::now().as_millis().saturated_into::<u64>();
+                  SessionEnd::<T>::put(
+                    (now_as_millis_u64 / duration)
+                      .saturating_add(1)
+                      .saturating_mul(duration),
+                  );
+                }
+                SessionsToEnd::<T>::take()
               }
-
-              // Emit end of session event on chain
-              Self::deposit_event(Event::<T>::SessionEnded {
-                era_index: active_era.index,
-                session_index: current_session,
-                session_fees_by_currency,
-              });
-
-              // increment our session
-              let new_session = current_session.saturating_add(1_u64);
-              CurrentSession::<T>::put(new_session);
-              StoredSessions::<T>::insert(current_session, ());
-              // record the session change for the era
-              active_era.last_session_block = Some(real_block);
-
-              if current_session == expected_end_session_for_era {
-                Self::deposit_event(Event::<T>::EraEnded {
-                  era_index: active_era.index,
-                });
-                // increment the era index
-                active_era.index = active_era.index.saturating_add(1);
-                // reset the era values
-                active_era.last_session_block = None;
-                active_era.start_block = None;
-                active_era.start_session_index = None;
-                active_era.start = None;
+              None => u32::from(Self::should_finalize_current_session()),
+            };
+
+            if sessions_to_end > 0 {
+              for _ in 0..sessions_to_end {
+                let era_ended = Self::end_session(&mut active_era, real_block);
+                if let Some(duration) = Self::session_duration() {
+                  SessionEnd::<T>::mutate(|session_end| {
+                    *session_end = session_end.map(|end| end.saturating_add(duration))
+                  });
+                }
+                // the remaining sessions are closed once the new era started
+                if era_ended {
+                  break;
+                }
               }
 
               // update active era
@@ -371,9 +425,161 @@ pub mod pallet {
       }
       // `on_finalize` weight is tracked in `on_initialize`
     }
+
+    fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+      Self::do_on_idle_account_fees_pruning(remaining_weight)
+    }
+  }
+
+  #[pallet::call]
+  impl<T: Config> Pallet<T> {
+    /// Set the distribution of the session fees of a currency between the stakers, the operator,
+    /// the treasury and the burn
+    ///
+    /// - `currency_id`: Currency of the fees
+    /// - `distribution`: Parts of the session fees, they must sum up to 100%. `None` restores the
+    ///   default distribution
+    ///
+    /// Emits `FeeDistributionUpdated` event when successful.
+    #[pallet::call_index(0)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_fee_distribution())]
+    pub fn set_fee_distribution(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      distribution: Option<FeeDistribution>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the distribution
+      match distribution {
+        Some(distribution) => {
+          ensure!(distribution.is_valid(), Error::<T>::InvalidFeeDistribution);
+          FeeDistributions::<T>::insert(currency_id, distribution);
+        }
+        None => FeeDistributions::<T>::remove(currency_id),
+      }
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::FeeDistributionUpdated {
+        currency_id,
+        distribution,
+      });
+
+      Ok(().into())
+    }
+
+    /// Set the duration of the sessions, the sessions end on the multiples of the duration since
+    /// the unix epoch, for example every hour of UTC time
+    ///
+    /// - `duration`: Duration of the sessions in milliseconds. `None` restores the sessions of
+    ///   `BlocksPerSession` blocks, starting from the current block
+    ///
+    /// Emits `SessionDurationUpdated` event when successful.
+    #[pallet::call_index(1)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_session_duration())]
+    pub fn set_session_duration(
+      origin: OriginFor<T>,
+      duration: Option<u64>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the duration, the end of the current session is computed with the new
+      //    duration in the next `on_finalize`
+      match duration {
+        Some(duration) => {
+          ensure!(!duration.is_zero(), Error::<T>::InvalidSessionDuration);
+          SessionDuration::<T>::put(duration);
+        }
+        None => {
+          SessionDuration::<T>::kill();
+          // the current session lasts `BlocksPerSession` blocks from now
+          ActiveEra::<T>::mutate(|active_era| {
+            if let Some(active_era) = active_era {
+              if active_era.start_block.is_some() {
+                active_era.last_session_block = Some(T::Security::get_current_block_count());
+              }
+            }
+          });
+        }
+      }
+      SessionEnd::<T>::kill();
+      SessionsToEnd::<T>::kill();
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::SessionDurationUpdated { duration });
+
+      Ok(().into())
+    }
   }
 
   impl<T: Config> Pallet<T> {
+    pub fn treasury_account_id() -> T::AccountId {
+      T::TreasuryPalletId::get().into_account_truncating()
+    }
+
+    // Distribution of the session fees of the currency, set by governance or the default one
+    pub fn fee_distribution_for(currency_id: CurrencyId) -> FeeDistribution {
+      Self::fee_distribution(currency_id)
+        .unwrap_or_else(|| FeeDistribution::default_for(currency_id))
+    }
+
+    // Split the session fees of the currency and transfer the operator and treasury shares, and
+    // burn the burn share. The stakers share is handed to the staking pallet by the caller.
+    //
+    // A share which can't be transferred or burned is kept in the fees pallet account and
+    // reported as zero.
+    pub(crate) fn distribute_session_fees(
+      session_index: SessionIndex,
+      currency_id: CurrencyId,
+      fees: Balance,
+    ) -> SessionFeesSplit {
+      let mut split = Self::fee_distribution_for(currency_id).split(fees);
+
+      for (share, destination) in [
+        (&mut split.operator, T::Staking::operator_account()),
+        (&mut split.treasury, Self::treasury_account_id()),
+      ] {
+        if share.is_zero() {
+          continue;
+        }
+        let result = T::CurrencyTidefi::transfer(
+          currency_id,
+          &Self::account_id(),
+          &destination,
+          *share,
+          false,
+        );
+        if result.is_err() {
+          log!(
+            error,
+            "session {} fees transfer failed for {:?}, outcome: {:?}",
+            session_index,
+            currency_id,
+            result
+          );
+          *share = Zero::zero();
+        }
+      }
+
+      if !split.burn.is_zero() {
+        let result = T::CurrencyTidefi::burn_from(currency_id, &Self::account_id(), split.burn);
+        if result.is_err() {
+          log!(
+            error,
+            "session {} fees burn failed for {:?}, outcome: {:?}",
+            session_index,
+            currency_id,
+            result
+          );
+          split.burn = Zero::zero();
+        }
+      }
+
+      split
+    }
+
     // Delete all session where the index < current_session - T::SessionsArchive
     pub(crate) fn drain_old_sessions() {
       let current_session = CurrentSession::<T>::get();
@@ -386,6 +592,112 @@ pub mod pallet {
       }
     }
 
+    /// Prune the account fees of the oldest era out of the archive, up to `T::BatchSize`
+    /// accounts.
+    pub fn do_on_idle_account_fees_pruning(remaining_weight: Weight) -> Weight {
+      // read the active era and the oldest era
+      let unaccounted_weight = T::DbWeight::get().reads(2);
+      let max_weight = <T as Config>::WeightInfo::on_idle_account_fees_pruning(T::BatchSize::get())
+        .saturating_add(unaccounted_weight);
+
+      if max_weight.any_gt(remaining_weight) {
+        log!(debug, "early exit because max weight is reached");
+        return Weight::zero();
+      }
+
+      let current_era_index = match Self::current_era() {
+        Some(current_era) => current_era.index,
+        None => return unaccounted_weight,
+      };
+
+      // the eras within the archive are kept
+      let era_index = OldestAccountFeesEra::<T>::get();
+      if era_index.saturating_add(T::ErasArchive::get()) >= current_era_index {
+        return unaccounted_weight;
+      }
+
+      let accounts = AccountFees::<T>::iter_key_prefix(era_index)
+        .take(T::BatchSize::get() as usize)
+        .collect::<Vec<T::AccountId>>();
+      let batch_size = accounts.len() as u32;
+
+      for account_id in accounts {
+        AccountFees::<T>::remove(era_index, account_id);
+      }
+
+      // all the account fees of the era are pruned
+      if batch_size < T::BatchSize::get() {
+        OldestAccountFeesEra::<T>::put(era_index.saturating_add(1));
+      }
+
+      log!(
+        debug,
+        "{} account fees of era {} pruned",
+        batch_size,
+        era_index
+      );
+
+      <T as Config>::WeightInfo::on_idle_account_fees_pruning(batch_size)
+        .saturating_add(unaccounted_weight)
+    }
+
+    // Get the fees paid by the account in each era not pruned yet, serialized for quick RPC call
+    pub fn get_account_fee_statements(account_id: &T::AccountId) -> Vec<AccountFeeStatement> {
+      let current_era_index = match Self::current_era() {
+        Some(current_era) => current_era.index,
+        None => return Vec::new(),
+      };
+
+      (Self::oldest_account_fees_era()..=current_era_index)
+        .filter_map(|era_index| {
+          let account_fees = AccountFees::<T>::get(era_index, account_id);
+          if account_fees.is_empty() {
+            return None;
+          }
+
+          let fees = account_fees
+            .into_iter()
+            .map(|(currency_id, fee)| CurrencyFeeStatement {
+              currency_id,
+              amount: fee.amount,
+              fee: fee.fee,
+              fee_tdfy: fee.fee_tdfy,
+            })
+            .collect::<Vec<CurrencyFeeStatement>>();
+          let total_fee_tdfy = fees.iter().fold(Balance::zero(), |total, fee| {
+            total.saturating_add(fee.fee_tdfy)
+          });
+
+          Some(AccountFeeStatement {
+            era_index,
+            fees,
+            total_fee_tdfy,
+          })
+        })
+        .collect()
+    }
+
+    // Number of sessions to end in the current block, up to `T::MaximumSessionsCatchUp` when the
+    // sessions follow the wall clock
+    pub(crate) fn sessions_to_end() -> u32 {
+      match Self::session_duration() {
+        Some(duration) => match (Self::current_era(), SessionEnd::<T>::get()) {
+          (Some(active_era), Some(session_end)) if active_era.start_block.is_some() => {
+            let now_as_millis_u64 = T::UnixTime::now().as_millis().saturated_into::<u64>();
+            if now_as_millis_u64 < session_end {
+              return 0;
+            }
+            // the current session and all the sessions skipped since the last block
+            (now_as_millis_u64.saturating_sub(session_end) / duration)
+              .saturating_add(1)
+              .min(T::MaximumSessionsCatchUp::get().into()) as u32
+          }
+          _ => 0,
+        },
+        None => u32::from(Self::should_finalize_current_session()),
+      }
+    }
+
     pub(crate) fn should_finalize_current_session() -> bool {
       if let Some(active_era) = Self::current_era() {
         let real_block = T::Security::get_current_block_count();
@@ -394,10 +706,114 @@ pub mod pallet {
           let expected_end_block_for_session =
             session_start_block.saturating_add(T::BlocksPerSession::get());
 
-          return real_block == expected_end_block_for_session;
+          return real_block >= expected_end_block_for_session;
+        }
+      }
+
+      false
+    }
+
+    // End the current session, returns whether the era ended with the session
+    fn end_session(
+      active_era: &mut ActiveEraInfo<T::BlockNumber>,
+      real_block: T::BlockNumber,
+    ) -> bool {
+      let current_session = CurrentSession::<T>::get();
+
+      let expected_end_session_for_era = match active_era.start_session_index {
+        Some(start_session_index) => start_session_index,
+        None => current_session,
+      }
+      .saturating_add(T::SessionsPerEra::get());
+
+      log!(
+        debug,
+        "Fees compound session #{} started in block #{:?}, and is now expired.",
+        current_session,
+        active_era.last_session_block.or(active_era.start_block)
+      );
+
+      // get current session total trade / currency
+      let session_fees_by_currency: Vec<(CurrencyId, Balance)> =
+        SessionTotalFees::<T>::iter_prefix(current_session)
+          .map(|(currency_id, fee)| (currency_id, fee.fee))
+          .collect();
+
+      // distribute the operator, treasury and burn shares of the session fees
+      let mut session_fees_split: Vec<(CurrencyId, SessionFeesSplit)> = session_fees_by_currency
+        .iter()
+        .map(|(currency_id, fees)| {
+          (
+            *currency_id,
+            Self::distribute_session_fees(current_session, *currency_id, *fees),
+          )
+        })
+        .collect();
+
+      // notify the staking pallet that we are done with this session
+      // the compute can be done for all stakers
+      let distributed_by_currency = T::Staking::distribute_session_rewards(
+        current_session,
+        session_fees_split
+          .iter()
+          .filter(|(_, split)| !split.stakers.is_zero())
+          .map(|(currency_id, split)| (*currency_id, split.stakers))
+          .collect(),
+        Self::account_id(),
+      );
+
+      // report the stakers share actually distributed to the pools, the fees not rewarded by
+      // the staking periods went to the operator
+      for (currency_id, split) in session_fees_split.iter_mut() {
+        if split.stakers.is_zero() {
+          continue;
+        }
+        match distributed_by_currency
+          .iter()
+          .find(|(distributed_currency_id, _)| distributed_currency_id == currency_id)
+        {
+          Some((_, distributed)) => {
+            split.operator = split
+              .operator
+              .saturating_add(split.stakers.saturating_sub(*distributed));
+            split.stakers = *distributed;
+          }
+          // the stakers share stays in the fees pallet account
+          None => split.stakers = Zero::zero(),
         }
       }
 
+      // Emit end of session event on chain
+      Self::deposit_event(Event::<T>::SessionEnded {
+        era_index: active_era.index,
+        session_index: current_session,
+        session_fees_by_currency,
+        session_fees_split,
+      });
+
+      // increment our session
+      let new_session = current_session.saturating_add(1_u64);
+      CurrentSession::<T>::put(new_session);
+      StoredSessions::<T>::insert(current_session, ());
+      // record the session change for the era
+      active_era.last_session_block = Some(real_block);
+
+      if current_session == expected_end_session_for_era {
+        Self::deposit_event(Event::<T>::EraEnded {
+          era_index: active_era.index,
+        });
+        // the claim window of the sunrise rewards of the era starts with its end
+        T::Sunrise::on_era_end(active_era.index);
+        // increment the era index
+        active_era.index = active_era.index.saturating_add(1);
+        // reset the era values
+        active_era.last_session_block = None;
+        active_era.start_block = None;
+        active_era.start_session_index = None;
+        active_era.start = None;
+        return true;
+      }
+
       false
     }
 
@@ -423,8 +839,15 @@ pub mod pallet {
 
   impl<T: Config> FeesExt<T::AccountId, T::BlockNumber> for Pallet<T> {
     fn era_blocks_count() -> T::BlockNumber {
-      T::BlocksPerSession::get()
-        .saturating_mul(T::BlockNumber::from(T::SessionsPerEra::get() as u32))
+      let session_blocks_count = match Self::session_duration() {
+        Some(duration) => duration
+          .checked_div(T::ExpectedBlockTime::get())
+          .unwrap_or_default()
+          .max(1)
+          .saturated_into(),
+        None => T::BlocksPerSession::get(),
+      };
+      session_blocks_count.saturating_mul(T::BlockNumber::from(T::SessionsPerEra::get() as u32))
     }
 
     fn account_id() -> T::AccountId {
@@ -481,6 +904,9 @@ pub mod pallet {
             currency_id,
           )?;
 
+          // Accrue the referral share of the fee to the referrer of the account
+          T::Sunrise::try_allocate_referral_rewards(&account_id, current_era.index, &new_fee)?;
+
           // Update fees pool for the current era / currency
           EraTotalFees::<T>::mutate_exists(
             current_era.index,
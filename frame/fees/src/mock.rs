@@ -23,7 +23,7 @@ use frame_utils::construct_mock_runtime;
 use sp_runtime::traits::AccountIdConversion;
 use system::EnsureRoot;
 use tidefi_primitives::{
-  assets, BlockNumber, CurrencyId, SessionIndex, StakeCurrencyMeta, SunriseSwapPool,
+  assets, BlockNumber, CurrencyId, EraIndex, SessionIndex, StakeCurrencyMeta, SunriseSwapPool,
 };
 
 pub struct EnsureRootOrAssetRegistry;
@@ -61,17 +61,19 @@ impl EnsureOrigin<RuntimeOrigin> for EnsureRootOrAssetRegistry {
 construct_mock_runtime!({
   Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
   AssetRegistry: pallet_asset_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
-  Fees: pallet_fees::{Pallet, Config<T>, Storage, Event<T>},
+  Fees: pallet_fees::{Pallet, Call, Config<T>, Storage, Event<T>},
   Sunrise: pallet_sunrise::{Pallet, Config<T>, Storage, Event<T>},
   Security: pallet_security::{Pallet, Call, Config, Storage, Event<T>},
   TidefiStaking: pallet_tidefi_stake::{Pallet, Call, Config<T>, Storage, Event<T>},
 }, {
   pub const AssetDeposit: u64 = 1;
+  pub const PauseDuration: u64 = 100;
   pub const ApprovalDeposit: u64 = 1;
   pub const StringLimit: u32 = 50;
   pub const MetadataDepositBase: u64 = 1;
   pub const MetadataDepositPerByte: u64 = 1;
   pub const SunrisePalletId: PalletId = PalletId(*b"sunr*pal");
+  pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
   pub const TidefiPalletId: PalletId = PalletId(*b"wrpr*pal");
   pub const QuorumPalletId: PalletId = PalletId(*b"qurm*pal");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
@@ -79,14 +81,23 @@ construct_mock_runtime!({
   pub const PeriodBasis: BlockNumber = 1000u32;
   pub const SessionsPerEra: SessionIndex = 10;
   pub const SessionsArchive: SessionIndex = 2;
+  pub const ErasArchive: EraIndex = 2;
+  pub const MaximumSessionsCatchUp: u32 = 5;
   pub const BlocksPerSession: BlockNumber = 50;
+  pub const ExpectedBlockTime: u64 = 6_000;
   pub const BlocksForceUnstake: BlockNumber = 10;
   pub const StakeAccountCap: u32 = 10;
   pub const FeeAmount: Permill = Permill::from_perthousand(20);
   pub const MarketMakerFeeAmount: Permill = Permill::from_perthousand(10);
   pub const MarketMakerLimitFeeAmount: Permill = Permill::from_perthousand(10);
   pub const StakingRewardCap: u32 = 10;
+  pub const SessionRewardsHistoryCap: u32 = 10;
+  pub const BlocksPerYear: BlockNumber = 14400 * 365;
   pub const Cooldown: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ClaimWindow: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ReferralShare: Permill = Permill::from_percent(10);
+  // max 1k referral rewards per referee
+  pub const MaximumReferralRewardPerReferee: Balance = 1_000_000_000_000_000;
   pub const MaximumRewardPerSwap: Balance = 10_000_000_000_000_000;
   pub const LeftoverSwapRebates: FixedU128 = FixedU128::from_inner(500_000_000_000_000_000);
   pub const BatchSize: u32 = 10;
@@ -118,15 +129,24 @@ impl pallet_assets::Config for Test {
 impl pallet_sunrise::Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type Security = Security;
+  type AssetRegistry = AssetRegistry;
   type SunrisePalletId = SunrisePalletId;
   type CurrencyTidefi = Adapter<AccountId>;
   type Cooldown = Cooldown;
+  type ClaimWindow = ClaimWindow;
+  type ReferralShare = ReferralShare;
+  type MaximumReferralRewardPerReferee = MaximumReferralRewardPerReferee;
+  type BatchSize = BatchSize;
   type MaximumRewardPerSwap = MaximumRewardPerSwap;
   type LeftoverSwapRebates = LeftoverSwapRebates;
+  type TreasuryPalletId = TreasuryPalletId;
+  type ForceOrigin = system::EnsureRoot<AccountId>;
+  type WeightInfo = pallet_sunrise::weights::SubstrateWeight<Test>;
 }
 
 impl pallet_fees::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type TreasuryPalletId = TreasuryPalletId;
   type Security = Security;
   type FeesPalletId = TidefiPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
@@ -134,7 +154,11 @@ impl pallet_fees::Config for Test {
   type UnixTime = Timestamp;
   type SessionsPerEra = SessionsPerEra;
   type SessionsArchive = SessionsArchive;
+  type ErasArchive = ErasArchive;
+  type MaximumSessionsCatchUp = MaximumSessionsCatchUp;
+  type BatchSize = BatchSize;
   type BlocksPerSession = BlocksPerSession;
+  type ExpectedBlockTime = ExpectedBlockTime;
   type FeeAmount = FeeAmount;
   type MarketMakerFeeAmount = MarketMakerFeeAmount;
   type MarketMakerLimitFeeAmount = MarketMakerLimitFeeAmount;
@@ -152,8 +176,12 @@ impl pallet_tidefi_stake::Config for Test {
   type BlocksForceUnstake = BlocksForceUnstake;
   type AssetRegistry = AssetRegistry;
   type StakingRewardCap = StakingRewardCap;
+  type SessionRewardsHistoryCap = SessionRewardsHistoryCap;
+  type BlocksPerYear = BlocksPerYear;
   type Security = Security;
+  type Sunrise = Sunrise;
   type BatchSize = BatchSize;
+  type ForceOrigin = EnsureRoot<AccountId>;
 }
 
 impl pallet_asset_registry::Config for Test {
@@ -165,6 +193,13 @@ impl pallet_asset_registry::Config for Test {
 
 impl pallet_security::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type PauseOrigin = system::EnsureRoot<AccountId>;
+  type ResumeOrigin = system::EnsureRoot<AccountId>;
+  type PauseDuration = PauseDuration;
+  type MaxPauseRenewals = ConstU32<2>;
+  type UnpausablePallets = ();
+  type MaxNameLength = ConstU32<32>;
+  type MaxReasonLength = ConstU32<64>;
   type WeightInfo = pallet_security::weights::SubstrateWeight<Test>;
 }
 
@@ -205,14 +240,24 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
   .assimilate_storage(&mut t)
   .unwrap();
   pallet_asset_registry::GenesisConfig::<Test> {
-    assets: vec![(
-      CurrencyId::Wrapped(2),
-      "Test".into(),
-      "TEST".into(),
-      8,
-      vec![],
-    )],
+    assets: vec![
+      (
+        CurrencyId::Wrapped(2),
+        "Test".into(),
+        "TEST".into(),
+        8,
+        vec![],
+      ),
+      (
+        CurrencyId::Wrapped(4),
+        "Tether".into(),
+        "USDT".into(),
+        6,
+        vec![],
+      ),
+    ],
     account: AccountId(0),
+    infos: Vec::new(),
   }
   .assimilate_storage(&mut t)
   .unwrap();
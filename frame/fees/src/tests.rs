@@ -14,15 +14,65 @@
 // You should have received a copy of the GNU General Public License
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::mock::{new_test_ext, AccountId, Fees, Sunrise};
-use frame_support::assert_ok;
-use sp_runtime::{FixedPointNumber, FixedU128};
+use crate::{
+  mock::{
+    new_test_ext, AccountId, Adapter, BatchSize, BlocksPerSession, ErasArchive, Fees,
+    MaximumSessionsCatchUp, RuntimeEvent, RuntimeOrigin, Sunrise, System, Test, Timestamp,
+  },
+  AccountFeeStatement, AccountFees, CurrencyFeeStatement, Error, Event, FeeDistribution,
+  FeeDistributions, OldestAccountFeesEra, SessionFeesSplit, SessionTotalFees,
+};
+use frame_support::{
+  assert_noop, assert_ok,
+  traits::{
+    fungibles::{Inspect, Mutate},
+    Get, Hooks,
+  },
+  weights::Weight,
+};
+use sp_runtime::{traits::BadOrigin, FixedPointNumber, FixedU128, Permill};
 use tidefi_primitives::{
   assets::Asset,
   pallet::{FeesExt, SunriseExt},
-  CurrencyId, SwapType,
+  CurrencyId, EraIndex, Fee, SwapType,
 };
 
+const OPERATOR_ACCOUNT_ID: AccountId = AccountId(0);
+const HOUR: u64 = 3_600_000;
+
+fn add_account_fee(era_index: EraIndex, account_id: AccountId, currency_id: CurrencyId, fee: Fee) {
+  AccountFees::<Test>::mutate(era_index, account_id, |account_fees| {
+    assert_ok!(account_fees.try_push((currency_id, fee)));
+  });
+}
+
+// Move the active era forward until `era_index`
+fn start_era_until(era_index: EraIndex) {
+  while Fees::current_era().unwrap().index < era_index {
+    Fees::start_era();
+  }
+}
+
+// Start the fees era in the block 1
+fn start_fees_era() {
+  pallet_security::CurrentBlockCount::<Test>::put(1);
+  Fees::on_finalize(1);
+}
+
+fn run_block(block_number: u64) {
+  Fees::on_initialize(block_number);
+  Fees::on_finalize(block_number);
+}
+
+fn fee_distribution() -> FeeDistribution {
+  FeeDistribution {
+    stakers: Permill::from_percent(40),
+    operator: Permill::from_percent(30),
+    treasury: Permill::from_percent(20),
+    burn: Permill::from_percent(10),
+  }
+}
+
 #[test]
 pub fn check_genesis_config() {
   new_test_ext().execute_with(|| {
@@ -127,6 +177,33 @@ pub fn register_swap_fees() {
   });
 }
 
+#[test]
+pub fn register_swap_fees_accrues_referral_rewards() {
+  new_test_ext().execute_with(|| {
+    pallet_sunrise::Referrers::<Test>::insert(AccountId(3u64), AccountId(4u64));
+    pallet_sunrise::ReferralBudget::<Test>::put(1_000_000_000_000_000);
+
+    let current_era = Fees::current_era().unwrap().index;
+    // 100 TDFY @ 2% should cost 2 TDFY
+    let calculated_fee = Fees::register_swap_fees(
+      3u64.into(),
+      CurrencyId::Tdfy,
+      100_000_000_000_000,
+      SwapType::Limit,
+      false,
+    )
+    .unwrap();
+    assert_eq!(calculated_fee.fee_tdfy, 2_000_000_000_000);
+
+    // 10% of the fee is accrued to the referrer
+    assert_eq!(
+      Sunrise::referral_rewards(AccountId(4u64), current_era),
+      200_000_000_000
+    );
+    assert_eq!(Sunrise::referral_budget(), 999_800_000_000_000);
+  });
+}
+
 #[test]
 pub fn test_calc_reward() {
   new_test_ext().execute_with(|| {
@@ -253,3 +330,437 @@ pub fn test_maximum_fee_values() {
     assert_eq!(reward, 12_500_000_000_000_000);
   });
 }
+
+#[test]
+pub fn set_fee_distribution() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Fees::set_fee_distribution(
+      RuntimeOrigin::root(),
+      CurrencyId::Tdfy,
+      Some(fee_distribution())
+    ));
+    assert_eq!(
+      FeeDistributions::<Test>::get(CurrencyId::Tdfy),
+      Some(fee_distribution())
+    );
+
+    // restore the default distribution
+    assert_ok!(Fees::set_fee_distribution(
+      RuntimeOrigin::root(),
+      CurrencyId::Tdfy,
+      None
+    ));
+    assert_eq!(
+      Fees::fee_distribution_for(CurrencyId::Tdfy),
+      FeeDistribution::default_for(CurrencyId::Tdfy)
+    );
+  });
+}
+
+#[test]
+pub fn set_fee_distribution_fails() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      Fees::set_fee_distribution(
+        RuntimeOrigin::signed(AccountId(1u64)),
+        CurrencyId::Tdfy,
+        Some(fee_distribution())
+      ),
+      BadOrigin
+    );
+
+    // 110%
+    let mut distribution = fee_distribution();
+    distribution.stakers = Permill::from_percent(50);
+    assert_noop!(
+      Fees::set_fee_distribution(RuntimeOrigin::root(), CurrencyId::Tdfy, Some(distribution)),
+      Error::<Test>::InvalidFeeDistribution
+    );
+  });
+}
+
+#[test]
+pub fn default_fee_distribution() {
+  new_test_ext().execute_with(|| {
+    // TDFY fees go to the operator
+    let split = Fees::fee_distribution_for(CurrencyId::Tdfy).split(1_000);
+    assert_eq!(split.operator, 1_000);
+    assert_eq!(split.stakers, 0);
+
+    // wrapped assets fees go to the stakers
+    let split = Fees::fee_distribution_for(CurrencyId::Wrapped(4)).split(1_000);
+    assert_eq!(split.stakers, 1_000);
+    assert_eq!(split.operator, 0);
+  });
+}
+
+#[test]
+pub fn distribute_session_fees() {
+  new_test_ext().execute_with(|| {
+    const SESSION_FEES: u128 = 1_000_000_000_000_000;
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tdfy,
+      &Fees::account_id(),
+      SESSION_FEES
+    ));
+    assert_ok!(Fees::set_fee_distribution(
+      RuntimeOrigin::root(),
+      CurrencyId::Tdfy,
+      Some(fee_distribution())
+    ));
+    let fees_account_balance_before = Adapter::balance(CurrencyId::Tdfy, &Fees::account_id());
+    let operator_balance_before = Adapter::balance(CurrencyId::Tdfy, &OPERATOR_ACCOUNT_ID);
+    let total_issuance_before = Adapter::total_issuance(CurrencyId::Tdfy);
+
+    let split = Fees::distribute_session_fees(1, CurrencyId::Tdfy, SESSION_FEES);
+    assert_eq!(
+      split,
+      SessionFeesSplit {
+        stakers: 400_000_000_000_000,
+        operator: 300_000_000_000_000,
+        treasury: 200_000_000_000_000,
+        burn: 100_000_000_000_000,
+      }
+    );
+
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tdfy, &OPERATOR_ACCOUNT_ID),
+      operator_balance_before + split.operator
+    );
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tdfy, &Fees::treasury_account_id()),
+      split.treasury
+    );
+    assert_eq!(
+      Adapter::total_issuance(CurrencyId::Tdfy),
+      total_issuance_before - split.burn
+    );
+    // the stakers share stays in the fees pallet account until handed to the staking pallet
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tdfy, &Fees::account_id()),
+      fees_account_balance_before - split.operator - split.treasury - split.burn
+    );
+  });
+}
+
+#[test]
+pub fn session_fees_split_reports_the_stakers_share_distributed() {
+  new_test_ext().execute_with(|| {
+    const SESSION_FEES: u128 = 1_000_000_000_000_000;
+    start_fees_era();
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tdfy,
+      &Fees::account_id(),
+      SESSION_FEES
+    ));
+    assert_ok!(Fees::set_fee_distribution(
+      RuntimeOrigin::root(),
+      CurrencyId::Tdfy,
+      Some(fee_distribution())
+    ));
+    let current_session = Fees::current_session();
+    SessionTotalFees::<Test>::insert(
+      current_session,
+      CurrencyId::Tdfy,
+      Fee {
+        amount: 0,
+        fee: SESSION_FEES,
+        fee_tdfy: SESSION_FEES,
+      },
+    );
+
+    pallet_security::CurrentBlockCount::<Test>::put(1 + BlocksPerSession::get());
+    run_block(2);
+    assert_eq!(Fees::current_session(), current_session + 1);
+
+    // without stakers, the stakers share is not rewarded and goes to the operator
+    System::assert_has_event(RuntimeEvent::Fees(Event::SessionEnded {
+      era_index: 1,
+      session_index: current_session,
+      session_fees_by_currency: vec![(CurrencyId::Tdfy, SESSION_FEES)],
+      session_fees_split: vec![(
+        CurrencyId::Tdfy,
+        SessionFeesSplit {
+          stakers: 0,
+          operator: 700_000_000_000_000,
+          treasury: 200_000_000_000_000,
+          burn: 100_000_000_000_000,
+        },
+      )],
+    }));
+  });
+}
+
+#[test]
+pub fn account_fees_are_pruned_out_of_the_archive() {
+  new_test_ext().execute_with(|| {
+    let fee = Fee {
+      amount: 100,
+      fee: 2,
+      fee_tdfy: 2,
+    };
+    add_account_fee(1, AccountId(1), CurrencyId::Tdfy, fee.clone());
+    add_account_fee(2, AccountId(1), CurrencyId::Tdfy, fee);
+
+    // all the eras are within the archive
+    start_era_until(1 + ErasArchive::get());
+    Fees::on_idle(1, Weight::MAX);
+    Fees::on_idle(2, Weight::MAX);
+    assert_eq!(Fees::oldest_account_fees_era(), 1);
+    assert_eq!(AccountFees::<Test>::iter_prefix(1).count(), 1);
+
+    // the era 1 is out of the archive
+    Fees::start_era();
+    Fees::on_idle(3, Weight::MAX);
+    Fees::on_idle(4, Weight::MAX);
+    assert_eq!(Fees::oldest_account_fees_era(), 2);
+    assert_eq!(AccountFees::<Test>::iter_prefix(1).count(), 0);
+    assert_eq!(AccountFees::<Test>::iter_prefix(2).count(), 1);
+  });
+}
+
+#[test]
+pub fn account_fees_pruning_is_batched() {
+  new_test_ext().execute_with(|| {
+    let accounts = BatchSize::get() + 2;
+    (0..accounts).for_each(|i| {
+      add_account_fee(
+        0,
+        AccountId(i.into()),
+        CurrencyId::Tdfy,
+        Fee {
+          amount: 100,
+          fee: 2,
+          fee_tdfy: 2,
+        },
+      )
+    });
+    start_era_until(1 + ErasArchive::get());
+
+    Fees::on_idle(1, Weight::MAX);
+    assert_eq!(AccountFees::<Test>::iter_prefix(0).count(), 2);
+    assert_eq!(Fees::oldest_account_fees_era(), 0);
+
+    Fees::on_idle(2, Weight::MAX);
+    assert_eq!(AccountFees::<Test>::iter_prefix(0).count(), 0);
+    assert_eq!(Fees::oldest_account_fees_era(), 1);
+  });
+}
+
+#[test]
+pub fn account_fees_pruning_without_enough_weight() {
+  new_test_ext().execute_with(|| {
+    add_account_fee(
+      0,
+      AccountId(1),
+      CurrencyId::Tdfy,
+      Fee {
+        amount: 100,
+        fee: 2,
+        fee_tdfy: 2,
+      },
+    );
+    start_era_until(1 + ErasArchive::get());
+
+    Fees::on_idle(1, Weight::zero());
+    assert_eq!(AccountFees::<Test>::iter_prefix(0).count(), 1);
+    assert_eq!(Fees::oldest_account_fees_era(), 0);
+  });
+}
+
+#[test]
+pub fn get_account_fee_statements() {
+  new_test_ext().execute_with(|| {
+    add_account_fee(
+      0,
+      AccountId(1),
+      CurrencyId::Tdfy,
+      Fee {
+        amount: 1_000,
+        fee: 20,
+        fee_tdfy: 20,
+      },
+    );
+    add_account_fee(
+      1,
+      AccountId(1),
+      CurrencyId::Tdfy,
+      Fee {
+        amount: 100,
+        fee: 2,
+        fee_tdfy: 2,
+      },
+    );
+    add_account_fee(
+      1,
+      AccountId(1),
+      CurrencyId::Wrapped(4),
+      Fee {
+        amount: 500,
+        fee: 10,
+        fee_tdfy: 30,
+      },
+    );
+    add_account_fee(
+      1,
+      AccountId(2),
+      CurrencyId::Tdfy,
+      Fee {
+        amount: 100,
+        fee: 2,
+        fee_tdfy: 2,
+      },
+    );
+    // the era 0 is already pruned
+    OldestAccountFeesEra::<Test>::put(1);
+    start_era_until(3);
+
+    assert_eq!(
+      Fees::get_account_fee_statements(&AccountId(1)),
+      vec![AccountFeeStatement {
+        era_index: 1,
+        fees: vec![
+          CurrencyFeeStatement {
+            currency_id: CurrencyId::Tdfy,
+            amount: 100,
+            fee: 2,
+            fee_tdfy: 2,
+          },
+          CurrencyFeeStatement {
+            currency_id: CurrencyId::Wrapped(4),
+            amount: 500,
+            fee: 10,
+            fee_tdfy: 30,
+          },
+        ],
+        total_fee_tdfy: 32,
+      }]
+    );
+    assert!(Fees::get_account_fee_statements(&AccountId(3)).is_empty());
+  });
+}
+
+#[test]
+pub fn set_session_duration() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    assert_ok!(Fees::set_session_duration(
+      RuntimeOrigin::root(),
+      Some(HOUR)
+    ));
+    assert_eq!(Fees::session_duration(), Some(HOUR));
+    System::assert_last_event(RuntimeEvent::Fees(Event::SessionDurationUpdated {
+      duration: Some(HOUR),
+    }));
+
+    // the current session lasts `BlocksPerSession` blocks from the current block
+    start_fees_era();
+    pallet_security::CurrentBlockCount::<Test>::put(10);
+    assert_ok!(Fees::set_session_duration(RuntimeOrigin::root(), None));
+    assert_eq!(Fees::session_duration(), None);
+    assert_eq!(Fees::session_end(), None);
+    assert_eq!(Fees::current_era().unwrap().last_session_block, Some(10));
+  });
+}
+
+#[test]
+pub fn era_blocks_count_follows_the_session_duration() {
+  new_test_ext().execute_with(|| {
+    assert_eq!(Fees::era_blocks_count(), 50 * 10);
+
+    // hourly sessions of 6 seconds blocks
+    assert_ok!(Fees::set_session_duration(
+      RuntimeOrigin::root(),
+      Some(HOUR)
+    ));
+    assert_eq!(Fees::era_blocks_count(), 600 * 10);
+  });
+}
+
+#[test]
+pub fn set_session_duration_fails() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      Fees::set_session_duration(RuntimeOrigin::signed(AccountId(1)), Some(HOUR)),
+      BadOrigin
+    );
+    assert_noop!(
+      Fees::set_session_duration(RuntimeOrigin::root(), Some(0)),
+      Error::<Test>::InvalidSessionDuration
+    );
+  });
+}
+
+#[test]
+pub fn session_ends_after_missed_block() {
+  new_test_ext().execute_with(|| {
+    start_fees_era();
+    let current_session = Fees::current_session();
+
+    // the block count skipped the expected end of the session
+    pallet_security::CurrentBlockCount::<Test>::put(1 + BlocksPerSession::get() + 3);
+    run_block(2);
+    assert_eq!(Fees::current_session(), current_session + 1);
+  });
+}
+
+#[test]
+pub fn sessions_follow_wall_clock() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    Timestamp::set_timestamp(HOUR / 2);
+    start_fees_era();
+    assert_ok!(Fees::set_session_duration(
+      RuntimeOrigin::root(),
+      Some(HOUR)
+    ));
+    let current_session = Fees::current_session();
+
+    // the session ends on the next hour
+    run_block(2);
+    assert_eq!(Fees::session_end(), Some(HOUR));
+    assert_eq!(Fees::current_session(), current_session);
+
+    Timestamp::set_timestamp(HOUR - 1);
+    run_block(3);
+    assert_eq!(Fees::current_session(), current_session);
+
+    // the sessions skipped since the last block are closed
+    Timestamp::set_timestamp(3 * HOUR + 1);
+    run_block(4);
+    assert_eq!(Fees::current_session(), current_session + 3);
+    assert_eq!(Fees::session_end(), Some(4 * HOUR));
+    System::assert_has_event(RuntimeEvent::Fees(Event::SessionEnded {
+      era_index: 1,
+      session_index: current_session + 2,
+      session_fees_by_currency: Vec::new(),
+      session_fees_split: Vec::new(),
+    }));
+  });
+}
+
+#[test]
+pub fn wall_clock_sessions_catch_up_is_capped() {
+  new_test_ext().execute_with(|| {
+    Timestamp::set_timestamp(HOUR / 2);
+    start_fees_era();
+    assert_ok!(Fees::set_session_duration(
+      RuntimeOrigin::root(),
+      Some(HOUR)
+    ));
+    run_block(2);
+    let current_session = Fees::current_session();
+
+    // 8 sessions ended since the last block
+    Timestamp::set_timestamp(8 * HOUR);
+    run_block(3);
+    assert_eq!(
+      Fees::current_session(),
+      current_session + u64::from(MaximumSessionsCatchUp::get())
+    );
+
+    run_block(4);
+    assert_eq!(Fees::current_session(), current_session + 8);
+    assert_eq!(Fees::session_end(), Some(9 * HOUR));
+  });
+}
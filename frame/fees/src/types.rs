@@ -0,0 +1,113 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Distribution of the session fees and account fee statements.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{inherent::Vec, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{PerThing, Permill};
+use tidefi_primitives::{Balance, CurrencyId, EraIndex};
+
+/// Distribution of the session fees of a currency, the parts sum up to 100%.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FeeDistribution {
+  /// Part handed to the staking pallet, the fees not rewarded by the staking periods go to the
+  /// operator
+  pub stakers: Permill,
+  pub operator: Permill,
+  pub treasury: Permill,
+  /// Part burned from the fees pallet account
+  pub burn: Permill,
+}
+
+impl FeeDistribution {
+  /// Distribution applied when governance didn't set one for the currency, the TDFY fees go to
+  /// the operator and the wrapped assets fees to the stakers.
+  pub fn default_for(currency_id: CurrencyId) -> Self {
+    let (stakers, operator) = match currency_id {
+      CurrencyId::Tdfy => (Permill::zero(), Permill::one()),
+      CurrencyId::Wrapped(_) => (Permill::one(), Permill::zero()),
+    };
+    Self {
+      stakers,
+      operator,
+      treasury: Permill::zero(),
+      burn: Permill::zero(),
+    }
+  }
+
+  /// Whether the parts sum up to 100%.
+  pub fn is_valid(&self) -> bool {
+    [self.stakers, self.operator, self.treasury, self.burn]
+      .iter()
+      .try_fold(0_u32, |total, part| total.checked_add(part.deconstruct()))
+      == Some(Permill::ACCURACY)
+  }
+
+  /// Split `fees` by the distribution, the rounding remainder goes to the stakers.
+  pub fn split(&self, fees: Balance) -> SessionFeesSplit {
+    let operator = self.operator.mul_floor(fees);
+    let treasury = self.treasury.mul_floor(fees);
+    let burn = self.burn.mul_floor(fees);
+    SessionFeesSplit {
+      stakers: fees
+        .saturating_sub(operator)
+        .saturating_sub(treasury)
+        .saturating_sub(burn),
+      operator,
+      treasury,
+      burn,
+    }
+  }
+}
+
+/// Session fees of a currency, split by its distribution.
+#[derive(Clone, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SessionFeesSplit {
+  /// Distributed to the staking pools once the session ended, the fees not rewarded by the
+  /// staking periods are added to the operator share
+  pub stakers: Balance,
+  pub operator: Balance,
+  pub treasury: Balance,
+  pub burn: Balance,
+}
+
+/// Fees paid by an account during an era, by currency.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccountFeeStatement {
+  pub era_index: EraIndex,
+  pub fees: Vec<CurrencyFeeStatement>,
+  /// Sum of the fees TDFY value
+  pub total_fee_tdfy: Balance,
+}
+
+/// Fees paid by an account in a currency.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CurrencyFeeStatement {
+  pub currency_id: CurrencyId,
+  /// Total amount swapped, before fees
+  pub amount: Balance,
+  pub fee: Balance,
+  /// TDFY value of the fee when it was paid
+  pub fee_tdfy: Balance,
+}
@@ -24,6 +24,9 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for `pallet_fees`.
 pub trait WeightInfo {
    fn on_finalize(a: u32, b: u32) -> Weight;
+   fn set_fee_distribution() -> Weight;
+   fn on_idle_account_fees_pruning(b: u32) -> Weight;
+   fn set_session_duration() -> Weight;
 }
 
 /// Weights for `pallet_fees` using the Substrate node and recommended hardware.
@@ -37,10 +40,26 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_ref_time(45_319_000).saturating_mul(b.into()))
 			.saturating_add(T::DbWeight::get().reads(12_u64))
 			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(a.into())))
-			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(b.into())))
 			.saturating_add(T::DbWeight::get().writes(9_u64))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
-			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(b.into())))
+	}
+	fn set_fee_distribution() -> Weight {
+		Weight::from_ref_time(16_903_000)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn on_idle_account_fees_pruning(b: u32) -> Weight {
+		Weight::from_ref_time(12_614_000)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_ref_time(3_148_000).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(b.into())))
+	}
+	fn set_session_duration() -> Weight {
+		Weight::from_ref_time(19_072_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
-
 }
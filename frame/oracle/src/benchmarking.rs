@@ -64,9 +64,10 @@ benchmarks! {
    }: _(RawOrigin::Signed(user), caller)
    add_market_pair {
       let market_pair = MarketPair {
-         base_asset: CurrencyId::Wrapped(1),
-         quote_asset: CurrencyId::Wrapped(100),
+         base_asset: CurrencyId::Wrapped(TEST_TOKEN),
+         quote_asset: CurrencyId::Wrapped(TEST_TOKEN2),
       };
+      SupportedMarketPairs::<T>::kill();
    }: _(RawOrigin::Root, market_pair)
    remove_market_pair {
       let market_pair = MarketPair {
@@ -41,7 +41,8 @@ pub mod pallet {
     traits::fungibles::{Inspect, InspectHold, Mutate, MutateHold, Transfer},
     PalletId,
   };
-  use frame_system::{ensure_root, pallet_prelude::*};
+  use frame_system::pallet_prelude::*;
+  use pallet_asset_registry::AssetInfoProvider;
   #[cfg(feature = "std")]
   use sp_runtime::traits::AccountIdConversion;
   use sp_runtime::Permill;
@@ -85,6 +86,12 @@ pub mod pallet {
     /// Tidefi sunrise traits
     type Sunrise: SunriseExt<Self::AccountId, Self::BlockNumber>;
 
+    /// The origin which may add and remove the supported market pairs
+    type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// Asset registry, the assets of the market pairs must be listed
+    type AssetRegistry: AssetInfoProvider;
+
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + Mutate<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
@@ -620,10 +627,16 @@ pub mod pallet {
       origin: OriginFor<T>,
       market_pair: MarketPair,
     ) -> DispatchResultWithPostInfo {
-      // 1. Make sure this is from sudo user
-      ensure_root(origin)?;
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure both assets are listed in the asset registry
+      ensure!(
+        Self::is_market_pair_listed(&market_pair),
+        Error::<T>::UnknownAsset
+      );
 
-      // 2. Add the new market pair to the storage
+      // 3. Add the new market pair to the storage
       let mut supported_market_pairs = Self::supported_market_pairs();
       ensure!(
         !supported_market_pairs
@@ -636,7 +649,7 @@ pub mod pallet {
         .map_err(|_| Error::<T>::MarketPairOverflow)?;
       SupportedMarketPairs::<T>::put(supported_market_pairs);
 
-      // 3. Emit event on chain
+      // 4. Emit event on chain
       Self::deposit_event(Event::<T>::MarketPairAdded { market_pair });
 
       // don't take tx fees on success
@@ -655,8 +668,8 @@ pub mod pallet {
       origin: OriginFor<T>,
       market_pair: MarketPair,
     ) -> DispatchResultWithPostInfo {
-      // 1. Make sure this is from sudo user
-      ensure_root(origin)?;
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
 
       // 2. Remove the market pair from the storage
       let mut supported_market_pairs = Self::supported_market_pairs();
@@ -1059,42 +1072,51 @@ pub mod pallet {
       })
     }
 
+    fn is_market_pair_listed(market_pair: &MarketPair) -> bool {
+      T::AssetRegistry::decimals(market_pair.base_asset).is_some()
+        && T::AssetRegistry::decimals(market_pair.quote_asset).is_some()
+    }
+
     fn set_initial_market_pairs() {
       if Self::supported_market_pairs().is_empty() {
+        // only the pairs of the assets listed in the asset registry are supported
+        let initial_market_pairs: Vec<MarketPair> = vec![
+          // ATH_USDC
+          MarketPair {
+            base_asset: Asset::AllTimeHigh.currency_id(),
+            quote_asset: Asset::USDCoin.currency_id(),
+          },
+          // BTC_USDC
+          MarketPair {
+            base_asset: Asset::Bitcoin.currency_id(),
+            quote_asset: Asset::USDCoin.currency_id(),
+          },
+          // ETH_USDC
+          MarketPair {
+            base_asset: Asset::Ethereum.currency_id(),
+            quote_asset: Asset::USDCoin.currency_id(),
+          },
+          // TDFY_BTC
+          MarketPair {
+            base_asset: Asset::Tdfy.currency_id(),
+            quote_asset: Asset::Bitcoin.currency_id(),
+          },
+          // TDFY_ETH
+          MarketPair {
+            base_asset: Asset::Tdfy.currency_id(),
+            quote_asset: Asset::Ethereum.currency_id(),
+          },
+          // TDFY_USDC
+          MarketPair {
+            base_asset: Asset::Tdfy.currency_id(),
+            quote_asset: Asset::USDCoin.currency_id(),
+          },
+        ]
+        .into_iter()
+        .filter(Self::is_market_pair_listed)
+        .collect();
         let initial_supported_market_pairs: BoundedVec<MarketPair, T::SupportedMarketPairsLimit> =
-          BoundedVec::try_from(vec![
-            // ATH_USDC
-            MarketPair {
-              base_asset: Asset::AllTimeHigh.currency_id(),
-              quote_asset: Asset::USDCoin.currency_id(),
-            },
-            // BTC_USDC
-            MarketPair {
-              base_asset: Asset::Bitcoin.currency_id(),
-              quote_asset: Asset::USDCoin.currency_id(),
-            },
-            // ETH_USDC
-            MarketPair {
-              base_asset: Asset::Ethereum.currency_id(),
-              quote_asset: Asset::USDCoin.currency_id(),
-            },
-            // TDFY_BTC
-            MarketPair {
-              base_asset: Asset::Tdfy.currency_id(),
-              quote_asset: Asset::Bitcoin.currency_id(),
-            },
-            // TDFY_ETH
-            MarketPair {
-              base_asset: Asset::Tdfy.currency_id(),
-              quote_asset: Asset::Ethereum.currency_id(),
-            },
-            // TDFY_USDC
-            MarketPair {
-              base_asset: Asset::Tdfy.currency_id(),
-              quote_asset: Asset::USDCoin.currency_id(),
-            },
-          ])
-          .unwrap();
+          BoundedVec::try_from(initial_market_pairs).unwrap();
 
         SupportedMarketPairs::<T>::put(initial_supported_market_pairs.clone());
       }
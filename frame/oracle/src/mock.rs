@@ -22,7 +22,7 @@ use frame_system as system;
 use frame_utils::construct_mock_runtime;
 use sp_runtime::traits::AccountIdConversion;
 use system::EnsureRoot;
-use tidefi_primitives::{assets, BlockNumber, CurrencyId, MarketPair, SessionIndex};
+use tidefi_primitives::{assets, BlockNumber, CurrencyId, EraIndex, MarketPair, SessionIndex};
 
 pub struct EnsureRootOrAssetRegistry;
 impl EnsureOrigin<RuntimeOrigin> for EnsureRootOrAssetRegistry {
@@ -66,18 +66,23 @@ construct_mock_runtime!({
   TidefiStaking: pallet_tidefi_stake::{Pallet, Call, Config<T>, Storage, Event<T>},
 }, {
   pub const AssetDeposit: u64 = 1;
+  pub const PauseDuration: u64 = 100;
   pub const ApprovalDeposit: u64 = 1;
   pub const StringLimit: u32 = 50;
   pub const MetadataDepositBase: u64 = 1;
   pub const MetadataDepositPerByte: u64 = 1;
   pub const SunrisePalletId: PalletId = PalletId(*b"sunr*pal");
+  pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
   pub const TidefiPalletId: PalletId = PalletId(*b"wrpr*pal");
   pub const FeesPalletId: PalletId = PalletId(*b"wrpr*pab");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
   pub const StakePalletId: PalletId = PalletId(*b"stak*pal");
   pub const SessionsPerEra: SessionIndex = 10;
   pub const SessionsArchive: SessionIndex = 2;
+  pub const ErasArchive: EraIndex = 2;
+  pub const MaximumSessionsCatchUp: u32 = 5;
   pub const BlocksPerSession: BlockNumber = 50;
+  pub const ExpectedBlockTime: u64 = 6_000;
   pub const BlocksForceUnstake: BlockNumber = 10;
   pub const StakeAccountCap: u32 = 10;
   pub const FeeAmount: Permill = Permill::from_perthousand(20);
@@ -87,7 +92,13 @@ construct_mock_runtime!({
   pub const SwapLimitByAccount: u32 = 100;
   pub const SupportedMarketPairsLimit: u8 = 255;
   pub const StakingRewardCap: u32 = 10;
+  pub const SessionRewardsHistoryCap: u32 = 10;
+  pub const BlocksPerYear: BlockNumber = 14400 * 365;
   pub const Cooldown: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ClaimWindow: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ReferralShare: Permill = Permill::from_percent(10);
+  // max 1k referral rewards per referee
+  pub const MaximumReferralRewardPerReferee: Balance = 1_000_000_000_000_000;
   pub const MaximumRewardPerSwap: Balance = 10_000_000_000_000_000;
   pub const LeftoverSwapRebates: FixedU128 = FixedU128::from_inner(500_000_000_000_000_000);
   pub const BatchSize: u32 = 10;
@@ -125,11 +136,20 @@ impl pallet_oracle::Config for Test {
   type Sunrise = Sunrise;
   type SwapLimitByAccount = SwapLimitByAccount;
   type SupportedMarketPairsLimit = SupportedMarketPairsLimit;
+  type ForceOrigin = EnsureRoot<AccountId>;
+  type AssetRegistry = AssetRegistry;
   type WeightInfo = crate::weights::SubstrateWeight<Test>;
 }
 
 impl pallet_security::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type PauseOrigin = system::EnsureRoot<AccountId>;
+  type ResumeOrigin = system::EnsureRoot<AccountId>;
+  type PauseDuration = PauseDuration;
+  type MaxPauseRenewals = ConstU32<2>;
+  type UnpausablePallets = ();
+  type MaxNameLength = ConstU32<32>;
+  type MaxReasonLength = ConstU32<64>;
   type WeightInfo = pallet_security::weights::SubstrateWeight<Test>;
 }
 
@@ -142,8 +162,12 @@ impl pallet_tidefi_stake::Config for Test {
   type BlocksForceUnstake = BlocksForceUnstake;
   type AssetRegistry = AssetRegistry;
   type StakingRewardCap = StakingRewardCap;
+  type SessionRewardsHistoryCap = SessionRewardsHistoryCap;
+  type BlocksPerYear = BlocksPerYear;
   type Security = Security;
+  type Sunrise = Sunrise;
   type BatchSize = BatchSize;
+  type ForceOrigin = EnsureRoot<AccountId>;
 }
 
 impl pallet_asset_registry::Config for Test {
@@ -155,6 +179,7 @@ impl pallet_asset_registry::Config for Test {
 
 impl pallet_fees::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type TreasuryPalletId = TreasuryPalletId;
   type Security = Security;
   type FeesPalletId = TidefiPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
@@ -162,10 +187,14 @@ impl pallet_fees::Config for Test {
   type UnixTime = Timestamp;
   type SessionsPerEra = SessionsPerEra;
   type SessionsArchive = SessionsArchive;
+  type ErasArchive = ErasArchive;
+  type MaximumSessionsCatchUp = MaximumSessionsCatchUp;
+  type BatchSize = BatchSize;
   type FeeAmount = FeeAmount;
   type MarketMakerFeeAmount = MarketMakerFeeAmount;
   type MarketMakerLimitFeeAmount = MarketMakerLimitFeeAmount;
   type BlocksPerSession = BlocksPerSession;
+  type ExpectedBlockTime = ExpectedBlockTime;
   type Staking = TidefiStaking;
   type Sunrise = Sunrise;
   type WeightInfo = pallet_fees::weights::SubstrateWeight<Test>;
@@ -174,11 +203,19 @@ impl pallet_fees::Config for Test {
 impl pallet_sunrise::Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type Security = Security;
+  type AssetRegistry = AssetRegistry;
   type SunrisePalletId = SunrisePalletId;
   type CurrencyTidefi = Adapter<AccountId>;
   type Cooldown = Cooldown;
+  type ClaimWindow = ClaimWindow;
+  type ReferralShare = ReferralShare;
+  type MaximumReferralRewardPerReferee = MaximumReferralRewardPerReferee;
+  type BatchSize = BatchSize;
   type MaximumRewardPerSwap = MaximumRewardPerSwap;
   type LeftoverSwapRebates = LeftoverSwapRebates;
+  type TreasuryPalletId = TreasuryPalletId;
+  type ForceOrigin = system::EnsureRoot<AccountId>;
+  type WeightInfo = pallet_sunrise::weights::SubstrateWeight<Test>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -248,6 +285,13 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         18,
         vec![],
       ),
+      (
+        CurrencyId::Wrapped(4),
+        "Tether".into(),
+        "USDT".into(),
+        6,
+        vec![],
+      ),
       (
         CurrencyId::Wrapped(5),
         "USD Coin".into(),
@@ -257,6 +301,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
       ),
     ],
     account: 0_u64.into(),
+    infos: Vec::new(),
   }
   .assimilate_storage(&mut storage)
   .unwrap();
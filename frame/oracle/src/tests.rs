@@ -3403,6 +3403,27 @@ mod add_market_pair {
       });
     }
 
+    #[test]
+    fn asset_is_not_listed() {
+      new_test_ext().execute_with(|| {
+        Context::default()
+          .set_oracle_status(true)
+          .set_market_makers(vec![CHARLIE_ACCOUNT_ID])
+          .mint_tdfy(ALICE_ACCOUNT_ID, ONE_TDFY);
+
+        assert_noop!(
+          Oracle::add_market_pair(
+            RuntimeOrigin::root(),
+            MarketPair {
+              base_asset: CurrencyId::Tdfy,
+              quote_asset: CurrencyId::Wrapped(100),
+            },
+          ),
+          Error::<Test>::UnknownAsset
+        );
+      });
+    }
+
     #[test]
     fn not_called_by_root_user() {
       new_test_ext().execute_with(|| {
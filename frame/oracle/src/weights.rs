@@ -67,9 +67,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
          .saturating_add(T::DbWeight::get().writes(5_u64))
    }
    fn add_market_pair() -> Weight {
-      Weight::from_ref_time(25_089_000)
-         .saturating_add(Weight::from_proof_size(2487))
-         .saturating_add(T::DbWeight::get().reads(1))
+      Weight::from_ref_time(31_089_000)
+         .saturating_add(Weight::from_proof_size(7717))
+         .saturating_add(T::DbWeight::get().reads(3))
          .saturating_add(T::DbWeight::get().writes(1))
    }
    fn remove_market_pair() -> Weight {
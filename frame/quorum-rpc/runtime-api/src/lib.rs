@@ -0,0 +1,34 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg_attr(not(feature = "std"), no_std)]
+use codec::Codec;
+use frame_support::inherent::Vec;
+use pallet_quorum::{CompletedWithdrawal, Page, ProposalInfo};
+use tidefi_primitives::{AssetId, Hash, WatchList, Withdrawal};
+
+type BurnedItem<AccountId, BlockNumber> = (Hash, Withdrawal<AccountId, BlockNumber, Vec<u8>>);
+
+sp_api::decl_runtime_apis! {
+    pub trait QuorumApi<AccountId, BlockNumber> where AccountId: Codec, BlockNumber: Codec,
+        {
+            fn get_proposals(offset: u32, limit: u32) -> Page<ProposalInfo<AccountId, BlockNumber>>;
+            fn get_proposal(proposal_id: Hash) -> Option<ProposalInfo<AccountId, BlockNumber>>;
+            fn get_burned_queue(offset: u32, limit: u32) -> Page<BurnedItem<AccountId, BlockNumber>>;
+            fn get_public_keys(asset_id: AssetId, offset: u32, limit: u32) -> Page<(AccountId, Vec<u8>)>;
+            fn get_account_watch_list(account_id: AccountId, offset: u32, limit: u32) -> Page<WatchList<BlockNumber, Vec<u8>>>;
+            fn get_completed_withdrawals(account_id: AccountId, offset: u32, limit: u32) -> Page<CompletedWithdrawal<BlockNumber, Vec<u8>>>;
+        }
+}
@@ -0,0 +1,225 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+#![cfg_attr(not(feature = "std"), no_std)]
+use codec::Codec;
+use frame_support::inherent::Vec;
+use jsonrpsee::{
+  core::{Error as JsonRpseeError, RpcResult},
+  proc_macros::rpc,
+  types::{error::CallError, ErrorObject},
+};
+use pallet_quorum::{CompletedWithdrawal, Page, ProposalInfo};
+pub use pallet_quorum_rpc_runtime_api::QuorumApi as QuorumRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+use tidefi_primitives::{AssetId, Hash, WatchList, Withdrawal};
+
+#[rpc(client, server)]
+pub trait QuorumApi<BlockHash, AccountId, BlockNumber> {
+  #[method(name = "quorum_getProposals")]
+  fn get_proposals(
+    &self,
+    offset: u32,
+    limit: u32,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Page<ProposalInfo<AccountId, BlockNumber>>>;
+
+  #[method(name = "quorum_getProposal")]
+  fn get_proposal(
+    &self,
+    proposal_id: Hash,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Option<ProposalInfo<AccountId, BlockNumber>>>;
+
+  #[method(name = "quorum_getBurnedQueue")]
+  fn get_burned_queue(
+    &self,
+    offset: u32,
+    limit: u32,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Page<(Hash, Withdrawal<AccountId, BlockNumber, Vec<u8>>)>>;
+
+  #[method(name = "quorum_getPublicKeys")]
+  fn get_public_keys(
+    &self,
+    asset_id: AssetId,
+    offset: u32,
+    limit: u32,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Page<(AccountId, Vec<u8>)>>;
+
+  #[method(name = "quorum_getAccountWatchList")]
+  fn get_account_watch_list(
+    &self,
+    account_id: AccountId,
+    offset: u32,
+    limit: u32,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Page<WatchList<BlockNumber, Vec<u8>>>>;
+
+  #[method(name = "quorum_getCompletedWithdrawals")]
+  fn get_completed_withdrawals(
+    &self,
+    account_id: AccountId,
+    offset: u32,
+    limit: u32,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Page<CompletedWithdrawal<BlockNumber, Vec<u8>>>>;
+}
+
+/// A struct that implements the [`QuorumApi`].
+pub struct QuorumRpc<C, B> {
+  client: Arc<C>,
+  _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> QuorumRpc<C, B> {
+  /// Create new `Quorum` with the given reference to the client.
+  pub fn new(client: Arc<C>) -> Self {
+    Self {
+      client,
+      _marker: Default::default(),
+    }
+  }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+  #[error("{0}")]
+  Call(String),
+}
+
+impl From<Error> for JsonRpseeError {
+  fn from(error: Error) -> Self {
+    JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+      1234,
+      error.to_string(),
+      None::<()>,
+    )))
+  }
+}
+
+impl<C, Block, AccountId, BlockNumber>
+  QuorumApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber> for QuorumRpc<C, Block>
+where
+  Block: BlockT,
+  C: Send + Sync + 'static,
+  C: ProvideRuntimeApi<Block>,
+  C: HeaderBackend<Block>,
+  C::Api: QuorumRuntimeApi<Block, AccountId, BlockNumber>,
+  AccountId: Codec,
+  BlockNumber: Codec,
+{
+  fn get_proposals(
+    &self,
+    offset: u32,
+    limit: u32,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Page<ProposalInfo<AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_proposals(at, offset, limit)
+      .map_err(|err| Error::Call(format!("{err:?}")).into())
+  }
+
+  fn get_proposal(
+    &self,
+    proposal_id: Hash,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Option<ProposalInfo<AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_proposal(at, proposal_id)
+      .map_err(|err| Error::Call(format!("{err:?}")).into())
+  }
+
+  fn get_burned_queue(
+    &self,
+    offset: u32,
+    limit: u32,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Page<(Hash, Withdrawal<AccountId, BlockNumber, Vec<u8>>)>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_burned_queue(at, offset, limit)
+      .map_err(|err| Error::Call(format!("{err:?}")).into())
+  }
+
+  fn get_public_keys(
+    &self,
+    asset_id: AssetId,
+    offset: u32,
+    limit: u32,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Page<(AccountId, Vec<u8>)>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_public_keys(at, asset_id, offset, limit)
+      .map_err(|err| Error::Call(format!("{err:?}")).into())
+  }
+
+  fn get_account_watch_list(
+    &self,
+    account_id: AccountId,
+    offset: u32,
+    limit: u32,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Page<WatchList<BlockNumber, Vec<u8>>>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_account_watch_list(at, account_id, offset, limit)
+      .map_err(|err| Error::Call(format!("{err:?}")).into())
+  }
+
+  fn get_completed_withdrawals(
+    &self,
+    account_id: AccountId,
+    offset: u32,
+    limit: u32,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Page<CompletedWithdrawal<BlockNumber, Vec<u8>>>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_completed_withdrawals(at, account_id, offset, limit)
+      .map_err(|err| Error::Call(format!("{err:?}")).into())
+  }
+}
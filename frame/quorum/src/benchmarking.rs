@@ -17,11 +17,13 @@
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 
+use codec::Decode;
 use frame_benchmarking::{
   account, benchmarks, impl_benchmark_test_suite, vec, whitelisted_caller, Vec,
 };
 use frame_support::BoundedVec;
 use frame_system::{self, RawOrigin};
+use sp_runtime::{traits::TrailingZeroInput, Permill};
 use tidefi_primitives::{
   pallet::SecurityExt, ComplianceLevel, CurrencyId, Hash, Mint, ProposalType, ProposalVotes,
   Withdrawal,
@@ -118,7 +120,8 @@ benchmarks! {
    acknowledge_burned {
       let user = pre_set_auth::<T>();
       let proposal_id = create_burned_queue::<T>();
-   }: _(RawOrigin::Signed(user), proposal_id)
+      let transaction_id = vec![1; T::StringLimit::get() as usize];
+   }: _(RawOrigin::Signed(user), proposal_id, transaction_id)
    reject_proposal {
       let user = pre_set_auth::<T>();
       let proposal_id = create_proposal::<T>();
@@ -137,6 +140,21 @@ benchmarks! {
       }
       let user = pre_set_auth::<T>();
    }: _(RawOrigin::Signed(user), all_keys)
+   submit_inclusion_proof {
+      let user = pre_set_auth::<T>();
+      let proposal_id = create_proposal::<T>();
+      let proof = <T as pallet::Config>::InclusionProof::decode(&mut TrailingZeroInput::zeroes()).unwrap();
+   }: _(RawOrigin::Signed(user), proposal_id, proof)
+   set_withdrawal_fee {
+      let fee = WithdrawalFee {
+         flat: 1_000_000,
+         percentage: Permill::from_parts(1_000),
+      };
+   }: _(RawOrigin::Root, CurrencyId::Tdfy, Some(fee))
+   submit_deposit_script {
+      let account_id: T::AccountId = account("test", 0, SEED);
+      let deposit_script = vec![0_u8; <T as pallet::Config>::StringLimit::get() as usize];
+   }: _(RawOrigin::Root, account_id, CurrencyId::Tdfy, deposit_script)
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
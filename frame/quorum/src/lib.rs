@@ -24,12 +24,52 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod types;
 pub mod weights;
+pub use types::*;
 pub use weights::*;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+use frame_support::dispatch::DispatchResult;
+use tidefi_primitives::{Balance, CurrencyId};
+
+/// Verify a deposit has been included in the external chain before the quorum mint it.
+pub trait InclusionVerifier<Proof> {
+  /// Returns `true` when the mints of `currency_id` require an inclusion proof.
+  fn is_proof_required(currency_id: CurrencyId) -> bool;
+
+  /// Verify the deposit `transaction_id` is included in the external chain with at least
+  /// `required_confirmations` confirmations and pays `amount` to `deposit_script`.
+  fn verify(
+    currency_id: CurrencyId,
+    transaction_id: &[u8],
+    amount: Balance,
+    deposit_script: &[u8],
+    required_confirmations: u32,
+    proof: &Proof,
+  ) -> DispatchResult;
+}
+
+/// No inclusion proof required, the quorum votes are trusted.
+impl<Proof> InclusionVerifier<Proof> for () {
+  fn is_proof_required(_currency_id: CurrencyId) -> bool {
+    false
+  }
+
+  fn verify(
+    _currency_id: CurrencyId,
+    _transaction_id: &[u8],
+    _amount: Balance,
+    _deposit_script: &[u8],
+    _required_confirmations: u32,
+    _proof: &Proof,
+  ) -> DispatchResult {
+    Ok(())
+  }
+}
+
 pub(crate) const LOG_TARGET: &str = "tidefi::quorum";
 
 // syntactic sugar for logging.
@@ -53,14 +93,14 @@ pub mod pallet {
     PalletId,
   };
   use frame_system::pallet_prelude::*;
+  use pallet_asset_registry::AssetInfoProvider;
   use rand_chacha::{
     rand_core::{RngCore, SeedableRng},
     ChaChaRng,
   };
-  use sp_runtime::traits::Zero;
+  use sp_runtime::traits::{AccountIdConversion, Zero};
   use sp_std::{vec, vec::Vec};
   use tidefi_primitives::{
-    assets::Asset,
     pallet::{AssetRegistryExt, QuorumExt, SecurityExt, SunriseExt},
     AssetId, Balance, ComplianceLevel, CurrencyId, Hash, Mint, ProposalStatus, ProposalType,
     ProposalVotes, WatchList, WatchListAction, Withdrawal,
@@ -87,6 +127,9 @@ pub mod pallet {
     Withdrawal<AccountId, BlockNumber, BoundedVec<u8, StringLimit>>,
   );
 
+  pub type CompletedWithdrawalItem<BlockNumber, StringLimit> =
+    CompletedWithdrawal<BlockNumber, BoundedVec<u8, StringLimit>>;
+
   pub type QuorumProposal<BlockNumber, AccountId> =
     ProposalType<AccountId, BlockNumber, Vec<u8>, Vec<AccountId>>;
 
@@ -139,8 +182,21 @@ pub mod pallet {
     #[pallet::constant]
     type PubkeyLimitPerAsset: Get<u32>;
 
+    /// The maximum number of completed withdrawals kept in the history of each account
+    #[pallet::constant]
+    type CompletedWithdrawalsLimit: Get<u32>;
+
     /// Asset registry traits
-    type AssetRegistry: AssetRegistryExt;
+    type AssetRegistry: AssetRegistryExt + AssetInfoProvider;
+
+    /// Proof of a deposit inclusion in the external chain
+    type InclusionProof: Parameter + Member + MaxEncodedLen;
+
+    /// Inclusion proof verifier, checked before minting
+    type InclusionVerifier: InclusionVerifier<Self::InclusionProof>;
+
+    /// The origin which may update the withdrawal fees
+    type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
@@ -228,6 +284,74 @@ pub mod pallet {
     ValueQuery,
   >;
 
+  /// Acknowledgements of the burned items, with the external chain transaction id submitted
+  /// by each member
+  #[pallet::storage]
+  #[pallet::getter(fn burned_acknowledgements)]
+  pub type BurnedAcknowledgements<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    Hash,
+    BoundedVec<
+      (
+        T::AccountId,
+        BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+      ),
+      T::VotesLimit,
+    >,
+    ValueQuery,
+  >;
+
+  /// History of the withdrawals paid out on the external chain, for each account
+  #[pallet::storage]
+  #[pallet::getter(fn completed_withdrawals)]
+  pub type CompletedWithdrawals<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    BoundedVec<
+      CompletedWithdrawalItem<T::BlockNumber, <T as pallet::Config>::StringLimit>,
+      T::CompletedWithdrawalsLimit,
+    >,
+    ValueQuery,
+  >;
+
+  /// Withdrawal fee for each currency, paid to the quorum account
+  #[pallet::storage]
+  #[pallet::getter(fn withdrawal_fees)]
+  pub type WithdrawalFees<T: Config> = StorageMap<_, Blake2_128Concat, CurrencyId, WithdrawalFee>;
+
+  /// Inclusion proofs submitted for the mint proposals
+  #[pallet::storage]
+  #[pallet::getter(fn inclusion_proofs)]
+  pub type InclusionProofs<T: Config> =
+    StorageMap<_, Blake2_128Concat, Hash, <T as pallet::Config>::InclusionProof>;
+
+  /// Deposit script of each account on the external chain, the deposits proven with an
+  /// inclusion proof must be paid to this script
+  #[pallet::storage]
+  #[pallet::getter(fn deposit_scripts)]
+  pub type DepositScripts<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Blake2_128Concat,
+    CurrencyId,
+    BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+  >;
+
+  /// Transaction ids already minted with an inclusion proof, a deposit can't be minted twice
+  #[pallet::storage]
+  #[pallet::getter(fn used_transaction_ids)]
+  pub type UsedTransactionIds<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    CurrencyId,
+    Blake2_128Concat,
+    BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    (),
+  >;
+
   /// Genesis configuration
   #[pallet::genesis_config]
   pub struct GenesisConfig<T: Config> {
@@ -288,16 +412,28 @@ pub mod pallet {
       watch_action: WatchListAction,
     },
 
-    /// Quorum burned token to the account from tidechain
+    /// Quorum burned token to the account from tidechain, `amount` is the net amount to
+    /// send on the external chain and `fee` the withdrawal fee paid to the quorum
     BurnedInitialized {
       proposal_id: Hash,
       account_id: T::AccountId,
       currency_id: CurrencyId,
       amount: Balance,
+      fee: Balance,
     },
 
-    /// Quorum member acknowledged the burned and initiated the process
-    BurnedAcknowledged { proposal_id: Hash },
+    /// Quorum member acknowledged the burned with the external chain transaction id
+    BurnedAcknowledgementSubmitted {
+      account_id: T::AccountId,
+      proposal_id: Hash,
+      transaction_id: Vec<u8>,
+    },
+
+    /// Threshold of quorum members acknowledged the burned, the withdrawal is completed
+    BurnedAcknowledged {
+      proposal_id: Hash,
+      transaction_id: Vec<u8>,
+    },
 
     /// Member voted for a proposal
     VoteFor {
@@ -328,6 +464,24 @@ pub mod pallet {
       members: Vec<T::AccountId>,
       threshold: u16,
     },
+
+    /// Inclusion proof has been submitted for a mint proposal
+    InclusionProofSubmitted {
+      account_id: T::AccountId,
+      proposal_id: Hash,
+    },
+
+    /// Withdrawal fee of a currency has been updated
+    WithdrawalFeeUpdated {
+      currency_id: CurrencyId,
+      fee: Option<WithdrawalFee>,
+    },
+
+    /// Deposit script of an account has been updated
+    DepositScriptUpdated {
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+    },
   }
 
   // Errors inform users that something went wrong.
@@ -377,6 +531,30 @@ pub mod pallet {
     UnknownError,
     /// Invalid asset
     InvalidAsset,
+    /// The mint requires an inclusion proof
+    MissingInclusionProof,
+    /// The inclusion proof is not valid
+    InvalidInclusionProof,
+    /// The account has no deposit script for this currency
+    MissingDepositScript,
+    /// The deposit script is too long
+    DepositScriptTooLong,
+    /// The deposit transaction has already been minted
+    TransactionIdAlreadyUsed,
+    /// The withdrawal amount doesn't cover the withdrawal fee
+    WithdrawalAmountBelowFee,
+    /// Unable to transfer the withdrawal fee
+    WithdrawalFeeTransferFailed,
+    /// No burned item with the ID was found in the queue
+    BurnedDoesNotExist,
+    /// Member already acknowledged this burned item with the same transaction id
+    MemberAlreadyAcknowledged,
+    /// Acknowledgements cap reached for this burned item
+    BurnedAcknowledgementsOverflow,
+    /// The mint amount is below the deposit minimum of the asset
+    MintAmountBelowMinimum,
+    /// The withdrawal amount is below the withdrawal minimum of the asset
+    WithdrawalAmountBelowMinimum,
   }
 
   #[pallet::hooks]
@@ -472,26 +650,77 @@ pub mod pallet {
       Ok(Pays::No.into())
     }
 
-    /// Quorum member acknowledge a burned item and started the process.
+    /// Quorum member acknowledge a burned item has been paid out on the external chain
+    /// with `transaction_id`.
+    ///
+    /// The withdrawal is completed, removed from the queue and recorded in the account
+    /// history once the threshold of members acknowledged the same transaction id. A member
+    /// can acknowledge again with another transaction id to replace its acknowledgement.
     #[pallet::call_index(2)]
     #[pallet::weight(<T as pallet::Config>::WeightInfo::acknowledge_burned())]
-    pub fn acknowledge_burned(origin: OriginFor<T>, proposal: Hash) -> DispatchResultWithPostInfo {
+    pub fn acknowledge_burned(
+      origin: OriginFor<T>,
+      proposal: Hash,
+      transaction_id: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
       // 1. Make sure the request is signed by `account_id`
       let sender = ensure_signed(origin)?;
 
       // 2. Make sure this is a quorum member
       ensure!(Self::is_member_and_ready(&sender), Error::<T>::AccessDenied);
 
-      // 3. Remove from the queue
-      BurnedQueue::<T>::mutate(|burned_queue| {
-        burned_queue.retain(|(proposal_id, _)| *proposal_id != proposal);
-      });
+      // 3. Make sure the burned item is still in the queue
+      let (_, withdrawal) = Self::burned_queue()
+        .into_iter()
+        .find(|(proposal_id, _)| *proposal_id == proposal)
+        .ok_or(Error::<T>::BurnedDoesNotExist)?;
 
-      // 4. Emit event on chain
-      Self::deposit_event(Event::<T>::BurnedAcknowledged {
+      // 4. Make sure the transaction id is valid
+      let transaction_id: BoundedVec<u8, <T as pallet::Config>::StringLimit> = transaction_id
+        .try_into()
+        .map_err(|_| Error::<T>::BadTransactionId)?;
+      ensure!(!transaction_id.is_empty(), Error::<T>::BadTransactionId);
+
+      // 5. Register the acknowledgement, a member can replace its own acknowledgement and the
+      // acknowledgements of the former members are dropped
+      let acknowledgements = BurnedAcknowledgements::<T>::try_mutate(
+        proposal,
+        |acknowledgements| -> Result<_, DispatchError> {
+          ensure!(
+            !acknowledgements
+              .iter()
+              .any(|(account_id, acknowledged_transaction_id)| {
+                *account_id == sender && *acknowledged_transaction_id == transaction_id
+              }),
+            Error::<T>::MemberAlreadyAcknowledged
+          );
+          acknowledgements
+            .retain(|(account_id, _)| *account_id != sender && Self::is_member(account_id));
+          acknowledgements
+            .try_push((sender.clone(), transaction_id.clone()))
+            .map_err(|_| Error::<T>::BurnedAcknowledgementsOverflow)?;
+          Ok(acknowledgements.clone())
+        },
+      )?;
+
+      Self::deposit_event(Event::<T>::BurnedAcknowledgementSubmitted {
+        account_id: sender,
         proposal_id: proposal,
+        transaction_id: transaction_id.to_vec(),
       });
 
+      // 6. Complete the withdrawal once the threshold of members agree on the transaction id
+      let confirmations = acknowledgements
+        .iter()
+        .filter(|(account_id, acknowledged_transaction_id)| {
+          *acknowledged_transaction_id == transaction_id && Self::is_member(account_id)
+        })
+        .count();
+
+      if confirmations >= Self::threshold().max(1) as usize {
+        Self::complete_withdrawal(proposal, withdrawal, transaction_id);
+      }
+
       // Don't take tx fees on success
       Ok(Pays::No.into())
     }
@@ -551,10 +780,145 @@ pub mod pallet {
       // Don't take tx fees on success
       Ok(Pays::No.into())
     }
+
+    /// Quorum member submit the inclusion proof of a mint proposal deposit
+    ///
+    /// The proof is verified when submitted, when the currency requires it, and again when the
+    /// proposal is approved, before minting. A valid proof replaces the previous one.
+    #[pallet::call_index(6)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_inclusion_proof())]
+    pub fn submit_inclusion_proof(
+      origin: OriginFor<T>,
+      proposal: Hash,
+      proof: <T as pallet::Config>::InclusionProof,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is signed by `account_id`
+      let sender = ensure_signed(origin)?;
+
+      // 2. Make sure this is a quorum member
+      ensure!(Self::is_member_and_ready(&sender), Error::<T>::AccessDenied);
+
+      // 3. Make sure this is a mint proposal
+      let mint = match Self::get_proposal(proposal)? {
+        ProposalType::Mint(mint) => mint,
+        _ => return Err(Error::<T>::BadProposal.into()),
+      };
+
+      // 4. Make sure the deposit is included in the external chain and paid to the account
+      //    deposit script when required
+      if T::InclusionVerifier::is_proof_required(mint.currency_id) {
+        let deposit_script = Self::deposit_scripts(&mint.account_id, mint.currency_id)
+          .ok_or(Error::<T>::MissingDepositScript)?;
+        T::InclusionVerifier::verify(
+          mint.currency_id,
+          &mint.transaction_id,
+          mint.mint_amount,
+          &deposit_script,
+          Self::required_confirmations(mint.currency_id),
+          &proof,
+        )
+        .map_err(|verify_error| {
+          log!(error, "Invalid inclusion proof {:?}", verify_error);
+          Error::<T>::InvalidInclusionProof
+        })?;
+      }
+
+      // 5. Store the proof, replacing the previous one
+      InclusionProofs::<T>::insert(proposal, proof);
+
+      // 6. Emit event on chain
+      Self::deposit_event(Event::<T>::InclusionProofSubmitted {
+        account_id: sender,
+        proposal_id: proposal,
+      });
+
+      // Don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+
+    /// Update the withdrawal fee of a currency, `None` remove the fee.
+    ///
+    /// The fee is taken when the withdrawal is processed and paid to the quorum account.
+    ///
+    /// Emits `WithdrawalFeeUpdated` event when successful.
+    #[pallet::call_index(7)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_withdrawal_fee())]
+    pub fn set_withdrawal_fee(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      fee: Option<WithdrawalFee>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is coming from the force origin
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the fee
+      WithdrawalFees::<T>::set(currency_id, fee);
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::WithdrawalFeeUpdated { currency_id, fee });
+
+      Ok(().into())
+    }
+
+    /// Set the deposit script of an account on the external chain
+    ///
+    /// The deposits proven with an inclusion proof must be paid to this script, it can only be
+    /// set by the force origin.
+    ///
+    /// - `account_id`: Account owning the deposit script
+    /// - `currency_id`: Currency deposited on the script
+    /// - `deposit_script`: Locking script of the deposit address
+    ///
+    /// Emits `DepositScriptUpdated` event when successful.
+    #[pallet::call_index(8)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::submit_deposit_script())]
+    pub fn submit_deposit_script(
+      origin: OriginFor<T>,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      deposit_script: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the request is coming from the force origin
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Store the script
+      let deposit_script: BoundedVec<u8, <T as pallet::Config>::StringLimit> = deposit_script
+        .try_into()
+        .map_err(|_| Error::<T>::DepositScriptTooLong)?;
+      DepositScripts::<T>::insert(&account_id, currency_id, deposit_script);
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::DepositScriptUpdated {
+        account_id,
+        currency_id,
+      });
+
+      Ok(().into())
+    }
   }
 
   // helper functions (not dispatchable)
   impl<T: Config> Pallet<T> {
+    /// Quorum account, receiving the withdrawal fees
+    pub fn account_id() -> T::AccountId {
+      T::QuorumPalletId::get().into_account_truncating()
+    }
+
+    /// External chain confirmations required before minting a deposit of `currency_id`, from
+    /// its asset record.
+    pub fn required_confirmations(currency_id: CurrencyId) -> u32 {
+      T::AssetRegistry::asset_info(currency_id)
+        .map(|info| info.required_confirmations)
+        .unwrap_or_default()
+    }
+
+    /// Withdrawal fee of `amount` for `currency_id`.
+    pub fn withdrawal_fee(currency_id: CurrencyId, amount: Balance) -> Balance {
+      Self::withdrawal_fees(currency_id)
+        .map(|fee| fee.compute(amount))
+        .unwrap_or_else(Zero::zero)
+    }
+
     fn clean_proposal_queue_with_max_weight(max_weight: Weight) -> Weight {
       let all_proposals = Proposals::<T>::get();
       let mut weight_used = <T as frame_system::Config>::DbWeight::get().reads(1);
@@ -693,6 +1057,43 @@ pub mod pallet {
       Self::members(who).unwrap_or(false) && at_least_one_public_key
     }
 
+    // Remove the burned item from the queue and record it in the account history
+    fn complete_withdrawal(
+      proposal_id: Hash,
+      withdrawal: Withdrawal<
+        T::AccountId,
+        T::BlockNumber,
+        BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+      >,
+      transaction_id: BoundedVec<u8, <T as pallet::Config>::StringLimit>,
+    ) {
+      BurnedQueue::<T>::mutate(|burned_queue| {
+        burned_queue.retain(|(burned_proposal_id, _)| *burned_proposal_id != proposal_id);
+      });
+      BurnedAcknowledgements::<T>::remove(proposal_id);
+
+      CompletedWithdrawals::<T>::mutate(&withdrawal.account_id, |history| {
+        // drop the oldest record when the history is full
+        if history.len() as u32 >= T::CompletedWithdrawalsLimit::get() && !history.is_empty() {
+          history.remove(0);
+        }
+        let _ = history.try_push(CompletedWithdrawal {
+          proposal_id,
+          currency_id: withdrawal.asset_id,
+          amount: withdrawal.amount,
+          external_address: withdrawal.external_address,
+          transaction_id: transaction_id.clone(),
+          requested_at: withdrawal.block_number,
+          completed_at: T::Security::get_current_block_count(),
+        });
+      });
+
+      Self::deposit_event(Event::<T>::BurnedAcknowledged {
+        proposal_id,
+        transaction_id: transaction_id.into_inner(),
+      });
+    }
+
     // Register a vote for the proposal
     fn vote_for(who: T::AccountId, proposal_id: Hash) -> Result<(), DispatchError> {
       Self::commit_vote(who, proposal_id, true)?;
@@ -849,23 +1250,46 @@ pub mod pallet {
         Error::<T>::AssetDisabled
       );
 
-      // 2. Remove the token from the account
-      T::CurrencyTidefi::burn_from(item.asset_id, &item.account_id, item.amount)
+      // 2. Make sure the amount covers the withdrawal fee
+      let fee = Self::withdrawal_fee(item.asset_id, item.amount);
+      let amount = item
+        .amount
+        .checked_sub(fee)
+        .filter(|amount| !amount.is_zero())
+        .ok_or(Error::<T>::WithdrawalAmountBelowFee)?;
+
+      // 3. Pay the withdrawal fee to the quorum account
+      if !fee.is_zero() {
+        T::CurrencyTidefi::transfer(
+          item.asset_id,
+          &item.account_id,
+          &Self::account_id(),
+          fee,
+          false,
+        )
+        .map_err(|_| Error::<T>::WithdrawalFeeTransferFailed)?;
+      }
+
+      // 4. Remove the token from the account
+      T::CurrencyTidefi::burn_from(item.asset_id, &item.account_id, amount)
         .map_err(|_| Error::<T>::BurnFailed)?;
 
-      // 3. Add to burned queue, the quorum can poll and initiate the chain deposit
+      // 5. Add to burned queue with the net amount, the quorum can poll and initiate the chain deposit
+      let mut withdrawal = item.clone();
+      withdrawal.amount = amount;
       BurnedQueue::<T>::try_mutate(|burned_queue| {
         burned_queue
-          .try_push((proposal_id, item.clone()))
+          .try_push((proposal_id, withdrawal))
           .map_err(|_| Error::<T>::BurnedQueueOverflow)
       })?;
 
-      // 4. Emit the event on chain
+      // 6. Emit the event on chain
       Self::deposit_event(Event::<T>::BurnedInitialized {
         proposal_id,
         account_id: item.account_id.clone(),
         currency_id: item.asset_id,
-        amount: item.amount,
+        amount,
+        fee,
       });
 
       Ok(())
@@ -881,6 +1305,15 @@ pub mod pallet {
         T::AssetRegistry::is_currency_enabled(item.currency_id),
         Error::<T>::AssetDisabled
       );
+      let asset_info = T::AssetRegistry::asset_info(item.currency_id);
+      ensure!(
+        item.mint_amount
+          >= asset_info
+            .as_ref()
+            .map(|info| info.deposit_minimum)
+            .unwrap_or_default(),
+        Error::<T>::MintAmountBelowMinimum
+      );
 
       // 2. Add `Amber` and `Red` to watch list
       if item.compliance_level == ComplianceLevel::Amber
@@ -900,26 +1333,50 @@ pub mod pallet {
       if item.compliance_level == ComplianceLevel::Green
         || item.compliance_level == ComplianceLevel::Amber
       {
+        // Make sure the deposit is included in the external chain and paid to the
+        // account deposit script when required, each deposit can be minted only once
+        if T::InclusionVerifier::is_proof_required(item.currency_id) {
+          ensure!(
+            !UsedTransactionIds::<T>::contains_key(item.currency_id, &item.transaction_id),
+            Error::<T>::TransactionIdAlreadyUsed
+          );
+          let proof =
+            Self::inclusion_proofs(proposal_id).ok_or(Error::<T>::MissingInclusionProof)?;
+          let deposit_script = Self::deposit_scripts(&item.account_id, item.currency_id)
+            .ok_or(Error::<T>::MissingDepositScript)?;
+          T::InclusionVerifier::verify(
+            item.currency_id,
+            &item.transaction_id,
+            item.mint_amount,
+            &deposit_script,
+            asset_info
+              .as_ref()
+              .map(|info| info.required_confirmations)
+              .unwrap_or_default(),
+            &proof,
+          )
+          .map_err(|verify_error| {
+            log!(error, "Invalid inclusion proof {:?}", verify_error);
+            Error::<T>::InvalidInclusionProof
+          })?;
+          UsedTransactionIds::<T>::insert(item.currency_id, &item.transaction_id, ());
+        }
+
         T::CurrencyTidefi::mint_into(item.currency_id, &item.account_id, item.mint_amount)
           .map_err(|_| Error::<T>::MintFailed)?;
 
-        // 3 a. If Quorum provide `gas_amount` try to process refunds based on sunrise allocation
-        if let Some(gas_amount) = item.gas_amount {
+        // 3 a. If Quorum provide `gas_amount` try to process refunds based on sunrise allocation,
+        // only the assets eligible in the asset registry are refunded
+        if let (Some(gas_amount), Some(asset_info)) =
+          (item.gas_amount, asset_info.filter(|info| info.gas_refund))
+        {
           // gas for USDT by example, are paid in ETH
           // we extract the base chain for the asset
           // and if needed extract the currency id
 
           // quorum would have sent us the amount in ETH
           // but the mint would have been for `USDT`
-          let asset_from: Asset = item
-            .currency_id
-            .try_into()
-            .map_err(|_| Error::<T>::InvalidAsset)?;
-
-          let real_currency_id = match asset_from.base_chain() {
-            Some(base_chain) => base_chain.currency_id(),
-            None => item.currency_id,
-          };
+          let real_currency_id = asset_info.base_chain.unwrap_or(item.currency_id);
           if let Err(refund_error) =
             T::Sunrise::try_refund_gas_for_deposit(&item.account_id, real_currency_id, gas_amount)
           {
@@ -968,6 +1425,7 @@ pub mod pallet {
 
     // Delete specific proposal
     fn delete_proposal(proposal_id: Hash) -> Result<(), Error<T>> {
+      InclusionProofs::<T>::remove(proposal_id);
       Proposals::<T>::mutate(|proposals| {
         proposals.retain(|(found_proposal_id, _, _)| *found_proposal_id != proposal_id);
         Ok(())
@@ -1022,6 +1480,158 @@ pub mod pallet {
     }
   }
 
+  // quorum queries, exposed by the runtime API
+  impl<T: Config> Pallet<T> {
+    /// Pending proposals with their votes, in submission order.
+    pub fn get_proposals_info(
+      offset: u32,
+      limit: u32,
+    ) -> Page<ProposalInfo<T::AccountId, T::BlockNumber>> {
+      let proposals = Self::proposals();
+      Page::paginate(
+        proposals.into_iter().map(Self::proposal_info),
+        offset,
+        limit,
+      )
+    }
+
+    /// Pending proposal with its votes.
+    pub fn get_proposal_info(
+      proposal_id: Hash,
+    ) -> Option<ProposalInfo<T::AccountId, T::BlockNumber>> {
+      Self::proposals()
+        .into_iter()
+        .find(|(found_proposal_id, _, _)| *found_proposal_id == proposal_id)
+        .map(Self::proposal_info)
+    }
+
+    /// Burned (withdrawal) requests waiting for the quorum to be processed.
+    pub fn get_burned_queue(
+      offset: u32,
+      limit: u32,
+    ) -> Page<(Hash, Withdrawal<T::AccountId, T::BlockNumber, Vec<u8>>)> {
+      let burned_queue = Self::burned_queue();
+      Page::paginate(
+        burned_queue.into_iter().map(|(proposal_id, withdrawal)| {
+          (
+            proposal_id,
+            Withdrawal {
+              account_id: withdrawal.account_id,
+              asset_id: withdrawal.asset_id,
+              amount: withdrawal.amount,
+              external_address: withdrawal.external_address.into_inner(),
+              block_number: withdrawal.block_number,
+            },
+          )
+        }),
+        offset,
+        limit,
+      )
+    }
+
+    /// Withdrawals of `account_id` paid out on the external chain, most recent first.
+    pub fn get_completed_withdrawals(
+      account_id: T::AccountId,
+      offset: u32,
+      limit: u32,
+    ) -> Page<CompletedWithdrawal<T::BlockNumber, Vec<u8>>> {
+      let history = Self::completed_withdrawals(account_id);
+      Page::paginate(
+        history.into_iter().rev().map(|item| CompletedWithdrawal {
+          proposal_id: item.proposal_id,
+          currency_id: item.currency_id,
+          amount: item.amount,
+          external_address: item.external_address.into_inner(),
+          transaction_id: item.transaction_id.into_inner(),
+          requested_at: item.requested_at,
+          completed_at: item.completed_at,
+        }),
+        offset,
+        limit,
+      )
+    }
+
+    /// Quorum members public keys for `asset_id`.
+    pub fn get_public_keys(
+      asset_id: AssetId,
+      offset: u32,
+      limit: u32,
+    ) -> Page<(T::AccountId, Vec<u8>)> {
+      let public_keys = Self::public_keys(asset_id);
+      Page::paginate(
+        public_keys
+          .into_iter()
+          .map(|(account_id, public_key)| (account_id, public_key.into_inner())),
+        offset,
+        limit,
+      )
+    }
+
+    /// Transactions of `account_id` flagged by the compliance checks.
+    pub fn get_account_watch_list(
+      account_id: T::AccountId,
+      offset: u32,
+      limit: u32,
+    ) -> Page<WatchList<T::BlockNumber, Vec<u8>>> {
+      let watch_list = Self::account_watch_list(account_id).unwrap_or_default();
+      Page::paginate(
+        watch_list.into_iter().map(|item| WatchList {
+          amount: item.amount,
+          block_number: item.block_number,
+          compliance_level: item.compliance_level,
+          currency_id: item.currency_id,
+          watch_action: item.watch_action,
+          transaction_id: item.transaction_id.into_inner(),
+        }),
+        offset,
+        limit,
+      )
+    }
+
+    // Build the proposal details with its votes
+    fn proposal_info(
+      (proposal_id, created_at, proposal): ProposalItem<
+        T::BlockNumber,
+        T::AccountId,
+        <T as pallet::Config>::StringLimit,
+        T::VotesLimit,
+      >,
+    ) -> ProposalInfo<T::AccountId, T::BlockNumber> {
+      let votes = Self::proposal_votes(proposal_id).unwrap_or_default();
+      let proposal = match proposal {
+        ProposalType::Mint(mint) => ProposalType::Mint(Mint {
+          account_id: mint.account_id,
+          currency_id: mint.currency_id,
+          mint_amount: mint.mint_amount,
+          gas_amount: mint.gas_amount,
+          transaction_id: mint.transaction_id.into_inner(),
+          compliance_level: mint.compliance_level,
+        }),
+        ProposalType::Withdrawal(withdrawal) => ProposalType::Withdrawal(Withdrawal {
+          account_id: withdrawal.account_id,
+          asset_id: withdrawal.asset_id,
+          amount: withdrawal.amount,
+          external_address: withdrawal.external_address.into_inner(),
+          block_number: withdrawal.block_number,
+        }),
+        ProposalType::UpdateConfiguration(members, threshold) => {
+          ProposalType::UpdateConfiguration(members.into_inner(), threshold)
+        }
+      };
+
+      ProposalInfo {
+        proposal_id,
+        created_at,
+        expiry: created_at + T::ProposalLifetime::get(),
+        proposal,
+        status: votes.status,
+        votes_for: votes.votes_for.into_inner(),
+        votes_against: votes.votes_against.into_inner(),
+        threshold: Self::threshold(),
+      }
+    }
+  }
+
   // quorum extension exposed in other pallets
   impl<T: Config> QuorumExt<T::AccountId, T::BlockNumber> for Pallet<T> {
     /// Get quorum status
@@ -1036,6 +1646,21 @@ pub mod pallet {
       amount: Balance,
       external_address: Vec<u8>,
     ) -> Result<(), DispatchError> {
+      // Make sure the amount covers the withdrawal fee
+      ensure!(
+        amount > Self::withdrawal_fee(asset_id, amount),
+        Error::<T>::WithdrawalAmountBelowFee
+      );
+
+      // Make sure the amount reaches the withdrawal minimum of the asset
+      ensure!(
+        amount
+          >= T::AssetRegistry::asset_info(asset_id)
+            .map(|info| info.withdrawal_minimum)
+            .unwrap_or_default(),
+        Error::<T>::WithdrawalAmountBelowMinimum
+      );
+
       let unique_id = T::Security::get_unique_id(account_id.clone());
       let block_number = T::Security::get_current_block_count();
 
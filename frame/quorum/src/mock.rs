@@ -14,15 +14,19 @@
 // You should have received a copy of the GNU General Public License
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::pallet as pallet_quorum;
+use crate::{pallet as pallet_quorum, InclusionVerifier};
 use frame_support::{
-  dispatch::RawOrigin, pallet_prelude::EnsureOrigin, traits::AsEnsureOriginWithArg, PalletId,
+  dispatch::{DispatchResult, RawOrigin},
+  ensure,
+  pallet_prelude::EnsureOrigin,
+  traits::AsEnsureOriginWithArg,
+  PalletId,
 };
 use frame_system as system;
 use frame_utils::construct_mock_runtime;
 use sp_runtime::traits::AccountIdConversion;
 
-use tidefi_primitives::{BlockNumber, CurrencyId};
+use tidefi_primitives::{BlockNumber, CurrencyId, Hash};
 
 pub struct EnsureRootOrAssetRegistry;
 impl EnsureOrigin<RuntimeOrigin> for EnsureRootOrAssetRegistry {
@@ -56,6 +60,37 @@ impl EnsureOrigin<RuntimeOrigin> for EnsureRootOrAssetRegistry {
   }
 }
 
+pub const VALID_INCLUSION_PROOF: Hash = Hash::repeat_byte(1);
+pub const VALID_DEPOSIT_SCRIPT: &[u8] = b"deposit script";
+pub const DEPOSIT_CONFIRMATIONS: u32 = 6;
+
+pub struct MockInclusionVerifier;
+impl InclusionVerifier<Hash> for MockInclusionVerifier {
+  fn is_proof_required(_currency_id: CurrencyId) -> bool {
+    InclusionProofRequired::get()
+  }
+
+  fn verify(
+    _currency_id: CurrencyId,
+    _transaction_id: &[u8],
+    _amount: Balance,
+    deposit_script: &[u8],
+    required_confirmations: u32,
+    proof: &Hash,
+  ) -> DispatchResult {
+    ensure!(*proof == VALID_INCLUSION_PROOF, "Invalid inclusion proof");
+    ensure!(
+      required_confirmations <= DEPOSIT_CONFIRMATIONS,
+      "Not enough confirmations"
+    );
+    ensure!(
+      deposit_script == VALID_DEPOSIT_SCRIPT,
+      "Deposit not paid to the deposit script"
+    );
+    Ok(())
+  }
+}
+
 construct_mock_runtime!({
   Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
   Quorum: pallet_quorum::{Pallet, Call, Config<T>, Storage, Event<T>},
@@ -64,9 +99,11 @@ construct_mock_runtime!({
   AssetRegistry: pallet_asset_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
 }, {
   pub const SunrisePalletId: PalletId = PalletId(*b"sunr*pal");
+  pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
   pub const TidefiPalletId: PalletId = PalletId(*b"wrpr*pal");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
   pub const AssetDeposit: u64 = 1;
+  pub const PauseDuration: u64 = 100;
   pub const ApprovalDeposit: u64 = 1;
   pub const MetadataDepositBase: u64 = 1;
   pub const MetadataDepositPerByte: u64 = 1;
@@ -85,12 +122,20 @@ construct_mock_runtime!({
   pub const WatchListLimit: u32 = 10000;
   // The maximum number of pubkey each asset can have, should alway be more more than the current quorum active member set
   pub const PubkeyLimitPerAsset: u32 = 10;
+  pub const CompletedWithdrawalsLimit: u32 = 2;
   pub const Cooldown: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ClaimWindow: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ReferralShare: Permill = Permill::from_percent(10);
+  // max 1k referral rewards per referee
+  pub const MaximumReferralRewardPerReferee: Balance = 1_000_000_000_000_000;
+  pub const BatchSize: u32 = 10;
   // max 10k rewards
   pub const MaximumRewardPerSwap: Balance = 10_000_000_000_000_000;
   // 50%
   pub const LeftoverSwapRebates: FixedU128 = FixedU128::from_inner(500_000_000_000_000_000);
 
+  // Mints require an inclusion proof
+  pub static InclusionProofRequired: bool = false;
 });
 
 impl pallet_assets::Config for Test {
@@ -124,6 +169,9 @@ impl pallet_quorum::Config for Test {
   type Sunrise = Sunrise;
   type CurrencyTidefi = Adapter<AccountId>;
   type AssetRegistry = AssetRegistry;
+  type InclusionProof = Hash;
+  type InclusionVerifier = MockInclusionVerifier;
+  type ForceOrigin = system::EnsureRoot<AccountId>;
   type ProposalsCap = ProposalsCap;
   type BurnedCap = BurnedCap;
   type ProposalLifetime = ProposalLifetime;
@@ -131,20 +179,36 @@ impl pallet_quorum::Config for Test {
   type VotesLimit = VotesLimit;
   type WatchListLimit = WatchListLimit;
   type PubkeyLimitPerAsset = PubkeyLimitPerAsset;
+  type CompletedWithdrawalsLimit = CompletedWithdrawalsLimit;
 }
 
 impl pallet_sunrise::Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type Security = Security;
+  type AssetRegistry = AssetRegistry;
   type SunrisePalletId = SunrisePalletId;
   type CurrencyTidefi = Adapter<AccountId>;
   type Cooldown = Cooldown;
+  type ClaimWindow = ClaimWindow;
+  type ReferralShare = ReferralShare;
+  type MaximumReferralRewardPerReferee = MaximumReferralRewardPerReferee;
+  type BatchSize = BatchSize;
   type MaximumRewardPerSwap = MaximumRewardPerSwap;
   type LeftoverSwapRebates = LeftoverSwapRebates;
+  type TreasuryPalletId = TreasuryPalletId;
+  type ForceOrigin = system::EnsureRoot<AccountId>;
+  type WeightInfo = pallet_sunrise::weights::SubstrateWeight<Test>;
 }
 
 impl pallet_security::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type PauseOrigin = system::EnsureRoot<AccountId>;
+  type ResumeOrigin = system::EnsureRoot<AccountId>;
+  type PauseDuration = PauseDuration;
+  type MaxPauseRenewals = ConstU32<2>;
+  type UnpausablePallets = ();
+  type MaxNameLength = ConstU32<32>;
+  type MaxReasonLength = ConstU32<64>;
   type WeightInfo = pallet_security::weights::SubstrateWeight<Test>;
 }
 
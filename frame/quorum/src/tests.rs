@@ -16,11 +16,13 @@
 
 use crate::{
   mock::{
-    new_test_ext, AccountId, Adapter, Assets, BurnedCap, ProposalLifetime, PubkeyLimitPerAsset,
-    Quorum, RuntimeEvent as MockEvent, RuntimeOrigin, Security, StringLimit, System, Test,
-    VotesLimit, WatchListLimit,
+    new_test_ext, AccountId, Adapter, Assets, BurnedCap, CompletedWithdrawalsLimit,
+    InclusionProofRequired, ProposalLifetime, PubkeyLimitPerAsset, Quorum,
+    RuntimeEvent as MockEvent, RuntimeOrigin, Security, StringLimit, System, Test, VotesLimit,
+    WatchListLimit, DEPOSIT_CONFIRMATIONS, VALID_DEPOSIT_SCRIPT, VALID_INCLUSION_PROOF,
   },
   pallet::*,
+  CompletedWithdrawal, ProposalInfo, WithdrawalFee,
 };
 use frame_support::{
   assert_err, assert_noop, assert_ok,
@@ -32,19 +34,22 @@ use frame_support::{
   BoundedVec,
 };
 use sp_core::H256;
-use sp_runtime::traits::BadOrigin;
+use sp_runtime::{traits::BadOrigin, Permill};
 use std::str::FromStr;
 
+use pallet_asset_registry::{AssetInfo, AssetInfos};
 use pallet_security::CurrentBlockCount as CurrentBlockNumber;
 use tidefi_primitives::{
-  pallet::SecurityExt, AssetId, Balance, ComplianceLevel, CurrencyId, Hash, Mint, ProposalStatus,
-  ProposalType, ProposalVotes, WatchList, WatchListAction, Withdrawal,
+  pallet::{QuorumExt, SecurityExt},
+  AssetId, Balance, ComplianceLevel, CurrencyId, Hash, Mint, ProposalStatus, ProposalType,
+  ProposalVotes, WatchList, WatchListAction, Withdrawal,
 };
 
 const ASSET_1: AssetId = 1u32;
 const ALICE_ACCOUNT_ID: u64 = 1;
 const BOB_ACCOUNT_ID: u64 = 2;
 const BLOCK_NUMBER_ZERO: u64 = 0;
+const TRANSACTION_ID: &[u8] = b"0x7b1cb85c";
 
 // TEMP Asset
 const TEMP_ASSET_ID: AssetId = 4;
@@ -63,6 +68,12 @@ const ONE_TDFY: u128 = 1_000_000_000_000;
 
 const INITIAL_10000_TEMPS: Balance = 10_000 * ONE_TEMP;
 
+// 0.10 TEMP + 1%
+const TEMP_WITHDRAWAL_FEE: WithdrawalFee = WithdrawalFee {
+  flat: 10,
+  percentage: Permill::from_parts(10_000),
+};
+
 type BlockNumber = u64;
 
 #[derive(Clone)]
@@ -179,6 +190,17 @@ impl Context {
     self
   }
 
+  fn insert_bob_with_public_key(self) -> Self {
+    Members::<Test>::insert(AccountId::from(BOB_ACCOUNT_ID), true);
+    assert_ok!(PublicKeys::<Test>::try_mutate(ASSET_1, |public_keys| {
+      public_keys.try_push((
+        BOB_ACCOUNT_ID.into(),
+        self.pub_key.clone().try_into().unwrap(),
+      ))
+    }));
+    self
+  }
+
   fn insert_a_valid_mint_proposal_with_green_compliance_level(self) -> Self {
     insert_mint_proposal(Default::default(), ComplianceLevel::Green, self.proposal_id);
     self
@@ -224,6 +246,74 @@ impl Context {
     self
   }
 
+  fn insert_a_valid_burned_item(self) -> Self {
+    insert_burned_item(self.proposal_id, &self.valid_withdrawal);
+    self
+  }
+
+  fn set_temp_withdrawal_fee(self) -> Self {
+    WithdrawalFees::<Test>::insert(TEMP_CURRENCY_ID, TEMP_WITHDRAWAL_FEE);
+    self
+  }
+
+  fn set_asset_minimums(
+    self,
+    currency_id: CurrencyId,
+    deposit_minimum: Balance,
+    withdrawal_minimum: Balance,
+  ) -> Self {
+    AssetInfos::<Test>::insert(
+      currency_id,
+      AssetInfo {
+        base_chain: None,
+        contract_address: None,
+        required_confirmations: 0,
+        deposit_minimum,
+        withdrawal_minimum,
+        gas_refund: false,
+      },
+    );
+    self
+  }
+
+  fn set_required_confirmations(
+    self,
+    currency_id: CurrencyId,
+    required_confirmations: u32,
+  ) -> Self {
+    AssetInfos::<Test>::insert(
+      currency_id,
+      AssetInfo {
+        base_chain: None,
+        contract_address: None,
+        required_confirmations,
+        deposit_minimum: 0,
+        withdrawal_minimum: 0,
+        gas_refund: false,
+      },
+    );
+    self
+  }
+
+  fn require_inclusion_proof(self) -> Self {
+    InclusionProofRequired::set(true);
+    self
+  }
+
+  fn insert_inclusion_proof(self, proof: Hash) -> Self {
+    InclusionProofs::<Test>::insert(self.proposal_id, proof);
+    self
+  }
+
+  fn insert_deposit_script(self, deposit_script: &[u8]) -> Self {
+    DepositScripts::<Test>::insert(
+      AccountId::from(ALICE_ACCOUNT_ID),
+      self.valid_mint.currency_id,
+      BoundedVec::try_from(deposit_script.to_vec()).unwrap(),
+    );
+    self
+  }
+
   fn set_valid_proposal_status(self, status: ProposalStatus) -> Self {
     Votes::<Test>::insert(
       self.proposal_id,
@@ -354,6 +444,39 @@ fn insert_mint_proposal(
   )));
 }
 
+fn insert_burned_item(proposal_id: Hash, withdrawal: &Withdrawal<AccountId, BlockNumber, Vec<u8>>) {
+  assert_ok!(BurnedQueue::<Test>::try_append((
+    proposal_id,
+    Withdrawal {
+      account_id: withdrawal.account_id,
+      asset_id: withdrawal.asset_id,
+      amount: withdrawal.amount,
+      external_address: BoundedVec::try_from(withdrawal.external_address.clone()).unwrap(),
+      block_number: withdrawal.block_number,
+    }
+  )));
+}
+
+fn assert_burned_item_is_completed(context: &Context) {
+  assert!(!Quorum::burned_queue()
+    .iter()
+    .any(|&(proposal_id, _)| proposal_id == context.proposal_id));
+  assert!(Quorum::burned_acknowledgements(context.proposal_id).is_empty());
+  assert_eq!(
+    Quorum::completed_withdrawals(AccountId::from(ALICE_ACCOUNT_ID)).into_inner(),
+    vec![CompletedWithdrawal {
+      proposal_id: context.proposal_id,
+      currency_id: context.valid_withdrawal.asset_id,
+      amount: context.valid_withdrawal.amount,
+      external_address: BoundedVec::try_from(context.valid_withdrawal.external_address.clone())
+        .unwrap(),
+      transaction_id: BoundedVec::try_from(TRANSACTION_ID.to_vec()).unwrap(),
+      requested_at: context.valid_withdrawal.block_number,
+      completed_at: BLOCK_NUMBER_ZERO,
+    }]
+  );
+}
+
 fn assert_proposal_and_its_votes_have_been_deleted(proposal_id: Hash) {
   assert!(!Quorum::proposals()
     .iter()
@@ -499,18 +622,35 @@ fn assert_event_is_emitted_minted(context: &Context, compliance_level: Complianc
   }));
 }
 
+fn assert_event_is_emitted_inclusion_proof_submitted(context: &Context) {
+  System::assert_has_event(MockEvent::Quorum(Event::InclusionProofSubmitted {
+    account_id: AccountId::from(ALICE_ACCOUNT_ID),
+    proposal_id: context.proposal_id,
+  }));
+}
+
 fn assert_event_is_emitted_burned_initialized(context: &Context) {
   System::assert_has_event(MockEvent::Quorum(Event::BurnedInitialized {
     proposal_id: context.proposal_id,
     account_id: context.valid_withdrawal.account_id,
     currency_id: context.valid_withdrawal.asset_id,
     amount: context.valid_withdrawal.amount,
+    fee: 0,
   }));
 }
 
 fn assert_event_is_emitted_burned_acknowledged(context: &Context) {
   System::assert_has_event(MockEvent::Quorum(Event::BurnedAcknowledged {
     proposal_id: context.proposal_id,
+    transaction_id: TRANSACTION_ID.to_vec(),
+  }));
+}
+
+fn assert_event_is_emitted_burned_acknowledgement_submitted(context: &Context, account_id: u64) {
+  System::assert_has_event(MockEvent::Quorum(Event::BurnedAcknowledgementSubmitted {
+    account_id: AccountId::from(account_id),
+    proposal_id: context.proposal_id,
+    transaction_id: TRANSACTION_ID.to_vec(),
   }));
 }
 
@@ -771,6 +911,40 @@ mod voting_for_proposals {
         });
       }
 
+      #[test]
+      pub fn acknowledged_with_a_valid_inclusion_proof() {
+        new_test_ext().execute_with(|| {
+          let context = Context::default()
+            .insert_asset1_with_alice_public_key()
+            .insert_a_valid_mint_proposal_with_green_compliance_level()
+            .require_inclusion_proof()
+            .insert_inclusion_proof(VALID_INCLUSION_PROOF)
+            .insert_deposit_script(VALID_DEPOSIT_SCRIPT);
+
+          let asset_balance_before = get_alice_tdfy_balance();
+
+          assert_ok!(Quorum::acknowledge_proposal(
+            context.alice.clone(),
+            context.proposal_id
+          ));
+
+          assert_eq!(
+            asset_balance_before + context.valid_mint.mint_amount,
+            get_alice_tdfy_balance()
+          );
+          assert_proposal_and_its_votes_have_been_deleted(context.proposal_id);
+          assert!(Quorum::inclusion_proofs(context.proposal_id).is_none());
+          assert!(Quorum::used_transaction_ids(
+            context.valid_mint.currency_id,
+            BoundedVec::<u8, StringLimit>::try_from(context.valid_mint.transaction_id.clone())
+              .unwrap()
+          )
+          .is_some());
+          assert_event_is_emitted_minted(&context, ComplianceLevel::Green);
+          assert_event_is_emitted_proposal_processed(&context);
+        });
+      }
+
       #[test]
       pub fn acknowledged_with_compliance_level_amber() {
         new_test_ext().execute_with(|| {
@@ -981,6 +1155,49 @@ mod voting_for_proposals {
         });
       }
 
+      #[test]
+      pub fn acknowledged_with_withdrawal_fee() {
+        new_test_ext().execute_with(|| {
+          let context = Context::default()
+            .insert_asset1_with_alice_public_key()
+            .mint_tdfy(AccountId::from(ALICE_ACCOUNT_ID), ONE_TDFY)
+            .create_temp_asset_and_metadata()
+            .mint_temp(AccountId::from(ALICE_ACCOUNT_ID), INITIAL_10000_TEMPS)
+            .set_temp_withdrawal_fee()
+            .insert_a_valid_withdrawal_proposal();
+
+          let asset_balance_before = get_alice_temp_balance();
+          // 0.10 TEMP + 1% of 1 TEMP
+          let expected_fee = 11;
+          let expected_amount = context.valid_withdrawal.amount - expected_fee;
+
+          assert_ok!(Quorum::acknowledge_proposal(
+            context.alice.clone(),
+            context.proposal_id
+          ));
+
+          assert_eq!(
+            asset_balance_before - context.valid_withdrawal.amount,
+            get_alice_temp_balance()
+          );
+          assert_eq!(
+            Adapter::balance(TEMP_CURRENCY_ID, &Quorum::account_id()),
+            expected_fee
+          );
+          assert_eq!(
+            Quorum::burned_queue().first().unwrap().1.amount,
+            expected_amount
+          );
+          System::assert_has_event(MockEvent::Quorum(Event::BurnedInitialized {
+            proposal_id: context.proposal_id,
+            account_id: context.valid_withdrawal.account_id,
+            currency_id: context.valid_withdrawal.asset_id,
+            amount: expected_amount,
+            fee: expected_fee,
+          }));
+        });
+      }
+
       #[test]
       pub fn rejected() {
         new_test_ext().execute_with(|| {
@@ -1429,6 +1646,165 @@ mod voting_for_proposals {
           });
         }
 
+        #[test]
+        pub fn amount_is_below_deposit_minimum() {
+          new_test_ext().execute_with(|| {
+            let context = Context::default()
+              .insert_asset1_with_alice_public_key()
+              .insert_a_valid_mint_proposal_with_green_compliance_level()
+              .set_asset_minimums(CurrencyId::Tdfy, 2 * ONE_TDFY, 0);
+
+            let asset_balance_before = get_alice_tdfy_balance();
+
+            assert_err!(
+              Quorum::acknowledge_proposal(context.alice.clone(), context.proposal_id),
+              Error::<Test>::MintAmountBelowMinimum
+            );
+
+            assert_eq!(asset_balance_before, get_alice_tdfy_balance());
+            assert_mint_proposal_exists_in_storage(&context, ComplianceLevel::Green);
+          });
+        }
+
+        #[test]
+        pub fn inclusion_proof_is_missing() {
+          new_test_ext().execute_with(|| {
+            let context = Context::default()
+              .insert_asset1_with_alice_public_key()
+              .insert_a_valid_mint_proposal_with_green_compliance_level()
+              .require_inclusion_proof();
+
+            let asset_balance_before = get_alice_tdfy_balance();
+
+            assert_err!(
+              Quorum::acknowledge_proposal(context.alice.clone(), context.proposal_id),
+              Error::<Test>::MissingInclusionProof
+            );
+
+            assert_eq!(asset_balance_before, get_alice_tdfy_balance());
+            assert_mint_proposal_exists_in_storage(&context, ComplianceLevel::Green);
+          });
+        }
+
+        #[test]
+        pub fn inclusion_proof_is_invalid() {
+          new_test_ext().execute_with(|| {
+            let context = Context::default()
+              .insert_asset1_with_alice_public_key()
+              .insert_a_valid_mint_proposal_with_green_compliance_level()
+              .require_inclusion_proof()
+              .insert_inclusion_proof(Hash::zero())
+              .insert_deposit_script(VALID_DEPOSIT_SCRIPT);
+
+            let asset_balance_before = get_alice_tdfy_balance();
+
+            assert_err!(
+              Quorum::acknowledge_proposal(context.alice.clone(), context.proposal_id),
+              Error::<Test>::InvalidInclusionProof
+            );
+
+            assert_eq!(asset_balance_before, get_alice_tdfy_balance());
+            assert_mint_proposal_exists_in_storage(&context, ComplianceLevel::Green);
+          });
+        }
+
+        #[test]
+        pub fn deposit_script_is_missing() {
+          new_test_ext().execute_with(|| {
+            let context = Context::default()
+              .insert_asset1_with_alice_public_key()
+              .insert_a_valid_mint_proposal_with_green_compliance_level()
+              .require_inclusion_proof()
+              .insert_inclusion_proof(VALID_INCLUSION_PROOF);
+
+            let asset_balance_before = get_alice_tdfy_balance();
+
+            assert_err!(
+              Quorum::acknowledge_proposal(context.alice.clone(), context.proposal_id),
+              Error::<Test>::MissingDepositScript
+            );
+
+            assert_eq!(asset_balance_before, get_alice_tdfy_balance());
+            assert_mint_proposal_exists_in_storage(&context, ComplianceLevel::Green);
+          });
+        }
+
+        #[test]
+        pub fn deposit_is_not_paid_to_the_deposit_script() {
+          new_test_ext().execute_with(|| {
+            let context = Context::default()
+              .insert_asset1_with_alice_public_key()
+              .insert_a_valid_mint_proposal_with_green_compliance_level()
+              .require_inclusion_proof()
+              .insert_inclusion_proof(VALID_INCLUSION_PROOF)
+              .insert_deposit_script(b"another script");
+
+            let asset_balance_before = get_alice_tdfy_balance();
+
+            assert_err!(
+              Quorum::acknowledge_proposal(context.alice.clone(), context.proposal_id),
+              Error::<Test>::InvalidInclusionProof
+            );
+
+            assert_eq!(asset_balance_before, get_alice_tdfy_balance());
+            assert_mint_proposal_exists_in_storage(&context, ComplianceLevel::Green);
+          });
+        }
+
+        #[test]
+        pub fn deposit_has_not_enough_confirmations_for_the_asset() {
+          new_test_ext().execute_with(|| {
+            let context = Context::default()
+              .insert_asset1_with_alice_public_key()
+              .insert_a_valid_mint_proposal_with_green_compliance_level()
+              .require_inclusion_proof()
+              .insert_inclusion_proof(VALID_INCLUSION_PROOF)
+              .insert_deposit_script(VALID_DEPOSIT_SCRIPT);
+            let context = context.set_required_confirmations(
+              context.valid_mint.currency_id,
+              DEPOSIT_CONFIRMATIONS + 1,
+            );
+
+            let asset_balance_before = get_alice_tdfy_balance();
+
+            assert_err!(
+              Quorum::acknowledge_proposal(context.alice.clone(), context.proposal_id),
+              Error::<Test>::InvalidInclusionProof
+            );
+
+            assert_eq!(asset_balance_before, get_alice_tdfy_balance());
+            assert_mint_proposal_exists_in_storage(&context, ComplianceLevel::Green);
+          });
+        }
+
+        #[test]
+        pub fn transaction_id_is_already_used() {
+          new_test_ext().execute_with(|| {
+            let context = Context::default()
+              .insert_asset1_with_alice_public_key()
+              .insert_a_valid_mint_proposal_with_green_compliance_level()
+              .require_inclusion_proof()
+              .insert_inclusion_proof(VALID_INCLUSION_PROOF)
+              .insert_deposit_script(VALID_DEPOSIT_SCRIPT);
+            UsedTransactionIds::<Test>::insert(
+              context.valid_mint.currency_id,
+              BoundedVec::<u8, StringLimit>::try_from(context.valid_mint.transaction_id.clone())
+                .unwrap(),
+              (),
+            );
+
+            let asset_balance_before = get_alice_tdfy_balance();
+
+            assert_err!(
+              Quorum::acknowledge_proposal(context.alice.clone(), context.proposal_id),
+              Error::<Test>::TransactionIdAlreadyUsed
+            );
+
+            assert_eq!(asset_balance_before, get_alice_tdfy_balance());
+            assert_mint_proposal_exists_in_storage(&context, ComplianceLevel::Green);
+          });
+        }
+
         #[test]
         pub fn watch_list_overflow() {
           new_test_ext().execute_with(|| {
@@ -1510,6 +1886,31 @@ mod voting_for_proposals {
           });
         }
 
+        #[test]
+        pub fn amount_is_below_withdrawal_fee() {
+          new_test_ext().execute_with(|| {
+            let context = Context::default()
+              .insert_asset1_with_alice_public_key()
+              .mint_tdfy(AccountId::from(ALICE_ACCOUNT_ID), ONE_TDFY)
+              .create_temp_asset_and_metadata()
+              .mint_temp(AccountId::from(ALICE_ACCOUNT_ID), INITIAL_10000_TEMPS)
+              .insert_a_valid_withdrawal_proposal();
+
+            WithdrawalFees::<Test>::insert(
+              TEMP_CURRENCY_ID,
+              WithdrawalFee {
+                flat: context.valid_withdrawal.amount,
+                percentage: Permill::zero(),
+              },
+            );
+
+            assert_noop!(
+              Quorum::acknowledge_proposal(context.alice.clone(), context.proposal_id),
+              Error::<Test>::WithdrawalAmountBelowFee
+            );
+          });
+        }
+
         #[test]
         pub fn burned_queue_overflow() {
           new_test_ext().execute_with(|| {
@@ -1563,45 +1964,203 @@ mod acknowledge_burned {
     new_test_ext().execute_with(|| {
       let context = Context::default()
         .insert_asset1_with_alice_public_key()
-        .insert_a_valid_mint_proposal_with_green_compliance_level()
-        .set_threshold(2);
+        .insert_a_valid_burned_item();
 
-      assert_ok!(Quorum::reject_proposal(
+      assert_ok!(Quorum::acknowledge_burned(
         context.alice.clone(),
-        context.proposal_id
+        context.proposal_id,
+        TRANSACTION_ID.to_vec()
       ));
 
+      assert_burned_item_is_completed(&context);
+      assert_event_is_emitted_burned_acknowledgement_submitted(&context, ALICE_ACCOUNT_ID);
+      assert_event_is_emitted_burned_acknowledged(&context);
+    });
+  }
+
+  #[test]
+  pub fn waits_for_the_threshold() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .insert_asset1_with_alice_public_key()
+        .insert_bob_with_public_key()
+        .insert_a_valid_burned_item()
+        .set_threshold(2);
+
       assert_ok!(Quorum::acknowledge_burned(
         context.alice.clone(),
-        context.proposal_id
+        context.proposal_id,
+        TRANSACTION_ID.to_vec()
       ));
 
-      assert!(!Quorum::burned_queue()
-        .iter()
-        .any(|&(proposal_id, _)| proposal_id == context.proposal_id));
+      assert_eq!(Quorum::burned_queue().len(), 1);
+      assert_eq!(
+        Quorum::burned_acknowledgements(context.proposal_id).len(),
+        1
+      );
+      assert!(Quorum::completed_withdrawals(AccountId::from(ALICE_ACCOUNT_ID)).is_empty());
+      assert_event_is_emitted_burned_acknowledgement_submitted(&context, ALICE_ACCOUNT_ID);
+
+      assert_ok!(Quorum::acknowledge_burned(
+        context.bob.clone(),
+        context.proposal_id,
+        TRANSACTION_ID.to_vec()
+      ));
 
+      assert_burned_item_is_completed(&context);
       assert_event_is_emitted_burned_acknowledged(&context);
     });
   }
 
-  mod fails_when {
-    use super::*;
+  #[test]
+  pub fn only_counts_acknowledgements_of_the_same_transaction_id() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .insert_asset1_with_alice_public_key()
+        .insert_bob_with_public_key()
+        .insert_a_valid_burned_item()
+        .set_threshold(2);
 
-    #[test]
-    pub fn sender_is_not_signed() {
-      new_test_ext().execute_with(|| {
-        let context = Context::default()
-          .insert_asset1_with_alice_public_key()
-          .insert_a_valid_mint_proposal_with_green_compliance_level()
-          .set_threshold(2);
+      assert_ok!(Quorum::acknowledge_burned(
+        context.alice.clone(),
+        context.proposal_id,
+        TRANSACTION_ID.to_vec()
+      ));
+      assert_ok!(Quorum::acknowledge_burned(
+        context.bob.clone(),
+        context.proposal_id,
+        b"0xdeadbeef".to_vec()
+      ));
+
+      assert_eq!(Quorum::burned_queue().len(), 1);
+      assert_eq!(
+        Quorum::burned_acknowledgements(context.proposal_id).len(),
+        2
+      );
+      assert!(Quorum::completed_withdrawals(AccountId::from(ALICE_ACCOUNT_ID)).is_empty());
+    });
+  }
+
+  #[test]
+  pub fn member_can_replace_its_acknowledgement() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .insert_asset1_with_alice_public_key()
+        .insert_bob_with_public_key()
+        .insert_a_valid_burned_item()
+        .set_threshold(2);
+
+      assert_ok!(Quorum::acknowledge_burned(
+        context.alice.clone(),
+        context.proposal_id,
+        b"0xdeadbeef".to_vec()
+      ));
+      assert_ok!(Quorum::acknowledge_burned(
+        context.bob.clone(),
+        context.proposal_id,
+        TRANSACTION_ID.to_vec()
+      ));
+      assert_eq!(Quorum::burned_queue().len(), 1);
 
-        assert_ok!(Quorum::reject_proposal(
+      // alice replaces the transaction id, the threshold is reached
+      assert_ok!(Quorum::acknowledge_burned(
+        context.alice.clone(),
+        context.proposal_id,
+        TRANSACTION_ID.to_vec()
+      ));
+
+      assert_burned_item_is_completed(&context);
+      assert_event_is_emitted_burned_acknowledged(&context);
+    });
+  }
+
+  #[test]
+  pub fn drops_the_acknowledgements_of_former_members() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .insert_asset1_with_alice_public_key()
+        .insert_bob_with_public_key()
+        .insert_a_valid_burned_item()
+        .set_threshold(2);
+
+      // acknowledgements left by former members fill the list
+      let former_members_acknowledgements: Vec<(AccountId, BoundedVec<u8, StringLimit>)> = (100
+        ..100 + VotesLimit::get() as u64)
+        .map(|account_id| {
+          (
+            AccountId::from(account_id),
+            BoundedVec::try_from(TRANSACTION_ID.to_vec()).unwrap(),
+          )
+        })
+        .collect();
+      BurnedAcknowledgements::<Test>::insert(
+        context.proposal_id,
+        BoundedVec::<_, VotesLimit>::try_from(former_members_acknowledgements).unwrap(),
+      );
+
+      assert_ok!(Quorum::acknowledge_burned(
+        context.alice.clone(),
+        context.proposal_id,
+        TRANSACTION_ID.to_vec()
+      ));
+
+      // the former members are not counted
+      assert_eq!(Quorum::burned_queue().len(), 1);
+      assert_eq!(
+        Quorum::burned_acknowledgements(context.proposal_id).to_vec(),
+        vec![(
+          AccountId::from(ALICE_ACCOUNT_ID),
+          BoundedVec::try_from(TRANSACTION_ID.to_vec()).unwrap()
+        )]
+      );
+    });
+  }
+
+  #[test]
+  pub fn drops_the_oldest_completed_withdrawal_when_the_history_is_full() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default().insert_asset1_with_alice_public_key();
+      let proposal_ids: Vec<Hash> = (1..=CompletedWithdrawalsLimit::get() + 1)
+        .map(|i| H256::from_low_u64_be(i.into()))
+        .collect();
+
+      for proposal_id in proposal_ids.iter() {
+        insert_burned_item(*proposal_id, &context.valid_withdrawal);
+        assert_ok!(Quorum::acknowledge_burned(
           context.alice.clone(),
-          context.proposal_id
+          *proposal_id,
+          TRANSACTION_ID.to_vec()
         ));
+      }
+
+      let history = Quorum::completed_withdrawals(AccountId::from(ALICE_ACCOUNT_ID));
+      assert_eq!(history.len() as u32, CompletedWithdrawalsLimit::get());
+      assert_eq!(
+        history
+          .iter()
+          .map(|completed_withdrawal| completed_withdrawal.proposal_id)
+          .collect::<Vec<Hash>>(),
+        proposal_ids[1..].to_vec()
+      );
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    pub fn sender_is_not_signed() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_a_valid_burned_item();
 
         assert_noop!(
-          Quorum::acknowledge_burned(RuntimeOrigin::none(), context.proposal_id),
+          Quorum::acknowledge_burned(
+            RuntimeOrigin::none(),
+            context.proposal_id,
+            TRANSACTION_ID.to_vec()
+          ),
           BadOrigin
         );
       });
@@ -1612,20 +2171,319 @@ mod acknowledge_burned {
       new_test_ext().execute_with(|| {
         let context = Context::default()
           .insert_asset1_with_alice_public_key()
-          .insert_a_valid_mint_proposal_with_green_compliance_level()
+          .insert_a_valid_burned_item();
+
+        assert_noop!(
+          Quorum::acknowledge_burned(context.bob, context.proposal_id, TRANSACTION_ID.to_vec()),
+          Error::<Test>::AccessDenied
+        );
+      });
+    }
+
+    #[test]
+    pub fn burned_item_does_not_exist() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default().insert_asset1_with_alice_public_key();
+
+        assert_noop!(
+          Quorum::acknowledge_burned(context.alice, context.proposal_id, TRANSACTION_ID.to_vec()),
+          Error::<Test>::BurnedDoesNotExist
+        );
+      });
+    }
+
+    #[test]
+    pub fn transaction_id_is_empty() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_a_valid_burned_item();
+
+        assert_noop!(
+          Quorum::acknowledge_burned(context.alice, context.proposal_id, Vec::new()),
+          Error::<Test>::BadTransactionId
+        );
+      });
+    }
+
+    #[test]
+    pub fn transaction_id_is_too_long() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_a_valid_burned_item();
+
+        assert_noop!(
+          Quorum::acknowledge_burned(
+            context.alice,
+            context.proposal_id,
+            vec![1; StringLimit::get() as usize + 1]
+          ),
+          Error::<Test>::BadTransactionId
+        );
+      });
+    }
+
+    #[test]
+    pub fn member_already_acknowledged() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_bob_with_public_key()
+          .insert_a_valid_burned_item()
           .set_threshold(2);
 
-        assert_ok!(Quorum::reject_proposal(
+        assert_ok!(Quorum::acknowledge_burned(
           context.alice.clone(),
-          context.proposal_id
+          context.proposal_id,
+          TRANSACTION_ID.to_vec()
         ));
 
         assert_noop!(
-          Quorum::acknowledge_burned(context.bob, context.proposal_id),
+          Quorum::acknowledge_burned(context.alice, context.proposal_id, TRANSACTION_ID.to_vec()),
+          Error::<Test>::MemberAlreadyAcknowledged
+        );
+      });
+    }
+
+    #[test]
+    pub fn acknowledgements_overflow() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_a_valid_burned_item()
+          .set_threshold(VotesLimit::get() as u16 + 1);
+
+        let members_acknowledgements: Vec<(AccountId, BoundedVec<u8, StringLimit>)> = (100
+          ..100 + VotesLimit::get() as u64)
+          .map(|account_id| {
+            Members::<Test>::insert(AccountId::from(account_id), true);
+            (
+              AccountId::from(account_id),
+              BoundedVec::try_from(TRANSACTION_ID.to_vec()).unwrap(),
+            )
+          })
+          .collect();
+        BurnedAcknowledgements::<Test>::insert(
+          context.proposal_id,
+          BoundedVec::<_, VotesLimit>::try_from(members_acknowledgements).unwrap(),
+        );
+
+        assert_noop!(
+          Quorum::acknowledge_burned(context.alice, context.proposal_id, TRANSACTION_ID.to_vec()),
+          Error::<Test>::BurnedAcknowledgementsOverflow
+        );
+      });
+    }
+  }
+}
+
+mod submit_inclusion_proof {
+  use super::*;
+
+  #[test]
+  pub fn succeeds() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .insert_asset1_with_alice_public_key()
+        .insert_a_valid_mint_proposal_with_green_compliance_level();
+
+      assert_ok!(Quorum::submit_inclusion_proof(
+        context.alice.clone(),
+        context.proposal_id,
+        VALID_INCLUSION_PROOF
+      ));
+
+      assert_eq!(
+        Quorum::inclusion_proofs(context.proposal_id),
+        Some(VALID_INCLUSION_PROOF)
+      );
+      assert_event_is_emitted_inclusion_proof_submitted(&context);
+    });
+  }
+
+  #[test]
+  pub fn replaces_an_invalid_proof() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .insert_asset1_with_alice_public_key()
+        .insert_a_valid_mint_proposal_with_green_compliance_level()
+        .insert_inclusion_proof(Hash::zero())
+        .require_inclusion_proof()
+        .insert_deposit_script(VALID_DEPOSIT_SCRIPT);
+
+      assert_ok!(Quorum::submit_inclusion_proof(
+        context.alice.clone(),
+        context.proposal_id,
+        VALID_INCLUSION_PROOF
+      ));
+
+      assert_eq!(
+        Quorum::inclusion_proofs(context.proposal_id),
+        Some(VALID_INCLUSION_PROOF)
+      );
+      assert_event_is_emitted_inclusion_proof_submitted(&context);
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    pub fn sender_is_not_signed() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_a_valid_mint_proposal_with_green_compliance_level();
+
+        assert_noop!(
+          Quorum::submit_inclusion_proof(
+            RuntimeOrigin::none(),
+            context.proposal_id,
+            VALID_INCLUSION_PROOF
+          ),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    pub fn sender_is_not_a_member() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_a_valid_mint_proposal_with_green_compliance_level();
+
+        assert_noop!(
+          Quorum::submit_inclusion_proof(context.bob, context.proposal_id, VALID_INCLUSION_PROOF),
           Error::<Test>::AccessDenied
         );
       });
     }
+
+    #[test]
+    pub fn proposal_does_not_exist() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default().insert_asset1_with_alice_public_key();
+
+        assert_noop!(
+          Quorum::submit_inclusion_proof(context.alice, context.proposal_id, VALID_INCLUSION_PROOF),
+          Error::<Test>::ProposalDoesNotExist
+        );
+      });
+    }
+
+    #[test]
+    pub fn proposal_is_not_a_mint() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_a_valid_withdrawal_proposal();
+
+        assert_noop!(
+          Quorum::submit_inclusion_proof(context.alice, context.proposal_id, VALID_INCLUSION_PROOF),
+          Error::<Test>::BadProposal
+        );
+      });
+    }
+
+    #[test]
+    pub fn proof_is_invalid() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_a_valid_mint_proposal_with_green_compliance_level()
+          .require_inclusion_proof()
+          .insert_deposit_script(VALID_DEPOSIT_SCRIPT)
+          .insert_inclusion_proof(VALID_INCLUSION_PROOF);
+
+        assert_noop!(
+          Quorum::submit_inclusion_proof(context.alice, context.proposal_id, Hash::zero()),
+          Error::<Test>::InvalidInclusionProof
+        );
+        assert_eq!(
+          Quorum::inclusion_proofs(context.proposal_id),
+          Some(VALID_INCLUSION_PROOF)
+        );
+      });
+    }
+
+    #[test]
+    pub fn deposit_script_is_missing() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .insert_asset1_with_alice_public_key()
+          .insert_a_valid_mint_proposal_with_green_compliance_level()
+          .require_inclusion_proof();
+
+        assert_noop!(
+          Quorum::submit_inclusion_proof(context.alice, context.proposal_id, VALID_INCLUSION_PROOF),
+          Error::<Test>::MissingDepositScript
+        );
+      });
+    }
+  }
+}
+
+mod submit_deposit_script {
+  use super::*;
+
+  #[test]
+  pub fn succeeds() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(Quorum::submit_deposit_script(
+        RuntimeOrigin::root(),
+        BOB_ACCOUNT_ID.into(),
+        TEMP_CURRENCY_ID,
+        VALID_DEPOSIT_SCRIPT.to_vec()
+      ));
+
+      assert_eq!(
+        Quorum::deposit_scripts(AccountId::from(BOB_ACCOUNT_ID), TEMP_CURRENCY_ID)
+          .map(|script| script.to_vec()),
+        Some(VALID_DEPOSIT_SCRIPT.to_vec())
+      );
+      System::assert_has_event(MockEvent::Quorum(Event::DepositScriptUpdated {
+        account_id: BOB_ACCOUNT_ID.into(),
+        currency_id: TEMP_CURRENCY_ID,
+      }));
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    pub fn sender_is_a_quorum_member() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default().insert_asset1_with_alice_public_key();
+
+        assert_noop!(
+          Quorum::submit_deposit_script(
+            context.alice,
+            BOB_ACCOUNT_ID.into(),
+            TEMP_CURRENCY_ID,
+            VALID_DEPOSIT_SCRIPT.to_vec()
+          ),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    pub fn deposit_script_is_too_long() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          Quorum::submit_deposit_script(
+            RuntimeOrigin::root(),
+            BOB_ACCOUNT_ID.into(),
+            TEMP_CURRENCY_ID,
+            vec![0; StringLimit::get() as usize + 1]
+          ),
+          Error::<Test>::DepositScriptTooLong
+        );
+      });
+    }
   }
 }
 
@@ -1973,3 +2831,269 @@ mod submit_public_keys {
     }
   }
 }
+
+mod set_withdrawal_fee {
+  use super::*;
+
+  #[test]
+  pub fn succeeds() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(Quorum::set_withdrawal_fee(
+        RuntimeOrigin::root(),
+        TEMP_CURRENCY_ID,
+        Some(TEMP_WITHDRAWAL_FEE)
+      ));
+      assert_eq!(
+        Quorum::withdrawal_fees(TEMP_CURRENCY_ID),
+        Some(TEMP_WITHDRAWAL_FEE)
+      );
+      assert_eq!(Quorum::withdrawal_fee(TEMP_CURRENCY_ID, 10 * ONE_TEMP), 20);
+      System::assert_has_event(MockEvent::Quorum(Event::WithdrawalFeeUpdated {
+        currency_id: TEMP_CURRENCY_ID,
+        fee: Some(TEMP_WITHDRAWAL_FEE),
+      }));
+
+      assert_ok!(Quorum::set_withdrawal_fee(
+        RuntimeOrigin::root(),
+        TEMP_CURRENCY_ID,
+        None
+      ));
+      assert_eq!(Quorum::withdrawal_fees(TEMP_CURRENCY_ID), None);
+      assert_eq!(Quorum::withdrawal_fee(TEMP_CURRENCY_ID, 10 * ONE_TEMP), 0);
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    pub fn sender_is_not_root() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default();
+
+        assert_noop!(
+          Quorum::set_withdrawal_fee(context.alice, TEMP_CURRENCY_ID, Some(TEMP_WITHDRAWAL_FEE)),
+          BadOrigin
+        );
+      });
+    }
+  }
+}
+
+mod add_new_withdrawal_in_queue {
+  use super::*;
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    pub fn amount_is_below_withdrawal_fee() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default().set_temp_withdrawal_fee();
+
+        assert_noop!(
+          Quorum::add_new_withdrawal_in_queue(
+            ALICE_ACCOUNT_ID.into(),
+            TEMP_CURRENCY_ID,
+            TEMP_WITHDRAWAL_FEE.flat,
+            context.valid_withdrawal.external_address,
+          ),
+          Error::<Test>::WithdrawalAmountBelowFee
+        );
+      });
+    }
+
+    #[test]
+    pub fn amount_is_below_withdrawal_minimum() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default().set_asset_minimums(TEMP_CURRENCY_ID, 0, 2 * ONE_TEMP);
+
+        assert_noop!(
+          Quorum::add_new_withdrawal_in_queue(
+            ALICE_ACCOUNT_ID.into(),
+            TEMP_CURRENCY_ID,
+            ONE_TEMP,
+            context.valid_withdrawal.external_address,
+          ),
+          Error::<Test>::WithdrawalAmountBelowMinimum
+        );
+      });
+    }
+  }
+}
+
+mod queries {
+  use super::*;
+
+  #[test]
+  pub fn get_proposals_info() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .insert_asset1_with_alice_public_key()
+        .insert_a_valid_mint_proposal_with_green_compliance_level()
+        .set_threshold(2)
+        .commit_a_valid_vote(true);
+
+      let page = Quorum::get_proposals_info(0, 10);
+      assert_eq!(page.total, 1);
+      assert_eq!(
+        page.items,
+        vec![ProposalInfo {
+          proposal_id: context.proposal_id,
+          created_at: BLOCK_NUMBER_ZERO,
+          expiry: BLOCK_NUMBER_ZERO + ProposalLifetime::get(),
+          proposal: ProposalType::Mint(context.valid_mint.clone()),
+          status: ProposalStatus::Initiated,
+          votes_for: vec![AccountId::from(ALICE_ACCOUNT_ID)],
+          votes_against: Vec::new(),
+          threshold: 2,
+        }]
+      );
+      assert_eq!(
+        Quorum::get_proposal_info(context.proposal_id),
+        page.items.first().cloned()
+      );
+      assert_eq!(Quorum::get_proposal_info(Hash::zero()), None);
+    });
+  }
+
+  #[test]
+  pub fn get_proposals_info_is_paginated() {
+    new_test_ext().execute_with(|| {
+      Context::default().insert_asset1_with_alice_public_key();
+
+      for i in 0..5 {
+        insert_mint_proposal(
+          Default::default(),
+          ComplianceLevel::Green,
+          Hash::repeat_byte(i),
+        );
+      }
+
+      let page = Quorum::get_proposals_info(3, 10);
+      assert_eq!(page.total, 5);
+      assert_eq!(
+        page
+          .items
+          .iter()
+          .map(|info| info.proposal_id)
+          .collect::<Vec<_>>(),
+        vec![Hash::repeat_byte(3), Hash::repeat_byte(4)]
+      );
+
+      assert_eq!(Quorum::get_proposals_info(1, 2).items.len(), 2);
+      assert!(Quorum::get_proposals_info(5, 10).items.is_empty());
+    });
+  }
+
+  #[test]
+  pub fn get_burned_queue() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .insert_asset1_with_alice_public_key()
+        .mint_tdfy(AccountId::from(ALICE_ACCOUNT_ID), ONE_TDFY)
+        .create_temp_asset_and_metadata()
+        .mint_temp(AccountId::from(ALICE_ACCOUNT_ID), INITIAL_10000_TEMPS)
+        .insert_a_valid_withdrawal_proposal();
+
+      assert_ok!(Quorum::acknowledge_proposal(
+        context.alice.clone(),
+        context.proposal_id
+      ));
+
+      let page = Quorum::get_burned_queue(0, 10);
+      assert_eq!(page.total, 1);
+      assert_eq!(
+        page.items,
+        vec![(context.proposal_id, context.valid_withdrawal.clone())]
+      );
+    });
+  }
+
+  #[test]
+  pub fn get_public_keys() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default().insert_asset1_with_alice_public_key();
+
+      let page = Quorum::get_public_keys(ASSET_1, 0, 10);
+      assert_eq!(page.total, 1);
+      assert_eq!(
+        page.items,
+        vec![(AccountId::from(ALICE_ACCOUNT_ID), context.pub_key)]
+      );
+      assert_eq!(Quorum::get_public_keys(TEMP_ASSET_ID, 0, 10).total, 0);
+    });
+  }
+
+  #[test]
+  pub fn get_account_watch_list() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .insert_asset1_with_alice_public_key()
+        .insert_a_valid_mint_proposal_with_amber_compliance_level();
+
+      assert_ok!(Quorum::acknowledge_proposal(
+        context.alice.clone(),
+        context.proposal_id
+      ));
+
+      let page = Quorum::get_account_watch_list(AccountId::from(ALICE_ACCOUNT_ID), 0, 10);
+      assert_eq!(page.total, 1);
+      assert_eq!(
+        page.items,
+        vec![WatchList {
+          amount: context.valid_mint.mint_amount,
+          block_number: BLOCK_NUMBER_ZERO,
+          compliance_level: ComplianceLevel::Amber,
+          currency_id: context.valid_mint.currency_id,
+          watch_action: WatchListAction::Mint,
+          transaction_id: context.valid_mint.transaction_id.clone(),
+        }]
+      );
+      assert_eq!(
+        Quorum::get_account_watch_list(AccountId::from(BOB_ACCOUNT_ID), 0, 10).total,
+        0
+      );
+    });
+  }
+
+  #[test]
+  pub fn get_completed_withdrawals() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default().insert_asset1_with_alice_public_key();
+      let first_proposal_id = H256::from_low_u64_be(1);
+      let second_proposal_id = H256::from_low_u64_be(2);
+
+      for proposal_id in [first_proposal_id, second_proposal_id] {
+        insert_burned_item(proposal_id, &context.valid_withdrawal);
+        assert_ok!(Quorum::acknowledge_burned(
+          context.alice.clone(),
+          proposal_id,
+          TRANSACTION_ID.to_vec()
+        ));
+      }
+
+      let page = Quorum::get_completed_withdrawals(AccountId::from(ALICE_ACCOUNT_ID), 0, 10);
+      assert_eq!(page.total, 2);
+      assert_eq!(
+        page.items,
+        vec![second_proposal_id, first_proposal_id]
+          .into_iter()
+          .map(|proposal_id| CompletedWithdrawal {
+            proposal_id,
+            currency_id: context.valid_withdrawal.asset_id,
+            amount: context.valid_withdrawal.amount,
+            external_address: context.valid_withdrawal.external_address.clone(),
+            transaction_id: TRANSACTION_ID.to_vec(),
+            requested_at: context.valid_withdrawal.block_number,
+            completed_at: BLOCK_NUMBER_ZERO,
+          })
+          .collect::<Vec<_>>()
+      );
+      assert_eq!(
+        Quorum::get_completed_withdrawals(AccountId::from(BOB_ACCOUNT_ID), 0, 10).total,
+        0
+      );
+    });
+  }
+}
@@ -0,0 +1,112 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types returned by the quorum queries (runtime API).
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::Permill;
+use sp_std::vec::Vec;
+use tidefi_primitives::{Balance, CurrencyId, Hash, ProposalStatus, ProposalType};
+
+/// Maximum number of items returned by a single page.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Page of items, `total` is the number of items available across all pages.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Page<Item> {
+  pub items: Vec<Item>,
+  pub total: u32,
+}
+
+impl<Item> Page<Item> {
+  /// Collect the items of the page starting at `offset`, at most `limit` (capped to
+  /// `MAX_PAGE_SIZE`) items are returned.
+  pub fn paginate<I: ExactSizeIterator<Item = Item>>(iter: I, offset: u32, limit: u32) -> Self {
+    let total = iter.len() as u32;
+    let items = iter
+      .skip(offset as usize)
+      .take(limit.min(MAX_PAGE_SIZE) as usize)
+      .collect();
+    Self { items, total }
+  }
+}
+
+/// Pending proposal with its votes.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalInfo<AccountId, BlockNumber> {
+  /// Proposal id
+  pub proposal_id: Hash,
+  /// Block number the proposal has been submitted
+  pub created_at: BlockNumber,
+  /// Block number after which the proposal is expired and can't be voted
+  pub expiry: BlockNumber,
+  /// Proposal details
+  pub proposal: ProposalType<AccountId, BlockNumber, Vec<u8>, Vec<AccountId>>,
+  /// Current status of the proposal
+  pub status: ProposalStatus,
+  /// Members who voted for the proposal
+  pub votes_for: Vec<AccountId>,
+  /// Members who voted against the proposal
+  pub votes_against: Vec<AccountId>,
+  /// Number of votes required to approve (or reject) the proposal
+  pub threshold: u16,
+}
+
+/// Fee taken on the withdrawals of a currency, to cover the external chain gas paid by
+/// the quorum.
+#[derive(
+  Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct WithdrawalFee {
+  /// Flat fee, in the currency withdrawn
+  pub flat: Balance,
+  /// Percentage of the amount withdrawn
+  pub percentage: Permill,
+}
+
+impl WithdrawalFee {
+  /// Total fee for a withdrawal of `amount`.
+  pub fn compute(&self, amount: Balance) -> Balance {
+    self.flat.saturating_add(self.percentage.mul_floor(amount))
+  }
+}
+
+/// Withdrawal paid out on the external chain and acknowledged by the quorum.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CompletedWithdrawal<BlockNumber, BoundedString> {
+  /// Burned proposal id
+  pub proposal_id: Hash,
+  /// Currency withdrawn
+  pub currency_id: CurrencyId,
+  /// Net amount sent on the external chain
+  pub amount: Balance,
+  /// External address the funds were sent to
+  pub external_address: BoundedString,
+  /// Transaction id on the external chain
+  pub transaction_id: BoundedString,
+  /// Block number the withdrawal has been requested
+  pub requested_at: BlockNumber,
+  /// Block number the quorum acknowledged the withdrawal
+  pub completed_at: BlockNumber,
+}
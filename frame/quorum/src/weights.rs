@@ -29,6 +29,9 @@ pub trait WeightInfo {
    fn reject_proposal() -> Weight;
    fn eval_proposal_state() -> Weight;
    fn submit_public_keys(_k: u32) -> Weight;
+   fn submit_inclusion_proof() -> Weight;
+   fn set_withdrawal_fee() -> Weight;
+   fn submit_deposit_script() -> Weight;
 }
 
 /// Weights for `pallet_tidefi` using the Substrate node and recommended hardware.
@@ -45,9 +48,9 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
          .saturating_add(T::DbWeight::get().writes(5_u64))
    }
    fn acknowledge_burned() -> Weight {
-      Weight::from_ref_time(60_000_300)
-         .saturating_add(T::DbWeight::get().reads(6_u64))
-         .saturating_add(T::DbWeight::get().writes(5_u64))
+      Weight::from_ref_time(78_000_300)
+         .saturating_add(T::DbWeight::get().reads(8_u64))
+         .saturating_add(T::DbWeight::get().writes(7_u64))
    }
    fn reject_proposal() -> Weight {
       Weight::from_ref_time(63_000_300)
@@ -65,4 +68,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
          .saturating_add(T::DbWeight::get().reads(6_u64))
          .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+   fn submit_inclusion_proof() -> Weight {
+      Weight::from_ref_time(48_000_300)
+         .saturating_add(T::DbWeight::get().reads(4_u64))
+         .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn set_withdrawal_fee() -> Weight {
+      Weight::from_ref_time(22_000_000)
+         .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
+   fn submit_deposit_script() -> Weight {
+      Weight::from_ref_time(36_000_000)
+         .saturating_add(T::DbWeight::get().reads(2_u64))
+         .saturating_add(T::DbWeight::get().writes(1_u64))
+   }
 }
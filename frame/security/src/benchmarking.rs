@@ -16,15 +16,74 @@
 
 #![cfg(feature = "runtime-benchmarks")]
 
-use crate::{Call, Config, Pallet};
+use crate::{
+  Call, Config, MaintenanceWindow, NameOf, Pallet, PausedCall, PausedCallOf, PausedCalls, ReasonOf,
+  ScheduledMaintenance,
+};
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::Get;
 use frame_system::RawOrigin;
-use tidefi_primitives::StatusCode;
+use sp_std::vec;
+use tidefi_primitives::{CurrencyId, StatusCode};
+
+fn max_length_reason<T: Config>() -> ReasonOf<T> {
+  vec![b'a'; T::MaxReasonLength::get() as usize]
+    .try_into()
+    .expect("reason of the maximum length")
+}
+
+fn max_length_paused_call<T: Config>() -> PausedCallOf<T> {
+  let name: NameOf<T> = vec![b'a'; T::MaxNameLength::get() as usize]
+    .try_into()
+    .expect("name of the maximum length");
+  PausedCall {
+    pallet_name: name.clone(),
+    call_name: Some(name),
+    currency_id: Some(CurrencyId::Wrapped(u32::MAX)),
+  }
+}
 
 benchmarks! {
    set_status {
       let new_status = StatusCode::Maintenance;
    }: _(RawOrigin::Root, new_status.clone())
+
+   pause {
+      let paused_call = max_length_paused_call::<T>();
+   }: _(RawOrigin::Root, paused_call.clone())
+   verify {
+      assert!(PausedCalls::<T>::contains_key(&paused_call));
+   }
+
+   resume {
+      let paused_call = max_length_paused_call::<T>();
+      PausedCalls::<T>::insert(&paused_call, T::PauseDuration::get());
+   }: _(RawOrigin::Root, paused_call.clone())
+   verify {
+      assert!(!PausedCalls::<T>::contains_key(&paused_call));
+   }
+
+   schedule_maintenance {
+      let start_block = frame_system::Pallet::<T>::block_number() + 10_u32.into();
+      let end_block = start_block + 10_u32.into();
+      let reason = max_length_reason::<T>();
+   }: _(RawOrigin::Root, start_block, end_block, reason, true)
+   verify {
+      assert!(ScheduledMaintenance::<T>::exists());
+   }
+
+   cancel_maintenance {
+      let start_block = frame_system::Pallet::<T>::block_number() + 10_u32.into();
+      ScheduledMaintenance::<T>::put(MaintenanceWindow {
+         start_block,
+         end_block: start_block + 10_u32.into(),
+         reason: max_length_reason::<T>(),
+         reject_during_notice: true,
+      });
+   }: _(RawOrigin::Root)
+   verify {
+      assert!(!ScheduledMaintenance::<T>::exists());
+   }
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -25,7 +25,9 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod types;
 pub mod weights;
+pub use types::*;
 pub use weights::*;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
@@ -34,18 +36,49 @@ pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
   use super::*;
-  use frame_support::pallet_prelude::*;
+  use frame_support::{
+    inherent::Vec,
+    pallet_prelude::{DispatchResultWithPostInfo, *},
+    traits::PalletInfoAccess,
+  };
   use frame_system::pallet_prelude::*;
   use sha2::{Digest, Sha256};
   use sp_core::{H256, U256};
   use sp_runtime::traits::Saturating;
-  use tidefi_primitives::{pallet::SecurityExt, Hash, StatusCode};
+  use sp_std::iter;
+  use tidefi_primitives::{pallet::SecurityExt, CurrencyId, Hash, StatusCode};
+
+  pub type NameOf<T> = BoundedVec<u8, <T as Config>::MaxNameLength>;
+  pub type PausedCallOf<T> = PausedCall<NameOf<T>>;
+  pub type ReasonOf<T> = BoundedVec<u8, <T as Config>::MaxReasonLength>;
+  pub type MaintenanceWindowOf<T> =
+    MaintenanceWindow<<T as frame_system::Config>::BlockNumber, ReasonOf<T>>;
 
   /// Security configuration
   #[pallet::config]
   pub trait Config: frame_system::Config {
     /// Events
     type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+    /// Origin allowed to pause the chain, the pallets and the calls
+    type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+    /// Origin allowed to resume the chain, the pallets and the calls
+    type ResumeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+    /// Number of blocks before a pause expires, unless it is renewed
+    #[pallet::constant]
+    type PauseDuration: Get<Self::BlockNumber>;
+    /// Number of times the pause origin can renew a pause, before the resume origin has to renew
+    /// or resume it
+    #[pallet::constant]
+    type MaxPauseRenewals: Get<u32>;
+    /// Pallets which can't be paused, as their calls keep the chain producing blocks or are
+    /// needed to resume the paused calls, for example `Timestamp`
+    type UnpausablePallets: Get<&'static [&'static str]>;
+    /// Maximum length of the pallet and call names
+    #[pallet::constant]
+    type MaxNameLength: Get<u32>;
+    /// Maximum length of the scheduled maintenance reason
+    #[pallet::constant]
+    type MaxReasonLength: Get<u32>;
     /// Weights
     type WeightInfo: WeightInfo;
   }
@@ -69,6 +102,36 @@ pub mod pallet {
   #[pallet::getter(fn current_block_number)]
   pub type CurrentBlockCount<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
+  /// Block at which the maintenance mode expires and the chain is running again
+  #[pallet::storage]
+  #[pallet::getter(fn maintenance_expiry)]
+  pub type MaintenanceExpiry<T: Config> = StorageValue<_, T::BlockNumber>;
+
+  /// Maintenance scheduled with an advance notice, kept until its end
+  #[pallet::storage]
+  #[pallet::getter(fn scheduled_maintenance)]
+  pub type ScheduledMaintenance<T: Config> = StorageValue<_, MaintenanceWindowOf<T>>;
+
+  /// Paused pallets and calls with the block at which the pause expires, enforced by the
+  /// runtime `BaseCallFilter`
+  #[pallet::storage]
+  #[pallet::getter(fn paused_calls)]
+  pub type PausedCalls<T: Config> =
+    StorageMap<_, Blake2_128Concat, PausedCallOf<T>, T::BlockNumber>;
+
+  /// Number of times the pause origin renewed the pause since the resume origin paused, renewed or
+  /// resumed the calls
+  #[pallet::storage]
+  #[pallet::getter(fn pause_renewals)]
+  pub type PauseRenewals<T: Config> =
+    StorageMap<_, Blake2_128Concat, PausedCallOf<T>, u32, ValueQuery>;
+
+  /// Number of times the pause origin renewed the maintenance mode since the resume origin set
+  /// the chain status, kept once the maintenance expired
+  #[pallet::storage]
+  #[pallet::getter(fn maintenance_renewals)]
+  pub type MaintenanceRenewals<T: Config> = StorageValue<_, u32>;
+
   /// Genesis config
   #[pallet::genesis_config]
   pub struct GenesisConfig {
@@ -98,6 +161,24 @@ pub mod pallet {
   pub enum Event<T: Config> {
     StatusChanged(StatusCode),
     UpdateCurrentBlock(T::BlockNumber),
+    /// Pallet or call paused (or pause renewed) until `expires_at`
+    CallPaused {
+      paused_call: PausedCallOf<T>,
+      expires_at: T::BlockNumber,
+    },
+    /// Pallet or call resumed
+    CallResumed {
+      paused_call: PausedCallOf<T>,
+    },
+    /// Maintenance scheduled, the chain enters maintenance at `start_block`
+    MaintenanceScheduled {
+      start_block: T::BlockNumber,
+      end_block: T::BlockNumber,
+      reason: ReasonOf<T>,
+      reject_during_notice: bool,
+    },
+    /// Scheduled maintenance cancelled before its start
+    MaintenanceCancelled,
   }
 
   // Errors inform users that something went wrong.
@@ -105,11 +186,53 @@ pub mod pallet {
   pub enum Error<T> {
     /// Chain is in maintenance mode
     ChainMaintenanceMode,
+    /// The pallet or call is not paused
+    NotPaused,
+    /// The security pallet and the unpausable pallets can't be paused
+    UnpausableCall,
+    /// The maintenance should start in a future block and end after its start
+    InvalidMaintenanceWindow,
+    /// The scheduled maintenance already started
+    MaintenanceInProgress,
+    /// No maintenance is scheduled
+    NoScheduledMaintenance,
+    /// The pause was renewed `MaxPauseRenewals` times, only the resume origin can renew it
+    TooManyPauseRenewals,
   }
 
   #[pallet::hooks]
   impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
-    fn on_initialize(_current_block: T::BlockNumber) -> Weight {
+    fn on_initialize(current_block: T::BlockNumber) -> Weight {
+      let mut weight = T::DbWeight::get().reads(2);
+
+      // the scheduled maintenance starts at its start block, the maintenance expiry exits it
+      // at its end block
+      if let Some(window) = Self::scheduled_maintenance() {
+        if window.start_block == current_block {
+          <ChainStatus<T>>::set(StatusCode::Maintenance);
+          // a maintenance already in progress with a later expiry is kept until its expiry
+          <MaintenanceExpiry<T>>::mutate(|expiry| {
+            *expiry = Some(expiry.map_or(window.end_block, |expiry| expiry.max(window.end_block)))
+          });
+          Self::deposit_event(Event::StatusChanged(StatusCode::Maintenance));
+          weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+        } else if window.end_block <= current_block {
+          <ScheduledMaintenance<T>>::kill();
+          weight = weight.saturating_add(T::DbWeight::get().writes(1));
+        }
+      }
+
+      // the maintenance mode expires unless it is renewed
+      if Self::status() != StatusCode::Running {
+        weight = weight.saturating_add(T::DbWeight::get().reads(1));
+        if Self::maintenance_expiry().map_or(false, |expiry| expiry <= current_block) {
+          <ChainStatus<T>>::set(StatusCode::Running);
+          <MaintenanceExpiry<T>>::kill();
+          Self::deposit_event(Event::StatusChanged(StatusCode::Running));
+          weight = weight.saturating_add(T::DbWeight::get().writes(2));
+        }
+      }
+
       // updated ONLY when the chain status is running
       if Self::status() == StatusCode::Running {
         let height = <CurrentBlockCount<T>>::mutate(|n| {
@@ -117,8 +240,10 @@ pub mod pallet {
           *n
         });
         Self::deposit_event(Event::UpdateCurrentBlock(height));
+        weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
       }
-      Weight::from_ref_time(0)
+
+      weight
     }
   }
 
@@ -134,21 +259,286 @@ pub mod pallet {
     /// This will also stop the staking blocks increment, so even if the Validators
     /// are still building blocks, they are not counted for the rewards.
     ///
+    /// The maintenance mode is set by the pause origin and expires after `PauseDuration`
+    /// blocks, calling it again renews the expiry. The pause origin can renew the maintenance
+    /// `MaxPauseRenewals` times, even once expired, before the resume origin has to renew it.
+    /// Only the resume origin can restore the running state before the expiry.
+    ///
     /// - `status_code`: New chain `StatusCode`
     ///
     /// Emits `StatusChanged` event when successful.
     #[pallet::call_index(0)]
     #[pallet::weight(<T as pallet::Config>::WeightInfo::set_status())]
     pub fn set_status(origin: OriginFor<T>, status_code: StatusCode) -> DispatchResultWithPostInfo {
-      ensure_root(origin)?;
+      // 1. Make sure the origin is allowed and update the maintenance expiry
+      if status_code == StatusCode::Running {
+        T::ResumeOrigin::ensure_origin(origin)?;
+        <MaintenanceExpiry<T>>::kill();
+        <MaintenanceRenewals<T>>::kill();
+        // the scheduled maintenance in progress ends early
+        if Self::is_maintenance_started() {
+          <ScheduledMaintenance<T>>::kill();
+        }
+      } else {
+        T::PauseOrigin::ensure_origin(origin.clone())?;
+        // the pause origin doesn't renew the maintenance indefinitely, the renewals of an
+        // expired maintenance are kept until the resume origin sets the chain status
+        let renewals = if T::ResumeOrigin::ensure_origin(origin).is_ok() {
+          0
+        } else {
+          Self::maintenance_renewals().map_or(0, |renewals| renewals.saturating_add(1))
+        };
+        ensure!(
+          renewals <= T::MaxPauseRenewals::get(),
+          Error::<T>::TooManyPauseRenewals
+        );
+        <MaintenanceRenewals<T>>::put(renewals);
+        <MaintenanceExpiry<T>>::put(Self::pause_expiry());
+      }
+
+      // 2. Update the chain status
       <ChainStatus<T>>::set(status_code.clone());
+
+      // 3. Emit event on chain
       Self::deposit_event(Event::StatusChanged(status_code));
       Ok(().into())
     }
+
+    /// Pause the calls of a pallet, while the rest of the chain keeps running.
+    ///
+    /// The pause expires after `PauseDuration` blocks, pausing the same calls again renews
+    /// the expiry. The pause origin can renew a pause `MaxPauseRenewals` times, even once
+    /// expired, before the resume origin has to renew or resume it.
+    ///
+    /// - `paused_call`: Name of the pallet in the runtime, with the name of the call to pause only
+    ///   this call and the currency to pause the calls only for this currency
+    ///
+    /// Emits `CallPaused` event when successful.
+    #[pallet::call_index(1)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::pause())]
+    pub fn pause(origin: OriginFor<T>, paused_call: PausedCallOf<T>) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::PauseOrigin::ensure_origin(origin.clone())?;
+      let is_resume_origin = T::ResumeOrigin::ensure_origin(origin).is_ok();
+
+      // 2. Make sure the security pallet and the unpausable pallets stay available
+      ensure!(
+        !Self::is_unpausable(&paused_call.pallet_name),
+        Error::<T>::UnpausableCall
+      );
+
+      // 3. Make sure the pause origin doesn't renew the pause indefinitely, the expired pauses
+      //    are kept until resumed
+      let renewals = if !is_resume_origin && PausedCalls::<T>::contains_key(&paused_call) {
+        Self::pause_renewals(&paused_call).saturating_add(1)
+      } else {
+        0
+      };
+      ensure!(
+        renewals <= T::MaxPauseRenewals::get(),
+        Error::<T>::TooManyPauseRenewals
+      );
+
+      // 4. Pause the calls, or renew the pause
+      let expires_at = Self::pause_expiry();
+      PausedCalls::<T>::insert(&paused_call, expires_at);
+      PauseRenewals::<T>::insert(&paused_call, renewals);
+
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::CallPaused {
+        paused_call,
+        expires_at,
+      });
+
+      Ok(().into())
+    }
+
+    /// Resume the calls paused with `pause`, before the pause expires.
+    ///
+    /// - `paused_call`: Pallet, call and currency paused
+    ///
+    /// Emits `CallResumed` event when successful.
+    #[pallet::call_index(2)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::resume())]
+    pub fn resume(
+      origin: OriginFor<T>,
+      paused_call: PausedCallOf<T>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ResumeOrigin::ensure_origin(origin)?;
+
+      // 2. Resume the calls
+      ensure!(
+        PausedCalls::<T>::contains_key(&paused_call),
+        Error::<T>::NotPaused
+      );
+      PausedCalls::<T>::remove(&paused_call);
+      PauseRenewals::<T>::remove(&paused_call);
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::CallResumed { paused_call });
+
+      Ok(().into())
+    }
+
+    /// Schedule a maintenance with an advance notice, replacing the maintenance scheduled
+    /// previously if it didn't start yet.
+    ///
+    /// The chain enters maintenance at `start_block` and exits it at `end_block`, the resume
+    /// origin can exit it early with `set_status`.
+    ///
+    /// - `start_block`: Block at which the chain enters maintenance
+    /// - `end_block`: Block at which the chain exits maintenance
+    /// - `reason`: Reason of the maintenance, displayed by the wallets
+    /// - `reject_during_notice`: Reject the new swaps and withdrawals until the maintenance starts
+    ///
+    /// Emits `MaintenanceScheduled` event when successful.
+    #[pallet::call_index(3)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::schedule_maintenance())]
+    pub fn schedule_maintenance(
+      origin: OriginFor<T>,
+      start_block: T::BlockNumber,
+      end_block: T::BlockNumber,
+      reason: ReasonOf<T>,
+      reject_during_notice: bool,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ResumeOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure the window is valid
+      ensure!(
+        start_block > frame_system::Pallet::<T>::block_number() && end_block > start_block,
+        Error::<T>::InvalidMaintenanceWindow
+      );
+      ensure!(
+        !Self::is_maintenance_started(),
+        Error::<T>::MaintenanceInProgress
+      );
+
+      // 3. Schedule the maintenance
+      ScheduledMaintenance::<T>::put(MaintenanceWindow {
+        start_block,
+        end_block,
+        reason: reason.clone(),
+        reject_during_notice,
+      });
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::MaintenanceScheduled {
+        start_block,
+        end_block,
+        reason,
+        reject_during_notice,
+      });
+
+      Ok(().into())
+    }
+
+    /// Cancel the scheduled maintenance before its start.
+    ///
+    /// Emits `MaintenanceCancelled` event when successful.
+    #[pallet::call_index(4)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::cancel_maintenance())]
+    pub fn cancel_maintenance(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ResumeOrigin::ensure_origin(origin)?;
+
+      // 2. Cancel the maintenance
+      ensure!(
+        ScheduledMaintenance::<T>::exists(),
+        Error::<T>::NoScheduledMaintenance
+      );
+      ensure!(
+        !Self::is_maintenance_started(),
+        Error::<T>::MaintenanceInProgress
+      );
+      ScheduledMaintenance::<T>::kill();
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::MaintenanceCancelled);
+
+      Ok(().into())
+    }
   }
 
   // helper functions (not dispatchable)
   impl<T: Config> Pallet<T> {
+    /// Whether the call is paused, with all the calls of its pallet or for one of its
+    /// `currencies`. The expired pauses are ignored.
+    pub fn is_call_paused(pallet_name: &[u8], call_name: &[u8], currencies: &[CurrencyId]) -> bool {
+      // the names longer than the limit can't be paused
+      let pallet_name = match NameOf::<T>::try_from(pallet_name.to_vec()) {
+        Ok(pallet_name) => pallet_name,
+        Err(_) => return false,
+      };
+      let call_name = NameOf::<T>::try_from(call_name.to_vec()).ok();
+      let is_paused = |paused_call: PausedCallOf<T>| {
+        Self::paused_calls(paused_call).map_or(false, |expires_at| !Self::is_expired(expires_at))
+      };
+
+      iter::once(None)
+        .chain(currencies.iter().copied().map(Some))
+        .any(|currency_id| {
+          is_paused(PausedCall {
+            pallet_name: pallet_name.clone(),
+            call_name: None,
+            currency_id,
+          }) || call_name.clone().map_or(false, |call_name| {
+            is_paused(PausedCall {
+              pallet_name: pallet_name.clone(),
+              call_name: Some(call_name),
+              currency_id,
+            })
+          })
+        })
+    }
+
+    // Get the paused pallets and calls which are not expired, serialized for quick RPC call
+    pub fn get_paused_calls() -> Vec<PausedCall<Vec<u8>>> {
+      PausedCalls::<T>::iter()
+        .filter(|(_, expires_at)| !Self::is_expired(*expires_at))
+        .map(|(paused_call, _)| paused_call.into_info())
+        .collect()
+    }
+
+    /// Whether the new swaps and withdrawals are rejected, during the notice of the scheduled
+    /// maintenance.
+    pub fn is_maintenance_notice() -> bool {
+      Self::scheduled_maintenance().map_or(false, |window| {
+        window.reject_during_notice
+          && frame_system::Pallet::<T>::block_number() < window.start_block
+      })
+    }
+
+    // Get the scheduled maintenance, serialized for quick RPC call
+    pub fn get_scheduled_maintenance() -> Option<MaintenanceWindow<T::BlockNumber, Vec<u8>>> {
+      Self::scheduled_maintenance().map(|window| window.into_info())
+    }
+
+    /// Whether the scheduled maintenance started.
+    fn is_maintenance_started() -> bool {
+      Self::scheduled_maintenance().map_or(false, |window| {
+        window.start_block <= frame_system::Pallet::<T>::block_number()
+      })
+    }
+
+    /// Whether the pallet can't be paused, the security pallet and `T::UnpausablePallets`.
+    fn is_unpausable(pallet_name: &[u8]) -> bool {
+      iter::once(<Pallet<T> as PalletInfoAccess>::name())
+        .chain(T::UnpausablePallets::get().iter().copied())
+        .any(|name| name.as_bytes() == pallet_name)
+    }
+
+    /// Block at which a pause started in the current block expires.
+    fn pause_expiry() -> T::BlockNumber {
+      frame_system::Pallet::<T>::block_number().saturating_add(T::PauseDuration::get())
+    }
+
+    /// Whether a pause expiring at `expires_at` is expired in the current block.
+    fn is_expired(expires_at: T::BlockNumber) -> bool {
+      expires_at <= frame_system::Pallet::<T>::block_number()
+    }
+
     /// Increment and return the `Nonce`.
     fn get_nonce() -> U256 {
       <Nonce<T>>::mutate(|n| {
@@ -15,10 +15,10 @@
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::pallet as pallet_security;
-use frame_support::traits::AsEnsureOriginWithArg;
+use frame_support::traits::{AsEnsureOriginWithArg, EitherOfDiverse, SortedMembers};
 use frame_system as system;
 use frame_utils::construct_mock_runtime;
-use system::EnsureRoot;
+use system::{EnsureRoot, EnsureSignedBy};
 use tidefi_primitives::CurrencyId;
 
 construct_mock_runtime!({
@@ -26,6 +26,8 @@ construct_mock_runtime!({
   Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
 }, {
   pub const AssetDeposit: u64 = 1;
+  pub const PauseDuration: u64 = 10;
+  pub const UnpausablePallets: &'static [&'static str] = &["System", "Timestamp", "Council"];
   pub const ApprovalDeposit: u64 = 1;
   pub const StringLimit: u32 = 50;
   pub const MetadataDepositBase: u64 = 1;
@@ -55,8 +57,24 @@ impl pallet_assets::Config for Test {
   type BenchmarkHelper = ();
 }
 
+pub const GUARDIAN: u64 = 2;
+
+pub struct Guardians;
+impl SortedMembers<AccountId> for Guardians {
+  fn sorted_members() -> Vec<AccountId> {
+    vec![GUARDIAN.into()]
+  }
+}
+
 impl pallet_security::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type PauseOrigin = EitherOfDiverse<EnsureRoot<AccountId>, EnsureSignedBy<Guardians, AccountId>>;
+  type ResumeOrigin = EnsureRoot<AccountId>;
+  type PauseDuration = PauseDuration;
+  type MaxPauseRenewals = ConstU32<2>;
+  type UnpausablePallets = UnpausablePallets;
+  type MaxNameLength = ConstU32<32>;
+  type MaxReasonLength = ConstU32<64>;
   type WeightInfo = crate::weights::SubstrateWeight<Test>;
 }
 
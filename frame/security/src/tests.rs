@@ -14,9 +14,28 @@
 // You should have received a copy of the GNU General Public License
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::mock::{new_test_ext, RuntimeOrigin, Security};
-use frame_support::{assert_noop, assert_ok, error::BadOrigin};
-use tidefi_primitives::StatusCode;
+use crate::{
+  mock::{new_test_ext, RuntimeEvent, RuntimeOrigin, Security, System, Test, GUARDIAN},
+  Error, Event, MaintenanceWindow, PausedCall, PausedCallOf, ReasonOf,
+};
+use frame_support::{assert_noop, assert_ok, error::BadOrigin, traits::Hooks};
+use tidefi_primitives::{CurrencyId, StatusCode};
+
+fn paused_call(
+  pallet_name: &[u8],
+  call_name: Option<&[u8]>,
+  currency_id: Option<CurrencyId>,
+) -> PausedCallOf<Test> {
+  PausedCall {
+    pallet_name: pallet_name.to_vec().try_into().unwrap(),
+    call_name: call_name.map(|call_name| call_name.to_vec().try_into().unwrap()),
+    currency_id,
+  }
+}
+
+fn reason(reason: &[u8]) -> ReasonOf<Test> {
+  reason.to_vec().try_into().unwrap()
+}
 
 #[test]
 pub fn check_genesis_config() {
@@ -46,6 +65,109 @@ mod set_status {
     });
   }
 
+  #[test]
+  fn guardian_can_only_set_maintenance() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::signed(GUARDIAN.into()),
+        StatusCode::Maintenance
+      ));
+      assert_eq!(Security::status(), StatusCode::Maintenance);
+      assert_eq!(Security::maintenance_expiry(), Some(11));
+
+      assert_noop!(
+        Security::set_status(RuntimeOrigin::signed(GUARDIAN.into()), StatusCode::Running),
+        BadOrigin
+      );
+
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::root(),
+        StatusCode::Running
+      ));
+      assert_eq!(Security::status(), StatusCode::Running);
+      assert_eq!(Security::maintenance_expiry(), None);
+    });
+  }
+
+  #[test]
+  fn maintenance_expires_unless_renewed() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::signed(GUARDIAN.into()),
+        StatusCode::Maintenance
+      ));
+
+      // renewed before the expiry
+      System::set_block_number(5);
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::signed(GUARDIAN.into()),
+        StatusCode::Maintenance
+      ));
+      assert_eq!(Security::maintenance_expiry(), Some(15));
+
+      Security::on_initialize(11);
+      assert_eq!(Security::status(), StatusCode::Maintenance);
+      assert_eq!(Security::current_block_number(), 0);
+
+      Security::on_initialize(15);
+      assert_eq!(Security::status(), StatusCode::Running);
+      assert_eq!(Security::maintenance_expiry(), None);
+      assert_eq!(Security::current_block_number(), 1);
+      System::assert_has_event(RuntimeEvent::Security(Event::StatusChanged(
+        StatusCode::Running,
+      )));
+    });
+  }
+
+  #[test]
+  fn guardian_maintenance_renewals_are_capped() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::signed(GUARDIAN.into()),
+        StatusCode::Maintenance
+      ));
+      for renewals in 1..=2 {
+        assert_ok!(Security::set_status(
+          RuntimeOrigin::signed(GUARDIAN.into()),
+          StatusCode::Maintenance
+        ));
+        assert_eq!(Security::maintenance_renewals(), Some(renewals));
+      }
+
+      // the renewals of the expired maintenance are still counted
+      Security::on_initialize(11);
+      assert_eq!(Security::status(), StatusCode::Running);
+      assert_noop!(
+        Security::set_status(
+          RuntimeOrigin::signed(GUARDIAN.into()),
+          StatusCode::Maintenance
+        ),
+        Error::<Test>::TooManyPauseRenewals
+      );
+
+      // the resume origin renews the maintenance and resets the renewals
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::root(),
+        StatusCode::Maintenance
+      ));
+      assert_eq!(Security::maintenance_renewals(), Some(0));
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::signed(GUARDIAN.into()),
+        StatusCode::Maintenance
+      ));
+      assert_eq!(Security::maintenance_renewals(), Some(1));
+
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::root(),
+        StatusCode::Running
+      ));
+      assert_eq!(Security::maintenance_renewals(), None);
+    });
+  }
+
   #[test]
   fn fails_when_signer_is_not_root() {
     new_test_ext().execute_with(|| {
@@ -56,3 +178,517 @@ mod set_status {
     });
   }
 }
+
+mod pause {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      let withdrawal = paused_call(b"Tidefi", Some(b"withdrawal"), None);
+      assert_ok!(Security::pause(RuntimeOrigin::root(), withdrawal.clone()));
+
+      assert_eq!(Security::paused_calls(&withdrawal), Some(11));
+      System::assert_last_event(RuntimeEvent::Security(Event::CallPaused {
+        paused_call: withdrawal,
+        expires_at: 11,
+      }));
+    });
+  }
+
+  #[test]
+  fn succeeds_with_guardian() {
+    new_test_ext().execute_with(|| {
+      let tidefi = paused_call(b"Tidefi", None, None);
+      assert_ok!(Security::pause(
+        RuntimeOrigin::signed(GUARDIAN.into()),
+        tidefi.clone()
+      ));
+
+      assert!(Security::paused_calls(&tidefi).is_some());
+    });
+  }
+
+  #[test]
+  fn renews_when_already_paused() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      let tidefi = paused_call(b"Tidefi", None, None);
+      assert_ok!(Security::pause(RuntimeOrigin::root(), tidefi.clone()));
+
+      System::set_block_number(8);
+      assert_ok!(Security::pause(RuntimeOrigin::root(), tidefi.clone()));
+
+      assert_eq!(Security::paused_calls(&tidefi), Some(18));
+      System::assert_last_event(RuntimeEvent::Security(Event::CallPaused {
+        paused_call: tidefi,
+        expires_at: 18,
+      }));
+    });
+  }
+
+  #[test]
+  fn guardian_renewals_are_capped() {
+    new_test_ext().execute_with(|| {
+      let tidefi = paused_call(b"Tidefi", None, None);
+      assert_ok!(Security::pause(
+        RuntimeOrigin::signed(GUARDIAN.into()),
+        tidefi.clone()
+      ));
+      for renewals in 1..=2 {
+        assert_ok!(Security::pause(
+          RuntimeOrigin::signed(GUARDIAN.into()),
+          tidefi.clone()
+        ));
+        assert_eq!(Security::pause_renewals(&tidefi), renewals);
+      }
+
+      // the expired pauses are still counted
+      System::set_block_number(100);
+      assert_noop!(
+        Security::pause(RuntimeOrigin::signed(GUARDIAN.into()), tidefi.clone()),
+        Error::<Test>::TooManyPauseRenewals
+      );
+
+      // the resume origin renews the pause and resets the renewals
+      assert_ok!(Security::pause(RuntimeOrigin::root(), tidefi.clone()));
+      assert_eq!(Security::pause_renewals(&tidefi), 0);
+      assert_ok!(Security::pause(
+        RuntimeOrigin::signed(GUARDIAN.into()),
+        tidefi.clone()
+      ));
+      assert_eq!(Security::pause_renewals(&tidefi), 1);
+
+      assert_ok!(Security::resume(RuntimeOrigin::root(), tidefi.clone()));
+      assert_eq!(Security::pause_renewals(&tidefi), 0);
+    });
+  }
+
+  #[test]
+  fn fails_when_signer_is_not_pause_origin() {
+    new_test_ext().execute_with(|| {
+      assert_noop!(
+        Security::pause(
+          RuntimeOrigin::signed(1_u64.into()),
+          paused_call(b"Tidefi", None, None)
+        ),
+        BadOrigin
+      );
+    });
+  }
+
+  #[test]
+  fn fails_to_pause_security_pallet() {
+    new_test_ext().execute_with(|| {
+      assert_noop!(
+        Security::pause(
+          RuntimeOrigin::root(),
+          paused_call(b"Security", Some(b"resume"), None)
+        ),
+        Error::<Test>::UnpausableCall
+      );
+    });
+  }
+
+  #[test]
+  fn fails_to_pause_council_pallet() {
+    new_test_ext().execute_with(|| {
+      assert_noop!(
+        Security::pause(
+          RuntimeOrigin::signed(GUARDIAN.into()),
+          paused_call(b"Council", None, None)
+        ),
+        Error::<Test>::UnpausableCall
+      );
+    });
+  }
+
+  #[test]
+  fn fails_to_pause_timestamp_pallet() {
+    new_test_ext().execute_with(|| {
+      assert_noop!(
+        Security::pause(
+          RuntimeOrigin::signed(GUARDIAN.into()),
+          paused_call(b"Timestamp", None, None)
+        ),
+        Error::<Test>::UnpausableCall
+      );
+    });
+  }
+}
+
+mod resume {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      let stake = paused_call(b"TidefiStaking", Some(b"stake"), None);
+      assert_ok!(Security::pause(RuntimeOrigin::root(), stake.clone()));
+      assert_ok!(Security::resume(RuntimeOrigin::root(), stake.clone()));
+
+      assert!(Security::paused_calls(&stake).is_none());
+      assert!(!Security::is_call_paused(b"TidefiStaking", b"stake", &[]));
+      System::assert_last_event(RuntimeEvent::Security(Event::CallResumed {
+        paused_call: stake,
+      }));
+    });
+  }
+
+  #[test]
+  fn fails_when_signer_is_guardian() {
+    new_test_ext().execute_with(|| {
+      let tidefi = paused_call(b"Tidefi", None, None);
+      assert_ok!(Security::pause(RuntimeOrigin::root(), tidefi.clone()));
+      assert_noop!(
+        Security::resume(RuntimeOrigin::signed(GUARDIAN.into()), tidefi),
+        BadOrigin
+      );
+    });
+  }
+
+  #[test]
+  fn fails_when_not_paused() {
+    new_test_ext().execute_with(|| {
+      assert_noop!(
+        Security::resume(RuntimeOrigin::root(), paused_call(b"Tidefi", None, None)),
+        Error::<Test>::NotPaused
+      );
+    });
+  }
+}
+
+mod is_call_paused {
+  use super::*;
+
+  #[test]
+  fn pallet_pause_applies_to_all_calls() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(Security::pause(
+        RuntimeOrigin::root(),
+        paused_call(b"Tidefi", None, None)
+      ));
+
+      assert!(Security::is_call_paused(b"Tidefi", b"swap", &[]));
+      assert!(Security::is_call_paused(
+        b"Tidefi",
+        b"withdrawal",
+        &[CurrencyId::Tdfy]
+      ));
+      assert!(!Security::is_call_paused(b"TidefiStaking", b"stake", &[]));
+    });
+  }
+
+  #[test]
+  fn call_pause_applies_only_to_the_call() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(Security::pause(
+        RuntimeOrigin::root(),
+        paused_call(b"TidefiStaking", Some(b"stake"), None)
+      ));
+
+      assert!(Security::is_call_paused(
+        b"TidefiStaking",
+        b"stake",
+        &[CurrencyId::Wrapped(2)]
+      ));
+      assert!(!Security::is_call_paused(b"TidefiStaking", b"unstake", &[]));
+    });
+  }
+
+  #[test]
+  fn currency_pause_applies_only_to_the_currency() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(Security::pause(
+        RuntimeOrigin::root(),
+        paused_call(b"Tidefi", Some(b"withdrawal"), Some(CurrencyId::Wrapped(2)))
+      ));
+
+      assert!(Security::is_call_paused(
+        b"Tidefi",
+        b"withdrawal",
+        &[CurrencyId::Wrapped(2)]
+      ));
+      assert!(!Security::is_call_paused(
+        b"Tidefi",
+        b"withdrawal",
+        &[CurrencyId::Tdfy]
+      ));
+      // the other calls of the currency keep running
+      assert!(!Security::is_call_paused(
+        b"Tidefi",
+        b"swap",
+        &[CurrencyId::Tdfy, CurrencyId::Wrapped(2)]
+      ));
+    });
+  }
+
+  #[test]
+  fn pause_expires_unless_renewed() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::pause(
+        RuntimeOrigin::root(),
+        paused_call(b"Tidefi", Some(b"swap"), None)
+      ));
+
+      System::set_block_number(10);
+      assert!(Security::is_call_paused(b"Tidefi", b"swap", &[]));
+
+      System::set_block_number(11);
+      assert!(!Security::is_call_paused(b"Tidefi", b"swap", &[]));
+      assert!(Security::get_paused_calls().is_empty());
+    });
+  }
+
+  #[test]
+  fn get_paused_calls() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(Security::pause(
+        RuntimeOrigin::root(),
+        paused_call(b"Tidefi", Some(b"withdrawal"), Some(CurrencyId::Tdfy))
+      ));
+
+      assert_eq!(
+        Security::get_paused_calls(),
+        vec![PausedCall {
+          pallet_name: b"Tidefi".to_vec(),
+          call_name: Some(b"withdrawal".to_vec()),
+          currency_id: Some(CurrencyId::Tdfy),
+        }]
+      );
+    });
+  }
+}
+
+mod schedule_maintenance {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::schedule_maintenance(
+        RuntimeOrigin::root(),
+        10,
+        20,
+        reason(b"runtime upgrade"),
+        true
+      ));
+
+      assert_eq!(
+        Security::get_scheduled_maintenance(),
+        Some(MaintenanceWindow {
+          start_block: 10,
+          end_block: 20,
+          reason: b"runtime upgrade".to_vec(),
+          reject_during_notice: true,
+        })
+      );
+      assert!(Security::is_maintenance_notice());
+      System::assert_last_event(RuntimeEvent::Security(Event::MaintenanceScheduled {
+        start_block: 10,
+        end_block: 20,
+        reason: reason(b"runtime upgrade"),
+        reject_during_notice: true,
+      }));
+    });
+  }
+
+  #[test]
+  fn enters_and_exits_maintenance() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::schedule_maintenance(
+        RuntimeOrigin::root(),
+        10,
+        20,
+        reason(b"runtime upgrade"),
+        false
+      ));
+      assert!(!Security::is_maintenance_notice());
+
+      System::set_block_number(10);
+      Security::on_initialize(10);
+      assert_eq!(Security::status(), StatusCode::Maintenance);
+      assert_eq!(Security::maintenance_expiry(), Some(20));
+      assert!(!Security::is_maintenance_notice());
+      System::assert_has_event(RuntimeEvent::Security(Event::StatusChanged(
+        StatusCode::Maintenance,
+      )));
+
+      System::set_block_number(20);
+      Security::on_initialize(20);
+      assert_eq!(Security::status(), StatusCode::Running);
+      assert_eq!(Security::scheduled_maintenance(), None);
+    });
+  }
+
+  #[test]
+  fn keeps_the_later_maintenance_expiry() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::schedule_maintenance(
+        RuntimeOrigin::root(),
+        10,
+        12,
+        reason(b"runtime upgrade"),
+        false
+      ));
+
+      // the guardians set the maintenance mode until the block 15
+      System::set_block_number(5);
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::signed(GUARDIAN.into()),
+        StatusCode::Maintenance
+      ));
+
+      System::set_block_number(10);
+      Security::on_initialize(10);
+      assert_eq!(Security::maintenance_expiry(), Some(15));
+
+      System::set_block_number(12);
+      Security::on_initialize(12);
+      assert_eq!(Security::status(), StatusCode::Maintenance);
+
+      System::set_block_number(15);
+      Security::on_initialize(15);
+      assert_eq!(Security::status(), StatusCode::Running);
+    });
+  }
+
+  #[test]
+  fn exits_early_with_set_status() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::schedule_maintenance(
+        RuntimeOrigin::root(),
+        10,
+        20,
+        reason(b"runtime upgrade"),
+        true
+      ));
+
+      System::set_block_number(10);
+      Security::on_initialize(10);
+      assert_ok!(Security::set_status(
+        RuntimeOrigin::root(),
+        StatusCode::Running
+      ));
+
+      assert_eq!(Security::status(), StatusCode::Running);
+      assert_eq!(Security::scheduled_maintenance(), None);
+    });
+  }
+
+  #[test]
+  fn fails_when_signer_is_guardian() {
+    new_test_ext().execute_with(|| {
+      assert_noop!(
+        Security::schedule_maintenance(
+          RuntimeOrigin::signed(GUARDIAN.into()),
+          10,
+          20,
+          reason(b"runtime upgrade"),
+          true
+        ),
+        BadOrigin
+      );
+    });
+  }
+
+  #[test]
+  fn fails_with_invalid_window() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(10);
+      assert_noop!(
+        Security::schedule_maintenance(RuntimeOrigin::root(), 10, 20, reason(b""), true),
+        Error::<Test>::InvalidMaintenanceWindow
+      );
+      assert_noop!(
+        Security::schedule_maintenance(RuntimeOrigin::root(), 20, 20, reason(b""), true),
+        Error::<Test>::InvalidMaintenanceWindow
+      );
+    });
+  }
+
+  #[test]
+  fn fails_when_maintenance_in_progress() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::schedule_maintenance(
+        RuntimeOrigin::root(),
+        10,
+        20,
+        reason(b""),
+        true
+      ));
+
+      System::set_block_number(10);
+      Security::on_initialize(10);
+      assert_noop!(
+        Security::schedule_maintenance(RuntimeOrigin::root(), 30, 40, reason(b""), true),
+        Error::<Test>::MaintenanceInProgress
+      );
+    });
+  }
+}
+
+mod cancel_maintenance {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::schedule_maintenance(
+        RuntimeOrigin::root(),
+        10,
+        20,
+        reason(b""),
+        true
+      ));
+      assert_ok!(Security::cancel_maintenance(RuntimeOrigin::root()));
+
+      assert_eq!(Security::scheduled_maintenance(), None);
+      assert!(!Security::is_maintenance_notice());
+      System::assert_last_event(RuntimeEvent::Security(Event::MaintenanceCancelled));
+
+      System::set_block_number(10);
+      Security::on_initialize(10);
+      assert_eq!(Security::status(), StatusCode::Running);
+    });
+  }
+
+  #[test]
+  fn fails_when_not_scheduled() {
+    new_test_ext().execute_with(|| {
+      assert_noop!(
+        Security::cancel_maintenance(RuntimeOrigin::root()),
+        Error::<Test>::NoScheduledMaintenance
+      );
+    });
+  }
+
+  #[test]
+  fn fails_when_maintenance_in_progress() {
+    new_test_ext().execute_with(|| {
+      System::set_block_number(1);
+      assert_ok!(Security::schedule_maintenance(
+        RuntimeOrigin::root(),
+        10,
+        20,
+        reason(b""),
+        true
+      ));
+
+      System::set_block_number(15);
+      assert_noop!(
+        Security::cancel_maintenance(RuntimeOrigin::root()),
+        Error::<Test>::MaintenanceInProgress
+      );
+    });
+  }
+}
@@ -0,0 +1,75 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Paused pallets and calls, scheduled maintenance.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{inherent::Vec, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use tidefi_primitives::CurrencyId;
+
+/// Calls paused by the security pallet, all the calls of the pallet without `call_name` and for
+/// all the currencies without `currency_id`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PausedCall<Name> {
+  /// Name of the pallet in the runtime, for example `Tidefi`
+  pub pallet_name: Name,
+  /// Name of the call in the pallet, for example `withdrawal`
+  pub call_name: Option<Name>,
+  /// Currency of the call, for example the withdrawn currency
+  pub currency_id: Option<CurrencyId>,
+}
+
+impl<Name: AsRef<[u8]>> PausedCall<Name> {
+  /// Convert the names, serialized for quick RPC call
+  pub fn into_info(self) -> PausedCall<Vec<u8>> {
+    PausedCall {
+      pallet_name: self.pallet_name.as_ref().to_vec(),
+      call_name: self.call_name.map(|call_name| call_name.as_ref().to_vec()),
+      currency_id: self.currency_id,
+    }
+  }
+}
+
+/// Maintenance scheduled in advance, the chain enters maintenance at `start_block` and exits
+/// it at `end_block`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct MaintenanceWindow<BlockNumber, Reason> {
+  /// Block at which the chain enters maintenance
+  pub start_block: BlockNumber,
+  /// Block at which the chain exits maintenance
+  pub end_block: BlockNumber,
+  /// Reason of the maintenance, displayed by the wallets
+  pub reason: Reason,
+  /// New swaps and withdrawals are rejected until the maintenance starts
+  pub reject_during_notice: bool,
+}
+
+impl<BlockNumber, Reason: AsRef<[u8]>> MaintenanceWindow<BlockNumber, Reason> {
+  /// Convert the reason, serialized for quick RPC call
+  pub fn into_info(self) -> MaintenanceWindow<BlockNumber, Vec<u8>> {
+    MaintenanceWindow {
+      start_block: self.start_block,
+      end_block: self.end_block,
+      reason: self.reason.as_ref().to_vec(),
+      reject_during_notice: self.reject_during_notice,
+    }
+  }
+}
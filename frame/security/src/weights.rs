@@ -24,6 +24,10 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for `pallet_tidefi`.
 pub trait WeightInfo {
    fn set_status() -> Weight;
+   fn pause() -> Weight;
+   fn resume() -> Weight;
+   fn schedule_maintenance() -> Weight;
+   fn cancel_maintenance() -> Weight;
 }
 
 /// Weights for `pallet_tidefi` using the Substrate node and recommended hardware.
@@ -34,4 +38,24 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
           .saturating_add(T::DbWeight::get().reads(6_u64))
           .saturating_add(T::DbWeight::get().writes(5_u64))
   }
+   fn pause() -> Weight {
+      Weight::from_ref_time(24_905_000)
+          .saturating_add(T::DbWeight::get().reads(2_u64))
+          .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+   fn resume() -> Weight {
+      Weight::from_ref_time(20_582_000)
+          .saturating_add(T::DbWeight::get().reads(1_u64))
+          .saturating_add(T::DbWeight::get().writes(2_u64))
+  }
+   fn schedule_maintenance() -> Weight {
+      Weight::from_ref_time(24_361_000)
+          .saturating_add(T::DbWeight::get().reads(1_u64))
+          .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
+   fn cancel_maintenance() -> Weight {
+      Weight::from_ref_time(19_870_000)
+          .saturating_add(T::DbWeight::get().reads(1_u64))
+          .saturating_add(T::DbWeight::get().writes(1_u64))
+  }
 }
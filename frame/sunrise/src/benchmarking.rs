@@ -0,0 +1,137 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarking setup for pallet-sunrise
+use crate::*;
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::{
+  assert_ok,
+  traits::{fungibles::Mutate, Get},
+};
+use frame_system::RawOrigin;
+use sp_runtime::{FixedPointNumber, FixedU128};
+use tidefi_primitives::{pallet::SunriseExt, CurrencyId};
+
+const POOL_ID: u8 = 10;
+const POOL_BALANCE: u128 = 1_000_000_000_000_000;
+const POOL_TRANSACTIONS: u32 = 1_000;
+
+fn assert_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+  frame_system::Pallet::<T>::assert_has_event(generic_event.into());
+}
+
+// Make sure the sunrise account is backing the pools and onboarding rebates, and fund the treasury
+fn fund_accounts<T: Config>() {
+  let committed_balance = Pallet::<T>::committed_balance().expect("Unable to compute balance");
+  assert_ok!(T::CurrencyTidefi::mint_into(
+    CurrencyId::Tdfy,
+    &Pallet::<T>::account_id(),
+    committed_balance.saturating_add(POOL_BALANCE)
+  ));
+  assert_ok!(T::CurrencyTidefi::mint_into(
+    CurrencyId::Tdfy,
+    &Pallet::<T>::treasury_account_id(),
+    POOL_BALANCE.saturating_mul(10)
+  ));
+}
+
+fn create_pool<T: Config>() {
+  fund_accounts::<T>();
+  assert_ok!(Pallet::<T>::add_pool(
+    RawOrigin::Root.into(),
+    POOL_ID,
+    0,
+    POOL_TRANSACTIONS,
+    POOL_BALANCE,
+    FixedU128::saturating_from_rational(125_u32, 100_u32)
+  ));
+}
+
+benchmarks! {
+  where_clause { where T: pallet_security::Config }
+
+  add_pool {
+    fund_accounts::<T>();
+    let rebates = FixedU128::saturating_from_rational(125_u32, 100_u32);
+  }: _(RawOrigin::Root, POOL_ID, 0, POOL_TRANSACTIONS, POOL_BALANCE, rebates)
+  verify {
+   assert_event::<T>(Event::<T>::PoolAdded { pool_id: POOL_ID, minimum_tdfy_value: 0, transactions_remaining: POOL_TRANSACTIONS, balance: POOL_BALANCE, rebates }.into());
+  }
+
+  replenish_pool {
+    create_pool::<T>();
+  }: _(RawOrigin::Root, POOL_ID, POOL_BALANCE, POOL_TRANSACTIONS)
+  verify {
+   assert_event::<T>(Event::<T>::PoolReplenished { pool_id: POOL_ID, amount: POOL_BALANCE, transactions: POOL_TRANSACTIONS }.into());
+  }
+
+  update_pool {
+    create_pool::<T>();
+    let rebates = FixedU128::saturating_from_rational(200_u32, 100_u32);
+  }: _(RawOrigin::Root, POOL_ID, POOL_BALANCE, rebates)
+  verify {
+   assert_event::<T>(Event::<T>::PoolUpdated { pool_id: POOL_ID, minimum_tdfy_value: POOL_BALANCE, rebates }.into());
+  }
+
+  close_pool {
+    create_pool::<T>();
+  }: _(RawOrigin::Root, POOL_ID)
+  verify {
+   assert_event::<T>(Event::<T>::PoolClosed { pool_id: POOL_ID, balance: POOL_BALANCE }.into());
+  }
+
+  top_up_onboarding {
+    fund_accounts::<T>();
+  }: _(RawOrigin::Root, POOL_BALANCE)
+  verify {
+   assert_event::<T>(Event::<T>::OnboardingToppedUp { amount: POOL_BALANCE }.into());
+  }
+
+  register_referrer {
+    let referee = frame_benchmarking::account::<T::AccountId>("referee", 0, 0);
+    let referrer = frame_benchmarking::account::<T::AccountId>("referrer", 0, 0);
+  }: _(RawOrigin::Signed(referee.clone()), referrer.clone())
+  verify {
+   assert_event::<T>(Event::<T>::ReferrerRegistered { account_id: referee, referrer }.into());
+  }
+
+  top_up_referral_budget {
+    fund_accounts::<T>();
+  }: _(RawOrigin::Root, POOL_BALANCE)
+  verify {
+   assert_event::<T>(Event::<T>::ReferralBudgetToppedUp { amount: POOL_BALANCE }.into());
+  }
+
+  on_idle_rewards_expiration {
+    let b in 1 .. T::BatchSize::get();
+    (0..b).for_each(|i| {
+      let account = frame_benchmarking::account::<T::AccountId>("rewards_claimer", i, 0);
+      Pallet::<T>::add_reward(&account, 1, POOL_BALANCE);
+    });
+    Pallet::<T>::on_era_end(1);
+    let expiration = EraRewardsExpiration::<T>::get(1).expect("Unable to get expiration");
+    pallet_security::CurrentBlockCount::<T>::put(expiration);
+    let remaining_weight = <T as frame_system::Config>::BlockWeights::get().max_block;
+  }: {
+    Pallet::<T>::do_on_idle_rewards_expiration(remaining_weight);
+  }
+  verify {
+   assert_eq!(EraRewardedAccounts::<T>::iter_key_prefix(1).count(), 0);
+   assert_eq!(Pallet::<T>::pools_left_over(), POOL_BALANCE.saturating_mul(b.into()));
+  }
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -22,9 +22,58 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod migrations;
+pub mod types;
+pub mod weights;
+pub use types::*;
+pub use weights::*;
+
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+use frame_support::inherent::Vec;
+use sp_runtime::{DispatchError, DispatchResult};
+use tidefi_primitives::{Balance, EraIndex, Fee};
+
+/// Query and claim the sunrise rewards of many eras at once.
+pub trait SunriseRewards<AccountId, BlockNumber> {
+  /// Unclaimed rewards of `account_id` by era, with the block from which they expire.
+  fn account_rewards(account_id: &AccountId) -> Vec<(EraIndex, Balance, Option<BlockNumber>)>;
+
+  /// Claim the rewards of `account_id` for the eras before `before_era_index` with a single
+  /// transfer, up to `max_eras` eras. The rewards of the eras still in cooldown are skipped
+  /// without counting against `max_eras`.
+  ///
+  /// Returns the number of eras claimed and the total reward.
+  fn try_claim_all_sunrise_rewards(
+    account_id: &AccountId,
+    before_era_index: EraIndex,
+    max_eras: u32,
+  ) -> Result<(u32, Balance), DispatchError>;
+
+  /// Start the claim window of the rewards of the completed era, its unclaimed rewards expire
+  /// `Cooldown` + `ClaimWindow` blocks after the end of the era.
+  fn on_era_end(era_index: EraIndex);
+}
+
+/// Accrue and claim the referral rewards, a share of the swap fees paid by the referees.
+pub trait SunriseReferral<AccountId> {
+  /// Accrue the share of the swap fee of `referee` to its referrer for the era.
+  ///
+  /// Returns the reward accrued, if the account has a referrer and the referral budget allows it.
+  fn try_allocate_referral_rewards(
+    referee: &AccountId,
+    era_index: EraIndex,
+    fee: &Fee,
+  ) -> Result<Option<Balance>, DispatchError>;
+
+  /// Claim the referral rewards of `account_id` for the era.
+  fn try_claim_referral_rewards(account_id: &AccountId, era_index: EraIndex) -> DispatchResult;
+}
+
 pub(crate) const LOG_TARGET: &str = "tidefi::sunrise";
 
 // syntactic sugar for logging.
@@ -44,17 +93,19 @@ pub mod pallet {
   use frame_support::{
     inherent::Vec,
     log,
-    pallet_prelude::*,
+    pallet_prelude::{DispatchResultWithPostInfo, *},
     traits::{
       tokens::fungibles::{Inspect, Mutate, Transfer},
       StorageVersion,
     },
     PalletId,
   };
+  use frame_system::pallet_prelude::*;
+  use pallet_asset_registry::AssetInfoProvider;
   use sp_arithmetic::traits::Zero;
   use sp_runtime::{
     traits::{AccountIdConversion, CheckedMul, Saturating},
-    FixedPointNumber, FixedU128,
+    FixedPointNumber, FixedU128, PerThing, Permill,
   };
   use sp_std::borrow::ToOwned;
   use tidefi_primitives::{
@@ -64,7 +115,7 @@ pub mod pallet {
   };
 
   /// The current storage version.
-  const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
   type BoundedPools = BoundedVec<SunriseSwapPool, ConstU32<6>>;
 
@@ -92,9 +143,39 @@ pub mod pallet {
     #[pallet::constant]
     type LeftoverSwapRebates: Get<FixedU128>;
 
+    /// Number of blocks the rewards of an era can be claimed once the cooldown is cleared, after
+    /// the end of the era. Unclaimed rewards are then moved to the pools left-over.
+    #[pallet::constant]
+    type ClaimWindow: Get<Self::BlockNumber>;
+
+    /// Maximum number of expired rewards processed in a single block
+    #[pallet::constant]
+    type BatchSize: Get<u32>;
+
+    /// Share of the swap fees paid by the referees, accrued to their referrer
+    #[pallet::constant]
+    type ReferralShare: Get<Permill>;
+
+    /// Maximum referral rewards a referrer can accrue from a single referee
+    #[pallet::constant]
+    type MaximumReferralRewardPerReferee: Get<Balance>;
+
+    /// Treasury pallet ID, the pools and onboarding rebates are funded by the treasury
+    #[pallet::constant]
+    type TreasuryPalletId: Get<PalletId>;
+
     /// Security traits
     type Security: SecurityExt<Self::AccountId, Self::BlockNumber>;
 
+    /// Asset registry, provides the decimals of the assets
+    type AssetRegistry: AssetInfoProvider;
+
+    /// The origin which may manage the sunrise pools and onboarding rebates
+    type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
+
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + Mutate<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
@@ -142,6 +223,64 @@ pub mod pallet {
     ValueQuery,
   >;
 
+  /// Block from which the unclaimed rewards of the era expire
+  #[pallet::storage]
+  #[pallet::getter(fn era_rewards_expiration)]
+  pub type EraRewardsExpiration<T: Config> =
+    StorageMap<_, Blake2_128Concat, EraIndex, T::BlockNumber>;
+
+  /// Accounts with unclaimed rewards, by era
+  #[pallet::storage]
+  pub type EraRewardedAccounts<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, EraIndex, Blake2_128Concat, T::AccountId, ()>;
+
+  /// Raw key of the last reward indexed by era, the rewards allocated before the upgrade to the
+  /// storage version 2 are indexed when the chain is idle, starting from the first reward
+  #[pallet::storage]
+  pub type RewardsIndexCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<128>>>;
+
+  /// Total of the sunrise and referral rewards allocated and not claimed nor expired yet, owed by
+  /// the pallet account. The rewards allocated before the upgrade to the storage version 2 are
+  /// counted once indexed.
+  #[pallet::storage]
+  #[pallet::getter(fn outstanding_rewards)]
+  pub type OutstandingRewards<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+  /// Referrer of each referee, registered once
+  #[pallet::storage]
+  #[pallet::getter(fn referrer)]
+  pub type Referrers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+  /// Number of referees of each referrer
+  #[pallet::storage]
+  #[pallet::getter(fn referees_count)]
+  pub type RefereesCount<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+  /// Referral rewards accrued from each referee, capped by `T::MaximumReferralRewardPerReferee`
+  #[pallet::storage]
+  #[pallet::getter(fn referee_accrued_rewards)]
+  pub type RefereeAccruedRewards<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+  /// Referral rewards of the referrers by era
+  #[pallet::storage]
+  #[pallet::getter(fn referral_rewards)]
+  pub type ReferralRewards<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Blake2_128Concat,
+    EraIndex,
+    Balance,
+    ValueQuery,
+  >;
+
+  /// The balance available for the referral rewards
+  #[pallet::storage]
+  #[pallet::getter(fn referral_budget)]
+  pub type ReferralBudget<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
   /// Genesis configuration
   #[pallet::genesis_config]
   pub struct GenesisConfig<T: Config> {
@@ -200,12 +339,67 @@ pub mod pallet {
       account_id: T::AccountId,
       reward: Balance,
     },
+    /// Sunrise rewards of many eras claimed at once
+    SunriseClaimedAll {
+      account_id: T::AccountId,
+      eras: u32,
+      reward: Balance,
+    },
+    /// Unclaimed sunrise rewards expired and moved to the pools left-over
+    SunriseRewardsExpired {
+      era_index: EraIndex,
+      account_id: T::AccountId,
+      reward: Balance,
+    },
     OnboardingRebatesApplied {
       account_id: T::AccountId,
       currency_id: CurrencyId,
       initial_amount: Balance,
       rebate: Balance,
     },
+    /// New sunrise pool funded by the treasury
+    PoolAdded {
+      pool_id: u8,
+      minimum_tdfy_value: Balance,
+      transactions_remaining: u32,
+      balance: Balance,
+      rebates: FixedU128,
+    },
+    /// Balance and transactions added to the sunrise pool, the balance is funded by the treasury
+    PoolReplenished {
+      pool_id: u8,
+      amount: Balance,
+      transactions: u32,
+    },
+    /// Tier of the sunrise pool updated
+    PoolUpdated {
+      pool_id: u8,
+      minimum_tdfy_value: Balance,
+      rebates: FixedU128,
+    },
+    /// Sunrise pool closed, its remaining balance returned to the treasury
+    PoolClosed { pool_id: u8, balance: Balance },
+    /// Onboarding rebates funded by the treasury
+    OnboardingToppedUp { amount: Balance },
+    /// Referrer registered by the referee
+    ReferrerRegistered {
+      account_id: T::AccountId,
+      referrer: T::AccountId,
+    },
+    /// Share of the swap fee of the referee accrued to its referrer
+    ReferralRewarded {
+      era_index: EraIndex,
+      referrer: T::AccountId,
+      referee: T::AccountId,
+      reward: Balance,
+    },
+    ReferralClaimed {
+      era_index: EraIndex,
+      account_id: T::AccountId,
+      reward: Balance,
+    },
+    /// Referral budget funded by the treasury
+    ReferralBudgetToppedUp { amount: Balance },
   }
 
   // Errors inform users that something went wrong.
@@ -223,9 +417,535 @@ pub mod pallet {
     NoRewardsAvailable,
     /// There is no rebates available to process the gas refund
     NoRebatesAvailable,
+    /// Sunrise pool with this ID already exist
+    SunrisePoolAlreadyExists,
+    /// Sunrise pools cap reached
+    SunrisePoolsOverflow,
+    /// The sunrise pallet account doesn't hold the balance of the pools and onboarding rebates
+    InsufficientSunriseBalance,
+    /// The account already registered a referrer
+    ReferrerAlreadyRegistered,
+    /// An account can't be its own referrer
+    SelfReferral,
+    /// The account already has referees, registering a referrer could create a referral cycle
+    ReferralCycle,
+  }
+
+  #[pallet::hooks]
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    /// Index the rewards allocated before the upgrade and expire the unclaimed rewards when chain
+    /// is idle
+    fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+      let indexing_weight = Self::do_on_idle_rewards_indexing(remaining_weight);
+      indexing_weight.saturating_add(Self::do_on_idle_rewards_expiration(
+        remaining_weight.saturating_sub(indexing_weight),
+      ))
+    }
   }
 
+  #[pallet::call]
   impl<T: Config> Pallet<T> {
+    /// Add a sunrise pool, its balance is transferred from the treasury
+    ///
+    /// - `pool_id`: Unique ID of the pool
+    /// - `minimum_tdfy_value`: Minimum TDFY value of the swap fee to be eligible to the pool
+    /// - `transactions_remaining`: Number of swaps rewarded by the pool
+    /// - `balance`: TDFY available for the rewards
+    /// - `rebates`: Rewards ratio of the swap fee
+    ///
+    /// Emits `PoolAdded` event when successful.
+    #[pallet::call_index(0)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::add_pool())]
+    pub fn add_pool(
+      origin: OriginFor<T>,
+      pool_id: u8,
+      minimum_tdfy_value: Balance,
+      transactions_remaining: u32,
+      balance: Balance,
+      rebates: FixedU128,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Add the pool
+      Pools::<T>::try_mutate(|pools| -> DispatchResult {
+        ensure!(
+          !pools.iter().any(|pool| pool.id == pool_id),
+          Error::<T>::SunrisePoolAlreadyExists
+        );
+        pools
+          .try_push(SunriseSwapPool {
+            id: pool_id,
+            minimum_tdfy_value,
+            transactions_remaining,
+            balance,
+            rebates,
+          })
+          .map_err(|_| Error::<T>::SunrisePoolsOverflow)?;
+        Ok(())
+      })?;
+
+      // 3. Fund the pool from the treasury
+      Self::fund_from_treasury(balance)?;
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::PoolAdded {
+        pool_id,
+        minimum_tdfy_value,
+        transactions_remaining,
+        balance,
+        rebates,
+      });
+
+      Ok(().into())
+    }
+
+    /// Add balance and transactions to a sunrise pool, the balance is transferred from the
+    /// treasury
+    ///
+    /// - `pool_id`: ID of the pool
+    /// - `amount`: TDFY to add to the pool balance
+    /// - `transactions`: Number of swaps to add to the pool
+    ///
+    /// Emits `PoolReplenished` event when successful.
+    #[pallet::call_index(1)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::replenish_pool())]
+    pub fn replenish_pool(
+      origin: OriginFor<T>,
+      pool_id: u8,
+      amount: Balance,
+      transactions: u32,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the pool
+      Pools::<T>::try_mutate(|pools| -> DispatchResult {
+        let pool = pools
+          .iter_mut()
+          .find(|pool| pool.id == pool_id)
+          .ok_or(Error::<T>::InvalidSunrisePool)?;
+        pool.balance = pool
+          .balance
+          .checked_add(amount)
+          .ok_or(Error::<T>::BalanceOverflow)?;
+        pool.transactions_remaining = pool
+          .transactions_remaining
+          .checked_add(transactions)
+          .ok_or(Error::<T>::BalanceOverflow)?;
+        Ok(())
+      })?;
+
+      // 3. Fund the pool from the treasury
+      Self::fund_from_treasury(amount)?;
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::PoolReplenished {
+        pool_id,
+        amount,
+        transactions,
+      });
+
+      Ok(().into())
+    }
+
+    /// Update the tier of a sunrise pool, the eligible pool with the highest minimum TDFY value
+    /// is selected first
+    ///
+    /// - `pool_id`: ID of the pool
+    /// - `minimum_tdfy_value`: Minimum TDFY value of the swap fee to be eligible to the pool
+    /// - `rebates`: Rewards ratio of the swap fee
+    ///
+    /// Emits `PoolUpdated` event when successful.
+    #[pallet::call_index(2)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::update_pool())]
+    pub fn update_pool(
+      origin: OriginFor<T>,
+      pool_id: u8,
+      minimum_tdfy_value: Balance,
+      rebates: FixedU128,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the pool
+      Pools::<T>::try_mutate(|pools| -> DispatchResult {
+        let pool = pools
+          .iter_mut()
+          .find(|pool| pool.id == pool_id)
+          .ok_or(Error::<T>::InvalidSunrisePool)?;
+        pool.minimum_tdfy_value = minimum_tdfy_value;
+        pool.rebates = rebates;
+        Ok(())
+      })?;
+
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::PoolUpdated {
+        pool_id,
+        minimum_tdfy_value,
+        rebates,
+      });
+
+      Ok(().into())
+    }
+
+    /// Close a sunrise pool, its remaining balance is transferred back to the treasury
+    ///
+    /// - `pool_id`: ID of the pool
+    ///
+    /// Emits `PoolClosed` event when successful.
+    #[pallet::call_index(3)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::close_pool())]
+    pub fn close_pool(origin: OriginFor<T>, pool_id: u8) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Remove the pool
+      let balance = Pools::<T>::try_mutate(|pools| -> Result<Balance, DispatchError> {
+        let index = pools
+          .iter()
+          .position(|pool| pool.id == pool_id)
+          .ok_or(Error::<T>::InvalidSunrisePool)?;
+        Ok(pools.remove(index).balance)
+      })?;
+
+      // 3. Return the remaining balance to the treasury
+      if !balance.is_zero() {
+        T::CurrencyTidefi::transfer(
+          CurrencyId::Tdfy,
+          &Self::account_id(),
+          &Self::treasury_account_id(),
+          balance,
+          true,
+        )
+        .map_err(|_| Error::<T>::InsufficientSunriseBalance)?;
+      }
+      Self::ensure_sunrise_balance()?;
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::PoolClosed { pool_id, balance });
+
+      Ok(().into())
+    }
+
+    /// Add onboarding rebates, transferred from the treasury
+    ///
+    /// - `amount`: TDFY to add to the onboarding rebates
+    ///
+    /// Emits `OnboardingToppedUp` event when successful.
+    #[pallet::call_index(4)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::top_up_onboarding())]
+    pub fn top_up_onboarding(origin: OriginFor<T>, amount: Balance) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the onboarding rebates, the initial amount is increased as well to keep the
+      // degressive schedule
+      Onboarding::<T>::try_mutate(|onboarding_rebates| -> DispatchResult {
+        onboarding_rebates.initial_amount = onboarding_rebates
+          .initial_amount
+          .checked_add(amount)
+          .ok_or(Error::<T>::BalanceOverflow)?;
+        onboarding_rebates.available_amount = onboarding_rebates
+          .available_amount
+          .checked_add(amount)
+          .ok_or(Error::<T>::BalanceOverflow)?;
+        Ok(())
+      })?;
+
+      // 3. Fund the onboarding rebates from the treasury
+      Self::fund_from_treasury(amount)?;
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::OnboardingToppedUp { amount });
+
+      Ok(().into())
+    }
+
+    /// Register the referrer of the account, it accrues a share of the swap fees paid by the
+    /// account
+    ///
+    /// The referrer can be registered only once, and before the account refers other accounts.
+    ///
+    /// - `referrer`: Account referring the signer
+    ///
+    /// Emits `ReferrerRegistered` event when successful.
+    #[pallet::call_index(5)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::register_referrer())]
+    pub fn register_referrer(
+      origin: OriginFor<T>,
+      referrer: T::AccountId,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the referral is allowed
+      ensure!(account_id != referrer, Error::<T>::SelfReferral);
+      ensure!(
+        !Referrers::<T>::contains_key(&account_id),
+        Error::<T>::ReferrerAlreadyRegistered
+      );
+      // an account without referees can't be an ancestor of the referrer
+      ensure!(
+        Self::referees_count(&account_id).is_zero(),
+        Error::<T>::ReferralCycle
+      );
+
+      // 3. Register the referrer
+      Referrers::<T>::insert(&account_id, &referrer);
+      RefereesCount::<T>::mutate(&referrer, |count| {
+        *count = count.saturating_add(1);
+      });
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::ReferrerRegistered {
+        account_id,
+        referrer,
+      });
+
+      Ok(().into())
+    }
+
+    /// Add balance to the referral budget, transferred from the treasury
+    ///
+    /// - `amount`: TDFY to add to the referral budget
+    ///
+    /// Emits `ReferralBudgetToppedUp` event when successful.
+    #[pallet::call_index(6)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::top_up_referral_budget())]
+    pub fn top_up_referral_budget(
+      origin: OriginFor<T>,
+      amount: Balance,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the referral budget
+      ReferralBudget::<T>::try_mutate(|budget| -> DispatchResult {
+        *budget = budget
+          .checked_add(amount)
+          .ok_or(Error::<T>::BalanceOverflow)?;
+        Ok(())
+      })?;
+
+      // 3. Fund the referral budget from the treasury
+      Self::fund_from_treasury(amount)?;
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::ReferralBudgetToppedUp { amount });
+
+      Ok(().into())
+    }
+  }
+
+  impl<T: Config> Pallet<T> {
+    pub fn treasury_account_id() -> T::AccountId {
+      T::TreasuryPalletId::get().into_account_truncating()
+    }
+
+    // Increment the reward of the account for the era, the rewards of the era expire
+    // `T::Cooldown` + `T::ClaimWindow` blocks after the end of the era.
+    pub(crate) fn add_reward(account_id: &T::AccountId, era_index: EraIndex, reward: Balance) {
+      Rewards::<T>::mutate(account_id, era_index, |rewards| {
+        *rewards = rewards.saturating_add(reward);
+      });
+      EraRewardedAccounts::<T>::insert(era_index, account_id, ());
+      if Self::is_reward_counted(account_id, era_index) {
+        Self::increase_outstanding_rewards(reward);
+      }
+    }
+
+    // The rewards allocated before the upgrade to the storage version 2 are counted in the
+    // outstanding rewards when indexed, their changes are ignored until then
+    fn is_reward_counted(account_id: &T::AccountId, era_index: EraIndex) -> bool {
+      RewardsIndexCursor::<T>::get().map_or(true, |cursor| {
+        Rewards::<T>::hashed_key_for(account_id, era_index) <= cursor.into_inner()
+      })
+    }
+
+    fn increase_outstanding_rewards(amount: Balance) {
+      OutstandingRewards::<T>::mutate(|outstanding| {
+        *outstanding = outstanding.saturating_add(amount);
+      });
+    }
+
+    fn decrease_outstanding_rewards(amount: Balance) {
+      OutstandingRewards::<T>::mutate(|outstanding| {
+        *outstanding = outstanding.saturating_sub(amount);
+      });
+    }
+
+    pub(crate) fn rewards_expiration_block() -> T::BlockNumber {
+      T::Security::get_current_block_count()
+        .saturating_add(T::Cooldown::get())
+        .saturating_add(T::ClaimWindow::get())
+    }
+
+    /// Index by era up to `T::BatchSize` rewards allocated before the upgrade to the storage
+    /// version 2, their claim window starts with the indexing unless their era already ended.
+    pub fn do_on_idle_rewards_indexing(remaining_weight: Weight) -> Weight {
+      let cursor = match RewardsIndexCursor::<T>::get() {
+        Some(cursor) => cursor,
+        None => return T::DbWeight::get().reads(1),
+      };
+
+      let batch_size = T::BatchSize::get() as u64;
+      let batch_weight = T::DbWeight::get().reads_writes(
+        batch_size.saturating_mul(2).saturating_add(2),
+        batch_size.saturating_mul(2).saturating_add(2),
+      );
+      if batch_weight.any_gt(remaining_weight) {
+        log!(debug, "early exit because max weight is reached");
+        return T::DbWeight::get().reads(1);
+      }
+
+      let mut rewards = if cursor.is_empty() {
+        Rewards::<T>::iter()
+      } else {
+        Rewards::<T>::iter_from(cursor.into_inner())
+      };
+      let mut rewards_indexed: u64 = 0;
+      for (account_id, era_index, reward) in rewards.by_ref().take(batch_size as usize) {
+        rewards_indexed.saturating_inc();
+        if reward.is_zero() {
+          continue;
+        }
+        Self::increase_outstanding_rewards(reward);
+        EraRewardedAccounts::<T>::insert(era_index, account_id, ());
+        if !EraRewardsExpiration::<T>::contains_key(era_index) {
+          EraRewardsExpiration::<T>::insert(era_index, Self::rewards_expiration_block());
+        }
+      }
+
+      // all the rewards are indexed once a batch isn't full
+      if rewards_indexed < batch_size {
+        RewardsIndexCursor::<T>::kill();
+        log!(info, "all the sunrise rewards are indexed by era");
+      } else {
+        match BoundedVec::try_from(rewards.last_raw_key().to_vec()) {
+          Ok(last_raw_key) => RewardsIndexCursor::<T>::put(last_raw_key),
+          Err(_) => {
+            RewardsIndexCursor::<T>::kill();
+            log!(error, "unable to store the last reward indexed");
+          }
+        }
+      }
+
+      batch_weight
+    }
+
+    /// Move the expired rewards of the first expired era to the pools left-over, up to
+    /// `T::BatchSize` rewards.
+    pub fn do_on_idle_rewards_expiration(remaining_weight: Weight) -> Weight {
+      let current_block = T::Security::get_current_block_count();
+
+      // the eras are kept only until their rewards expire
+      let mut eras_read: u64 = 0;
+      let expired_era_index = EraRewardsExpiration::<T>::iter()
+        .inspect(|_| eras_read.saturating_inc())
+        .find(|(_, expiration)| *expiration <= current_block)
+        .map(|(era_index, _)| era_index);
+      let unaccounted_weight = T::DbWeight::get().reads(eras_read.saturating_add(1));
+
+      let era_index = match expired_era_index {
+        Some(era_index) => era_index,
+        None => return unaccounted_weight,
+      };
+
+      let accounts = EraRewardedAccounts::<T>::iter_key_prefix(era_index)
+        .take(T::BatchSize::get() as usize)
+        .collect::<Vec<T::AccountId>>();
+      let batch_size = accounts.len() as u32;
+
+      if <T as Config>::WeightInfo::on_idle_rewards_expiration(batch_size)
+        .saturating_add(unaccounted_weight)
+        .any_gt(remaining_weight)
+      {
+        log!(debug, "early exit because max weight is reached");
+        return unaccounted_weight;
+      }
+
+      let mut expired_rewards = Balance::zero();
+      for account_id in accounts {
+        EraRewardedAccounts::<T>::remove(era_index, &account_id);
+        let is_reward_counted = Self::is_reward_counted(&account_id, era_index);
+        let reward = Rewards::<T>::take(&account_id, era_index);
+        if reward.is_zero() {
+          continue;
+        }
+        if is_reward_counted {
+          Self::decrease_outstanding_rewards(reward);
+        }
+        expired_rewards = expired_rewards.saturating_add(reward);
+        Self::deposit_event(Event::<T>::SunriseRewardsExpired {
+          era_index,
+          account_id,
+          reward,
+        });
+      }
+
+      PoolsLeftOverBalance::<T>::mutate(|left_over| {
+        *left_over = left_over.saturating_add(expired_rewards);
+      });
+
+      // all the rewards of the era are expired
+      if EraRewardedAccounts::<T>::iter_key_prefix(era_index)
+        .next()
+        .is_none()
+      {
+        EraRewardsExpiration::<T>::remove(era_index);
+      }
+
+      log!(
+        debug,
+        "{} rewards of era {} expired, {} moved to the pools left-over",
+        batch_size,
+        era_index,
+        expired_rewards
+      );
+
+      <T as Config>::WeightInfo::on_idle_rewards_expiration(batch_size)
+        .saturating_add(unaccounted_weight)
+    }
+
+    /// Balance of the sunrise pallet account committed to the pools, the left-over, the
+    /// onboarding rebates, the referral budget and the rewards not claimed yet.
+    pub fn committed_balance() -> Result<Balance, DispatchError> {
+      Pools::<T>::get()
+        .iter()
+        .try_fold(Self::pools_left_over(), |total, pool| {
+          total.checked_add(pool.balance)
+        })
+        .and_then(|total| total.checked_add(Self::onboarding().available_amount))
+        .and_then(|total| total.checked_add(Self::referral_budget()))
+        .and_then(|total| total.checked_add(Self::outstanding_rewards()))
+        .ok_or_else(|| Error::<T>::BalanceOverflow.into())
+    }
+
+    /// Make sure the sunrise pallet account holds the committed balance.
+    fn ensure_sunrise_balance() -> DispatchResult {
+      ensure!(
+        T::CurrencyTidefi::reducible_balance(CurrencyId::Tdfy, &Self::account_id(), true)
+          >= Self::committed_balance()?,
+        Error::<T>::InsufficientSunriseBalance
+      );
+      Ok(())
+    }
+
+    /// Transfer `amount` from the treasury to the sunrise pallet account.
+    fn fund_from_treasury(amount: Balance) -> DispatchResult {
+      if !amount.is_zero() {
+        T::CurrencyTidefi::transfer(
+          CurrencyId::Tdfy,
+          &Self::treasury_account_id(),
+          &Self::account_id(),
+          amount,
+          true,
+        )?;
+      }
+      Self::ensure_sunrise_balance()
+    }
+
     // Based on the fee, try to select the highest matching sunrise pool
     pub(crate) fn try_select_first_eligible_sunrise_pool(
       fee: &Fee,
@@ -295,6 +1015,55 @@ pub mod pallet {
         .map_err(Into::into)
     }
 
+    // Onboarding rebates paid out at 100%, before the degressive schedule starts
+    pub fn onboarding_full_rebate_threshold() -> Balance {
+      Asset::Tdfy.saturating_mul(18_000_000)
+    }
+
+    // Get the sunrise pool tiers and the left-over, serialized for quick RPC call
+    pub fn get_sunrise_pools() -> SunrisePools {
+      SunrisePools {
+        pools: Pools::<T>::get()
+          .into_iter()
+          .map(|pool| SunrisePoolInfo {
+            id: pool.id,
+            minimum_tdfy_value: pool.minimum_tdfy_value,
+            transactions_remaining: pool.transactions_remaining,
+            balance: pool.balance,
+            rebates: pool.rebates,
+          })
+          .collect(),
+        left_over: Self::pools_left_over(),
+      }
+    }
+
+    // Get the onboarding rebates progress, serialized for quick RPC call
+    pub fn get_onboarding_rebates_progress() -> OnboardingRebatesProgress {
+      let onboarding_rebates = Self::onboarding();
+      let distributed = onboarding_rebates
+        .initial_amount
+        .saturating_sub(onboarding_rebates.available_amount);
+      let full_rebate_threshold = Self::onboarding_full_rebate_threshold();
+      let rebate_ratio = if distributed <= full_rebate_threshold {
+        FixedU128::from(1)
+      } else {
+        FixedU128::checked_from_rational(
+          onboarding_rebates.available_amount,
+          onboarding_rebates.initial_amount,
+        )
+        .unwrap_or_default()
+      };
+
+      OnboardingRebatesProgress {
+        initial_amount: onboarding_rebates.initial_amount,
+        available_amount: onboarding_rebates.available_amount,
+        distributed,
+        full_rebate_threshold,
+        full_rebate_remaining: full_rebate_threshold.saturating_sub(distributed),
+        rebate_ratio,
+      }
+    }
+
     pub fn get_next_onboarding_rebates(
       amount_in_tdfy: Balance,
       onboarding_rebate: &OnboardingRebates,
@@ -309,7 +1078,7 @@ pub mod pallet {
         .saturating_sub(onboarding_rebate.available_amount);
 
       // The first 18 million TDFY of onboarding rebates will be paid out to fully rebate at 100%.
-      if amount_already_assigned <= Asset::Tdfy.saturating_mul(18_000_000) {
+      if amount_already_assigned <= Self::onboarding_full_rebate_threshold() {
         return Ok(amount_in_tdfy);
       }
 
@@ -338,9 +1107,7 @@ pub mod pallet {
     }
 
     fn try_get_tdfy_value(currency_id: CurrencyId, amount: u128) -> Result<u128, DispatchError> {
-      let asset_from: Asset = currency_id
-        .try_into()
-        .map_err(|_| Error::<T>::InvalidAsset)?;
+      let decimals = T::AssetRegistry::decimals(currency_id).ok_or(Error::<T>::InvalidAsset)?;
 
       let order_book_price = match currency_id {
         CurrencyId::Tdfy => FixedU128::from(1),
@@ -351,8 +1118,11 @@ pub mod pallet {
         return Ok(0);
       }
 
-      let amount = FixedU128::saturating_from_rational(amount, asset_from.saturating_mul(1))
-        .saturating_mul(order_book_price);
+      let unit = 10_u128
+        .checked_pow(decimals as u32)
+        .ok_or(Error::<T>::InvalidAsset)?;
+      let amount =
+        FixedU128::saturating_from_rational(amount, unit).saturating_mul(order_book_price);
 
       Self::convert_fixed_balance_to_tdfy_balance(amount)
     }
@@ -408,9 +1178,7 @@ pub mod pallet {
         })?;
 
         // Increment reward for the account
-        Rewards::<T>::mutate(account_id.clone(), era_index, |rewards| {
-          *rewards = rewards.saturating_add(real_fees_in_tdfy_with_rebates);
-        });
+        Self::add_reward(account_id, era_index, real_fees_in_tdfy_with_rebates);
 
         // Emit event
         Self::deposit_event(Event::<T>::SunriseRewarded {
@@ -429,9 +1197,7 @@ pub mod pallet {
 
         if available_left_over >= real_fees_in_tdfy_with_rebates {
           // Increment reward for the account
-          Rewards::<T>::mutate(account_id.clone(), era_index, |rewards| {
-            *rewards = rewards.saturating_add(real_fees_in_tdfy_with_rebates);
-          });
+          Self::add_reward(account_id, era_index, real_fees_in_tdfy_with_rebates);
 
           // Reduce leftover
           PoolsLeftOverBalance::<T>::mutate(|left_over| {
@@ -516,7 +1282,11 @@ pub mod pallet {
               reward: *reward,
             });
             // delete storage
+            if Self::is_reward_counted(account_id, era_index) {
+              Self::decrease_outstanding_rewards(*reward);
+            }
             *found_reward = None;
+            EraRewardedAccounts::<T>::remove(era_index, account_id);
 
             log!(
               debug,
@@ -534,4 +1304,172 @@ pub mod pallet {
       T::Cooldown::get()
     }
   }
+  impl<T: Config> SunriseRewards<T::AccountId, T::BlockNumber> for Pallet<T> {
+    fn account_rewards(
+      account_id: &T::AccountId,
+    ) -> Vec<(EraIndex, Balance, Option<T::BlockNumber>)> {
+      let mut rewards = Rewards::<T>::iter_prefix(account_id)
+        .filter(|(_, reward)| !reward.is_zero())
+        .map(|(era_index, reward)| (era_index, reward, Self::era_rewards_expiration(era_index)))
+        .collect::<Vec<_>>();
+      rewards.sort_by_key(|(era_index, _, _)| *era_index);
+      rewards
+    }
+
+    fn on_era_end(era_index: EraIndex) {
+      if EraRewardedAccounts::<T>::iter_key_prefix(era_index)
+        .next()
+        .is_some()
+      {
+        EraRewardsExpiration::<T>::insert(era_index, Self::rewards_expiration_block());
+      }
+    }
+
+    fn try_claim_all_sunrise_rewards(
+      account_id: &T::AccountId,
+      before_era_index: EraIndex,
+      max_eras: u32,
+    ) -> Result<(u32, Balance), DispatchError> {
+      // the eras are not ordered in the storage, the rewards of the eras still in cooldown are
+      // skipped without counting against `max_eras`, they are bounded by the eras in cooldown
+      let claimable_eras = Rewards::<T>::iter_prefix(account_id)
+        .filter(|(era_index, _)| *era_index < before_era_index)
+        .take(max_eras as usize)
+        .collect::<Vec<(EraIndex, Balance)>>();
+      let eras = claimable_eras.len() as u32;
+
+      let total_reward = claimable_eras
+        .iter()
+        .try_fold(Balance::zero(), |total, (_, reward)| {
+          total.checked_add(*reward)
+        })
+        .ok_or(Error::<T>::BalanceOverflow)?;
+
+      if total_reward.is_zero() {
+        return Err(Error::<T>::NoRewardsAvailable.into());
+      }
+
+      // transfer funds
+      T::CurrencyTidefi::transfer(
+        CurrencyId::Tdfy,
+        &Self::account_id(),
+        account_id,
+        total_reward,
+        true,
+      )?;
+
+      // delete storage
+      for (era_index, reward) in claimable_eras.iter() {
+        if Self::is_reward_counted(account_id, *era_index) {
+          Self::decrease_outstanding_rewards(*reward);
+        }
+        Rewards::<T>::remove(account_id, era_index);
+        EraRewardedAccounts::<T>::remove(era_index, account_id);
+      }
+
+      // emit event
+      Self::deposit_event(Event::<T>::SunriseClaimedAll {
+        account_id: account_id.clone(),
+        eras,
+        reward: total_reward,
+      });
+
+      log!(
+        debug,
+        "Sunrise rewards of {} eras claimed for {:?} successfully.",
+        eras,
+        account_id
+      );
+
+      Ok((eras, total_reward))
+    }
+  }
+
+  impl<T: Config> SunriseReferral<T::AccountId> for Pallet<T> {
+    fn try_allocate_referral_rewards(
+      referee: &T::AccountId,
+      era_index: EraIndex,
+      fee: &Fee,
+    ) -> Result<Option<Balance>, DispatchError> {
+      let referrer = match Self::referrer(referee) {
+        Some(referrer) => referrer,
+        None => return Ok(None),
+      };
+
+      // the share of the fee is limited by the referee cap and the referral budget
+      let reward = T::ReferralShare::get()
+        .mul_floor(fee.fee_tdfy)
+        .min(
+          T::MaximumReferralRewardPerReferee::get()
+            .saturating_sub(Self::referee_accrued_rewards(referee)),
+        )
+        .min(Self::referral_budget());
+
+      if reward.is_zero() {
+        return Ok(None);
+      }
+
+      // Reduce the referral budget
+      ReferralBudget::<T>::mutate(|budget| {
+        *budget = budget.saturating_sub(reward);
+      });
+
+      // Increment the rewards accrued from the referee and the reward of the referrer
+      RefereeAccruedRewards::<T>::mutate(referee, |accrued| {
+        *accrued = accrued.saturating_add(reward);
+      });
+      ReferralRewards::<T>::mutate(&referrer, era_index, |rewards| {
+        *rewards = rewards.saturating_add(reward);
+      });
+      Self::increase_outstanding_rewards(reward);
+
+      // Emit event
+      Self::deposit_event(Event::<T>::ReferralRewarded {
+        era_index,
+        referrer,
+        referee: referee.clone(),
+        reward,
+      });
+
+      Ok(Some(reward))
+    }
+
+    fn try_claim_referral_rewards(
+      account_id: &T::AccountId,
+      era_index: EraIndex,
+    ) -> DispatchResult {
+      let reward = ReferralRewards::<T>::get(account_id, era_index);
+      if reward.is_zero() {
+        return Err(Error::<T>::NoRewardsAvailable.into());
+      }
+
+      // transfer funds
+      T::CurrencyTidefi::transfer(
+        CurrencyId::Tdfy,
+        &Self::account_id(),
+        account_id,
+        reward,
+        true,
+      )?;
+
+      // delete storage
+      ReferralRewards::<T>::remove(account_id, era_index);
+      Self::decrease_outstanding_rewards(reward);
+
+      // emit event
+      Self::deposit_event(Event::<T>::ReferralClaimed {
+        era_index,
+        account_id: account_id.clone(),
+        reward,
+      });
+
+      log!(
+        debug,
+        "Referral reward claimed for {:?} successfully.",
+        account_id
+      );
+
+      Ok(())
+    }
+  }
 }
@@ -0,0 +1,72 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+  log,
+  pallet_prelude::*,
+  traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+pub mod v2 {
+  use super::*;
+
+  /// Index the unclaimed rewards by era, so they can expire.
+  ///
+  /// The rewards are indexed by batches of `T::BatchSize` when the chain is idle, starting from
+  /// the `RewardsIndexCursor` set by the upgrade. The rewards already allocated can be claimed for
+  /// a full `T::Cooldown` + `T::ClaimWindow` from their indexing.
+  pub struct MigrateToV2<T>(sp_std::marker::PhantomData<T>);
+  impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+    fn on_runtime_upgrade() -> Weight {
+      let onchain_version = Pallet::<T>::on_chain_storage_version();
+      if onchain_version != 1 {
+        log::warn!(
+          target: LOG_TARGET,
+          "skipping v2 migration, on-chain storage version is {:?}",
+          onchain_version
+        );
+        return T::DbWeight::get().reads(1);
+      }
+
+      // an empty cursor starts the indexing from the first reward
+      RewardsIndexCursor::<T>::put(BoundedVec::default());
+      StorageVersion::new(2).put::<Pallet<T>>();
+
+      log::info!(
+        target: LOG_TARGET,
+        "migrated sunrise to v2, the rewards are indexed when the chain is idle"
+      );
+
+      T::DbWeight::get().reads_writes(1, 2)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+      ensure!(
+        RewardsIndexCursor::<T>::exists(),
+        "the rewards indexing must be started"
+      );
+      ensure!(
+        Pallet::<T>::on_chain_storage_version() == 2,
+        "storage version must be 2"
+      );
+      Ok(())
+    }
+  }
+}
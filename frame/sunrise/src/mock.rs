@@ -58,16 +58,18 @@ impl EnsureOrigin<RuntimeOrigin> for EnsureRootOrAssetRegistry {
 construct_mock_runtime!({
   Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
   AssetRegistry: pallet_asset_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
-  Sunrise: pallet_sunrise::{Pallet, Config<T>, Storage, Event<T>},
+  Sunrise: pallet_sunrise::{Pallet, Call, Config<T>, Storage, Event<T>},
   Security: pallet_security::{Pallet, Call, Config, Storage, Event<T>},
   TidefiStaking: pallet_tidefi_stake::{Pallet, Call, Config<T>, Storage, Event<T>},
 }, {
   pub const AssetDeposit: u64 = 1;
+  pub const PauseDuration: u64 = 100;
   pub const ApprovalDeposit: u64 = 1;
   pub const StringLimit: u32 = 50;
   pub const MetadataDepositBase: u64 = 1;
   pub const MetadataDepositPerByte: u64 = 1;
   pub const SunrisePalletId: PalletId = PalletId(*b"sunr*pal");
+  pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
   pub const TidefiPalletId: PalletId = PalletId(*b"wrpr*pal");
   pub const QuorumPalletId: PalletId = PalletId(*b"qurm*pal");
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
@@ -77,7 +79,13 @@ construct_mock_runtime!({
   pub const StakeAccountCap: u32 = 10;
   // Maximum number of staking period the chain can support
   pub const StakingRewardCap: u32 = 10;
+  pub const SessionRewardsHistoryCap: u32 = 10;
+  pub const BlocksPerYear: BlockNumber = 14400 * 365;
   pub const Cooldown: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ClaimWindow: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ReferralShare: Permill = Permill::from_percent(10);
+  // max 1k referral rewards per referee
+  pub const MaximumReferralRewardPerReferee: Balance = 1_000_000_000_000_000;
   // max 10k rewards
   pub const MaximumRewardPerSwap: Balance = 10_000_000_000_000_000;
   // 50%
@@ -112,11 +120,19 @@ impl pallet_assets::Config for Test {
 impl pallet_sunrise::Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type Security = Security;
+  type AssetRegistry = AssetRegistry;
   type SunrisePalletId = SunrisePalletId;
   type CurrencyTidefi = Adapter<AccountId>;
   type Cooldown = Cooldown;
+  type ClaimWindow = ClaimWindow;
+  type ReferralShare = ReferralShare;
+  type MaximumReferralRewardPerReferee = MaximumReferralRewardPerReferee;
+  type BatchSize = BatchSize;
   type MaximumRewardPerSwap = MaximumRewardPerSwap;
   type LeftoverSwapRebates = LeftoverSwapRebates;
+  type TreasuryPalletId = TreasuryPalletId;
+  type ForceOrigin = system::EnsureRoot<AccountId>;
+  type WeightInfo = crate::weights::SubstrateWeight<Test>;
 }
 
 impl pallet_tidefi_stake::Config for Test {
@@ -127,9 +143,13 @@ impl pallet_tidefi_stake::Config for Test {
   type BlocksForceUnstake = BlocksForceUnstake;
   type AssetRegistry = AssetRegistry;
   type StakingRewardCap = StakingRewardCap;
+  type SessionRewardsHistoryCap = SessionRewardsHistoryCap;
+  type BlocksPerYear = BlocksPerYear;
   type Security = Security;
+  type Sunrise = Sunrise;
   type WeightInfo = pallet_tidefi_stake::weights::SubstrateWeight<Test>;
   type BatchSize = BatchSize;
+  type ForceOrigin = system::EnsureRoot<AccountId>;
 }
 
 impl pallet_asset_registry::Config for Test {
@@ -141,6 +161,13 @@ impl pallet_asset_registry::Config for Test {
 
 impl pallet_security::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type PauseOrigin = system::EnsureRoot<AccountId>;
+  type ResumeOrigin = system::EnsureRoot<AccountId>;
+  type PauseDuration = PauseDuration;
+  type MaxPauseRenewals = ConstU32<2>;
+  type UnpausablePallets = ();
+  type MaxNameLength = ConstU32<32>;
+  type MaxReasonLength = ConstU32<64>;
   type WeightInfo = pallet_security::weights::SubstrateWeight<Test>;
 }
 
@@ -180,6 +207,28 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
   pallet_balances::GenesisConfig::<Test>::default()
     .assimilate_storage(&mut t)
     .unwrap();
+  pallet_asset_registry::GenesisConfig::<Test> {
+    assets: vec![
+      (
+        CurrencyId::Wrapped(2),
+        "Bitcoin".into(),
+        "BTC".into(),
+        8,
+        vec![],
+      ),
+      (
+        CurrencyId::Wrapped(3),
+        "Ethereum".into(),
+        "ETH".into(),
+        18,
+        vec![],
+      ),
+    ],
+    account: AccountId(0),
+    infos: Vec::new(),
+  }
+  .assimilate_storage(&mut t)
+  .unwrap();
 
   t.into()
 }
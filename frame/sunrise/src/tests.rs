@@ -15,15 +15,58 @@
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-  mock::{new_test_ext, AccountId, Adapter, Sunrise, Test},
-  Error,
+  migrations,
+  mock::{
+    new_test_ext, AccountId, Adapter, BatchSize, ClaimWindow, Cooldown,
+    MaximumReferralRewardPerReferee, RuntimeEvent, RuntimeOrigin, Sunrise, System, Test,
+  },
+  EraRewardedAccounts, EraRewardsExpiration, Error, Event, Onboarding, OutstandingRewards,
+  PoolsLeftOverBalance, ReferralBudget, ReferralRewards, Rewards, RewardsIndexCursor,
+  SunriseReferral, SunriseRewards,
+};
+use frame_support::{
+  assert_noop, assert_ok,
+  traits::{
+    fungibles::{Inspect, Mutate},
+    Get, Hooks, OnRuntimeUpgrade, StorageVersion,
+  },
+  weights::Weight,
 };
-use frame_support::{assert_noop, assert_ok, traits::fungibles::Mutate};
 use pallet_balances::Error as BalancesError;
-use sp_runtime::{traits::CheckedDiv, FixedPointNumber, FixedU128};
+use sp_runtime::{traits::CheckedDiv, DispatchError, FixedPointNumber, FixedU128};
 use tidefi_primitives::{assets::Asset, pallet::SunriseExt, CurrencyId, Fee, OnboardingRebates};
 
 const ALICE_ACCOUNT_ID: AccountId = AccountId(1);
+const TREASURY_INITIAL_BALANCE: u128 = 1_000_000_000_000_000_000_000;
+const POOL_ID: u8 = 3;
+const POOL_BALANCE: u128 = 100_000_000_000_000_000_000;
+
+// Fund the sunrise account with the genesis pools and onboarding rebates, and the treasury
+fn fund_sunrise_and_treasury() {
+  assert_ok!(Adapter::mint_into(
+    CurrencyId::Tdfy,
+    &Sunrise::account_id(),
+    Sunrise::committed_balance()
+      .unwrap()
+      .saturating_add(Adapter::minimum_balance(CurrencyId::Tdfy)),
+  ));
+  assert_ok!(Adapter::mint_into(
+    CurrencyId::Tdfy,
+    &Sunrise::treasury_account_id(),
+    TREASURY_INITIAL_BALANCE,
+  ));
+}
+
+fn add_pool() {
+  assert_ok!(Sunrise::add_pool(
+    RuntimeOrigin::root(),
+    POOL_ID,
+    5_000_000_000_000_000,
+    10,
+    POOL_BALANCE,
+    FixedU128::saturating_from_rational(300_u32, 100_u32),
+  ));
+}
 
 #[test]
 pub fn test_select_first_eligible_sunrise_pool() {
@@ -75,6 +118,22 @@ pub fn test_select_first_eligible_sunrise_pool() {
   })
 }
 
+#[test]
+pub fn test_get_tdfy_value_of_unregistered_asset() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Sunrise::register_exchange_rate(vec![(
+      4,
+      1_000_000_000_000
+    )]));
+
+    // the decimals of the asset are unknown to the asset registry
+    assert_noop!(
+      Sunrise::try_get_tdfy_value(CurrencyId::Wrapped(4), 1_000_000),
+      Error::<Test>::InvalidAsset
+    );
+  })
+}
+
 #[test]
 pub fn test_convert_fixed_balance_to_tdfy_balance() {
   // 0.03133 ETH for 1 TDFY
@@ -390,3 +449,693 @@ pub fn test_try_allocate_rewards_for_swap() {
     );
   });
 }
+
+#[test]
+pub fn test_add_pool() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    fund_sunrise_and_treasury();
+    let rebates = FixedU128::saturating_from_rational(300_u32, 100_u32);
+
+    assert_ok!(Sunrise::add_pool(
+      RuntimeOrigin::root(),
+      POOL_ID,
+      5_000_000_000_000_000,
+      10,
+      POOL_BALANCE,
+      rebates,
+    ));
+
+    assert_eq!(Sunrise::sunrise_pools().len(), 3);
+    let pool = Sunrise::sunrise_pools().last().cloned().unwrap();
+    assert_eq!(pool.id, POOL_ID);
+    assert_eq!(pool.minimum_tdfy_value, 5_000_000_000_000_000);
+    assert_eq!(pool.transactions_remaining, 10);
+    assert_eq!(pool.balance, POOL_BALANCE);
+    assert_eq!(pool.rebates, rebates);
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tdfy, &Sunrise::treasury_account_id()),
+      TREASURY_INITIAL_BALANCE - POOL_BALANCE
+    );
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::PoolAdded {
+      pool_id: POOL_ID,
+      minimum_tdfy_value: 5_000_000_000_000_000,
+      transactions_remaining: 10,
+      balance: POOL_BALANCE,
+      rebates,
+    }));
+  });
+}
+
+#[test]
+pub fn test_add_pool_with_bad_origin_fails() {
+  new_test_ext().execute_with(|| {
+    fund_sunrise_and_treasury();
+    assert_noop!(
+      Sunrise::add_pool(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        POOL_ID,
+        0,
+        10,
+        POOL_BALANCE,
+        FixedU128::from(1),
+      ),
+      DispatchError::BadOrigin
+    );
+  });
+}
+
+#[test]
+pub fn test_add_pool_with_existing_id_fails() {
+  new_test_ext().execute_with(|| {
+    fund_sunrise_and_treasury();
+    assert_noop!(
+      Sunrise::add_pool(
+        RuntimeOrigin::root(),
+        1,
+        0,
+        10,
+        POOL_BALANCE,
+        FixedU128::from(1)
+      ),
+      Error::<Test>::SunrisePoolAlreadyExists
+    );
+  });
+}
+
+#[test]
+pub fn test_add_pool_with_underfunded_sunrise_account_fails() {
+  new_test_ext().execute_with(|| {
+    // the genesis pools are not backed by the sunrise account
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tdfy,
+      &Sunrise::treasury_account_id(),
+      TREASURY_INITIAL_BALANCE,
+    ));
+    assert_noop!(
+      Sunrise::add_pool(
+        RuntimeOrigin::root(),
+        POOL_ID,
+        0,
+        10,
+        POOL_BALANCE,
+        FixedU128::from(1)
+      ),
+      Error::<Test>::InsufficientSunriseBalance
+    );
+  });
+}
+
+#[test]
+pub fn test_add_pool_with_underfunded_treasury_fails() {
+  new_test_ext().execute_with(|| {
+    fund_sunrise_and_treasury();
+    assert_noop!(
+      Sunrise::add_pool(
+        RuntimeOrigin::root(),
+        POOL_ID,
+        0,
+        10,
+        TREASURY_INITIAL_BALANCE + 1,
+        FixedU128::from(1)
+      ),
+      BalancesError::<Test>::InsufficientBalance
+    );
+  });
+}
+
+#[test]
+pub fn test_replenish_pool() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    fund_sunrise_and_treasury();
+    add_pool();
+
+    assert_ok!(Sunrise::replenish_pool(
+      RuntimeOrigin::root(),
+      POOL_ID,
+      POOL_BALANCE,
+      5
+    ));
+
+    let pool = Sunrise::sunrise_pools()
+      .into_iter()
+      .find(|pool| pool.id == POOL_ID)
+      .unwrap();
+    assert_eq!(pool.balance, POOL_BALANCE * 2);
+    assert_eq!(pool.transactions_remaining, 15);
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tdfy, &Sunrise::treasury_account_id()),
+      TREASURY_INITIAL_BALANCE - POOL_BALANCE * 2
+    );
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::PoolReplenished {
+      pool_id: POOL_ID,
+      amount: POOL_BALANCE,
+      transactions: 5,
+    }));
+
+    assert_noop!(
+      Sunrise::replenish_pool(RuntimeOrigin::root(), 42, POOL_BALANCE, 5),
+      Error::<Test>::InvalidSunrisePool
+    );
+  });
+}
+
+#[test]
+pub fn test_update_pool() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let rebates = FixedU128::saturating_from_rational(150_u32, 100_u32);
+
+    assert_ok!(Sunrise::update_pool(
+      RuntimeOrigin::root(),
+      2,
+      2_000_000_000_000_000,
+      rebates
+    ));
+
+    let pool = Sunrise::sunrise_pools()
+      .into_iter()
+      .find(|pool| pool.id == 2)
+      .unwrap();
+    assert_eq!(pool.minimum_tdfy_value, 2_000_000_000_000_000);
+    assert_eq!(pool.rebates, rebates);
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::PoolUpdated {
+      pool_id: 2,
+      minimum_tdfy_value: 2_000_000_000_000_000,
+      rebates,
+    }));
+
+    assert_noop!(
+      Sunrise::update_pool(RuntimeOrigin::root(), 42, 0, rebates),
+      Error::<Test>::InvalidSunrisePool
+    );
+  });
+}
+
+#[test]
+pub fn test_close_pool() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    fund_sunrise_and_treasury();
+    add_pool();
+
+    assert_ok!(Sunrise::close_pool(RuntimeOrigin::root(), POOL_ID));
+
+    assert_eq!(Sunrise::sunrise_pools().len(), 2);
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tdfy, &Sunrise::treasury_account_id()),
+      TREASURY_INITIAL_BALANCE
+    );
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::PoolClosed {
+      pool_id: POOL_ID,
+      balance: POOL_BALANCE,
+    }));
+
+    assert_noop!(
+      Sunrise::close_pool(RuntimeOrigin::root(), POOL_ID),
+      Error::<Test>::InvalidSunrisePool
+    );
+  });
+}
+
+#[test]
+pub fn test_close_pool_with_outstanding_rewards_fails() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    fund_sunrise_and_treasury();
+    add_pool();
+    Sunrise::add_reward(&ALICE_ACCOUNT_ID, 1, POOL_BALANCE);
+    assert_eq!(Sunrise::outstanding_rewards(), POOL_BALANCE);
+
+    assert_noop!(
+      Sunrise::close_pool(RuntimeOrigin::root(), POOL_ID),
+      Error::<Test>::InsufficientSunriseBalance
+    );
+  });
+}
+
+#[test]
+pub fn test_top_up_onboarding() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    fund_sunrise_and_treasury();
+    let onboarding_before = Sunrise::onboarding();
+
+    assert_ok!(Sunrise::top_up_onboarding(
+      RuntimeOrigin::root(),
+      POOL_BALANCE
+    ));
+
+    let onboarding_after = Sunrise::onboarding();
+    assert_eq!(
+      onboarding_after.initial_amount,
+      onboarding_before.initial_amount + POOL_BALANCE
+    );
+    assert_eq!(
+      onboarding_after.available_amount,
+      onboarding_before.available_amount + POOL_BALANCE
+    );
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tdfy, &Sunrise::treasury_account_id()),
+      TREASURY_INITIAL_BALANCE - POOL_BALANCE
+    );
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::OnboardingToppedUp {
+      amount: POOL_BALANCE,
+    }));
+  });
+}
+
+#[test]
+pub fn test_rewards_expiration_is_set_on_era_end() {
+  new_test_ext().execute_with(|| {
+    Sunrise::add_reward(&ALICE_ACCOUNT_ID, 1, 1_000);
+    assert_eq!(Sunrise::era_rewards_expiration(1), None);
+    assert!(EraRewardedAccounts::<Test>::contains_key(
+      1,
+      ALICE_ACCOUNT_ID
+    ));
+
+    // the claim window starts with the end of the era, not with its first reward
+    pallet_security::CurrentBlockCount::<Test>::put(100);
+    Sunrise::add_reward(&AccountId(2), 1, 1_000);
+    Sunrise::on_era_end(1);
+    assert_eq!(
+      Sunrise::era_rewards_expiration(1),
+      Some(100 + Cooldown::get() + ClaimWindow::get())
+    );
+
+    // eras without rewards don't expire
+    Sunrise::on_era_end(2);
+    assert_eq!(Sunrise::era_rewards_expiration(2), None);
+  });
+}
+
+#[test]
+pub fn test_rewards_allocated_before_the_upgrade_are_indexed_by_batches() {
+  new_test_ext().execute_with(|| {
+    let rewarded_accounts = BatchSize::get() + 2;
+    (0..rewarded_accounts).for_each(|i| Rewards::<Test>::insert(AccountId(i.into()), 1, 1_000));
+    StorageVersion::new(1).put::<Sunrise>();
+
+    migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+    assert!(RewardsIndexCursor::<Test>::exists());
+    assert_eq!(EraRewardedAccounts::<Test>::iter_key_prefix(1).count(), 0);
+
+    Sunrise::on_idle(1, Weight::MAX);
+    assert_eq!(
+      EraRewardedAccounts::<Test>::iter_key_prefix(1).count() as u32,
+      BatchSize::get()
+    );
+    assert_eq!(
+      Sunrise::outstanding_rewards(),
+      1_000 * u128::from(BatchSize::get())
+    );
+    assert_eq!(
+      Sunrise::era_rewards_expiration(1),
+      Some(Cooldown::get() + ClaimWindow::get())
+    );
+
+    Sunrise::on_idle(2, Weight::MAX);
+    assert_eq!(
+      EraRewardedAccounts::<Test>::iter_key_prefix(1).count() as u32,
+      rewarded_accounts
+    );
+    assert!(!RewardsIndexCursor::<Test>::exists());
+    assert_eq!(
+      Sunrise::outstanding_rewards(),
+      1_000 * u128::from(rewarded_accounts)
+    );
+  });
+}
+
+#[test]
+pub fn test_claimed_rewards_are_removed_from_era_index() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tdfy,
+      &Sunrise::account_id(),
+      200_000_000_000_000_000,
+    ));
+    Sunrise::add_reward(&ALICE_ACCOUNT_ID, 1, 1_000_000_000_000);
+    assert_eq!(Sunrise::outstanding_rewards(), 1_000_000_000_000);
+
+    assert_ok!(Sunrise::try_claim_sunrise_rewards(&ALICE_ACCOUNT_ID, 1));
+    assert!(!EraRewardedAccounts::<Test>::contains_key(
+      1,
+      ALICE_ACCOUNT_ID
+    ));
+    assert_eq!(Sunrise::outstanding_rewards(), 0);
+  });
+}
+
+#[test]
+pub fn test_expired_rewards_are_moved_to_left_over() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    Sunrise::add_reward(&ALICE_ACCOUNT_ID, 1, 1_000);
+    Sunrise::add_reward(&AccountId(2), 1, 2_000);
+    Sunrise::on_era_end(1);
+    let expiration = Sunrise::era_rewards_expiration(1).unwrap();
+    let left_over_before = Sunrise::pools_left_over();
+
+    // nothing expired yet
+    pallet_security::CurrentBlockCount::<Test>::put(expiration - 1);
+    Sunrise::on_idle(1, Weight::MAX);
+    assert_eq!(Sunrise::sunrise_rewards(ALICE_ACCOUNT_ID, 1), 1_000);
+    assert_eq!(Sunrise::pools_left_over(), left_over_before);
+
+    pallet_security::CurrentBlockCount::<Test>::put(expiration);
+    Sunrise::on_idle(1, Weight::MAX);
+
+    assert_eq!(Sunrise::sunrise_rewards(ALICE_ACCOUNT_ID, 1), 0);
+    assert_eq!(Sunrise::sunrise_rewards(AccountId(2), 1), 0);
+    assert_eq!(Sunrise::pools_left_over(), left_over_before + 3_000);
+    assert_eq!(Sunrise::outstanding_rewards(), 0);
+    assert_eq!(EraRewardedAccounts::<Test>::iter_key_prefix(1).count(), 0);
+    assert_eq!(Sunrise::era_rewards_expiration(1), None);
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::SunriseRewardsExpired {
+      era_index: 1,
+      account_id: ALICE_ACCOUNT_ID,
+      reward: 1_000,
+    }));
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::SunriseRewardsExpired {
+      era_index: 1,
+      account_id: AccountId(2),
+      reward: 2_000,
+    }));
+  });
+}
+
+#[test]
+pub fn test_rewards_expiration_is_batched() {
+  new_test_ext().execute_with(|| {
+    let rewarded_accounts = BatchSize::get() + 2;
+    (0..rewarded_accounts).for_each(|i| Sunrise::add_reward(&AccountId(i.into()), 1, 1_000));
+    Sunrise::on_era_end(1);
+    pallet_security::CurrentBlockCount::<Test>::put(Sunrise::era_rewards_expiration(1).unwrap());
+
+    Sunrise::on_idle(1, Weight::MAX);
+    assert_eq!(Rewards::<Test>::iter().count(), 2);
+    assert!(EraRewardsExpiration::<Test>::contains_key(1));
+
+    Sunrise::on_idle(2, Weight::MAX);
+    assert_eq!(Rewards::<Test>::iter().count(), 0);
+    assert!(!EraRewardsExpiration::<Test>::contains_key(1));
+    assert_eq!(
+      Sunrise::pools_left_over(),
+      1_000 * u128::from(rewarded_accounts)
+    );
+  });
+}
+
+#[test]
+pub fn test_rewards_expiration_without_enough_weight() {
+  new_test_ext().execute_with(|| {
+    Sunrise::add_reward(&ALICE_ACCOUNT_ID, 1, 1_000);
+    Sunrise::on_era_end(1);
+    pallet_security::CurrentBlockCount::<Test>::put(Sunrise::era_rewards_expiration(1).unwrap());
+
+    Sunrise::on_idle(1, Weight::zero());
+    assert_eq!(Sunrise::sunrise_rewards(ALICE_ACCOUNT_ID, 1), 1_000);
+  });
+}
+
+#[test]
+pub fn test_get_sunrise_pools() {
+  new_test_ext().execute_with(|| {
+    PoolsLeftOverBalance::<Test>::put(1_000);
+
+    let sunrise_pools = Sunrise::get_sunrise_pools();
+
+    assert_eq!(sunrise_pools.left_over, 1_000);
+    assert_eq!(sunrise_pools.pools.len(), 2);
+    assert_eq!(sunrise_pools.pools[1].id, 2);
+    assert_eq!(
+      sunrise_pools.pools[1].minimum_tdfy_value,
+      1_000_000_000_000_000
+    );
+    assert_eq!(sunrise_pools.pools[1].transactions_remaining, 1);
+    assert_eq!(
+      sunrise_pools.pools[1].balance,
+      Asset::Tdfy.saturating_mul(67_200_000)
+    );
+    assert_eq!(
+      sunrise_pools.pools[1].rebates,
+      FixedU128::saturating_from_rational(200_u32, 100_u32)
+    );
+  });
+}
+
+#[test]
+pub fn test_get_onboarding_rebates_progress() {
+  new_test_ext().execute_with(|| {
+    let progress = Sunrise::get_onboarding_rebates_progress();
+    assert_eq!(progress.distributed, 0);
+    assert_eq!(
+      progress.full_rebate_threshold,
+      Asset::Tdfy.saturating_mul(18_000_000)
+    );
+    assert_eq!(
+      progress.full_rebate_remaining,
+      Asset::Tdfy.saturating_mul(18_000_000)
+    );
+    assert_eq!(progress.rebate_ratio, FixedU128::from(1));
+
+    // 24M TDFY distributed, the degressive schedule started
+    Onboarding::<Test>::put(OnboardingRebates {
+      initial_amount: Asset::Tdfy.saturating_mul(48_000_000),
+      available_amount: Asset::Tdfy.saturating_mul(24_000_000),
+    });
+
+    let progress = Sunrise::get_onboarding_rebates_progress();
+    assert_eq!(progress.distributed, Asset::Tdfy.saturating_mul(24_000_000));
+    assert_eq!(progress.full_rebate_remaining, 0);
+    assert_eq!(
+      progress.rebate_ratio,
+      FixedU128::saturating_from_rational(1_u32, 2_u32)
+    );
+  });
+}
+
+fn referral_fee(fee_tdfy: u128) -> Fee {
+  Fee {
+    amount: fee_tdfy.saturating_mul(50),
+    fee: fee_tdfy,
+    fee_tdfy,
+  }
+}
+
+#[test]
+pub fn test_register_referrer() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    assert_ok!(Sunrise::register_referrer(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      AccountId(2)
+    ));
+
+    assert_eq!(Sunrise::referrer(ALICE_ACCOUNT_ID), Some(AccountId(2)));
+    assert_eq!(Sunrise::referees_count(AccountId(2)), 1);
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::ReferrerRegistered {
+      account_id: ALICE_ACCOUNT_ID,
+      referrer: AccountId(2),
+    }));
+  });
+}
+
+#[test]
+pub fn test_register_referrer_twice_fails() {
+  new_test_ext().execute_with(|| {
+    assert_ok!(Sunrise::register_referrer(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      AccountId(2)
+    ));
+    assert_noop!(
+      Sunrise::register_referrer(RuntimeOrigin::signed(ALICE_ACCOUNT_ID), AccountId(3)),
+      Error::<Test>::ReferrerAlreadyRegistered
+    );
+  });
+}
+
+#[test]
+pub fn test_register_self_referral_fails() {
+  new_test_ext().execute_with(|| {
+    assert_noop!(
+      Sunrise::register_referrer(RuntimeOrigin::signed(ALICE_ACCOUNT_ID), ALICE_ACCOUNT_ID),
+      Error::<Test>::SelfReferral
+    );
+  });
+}
+
+#[test]
+pub fn test_register_referral_cycle_fails() {
+  new_test_ext().execute_with(|| {
+    // 2 -> 3 -> ALICE, ALICE can't be referred by 2
+    assert_ok!(Sunrise::register_referrer(
+      RuntimeOrigin::signed(AccountId(3)),
+      ALICE_ACCOUNT_ID
+    ));
+    assert_ok!(Sunrise::register_referrer(
+      RuntimeOrigin::signed(AccountId(2)),
+      AccountId(3)
+    ));
+    assert_noop!(
+      Sunrise::register_referrer(RuntimeOrigin::signed(ALICE_ACCOUNT_ID), AccountId(2)),
+      Error::<Test>::ReferralCycle
+    );
+  });
+}
+
+#[test]
+pub fn test_top_up_referral_budget() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    fund_sunrise_and_treasury();
+
+    assert_ok!(Sunrise::top_up_referral_budget(
+      RuntimeOrigin::root(),
+      POOL_BALANCE
+    ));
+
+    assert_eq!(Sunrise::referral_budget(), POOL_BALANCE);
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tdfy, &Sunrise::treasury_account_id()),
+      TREASURY_INITIAL_BALANCE - POOL_BALANCE
+    );
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::ReferralBudgetToppedUp {
+      amount: POOL_BALANCE,
+    }));
+  });
+}
+
+#[test]
+pub fn test_top_up_referral_budget_with_bad_origin_fails() {
+  new_test_ext().execute_with(|| {
+    fund_sunrise_and_treasury();
+    assert_noop!(
+      Sunrise::top_up_referral_budget(RuntimeOrigin::signed(ALICE_ACCOUNT_ID), POOL_BALANCE),
+      DispatchError::BadOrigin
+    );
+  });
+}
+
+#[test]
+pub fn test_try_allocate_referral_rewards() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    ReferralBudget::<Test>::put(POOL_BALANCE);
+
+    // no referrer
+    assert_eq!(
+      Sunrise::try_allocate_referral_rewards(&ALICE_ACCOUNT_ID, 1, &referral_fee(1_000_000))
+        .unwrap(),
+      None
+    );
+
+    assert_ok!(Sunrise::register_referrer(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      AccountId(2)
+    ));
+
+    // 10% of the fee
+    assert_eq!(
+      Sunrise::try_allocate_referral_rewards(&ALICE_ACCOUNT_ID, 1, &referral_fee(1_000_000))
+        .unwrap(),
+      Some(100_000)
+    );
+    assert_eq!(Sunrise::referral_rewards(AccountId(2), 1), 100_000);
+    assert_eq!(Sunrise::referee_accrued_rewards(ALICE_ACCOUNT_ID), 100_000);
+    assert_eq!(Sunrise::referral_budget(), POOL_BALANCE - 100_000);
+    assert_eq!(Sunrise::outstanding_rewards(), 100_000);
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::ReferralRewarded {
+      era_index: 1,
+      referrer: AccountId(2),
+      referee: ALICE_ACCOUNT_ID,
+      reward: 100_000,
+    }));
+  });
+}
+
+#[test]
+pub fn test_referral_rewards_are_capped_per_referee() {
+  new_test_ext().execute_with(|| {
+    ReferralBudget::<Test>::put(POOL_BALANCE);
+    assert_ok!(Sunrise::register_referrer(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      AccountId(2)
+    ));
+
+    let maximum_reward = MaximumReferralRewardPerReferee::get();
+    assert_eq!(
+      Sunrise::try_allocate_referral_rewards(
+        &ALICE_ACCOUNT_ID,
+        1,
+        &referral_fee(maximum_reward.saturating_mul(20))
+      )
+      .unwrap(),
+      Some(maximum_reward)
+    );
+    assert_eq!(
+      Sunrise::try_allocate_referral_rewards(&ALICE_ACCOUNT_ID, 2, &referral_fee(1_000_000))
+        .unwrap(),
+      None
+    );
+    assert_eq!(Sunrise::referral_rewards(AccountId(2), 2), 0);
+  });
+}
+
+#[test]
+pub fn test_referral_rewards_are_limited_by_budget() {
+  new_test_ext().execute_with(|| {
+    ReferralBudget::<Test>::put(50_000);
+    assert_ok!(Sunrise::register_referrer(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      AccountId(2)
+    ));
+
+    assert_eq!(
+      Sunrise::try_allocate_referral_rewards(&ALICE_ACCOUNT_ID, 1, &referral_fee(1_000_000))
+        .unwrap(),
+      Some(50_000)
+    );
+    assert_eq!(Sunrise::referral_budget(), 0);
+    assert_eq!(
+      Sunrise::try_allocate_referral_rewards(&ALICE_ACCOUNT_ID, 1, &referral_fee(1_000_000))
+        .unwrap(),
+      None
+    );
+  });
+}
+
+#[test]
+pub fn test_try_claim_referral_rewards() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    assert_ok!(Adapter::mint_into(
+      CurrencyId::Tdfy,
+      &Sunrise::account_id(),
+      200_000_000_000_000_000,
+    ));
+    ReferralRewards::<Test>::insert(AccountId(2), 1, 1_000_000_000_000);
+    OutstandingRewards::<Test>::put(1_000_000_000_000);
+
+    assert_ok!(Sunrise::try_claim_referral_rewards(&AccountId(2), 1));
+    assert_eq!(
+      Adapter::balance(CurrencyId::Tdfy, &AccountId(2)),
+      1_000_000_000_000
+    );
+    assert_eq!(Sunrise::outstanding_rewards(), 0);
+    assert!(!ReferralRewards::<Test>::contains_key(AccountId(2), 1));
+    System::assert_has_event(RuntimeEvent::Sunrise(Event::ReferralClaimed {
+      era_index: 1,
+      account_id: AccountId(2),
+      reward: 1_000_000_000_000,
+    }));
+
+    assert_noop!(
+      Sunrise::try_claim_referral_rewards(&AccountId(2), 1),
+      Error::<Test>::NoRewardsAvailable
+    );
+  });
+}
@@ -0,0 +1,74 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types returned by the sunrise queries (runtime API).
+
+use codec::{Decode, Encode};
+use frame_support::{inherent::Vec, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::FixedU128;
+use tidefi_primitives::{Balance, EraIndex};
+
+/// Sunrise reward of an account for an era.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SunriseEraReward<BlockNumber> {
+  pub era_index: EraIndex,
+  pub reward: Balance,
+  /// Block from which the reward can be claimed, estimated with the current era length
+  pub claimable_at: BlockNumber,
+  /// Block from which the unclaimed reward expires
+  pub expire_at: Option<BlockNumber>,
+}
+
+/// Sunrise pool tier.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SunrisePoolInfo {
+  pub id: u8,
+  /// Minimum TDFY value of the swap fee to be eligible to the pool
+  pub minimum_tdfy_value: Balance,
+  pub transactions_remaining: u32,
+  pub balance: Balance,
+  /// Rewards ratio of the swap fee
+  pub rebates: FixedU128,
+}
+
+/// Sunrise pool tiers and the balance left-over by the exhausted pools.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SunrisePools {
+  pub pools: Vec<SunrisePoolInfo>,
+  pub left_over: Balance,
+}
+
+/// Progress of the onboarding rebates.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OnboardingRebatesProgress {
+  pub initial_amount: Balance,
+  pub available_amount: Balance,
+  /// Rebates already paid out
+  pub distributed: Balance,
+  /// Rebates paid out at 100% before the degressive schedule starts
+  pub full_rebate_threshold: Balance,
+  /// Rebates left to be paid out at 100%
+  pub full_rebate_remaining: Balance,
+  /// Ratio applied to the next rebate
+  pub rebate_ratio: FixedU128,
+}
@@ -0,0 +1,83 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for `pallet_sunrise`.
+pub trait WeightInfo {
+   fn add_pool() -> Weight;
+   fn replenish_pool() -> Weight;
+   fn update_pool() -> Weight;
+   fn close_pool() -> Weight;
+   fn top_up_onboarding() -> Weight;
+   fn on_idle_rewards_expiration(b: u32) -> Weight;
+   fn register_referrer() -> Weight;
+   fn top_up_referral_budget() -> Weight;
+}
+
+/// Weights for `pallet_sunrise` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn add_pool() -> Weight {
+		Weight::from_ref_time(58_312_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn replenish_pool() -> Weight {
+		Weight::from_ref_time(56_947_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn update_pool() -> Weight {
+		Weight::from_ref_time(21_406_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	fn close_pool() -> Weight {
+		Weight::from_ref_time(57_830_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn top_up_onboarding() -> Weight {
+		Weight::from_ref_time(55_114_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	fn on_idle_rewards_expiration(b: u32) -> Weight {
+		Weight::from_ref_time(18_742_000)
+			// Standard Error: 12_000
+			.saturating_add(Weight::from_ref_time(21_336_000).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(b.into())))
+	}
+	fn register_referrer() -> Weight {
+		Weight::from_ref_time(24_518_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn top_up_referral_budget() -> Weight {
+		Weight::from_ref_time(55_873_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
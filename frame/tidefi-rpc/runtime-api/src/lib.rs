@@ -17,6 +17,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use codec::Codec;
 use frame_support::inherent::Vec;
+use pallet_asset_registry::AssetInfo;
+use pallet_fees::AccountFeeStatement;
+use pallet_security::{MaintenanceWindow, PausedCall};
+use pallet_sunrise::{OnboardingRebatesProgress, SunriseEraReward, SunrisePools};
+use pallet_tidefi_stake::{StakeProjection, StakingYield};
 use sp_runtime::DispatchError;
 use tidefi_primitives::{
   BalanceInfo, BlockNumber, CurrencyBalance, CurrencyId, CurrencyMetadata, Stake,
@@ -32,5 +37,14 @@ sp_api::decl_runtime_apis! {
             fn get_account_balances(account_id: AccountId) -> Result<Vec<(CurrencyId, CurrencyBalance<BalanceInfo>)>, DispatchError>;
             fn get_account_stakes(account_id: AccountId) -> Result<Vec<CurrenciesStake>, DispatchError>;
             fn get_assets() -> Result<Vec<CurrenciesMetadata>, DispatchError>;
+            fn get_asset_infos() -> Result<Vec<(CurrencyId, AssetInfo<Vec<u8>>)>, DispatchError>;
+            fn get_staking_yields() -> Result<Vec<StakingYield<BlockNumber>>, DispatchError>;
+            fn get_account_stakes_projection(account_id: AccountId) -> Result<Vec<StakeProjection<BlockNumber>>, DispatchError>;
+            fn get_account_sunrise_rewards(account_id: AccountId) -> Result<Vec<SunriseEraReward<BlockNumber>>, DispatchError>;
+            fn get_sunrise_pools() -> Result<SunrisePools, DispatchError>;
+            fn get_onboarding_rebates() -> Result<OnboardingRebatesProgress, DispatchError>;
+            fn get_account_fee_statements(account_id: AccountId) -> Result<Vec<AccountFeeStatement>, DispatchError>;
+            fn get_paused_calls() -> Result<Vec<PausedCall<Vec<u8>>>, DispatchError>;
+            fn get_scheduled_maintenance() -> Result<Option<MaintenanceWindow<BlockNumber, Vec<u8>>>, DispatchError>;
         }
 }
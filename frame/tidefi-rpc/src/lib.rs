@@ -21,7 +21,12 @@ use jsonrpsee::{
   proc_macros::rpc,
   types::{error::CallError, ErrorObject},
 };
+use pallet_asset_registry::AssetInfo;
+use pallet_fees::AccountFeeStatement;
+use pallet_security::{MaintenanceWindow, PausedCall};
+use pallet_sunrise::{OnboardingRebatesProgress, SunriseEraReward, SunrisePools};
 pub use pallet_tidefi_rpc_runtime_api::TidefiApi as TidefiRuntimeApi;
+use pallet_tidefi_stake::{StakeProjection, StakingYield};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::Block as BlockT;
@@ -38,6 +43,12 @@ pub trait TidefiApi<BlockHash, AccountId> {
     at: Option<BlockHash>,
   ) -> RpcResult<Vec<(CurrencyId, CurrencyMetadata<Vec<u8>>)>>;
 
+  #[method(name = "tidefi_getAssetInfos")]
+  fn get_asset_infos(
+    &self,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<(CurrencyId, AssetInfo<Vec<u8>>)>>;
+
   #[method(name = "tidefi_getAccountBalance")]
   fn get_account_balance(
     &self,
@@ -59,6 +70,45 @@ pub trait TidefiApi<BlockHash, AccountId> {
     account_id: AccountId,
     at: Option<BlockHash>,
   ) -> RpcResult<Vec<(CurrencyId, Stake<BalanceInfo, BlockNumber>)>>;
+
+  #[method(name = "tidefi_getStakingYields")]
+  fn get_staking_yields(&self, at: Option<BlockHash>) -> RpcResult<Vec<StakingYield<BlockNumber>>>;
+
+  #[method(name = "tidefi_getAccountStakesProjection")]
+  fn get_account_stakes_projection(
+    &self,
+    account_id: AccountId,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<StakeProjection<BlockNumber>>>;
+
+  #[method(name = "tidefi_getAccountSunriseRewards")]
+  fn get_account_sunrise_rewards(
+    &self,
+    account_id: AccountId,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<SunriseEraReward<BlockNumber>>>;
+
+  #[method(name = "tidefi_getSunrisePools")]
+  fn get_sunrise_pools(&self, at: Option<BlockHash>) -> RpcResult<SunrisePools>;
+
+  #[method(name = "tidefi_getOnboardingRebates")]
+  fn get_onboarding_rebates(&self, at: Option<BlockHash>) -> RpcResult<OnboardingRebatesProgress>;
+
+  #[method(name = "tidefi_getAccountFeeStatements")]
+  fn get_account_fee_statements(
+    &self,
+    account_id: AccountId,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<AccountFeeStatement>>;
+
+  #[method(name = "tidefi_getPausedCalls")]
+  fn get_paused_calls(&self, at: Option<BlockHash>) -> RpcResult<Vec<PausedCall<Vec<u8>>>>;
+
+  #[method(name = "tidefi_getScheduledMaintenance")]
+  fn get_scheduled_maintenance(
+    &self,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Option<MaintenanceWindow<BlockNumber, Vec<u8>>>>;
 }
 
 /// A struct that implements the [`TidefiApi`].
@@ -153,6 +203,21 @@ where
       .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
   }
 
+  fn get_asset_infos(
+    &self,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Vec<(CurrencyId, AssetInfo<Vec<u8>>)>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_asset_infos(at)
+      .map_err(|err| Error::Call(format!("{err:?}")))?
+      .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
+  }
+
   fn get_account_balances(
     &self,
     account_id: AccountId,
@@ -168,4 +233,123 @@ where
       .map_err(|err| Error::Call(format!("{err:?}")))?
       .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
   }
+
+  fn get_staking_yields(
+    &self,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Vec<StakingYield<BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_staking_yields(at)
+      .map_err(|err| Error::Call(format!("{err:?}")))?
+      .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
+  }
+
+  fn get_account_stakes_projection(
+    &self,
+    account_id: AccountId,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Vec<StakeProjection<BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_account_stakes_projection(at, account_id)
+      .map_err(|err| Error::Call(format!("{err:?}")))?
+      .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
+  }
+  fn get_account_sunrise_rewards(
+    &self,
+    account_id: AccountId,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Vec<SunriseEraReward<BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_account_sunrise_rewards(at, account_id)
+      .map_err(|err| Error::Call(format!("{err:?}")))?
+      .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
+  }
+
+  fn get_sunrise_pools(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<SunrisePools> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_sunrise_pools(at)
+      .map_err(|err| Error::Call(format!("{err:?}")))?
+      .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
+  }
+
+  fn get_onboarding_rebates(
+    &self,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<OnboardingRebatesProgress> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_onboarding_rebates(at)
+      .map_err(|err| Error::Call(format!("{err:?}")))?
+      .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
+  }
+
+  fn get_account_fee_statements(
+    &self,
+    account_id: AccountId,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Vec<AccountFeeStatement>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_account_fee_statements(at, account_id)
+      .map_err(|err| Error::Call(format!("{err:?}")))?
+      .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
+  }
+
+  fn get_paused_calls(
+    &self,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Vec<PausedCall<Vec<u8>>>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_paused_calls(at)
+      .map_err(|err| Error::Call(format!("{err:?}")))?
+      .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
+  }
+
+  fn get_scheduled_maintenance(
+    &self,
+    at: Option<<Block as BlockT>::Hash>,
+  ) -> RpcResult<Option<MaintenanceWindow<BlockNumber, Vec<u8>>>> {
+    let api = self.client.runtime_api();
+    let at = at.unwrap_or(
+      // If the block hash is not supplied assume the best block.
+      self.client.info().best_hash,
+    );
+    api
+      .get_scheduled_maintenance(at)
+      .map_err(|err| Error::Call(format!("{err:?}")))?
+      .map_err(|err| Error::Dispatch(format!("{err:?}")).into())
+  }
 }
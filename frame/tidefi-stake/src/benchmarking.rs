@@ -22,14 +22,19 @@ use frame_support::{
   traits::{fungibles::Mutate, Get, OnIdle},
 };
 use frame_system::RawOrigin;
-use sp_runtime::traits::{Saturating, Zero};
+use sp_runtime::{
+  traits::{Saturating, Zero},
+  Percent,
+};
 use sp_std::prelude::*;
-use tidefi_primitives::{pallet::StakingExt, CurrencyId, SessionIndex};
+use tidefi_primitives::{pallet::SunriseExt, CurrencyId, StakeCurrencyMeta};
 
 const USER_SEED: u32 = 0;
 const INITIAL_AMOUNT: u128 = 500_000_000;
 const IA_MULTIPLIER: u32 = 2;
 const TEST_TOKEN: u32 = 2;
+const RECEIPT_TOKEN: u32 = 3;
+const TDFY_PER_TEST_TOKEN: u128 = 1_000_000_000_000_000;
 const FIFTEEN_DAYS: u32 = 14400 * 15;
 
 fn assert_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
@@ -51,8 +56,14 @@ fn create_stake_batch<T: Config>(batch_size: u32) -> Vec<T::AccountId> {
     .collect()
 }
 
-fn create_session_batch<T: Config>(batch_size: u32) {
-  (0..batch_size).for_each(|i| trigger_on_session_end::<T>(i.into()))
+fn enable_liquid_staking<T: Config>() {
+  LiquidStaking::<T>::insert(
+    CurrencyId::Wrapped(TEST_TOKEN),
+    LiquidStakingMeta {
+      receipt_currency_id: CurrencyId::Wrapped(RECEIPT_TOKEN),
+      reward: Percent::from_parts(2),
+    },
+  );
 }
 
 fn fund_and_stake_account<T: Config>(account: &T::AccountId) {
@@ -66,24 +77,8 @@ fn fund_and_stake_account<T: Config>(account: &T::AccountId) {
     RawOrigin::Signed(account.clone()).into(),
     CurrencyId::Wrapped(TEST_TOKEN),
     INITIAL_AMOUNT,
-    FIFTEEN_DAYS.into()
-  ));
-}
-
-fn trigger_on_session_end<T: Config>(session_index: SessionIndex) {
-  let dummy_fee_account = frame_benchmarking::account::<T::AccountId>("fees_pallet", 1, USER_SEED);
-  assert_ok!(<T as Config>::CurrencyTidefi::mint_into(
-    CurrencyId::Wrapped(TEST_TOKEN),
-    &dummy_fee_account,
-    INITIAL_AMOUNT.saturating_mul(100),
-  ));
-  assert_ok!(Pallet::<T>::on_session_end(
-    session_index,
-    vec![(
-      CurrencyId::Wrapped(TEST_TOKEN),
-      INITIAL_AMOUNT.saturating_mul(100),
-    )],
-    dummy_fee_account,
+    FIFTEEN_DAYS.into(),
+    false
   ));
 }
 
@@ -93,76 +88,155 @@ benchmarks! {
   stake {
       let caller: T::AccountId = whitelisted_caller();
       fund_and_stake_account::<T>(&caller);
-   }: _(RawOrigin::Signed(caller.clone()), CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, FIFTEEN_DAYS.into())
+   }: _(RawOrigin::Signed(caller.clone()), CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, FIFTEEN_DAYS.into(), true)
 
    unstake {
       let caller: T::AccountId = whitelisted_caller();
       let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
       <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
 
-      let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, FIFTEEN_DAYS.into()).expect("Unable to stake asset");
-   }: _(RawOrigin::Signed(caller), stake_id, true)
+      let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, FIFTEEN_DAYS.into(), false).expect("Unable to stake asset");
+
+      // pay the fee in TDFY
+      assert_ok!(<T as Config>::Sunrise::register_exchange_rate(vec![(TEST_TOKEN, TDFY_PER_TEST_TOKEN)]));
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Tdfy, &caller, TDFY_PER_TEST_TOKEN).expect("Unable to mint token");
+   }: _(RawOrigin::Signed(caller.clone()), stake_id, true, true)
+   verify {
+    assert_eq!(QueueUnstake::<T>::count(), 1);
+   }
 
-   on_idle_compound_finalize {
-    let a = 1;
+  on_idle_unstake {
     let b in 1 .. T::BatchSize::get();
 
-    create_stake_batch::<T>(a);
-    create_session_batch::<T>(b);
+    create_stake_batch::<T>(b).iter().for_each(|account_id| {
+      let request_id = AccountStakes::<T>::get(account_id)
+      .first()
+      .expect("created previously")
+      .unique_id;
 
-    assert_eq!(QueueCompound::<T>::count(), a);
-    assert_eq!(PendingStoredSessions::<T>::count(), b);
+      assert_ok!(Pallet::<T>::unstake(RawOrigin::Signed(account_id.clone()).into(), request_id, true, false));
+    });
 
-    on_idle_full_block::<T>();
-    assert_eq!(QueueCompound::<T>::count(), 0);
+    assert_eq!(QueueUnstake::<T>::count(), b);
+    let (_, expected_end_block)  = QueueUnstake::<T>::iter_values().next().unwrap();
+    pallet_security::CurrentBlockCount::<T>::put(expected_end_block.saturating_add(1_u32.into()));
   }
   : {
     on_idle_full_block::<T>();
   }
   verify {
-   assert_event::<T>(Event::<T>::BatchFinished { size: b, kind: BatchType::Compound }.into());
-   assert_eq!(PendingStoredSessions::<T>::count(), 0);
+   assert_event::<T>(Event::<T>::BatchFinished { size: b, kind: BatchType::Unstake }.into());
+   assert_eq!(QueueUnstake::<T>::count(), 0);
   }
 
-  on_idle_compound {
+  on_idle_expiration {
     let b in 1 .. T::BatchSize::get();
 
     create_stake_batch::<T>(b);
-    trigger_on_session_end::<T>(1);
-
-    assert_eq!(QueueCompound::<T>::count(), b);
+    assert_eq!(CounterForQueueExpiration::<T>::get(), b);
+    pallet_security::CurrentBlockCount::<T>::put(T::BlockNumber::from(FIFTEEN_DAYS).saturating_add(1_u32.into()));
   }
   : {
     on_idle_full_block::<T>();
   }
   verify {
-   assert_event::<T>(Event::<T>::BatchCompound { size: b }.into());
-   assert_eq!(QueueCompound::<T>::count(), 0);
-   assert_eq!(PendingStoredSessions::<T>::count(), 1);
+   assert_event::<T>(Event::<T>::BatchFinished { size: b, kind: BatchType::Expiration }.into());
+   assert_eq!(CounterForQueueExpiration::<T>::get(), 0);
   }
 
-  on_idle_unstake {
-    let b in 1 .. T::BatchSize::get();
+  add_staking_period {
+    let duration: T::BlockNumber = (FIFTEEN_DAYS * 4).into();
+    StakingPeriodRewards::<T>::mutate(|periods| {
+      periods.retain(|(iter_duration, _)| *iter_duration != duration)
+    });
+  }: _(RawOrigin::Root, duration, Percent::from_parts(10))
+  verify {
+   assert_event::<T>(Event::<T>::StakingPeriodAdded { duration, reward: Percent::from_parts(10) }.into());
+  }
 
-    create_stake_batch::<T>(b).iter().for_each(|account_id| {
-      let request_id = AccountStakes::<T>::get(account_id)
-      .first()
-      .expect("created previously")
-      .unique_id;
+  retire_staking_period {
+    let duration: T::BlockNumber = FIFTEEN_DAYS.into();
+  }: _(RawOrigin::Root, duration)
+  verify {
+   assert_event::<T>(Event::<T>::StakingPeriodRetired { duration }.into());
+  }
 
-      assert_ok!(Pallet::<T>::unstake(RawOrigin::Signed(account_id.clone()).into(), request_id, true));
-    });
+  update_staking_period_reward {
+    let duration: T::BlockNumber = FIFTEEN_DAYS.into();
+  }: _(RawOrigin::Root, duration, Percent::from_parts(10))
+  verify {
+   assert_event::<T>(Event::<T>::StakingPeriodRewardUpdated { duration, reward: Percent::from_parts(10) }.into());
+  }
 
-    assert_eq!(QueueUnstake::<T>::count(), b);
-    let (_, expected_end_block)  = QueueUnstake::<T>::iter_values().next().unwrap();
-    pallet_security::CurrentBlockCount::<T>::put(expected_end_block.saturating_add(1_u32.into()));
+  set_staking_currency_meta {
+    let meta = StakeCurrencyMeta { minimum_amount: 1, maximum_amount: INITIAL_AMOUNT };
+  }: _(RawOrigin::Root, CurrencyId::Wrapped(TEST_TOKEN), Some(meta.clone()))
+  verify {
+   assert_event::<T>(Event::<T>::StakingCurrencyMetaUpdated { currency_id: CurrencyId::Wrapped(TEST_TOKEN), meta: Some(meta) }.into());
   }
-  : {
-    on_idle_full_block::<T>();
+
+  set_unstake_fee {
+  }: _(RawOrigin::Root, Percent::from_parts(2))
+  verify {
+   assert_event::<T>(Event::<T>::UnstakeFeeUpdated { fee: Percent::from_parts(2) }.into());
   }
+
+  unstake_partial {
+    let caller: T::AccountId = whitelisted_caller();
+    let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+    <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
+
+    let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, FIFTEEN_DAYS.into(), false).expect("Unable to stake asset");
+  }: _(RawOrigin::Signed(caller.clone()), stake_id, INITIAL_AMOUNT / 2)
   verify {
-   assert_event::<T>(Event::<T>::BatchFinished { size: b, kind: BatchType::Unstake }.into());
-   assert_eq!(QueueCompound::<T>::count(), 0);
+   assert_eq!(AccountStakes::<T>::get(caller).first().expect("created previously").initial_balance, INITIAL_AMOUNT / 2);
+  }
+
+  add_to_stake {
+    let caller: T::AccountId = whitelisted_caller();
+    let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
+    <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
+
+    let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, FIFTEEN_DAYS.into(), false).expect("Unable to stake asset");
+  }: _(RawOrigin::Signed(caller.clone()), stake_id, INITIAL_AMOUNT)
+  verify {
+   assert_event::<T>(Event::<T>::StakeIncreased { request_id: stake_id, account_id: caller, currency_id: CurrencyId::Wrapped(TEST_TOKEN), amount: INITIAL_AMOUNT }.into());
+  }
+
+  set_auto_renew {
+    let caller: T::AccountId = whitelisted_caller();
+    <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, INITIAL_AMOUNT).expect("Unable to mint token");
+
+    let stake_id = Pallet::<T>::add_account_stake(&caller, CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT, FIFTEEN_DAYS.into(), false).expect("Unable to stake asset");
+  }: _(RawOrigin::Signed(caller.clone()), stake_id, true)
+  verify {
+   assert_event::<T>(Event::<T>::AutoRenewUpdated { request_id: stake_id, account_id: caller, auto_renew: true }.into());
+  }
+
+  set_liquid_staking {
+    let meta = LiquidStakingMeta { receipt_currency_id: CurrencyId::Wrapped(RECEIPT_TOKEN), reward: Percent::from_parts(2) };
+  }: _(RawOrigin::Root, CurrencyId::Wrapped(TEST_TOKEN), Some(meta.clone()))
+  verify {
+   assert_event::<T>(Event::<T>::LiquidStakingUpdated { currency_id: CurrencyId::Wrapped(TEST_TOKEN), meta: Some(meta) }.into());
+  }
+
+  stake_liquid {
+    let caller: T::AccountId = whitelisted_caller();
+    <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, INITIAL_AMOUNT).expect("Unable to mint token");
+    enable_liquid_staking::<T>();
+  }: _(RawOrigin::Signed(caller.clone()), CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT)
+  verify {
+   assert_event::<T>(Event::<T>::LiquidStaked { account_id: caller, currency_id: CurrencyId::Wrapped(TEST_TOKEN), amount: INITIAL_AMOUNT, receipts: INITIAL_AMOUNT }.into());
+  }
+
+  unstake_liquid {
+    let caller: T::AccountId = whitelisted_caller();
+    <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, INITIAL_AMOUNT).expect("Unable to mint token");
+    enable_liquid_staking::<T>();
+    assert_ok!(Pallet::<T>::stake_liquid(RawOrigin::Signed(caller.clone()).into(), CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT));
+  }: _(RawOrigin::Signed(caller.clone()), CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT)
+  verify {
+   assert!(SharesPools::<T>::get(CurrencyId::Wrapped(TEST_TOKEN), T::BlockNumber::from(FIFTEEN_DAYS)).total_shares.is_zero());
   }
 }
 
@@ -25,13 +25,34 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod types;
 pub mod weights;
+pub use types::*;
 pub use weights::*;
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
 use frame_system::ensure_root;
+use sp_std::vec::Vec;
+use tidefi_primitives::{Balance, CurrencyId, SessionIndex};
+
+/// Operator of the staking pallet, and distribution of the session fees to the stakers.
+pub trait StakingOperator<AccountId> {
+  /// Account receiving the operator share of the session fees.
+  fn operator_account() -> AccountId;
+
+  /// Distribute the stakers share of the session fees held by `fees_account_id` to the staking
+  /// pools, the fees not rewarded by the staking periods go to the operator.
+  ///
+  /// Returns the amount distributed to the pools, by currency.
+  fn distribute_session_rewards(
+    session_index: SessionIndex,
+    session_fees: Vec<(CurrencyId, Balance)>,
+    fees_account_id: AccountId,
+  ) -> Vec<(CurrencyId, Balance)>;
+}
 
 pub(crate) const LOG_TARGET: &str = "tidefi::staking";
 
@@ -61,22 +82,74 @@ pub mod pallet {
   };
   use frame_system::pallet_prelude::*;
   use sp_runtime::{
-    traits::{AccountIdConversion, Saturating},
-    ArithmeticError, Percent, Perquintill,
+    helpers_128bit::multiply_by_rational_with_rounding,
+    traits::{AccountIdConversion, SaturatedConversion, Saturating, Zero},
+    ArithmeticError, Percent, Perquintill, Rounding,
   };
-  use sp_std::vec;
   use tidefi_primitives::{
-    pallet::{AssetRegistryExt, SecurityExt, StakingExt},
+    pallet::{AssetRegistryExt, SecurityExt, StakingExt, SunriseExt},
     Balance, BalanceInfo, CurrencyId, Hash, SessionIndex, Stake, StakeCurrencyMeta, StakeStatus,
   };
 
   /// The current storage version.
-  const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+  const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
 
   #[derive(Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug)]
   pub enum BatchType {
     Unstake,
-    Compound,
+    Expiration,
+  }
+
+  /// Shares of the stakes of a currency for a staking period.
+  ///
+  /// The session rewards are added to `total_balance`, the value of a stake is its part of the
+  /// shares, so the rewards are distributed without iterating the stakes.
+  #[derive(Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug, Default)]
+  pub struct SharesPool {
+    /// Total shares minted for the stakes
+    pub total_shares: Balance,
+    /// Total value of the stakes, including the rewards
+    pub total_balance: Balance,
+  }
+
+  impl SharesPool {
+    /// Number of shares to mint for `amount`, one share per unit when the pool is empty.
+    pub fn shares_for(&self, amount: Balance) -> Option<Balance> {
+      if self.total_shares.is_zero() || self.total_balance.is_zero() {
+        Some(amount)
+      } else {
+        multiply_by_rational_with_rounding(
+          amount,
+          self.total_shares,
+          self.total_balance,
+          Rounding::Down,
+        )
+      }
+    }
+
+    /// Current value of `shares`.
+    pub fn balance_of(&self, shares: Balance) -> Balance {
+      if self.total_shares.is_zero() {
+        return Zero::zero();
+      }
+      multiply_by_rational_with_rounding(
+        shares,
+        self.total_balance,
+        self.total_shares,
+        Rounding::Down,
+      )
+      .unwrap_or_default()
+    }
+  }
+
+  /// Liquid staking of a currency, the stakers receive transferable receipt tokens instead of a
+  /// locked stake.
+  #[derive(Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug)]
+  pub struct LiquidStakingMeta {
+    /// Currency minted to the stakers, one receipt token for each share of the liquid shares pool
+    pub receipt_currency_id: CurrencyId,
+    /// Reward of the liquid shares pool, as the reward of a staking period
+    pub reward: Percent,
   }
 
   #[pallet::config]
@@ -101,6 +174,14 @@ pub mod pallet {
     #[pallet::constant]
     type BlocksForceUnstake: Get<Self::BlockNumber>;
 
+    /// Number of session rewards kept for each shares pool, to compute the staking yield.
+    #[pallet::constant]
+    type SessionRewardsHistoryCap: Get<u32>;
+
+    /// Number of blocks in a year, to annualise the staking yield.
+    #[pallet::constant]
+    type BlocksPerYear: Get<Self::BlockNumber>;
+
     /// Batch size.
     ///
     /// This many accounts and unstake are processed in each on_idle` request.
@@ -115,6 +196,12 @@ pub mod pallet {
     /// Security traits
     type Security: SecurityExt<Self::AccountId, Self::BlockNumber>;
 
+    /// Sunrise traits
+    type Sunrise: SunriseExt<Self::AccountId, Self::BlockNumber>;
+
+    /// The origin which may update the staking periods, currency limits and unstake fee
+    type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + Mutate<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
@@ -137,6 +224,13 @@ pub mod pallet {
   pub type StakingPeriodRewards<T: Config> =
     StorageValue<_, BoundedVec<(T::BlockNumber, Percent), T::StakingRewardCap>, ValueQuery>;
 
+  /// Staking periods retired by the council, no new stake can be created with these durations
+  /// but the existing stakes keep being rewarded at the rate they had when retired
+  #[pallet::storage]
+  #[pallet::getter(fn retired_staking_rewards)]
+  pub type RetiredStakingPeriodRewards<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::BlockNumber, Percent>;
+
   /// Staking metadata defined by the council (minimum and maximum stake amount)
   #[pallet::storage]
   #[pallet::getter(fn staking_meta)]
@@ -148,38 +242,43 @@ pub mod pallet {
   #[pallet::getter(fn unstake_fee)]
   pub type UnstakeFee<T: Config> = StorageValue<_, Percent, ValueQuery>;
 
-  /// The last session we should compound the account interests.
+  /// The last session where the fees have been distributed to the staking pools.
   #[pallet::storage]
   #[pallet::getter(fn interest_compound_last_session)]
   pub type InterestCompoundLastSession<T: Config> = StorageValue<_, SessionIndex, ValueQuery>;
 
-  /// Map from all pending stored sessions.
-  // When all stake that are bounded for this sessions are compounded, they got removed from the map.
-  // When the map is empty, the `do_next_compound_interest_operation` is not triggered.
-  // SessionIndex (amount distributed)
+  /// Shares pools by currency and staking period
   #[pallet::storage]
-  #[pallet::getter(fn pending_sessions)]
-  pub type PendingStoredSessions<T: Config> = CountedStorageMap<
+  #[pallet::getter(fn shares_pool)]
+  pub type SharesPools<T: Config> = StorageDoubleMap<
     _,
     Blake2_128Concat,
-    SessionIndex,
-    BoundedVec<(CurrencyId, Balance), T::StakingRewardCap>,
+    CurrencyId,
+    Blake2_128Concat,
+    T::BlockNumber,
+    SharesPool,
+    ValueQuery,
   >;
 
-  /// The total fees for the session.
-  /// If total hasn't been set or has been removed then 0 stake is returned.
+  /// Block and reward rate of the last sessions of each shares pool, the rate is the part of the
+  /// pool balance distributed as reward at the end of the session
   #[pallet::storage]
-  #[pallet::getter(fn session_total_fees)]
-  pub type SessionTotalFees<T: Config> = StorageDoubleMap<
+  #[pallet::getter(fn session_rewards_history)]
+  pub type SessionRewardsHistory<T: Config> = StorageDoubleMap<
     _,
     Blake2_128Concat,
-    SessionIndex,
-    Blake2_128Concat,
     CurrencyId,
-    Balance,
+    Blake2_128Concat,
+    T::BlockNumber,
+    BoundedVec<(T::BlockNumber, Perquintill), T::SessionRewardsHistoryCap>,
     ValueQuery,
   >;
 
+  /// Shares owned by each stake
+  #[pallet::storage]
+  #[pallet::getter(fn stake_shares)]
+  pub type StakeShares<T: Config> = StorageMap<_, Blake2_128Concat, Hash, Balance, ValueQuery>;
+
   /// Account staking by CurrencyID
   #[pallet::storage]
   #[pallet::getter(fn account_stakes)]
@@ -191,14 +290,42 @@ pub mod pallet {
     ValueQuery,
   >;
 
-  #[pallet::storage]
-  pub type QueueCompound<T: Config> =
-    CountedStorageMap<_, Blake2_128Concat, T::AccountId, SessionIndex>;
-
   #[pallet::storage]
   pub type QueueUnstake<T: Config> =
     CountedStorageMap<_, Blake2_128Concat, Hash, (T::AccountId, T::BlockNumber)>;
 
+  /// Active stakes by expiration block, renewed or paid out by `on_idle` once expired
+  #[pallet::storage]
+  pub type QueueExpiration<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Blake2_128Concat, Hash, T::AccountId>;
+
+  /// Number of stakes in the expiration queue
+  #[pallet::storage]
+  pub type CounterForQueueExpiration<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+  /// Next block of the expiration queue checked by `on_idle`, no stake expires before it
+  #[pallet::storage]
+  #[pallet::getter(fn next_expiration_block)]
+  pub type NextExpirationBlock<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+  /// Stakes renewed for a new term when they expire
+  #[pallet::storage]
+  #[pallet::getter(fn auto_renew)]
+  pub type AutoRenewStakes<T: Config> = StorageMap<_, Blake2_128Concat, Hash, bool, ValueQuery>;
+
+  /// Liquid staking enabled by the council, the receipt currency and staking period of each
+  /// staked currency
+  #[pallet::storage]
+  #[pallet::getter(fn liquid_staking)]
+  pub type LiquidStaking<T: Config> =
+    StorageMap<_, Blake2_128Concat, CurrencyId, LiquidStakingMeta>;
+
+  /// Shares pool of the liquid stakes of each currency, kept apart from the staking periods pools
+  #[pallet::storage]
+  #[pallet::getter(fn liquid_shares_pool)]
+  pub type LiquidSharesPools<T: Config> =
+    StorageMap<_, Blake2_128Concat, CurrencyId, SharesPool, ValueQuery>;
+
   /// Operator account
   #[pallet::storage]
   #[pallet::getter(fn operator_account_id)]
@@ -221,8 +348,6 @@ pub mod pallet {
         // 1%
         unstake_fee: Percent::from_parts(1),
         staking_periods: vec![
-          // FIXME: Remove the 15 minutes after our tests
-          (150_u32.into(), Percent::from_parts(1)),
           ((14400_u32 * 15_u32).into(), Percent::from_parts(2)),
           ((14400_u32 * 30_u32).into(), Percent::from_parts(3)),
           ((14400_u32 * 60_u32).into(), Percent::from_parts(4)),
@@ -279,10 +404,12 @@ pub mod pallet {
       amount: Balance,
       duration: T::BlockNumber,
     },
-    /// The assets unstaking has been queued
+    /// The assets unstaking has been queued, `fee` has been paid in `fee_currency_id`
     UnstakeQueued {
       request_id: Hash,
       account_id: T::AccountId,
+      fee_currency_id: CurrencyId,
+      fee: Balance,
     },
     /// The assets get `unstaked` successfully
     Unstaked {
@@ -292,16 +419,85 @@ pub mod pallet {
       initial_balance: Balance,
       final_balance: Balance,
     },
-    /// Batch finished, number of session or unstaking processed.
+    /// Batch finished, number of unstaking or expired stakes processed.
     BatchFinished { size: u32, kind: BatchType },
-    /// Batch compound, number of accounts
-    BatchCompound { size: u32 },
-    /// Session finished
+    /// Session fees distributed to the staking pools, the remaining goes to the operator
     SessionFinished {
       session_index: SessionIndex,
       pool: Vec<(CurrencyId, Balance)>,
       operator: Vec<(CurrencyId, Balance)>,
     },
+    /// New staking period available
+    StakingPeriodAdded {
+      duration: T::BlockNumber,
+      reward: Percent,
+    },
+    /// Staking period retired, existing stakes are still rewarded until they expire
+    StakingPeriodRetired { duration: T::BlockNumber },
+    /// Staking period reward updated
+    StakingPeriodRewardUpdated {
+      duration: T::BlockNumber,
+      reward: Percent,
+    },
+    /// Staking limits of the currency updated, `None` if the limits have been removed
+    StakingCurrencyMetaUpdated {
+      currency_id: CurrencyId,
+      meta: Option<StakeCurrencyMeta<Balance>>,
+    },
+    /// Unstake fee updated
+    UnstakeFeeUpdated { fee: Percent },
+    /// Operator account updated
+    OperatorAccountUpdated { account_id: T::AccountId },
+    /// Part of the stake has been unstaked, `fee` is deducted from `amount` if the stake isn't
+    /// expired
+    PartiallyUnstaked {
+      request_id: Hash,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+      fee: Balance,
+    },
+    /// The assets have been added to the stake
+    StakeIncreased {
+      request_id: Hash,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+    },
+    /// The stake expired and has been renewed for a new term
+    StakeRenewed {
+      request_id: Hash,
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      principal: Balance,
+      duration: T::BlockNumber,
+    },
+    /// Auto-renew of the stake updated
+    AutoRenewUpdated {
+      request_id: Hash,
+      account_id: T::AccountId,
+      auto_renew: bool,
+    },
+    /// Liquid staking of the currency updated, `None` if the liquid staking has been disabled
+    LiquidStakingUpdated {
+      currency_id: CurrencyId,
+      meta: Option<LiquidStakingMeta>,
+    },
+    /// The assets get staked and `receipts` receipt tokens have been minted
+    LiquidStaked {
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+      receipts: Balance,
+    },
+    /// The receipt tokens have been burned and `amount` unstaked, `fee` is deducted from `amount`
+    LiquidUnstaked {
+      account_id: T::AccountId,
+      currency_id: CurrencyId,
+      receipts: Balance,
+      amount: Balance,
+      fee: Balance,
+    },
   }
 
   // Errors inform users that something went wrong.
@@ -329,6 +525,22 @@ pub mod pallet {
     AmountTooSmall,
     /// The staked amount is above the maximum stake amount for this currency.
     AmountTooLarge,
+    /// Staking period with this duration already exist.
+    StakingPeriodAlreadyExists,
+    /// Staking periods cap reached.
+    StakingPeriodsOverflow,
+    /// The minimum stake amount is above the maximum stake amount.
+    InvalidStakingCurrencyMeta,
+    /// The stake is queued for unstaking.
+    StakeIsPendingUnlock,
+    /// The staking period of the stake is over.
+    StakeIsExpired,
+    /// Liquid staking is not enabled for this currency.
+    LiquidStakingNotEnabled,
+    /// The receipt currency can't be minted for this currency.
+    InvalidReceiptCurrency,
+    /// Receipt tokens of the currency are still in circulation.
+    LiquidStakingInUse,
   }
 
   #[pallet::hooks]
@@ -336,25 +548,23 @@ pub mod pallet {
     /// Try to compute when chain is idle
     fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
       let unstake_queue_size = QueueUnstake::<T>::count();
-      let compound_queue_size = QueueCompound::<T>::count();
-
-      let do_next_compound_interest_operation_weight =
-        <T as Config>::WeightInfo::on_idle_compound(compound_queue_size).saturating_add(
-          T::DbWeight::get().reads_writes(compound_queue_size.into(), compound_queue_size.into()),
-        );
 
       let do_next_unstake_operation_weight =
         <T as Config>::WeightInfo::on_idle_unstake(unstake_queue_size).saturating_add(
           T::DbWeight::get().reads_writes(unstake_queue_size.into(), unstake_queue_size.into()),
         );
 
-      if remaining_weight.any_gt(do_next_compound_interest_operation_weight)
-        && PendingStoredSessions::<T>::count() > 0
-      {
-        Self::do_on_idle_compound(remaining_weight);
-      } else if remaining_weight.any_gt(do_next_unstake_operation_weight) && unstake_queue_size > 0
-      {
+      let batch_size = T::BatchSize::get();
+      let do_next_expiration_operation_weight =
+        <T as Config>::WeightInfo::on_idle_expiration(batch_size)
+          .saturating_add(T::DbWeight::get().reads_writes(batch_size.into(), batch_size.into()));
+      let has_expired_stakes = CounterForQueueExpiration::<T>::get() > 0
+        && Self::next_expiration_block() <= T::Security::get_current_block_count();
+
+      if remaining_weight.any_gt(do_next_unstake_operation_weight) && unstake_queue_size > 0 {
         Self::do_on_idle_unstake(remaining_weight);
+      } else if remaining_weight.any_gt(do_next_expiration_operation_weight) && has_expired_stakes {
+        Self::do_on_idle_expiration(remaining_weight);
       }
 
       remaining_weight
@@ -368,6 +578,7 @@ pub mod pallet {
     /// - `currency_id`: The currency to stake
     /// - `amount`: The amount to stake
     /// - `duration`: The duration is in numbers of blocks. (blocks are ~6seconds)
+    /// - `auto_renew`: Renew the stake for a new term when it expires, instead of paying it out
     ///
     /// Emits `Staked` event when successful.
     ///
@@ -379,6 +590,7 @@ pub mod pallet {
       currency_id: CurrencyId,
       amount: Balance,
       duration: T::BlockNumber,
+      auto_renew: bool,
     ) -> DispatchResultWithPostInfo {
       // 1. Make sure the transaction is signed
       let account_id = ensure_signed(origin)?;
@@ -405,7 +617,7 @@ pub mod pallet {
 
       // create unique hash
       let unique_stake_request_id =
-        Self::add_account_stake(&account_id, currency_id, amount, duration)?;
+        Self::add_account_stake(&account_id, currency_id, amount, duration, auto_renew)?;
 
       // 4. Emit event on chain
       Self::deposit_event(Event::<T>::Staked {
@@ -423,6 +635,8 @@ pub mod pallet {
     ///
     /// - `stake_id`: Unique Stake ID
     /// - `force_unstake`: Unstake with extra fees, even if the staking is not expired
+    /// - `pay_fee_in_tdfy`: Pay the extra fees in TDFY at the oracle rate, the fees are paid in
+    ///   the staked currency if there is no rate
     ///
     /// Emits `Unstaked` event when successful.
     ///
@@ -433,6 +647,7 @@ pub mod pallet {
       origin: OriginFor<T>,
       stake_id: Hash,
       force_unstake: bool,
+      pay_fee_in_tdfy: bool,
     ) -> DispatchResultWithPostInfo {
       // 1. Make sure the transaction is signed
       let account_id = ensure_signed(origin)?;
@@ -447,32 +662,37 @@ pub mod pallet {
 
       if staking_is_expired {
         // we can process to unstaking immediately
-        Self::do_process_unstake(&account_id, stake_id)?;
+        let final_balance = Self::do_process_unstake(&account_id, stake_id)?;
         Self::deposit_event(Event::<T>::Unstaked {
           request_id: stake_id,
           account_id,
           currency_id: stake.currency_id,
           initial_balance: stake.initial_balance,
-          final_balance: stake.principal,
+          final_balance,
         });
       } else {
         ensure!(force_unstake, Error::<T>::UnstakingNotReady);
 
-        // FIXME: Validate not already queued
+        // make sure the stake isn't already queued
+        ensure!(
+          matches!(stake.status, StakeStatus::Staked) && !QueueUnstake::<T>::contains_key(stake_id),
+          Error::<T>::StakeIsPendingUnlock
+        );
 
         // we should add to unstaking queue and take immeditately the extra fees
         // for the queue storage
 
         // take the fee
-        // FIXME: would be great to convert to TDFY
-        let unstaking_fee = Self::unstake_fee() * stake.initial_balance;
-        T::CurrencyTidefi::can_withdraw(stake.currency_id, &account_id, unstaking_fee)
+        let (fee_currency_id, unstaking_fee) =
+          Self::unstake_fee_for(stake.currency_id, stake.initial_balance, pay_fee_in_tdfy);
+        T::CurrencyTidefi::can_withdraw(fee_currency_id, &account_id, unstaking_fee)
           .into_result()
           .map_err(|_| Error::<T>::InsufficientBalance)?;
 
         let expected_block_end =
           T::Security::get_current_block_count().saturating_add(T::BlocksForceUnstake::get());
         QueueUnstake::<T>::insert(stake_id, (account_id.clone(), expected_block_end));
+        Self::dequeue_expiration(&stake);
 
         // update `AccountStakes` status
         AccountStakes::<T>::try_mutate(account_id.clone(), |stakes| -> DispatchResult {
@@ -486,7 +706,7 @@ pub mod pallet {
 
         // Pay unstaking fees to operator account instead of staking pool
         T::CurrencyTidefi::transfer(
-          stake.currency_id,
+          fee_currency_id,
           &account_id,
           &Self::operator_account(),
           unstaking_fee,
@@ -497,6 +717,8 @@ pub mod pallet {
         Self::deposit_event(Event::<T>::UnstakeQueued {
           request_id: stake_id,
           account_id,
+          fee_currency_id,
+          fee: unstaking_fee,
         });
       }
 
@@ -512,361 +734,944 @@ pub mod pallet {
       new_operator_account_id: T::AccountId,
     ) -> DispatchResultWithPostInfo {
       ensure_root(origin)?;
-      OperatorAccountId::<T>::put(new_operator_account_id);
-
-      Ok(().into())
-    }
-  }
+      OperatorAccountId::<T>::put(new_operator_account_id.clone());
 
-  // helper functions (not dispatchable)
-  impl<T: Config> Pallet<T> {
-    pub fn account_id() -> T::AccountId {
-      <T as pallet::Config>::StakePalletId::get().into_account_truncating()
-    }
+      Self::deposit_event(Event::<T>::OperatorAccountUpdated {
+        account_id: new_operator_account_id,
+      });
 
-    pub fn operator_account() -> T::AccountId {
-      match Self::operator_account_id() {
-        Some(account_id) => account_id,
-        None => Self::account_id(),
-      }
+      Ok(().into())
     }
 
-    pub fn add_account_stake(
-      account_id: &T::AccountId,
-      currency_id: CurrencyId,
-      amount: Balance,
+    /// Add a new staking period
+    ///
+    /// - `duration`: The duration is in numbers of blocks. (blocks are ~6seconds)
+    /// - `reward`: The percentage of the session fees distributed to the stakes of this period
+    ///
+    /// Emits `StakingPeriodAdded` event when successful.
+    #[pallet::call_index(3)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::add_staking_period())]
+    pub fn add_staking_period(
+      origin: OriginFor<T>,
       duration: T::BlockNumber,
-    ) -> Result<Hash, DispatchError> {
-      // create unique hash
-      let unique_stake_request_id = <T as Config>::Security::get_unique_id(account_id.clone());
-
-      // 3. Transfer the funds into the staking pool
-      T::CurrencyTidefi::can_withdraw(currency_id, account_id, amount)
-        .into_result()
-        .map_err(|_| Error::<T>::InsufficientBalance)?;
+      reward: Percent,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
 
-      T::CurrencyTidefi::transfer(currency_id, account_id, &Self::account_id(), amount, false)?;
+      // 2. Make sure the duration is valid
+      ensure!(!duration.is_zero(), Error::<T>::InvalidDuration);
 
-      // 4. Update our `StakingPool` storage
-      StakingPool::<T>::try_mutate(currency_id, |balance| -> DispatchResult {
-        if let Some(b) = balance {
-          *balance = Some(b.checked_add(amount).ok_or(ArithmeticError::Overflow)?);
-        } else {
-          *balance = Some(amount)
-        }
+      // 3. Add the period
+      StakingPeriodRewards::<T>::try_mutate(|periods| -> DispatchResult {
+        ensure!(
+          !periods
+            .iter()
+            .any(|(iter_duration, _)| *iter_duration == duration),
+          Error::<T>::StakingPeriodAlreadyExists
+        );
+        periods
+          .try_push((duration, reward))
+          .map_err(|_| Error::<T>::StakingPeriodsOverflow)?;
         Ok(())
       })?;
 
-      // 5. Insert the new staking
-      let initial_block = T::Security::get_current_block_count();
-      AccountStakes::<T>::mutate(account_id.clone(), |stake| -> DispatchResult {
-        stake
-          .try_push(Stake {
-            currency_id,
-            unique_id: unique_stake_request_id,
-            last_session_index_compound: InterestCompoundLastSession::<T>::get(),
-            initial_block,
-            initial_balance: amount,
-            principal: amount,
-            duration,
-            status: StakeStatus::Staked,
-          })
-          .map_err(|_| DispatchError::Other("Invalid stake; eqd"))
-      })?;
+      // 4. The period is active again, the existing stakes use the new reward
+      RetiredStakingPeriodRewards::<T>::remove(duration);
 
-      Ok(unique_stake_request_id)
-    }
+      // 5. Emit event on chain
+      Self::deposit_event(Event::<T>::StakingPeriodAdded { duration, reward });
 
-    fn get_account_stake(
-      account_id: &T::AccountId,
-      stake_id: Hash,
-    ) -> Option<Stake<Balance, T::BlockNumber>> {
-      AccountStakes::<T>::get(account_id)
-        .into_iter()
-        .find(|stake| stake.unique_id == stake_id)
+      Ok(().into())
     }
 
-    fn do_process_unstake(account_id: &T::AccountId, stake_id: Hash) -> DispatchResult {
-      let current_stake =
-        Self::get_account_stake(account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
-      AccountStakes::<T>::try_mutate_exists(account_id, |account_stakes| match account_stakes {
-        None => Err(Error::<T>::InvalidStakeId),
-        Some(stakes) => {
-          T::CurrencyTidefi::can_withdraw(
-            current_stake.currency_id,
-            &Self::account_id(),
-            current_stake.initial_balance,
-          )
-          .into_result()
-          .map_err(|_| Error::<T>::InsufficientBalance)?;
-
-          StakingPool::<T>::try_mutate(current_stake.currency_id, |balance| -> DispatchResult {
-            if let Some(b) = balance {
-              *balance = Some(
-                b.checked_sub(current_stake.initial_balance)
-                  .ok_or(ArithmeticError::Underflow)?,
-              )
-            }
-            Ok(())
-          })
-          .map_err(|_| Error::<T>::NotEnoughInPoolToUnstake)?;
+    /// Retire a staking period
+    ///
+    /// No new stake can be created for this duration, the existing stakes are rewarded with the
+    /// current reward until they expire.
+    ///
+    /// Emits `StakingPeriodRetired` event when successful.
+    #[pallet::call_index(4)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::retire_staking_period())]
+    pub fn retire_staking_period(
+      origin: OriginFor<T>,
+      duration: T::BlockNumber,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
 
-          T::CurrencyTidefi::transfer(
-            current_stake.currency_id,
-            &Self::account_id(),
-            account_id,
-            current_stake.principal,
-            false,
-          )
-          .map_err(|_| Error::<T>::TransferFailed)?;
+      // 2. Remove the period from the active periods
+      let reward = StakingPeriodRewards::<T>::try_mutate(|periods| -> Result<_, DispatchError> {
+        let index = periods
+          .iter()
+          .position(|(iter_duration, _)| *iter_duration == duration)
+          .ok_or(Error::<T>::InvalidDuration)?;
+        let (_, reward) = periods.remove(index);
+        Ok(reward)
+      })?;
 
-          if stakes.len() > 1 {
-            stakes.retain(|stake| stake.unique_id != stake_id);
-          } else {
-            *account_stakes = None;
-          }
+      // 3. Keep the reward for the existing stakes
+      RetiredStakingPeriodRewards::<T>::insert(duration, reward);
 
-          Ok(())
-        }
-      })?;
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::StakingPeriodRetired { duration });
 
-      Ok(())
+      Ok(().into())
     }
 
-    fn do_account_compound(
-      account_id: &T::AccountId,
-      session_fee_by_currency: &[(SessionIndex, Vec<(CurrencyId, Balance)>)],
-    ) -> DispatchResult {
-      AccountStakes::<T>::try_mutate(account_id, |staking_details| -> DispatchResult {
-        // distribute reward for each session for each stake
-        staking_details
-          .iter_mut()
-          .filter(|stake| {
-            T::Security::get_current_block_count()
-              <= stake.initial_block.saturating_add(stake.duration)
-          })
-          .for_each(|stake| {
-            session_fee_by_currency.iter().for_each(
-              |(session_to_index, collected_fees_by_currency)| {
-                // do not compound if it's already processed
-                if stake.last_session_index_compound >= *session_to_index {
-                  return;
-                }
-
-                let session_fee_for_currency = collected_fees_by_currency
-                  .iter()
-                  .find_map(|(currency_id, fees_collected)| {
-                    if *currency_id == stake.currency_id {
-                      Some(*fees_collected)
-                    } else {
-                      None
-                    }
-                  })
-                  .unwrap_or_default();
-
-                // FIXME: we could probably find the closest reward
-                // but in theory this should never happens
-                let available_reward = StakingPeriodRewards::<T>::get()
-                  .into_iter()
-                  .find(|(duration, _)| *duration == stake.duration)
-                  .map(|(_, reward)| reward)
-                  .unwrap_or_else(Percent::zero)
-                  * session_fee_for_currency;
-
-                // calculate proportional reward base on the stake pool
-                let staking_pool_percentage = Perquintill::from_rational(
-                  stake.principal,
-                  StakingPool::<T>::get(stake.currency_id).unwrap_or(0),
-                );
-
-                let proportional_reward = staking_pool_percentage * available_reward;
-                stake.principal = stake.principal.saturating_add(proportional_reward);
-
-                // add the `proportional_reward` to the staking pool
-                StakingPool::<T>::mutate(stake.currency_id, |balance| {
-                  if let Some(b) = balance {
-                    *balance = Some(b.saturating_add(proportional_reward));
-                  } else {
-                    *balance = Some(proportional_reward);
-                  }
-                });
-
-                // increment the distribution
-                PendingStoredSessions::<T>::mutate(
-                  *session_to_index,
-                  |maybe_currently_rewarded: &mut Option<
-                    BoundedVec<(CurrencyId, Balance), T::StakingRewardCap>,
-                  >| {
-                    match maybe_currently_rewarded {
-                      Some(currently_rewarded) => {
-                        let maybe_rewarded_for_current_currency = currently_rewarded
-                          .iter_mut()
-                          .find(|(currency_id, _)| *currency_id == stake.currency_id);
-                        if let Some((_, balance)) = maybe_rewarded_for_current_currency {
-                          *balance = balance.saturating_add(proportional_reward);
-                        } else {
-                          let _ =
-                            currently_rewarded.try_push((stake.currency_id, proportional_reward));
-                        }
-                      }
-                      None => {
-                        *maybe_currently_rewarded = Some(
-                          vec![(stake.currency_id, proportional_reward)]
-                            .try_into()
-                            .expect("too many rewards"),
-                        )
-                      }
-                    }
-                  },
-                );
-
-                // update the last session index for this stake
-                stake.last_session_index_compound = *session_to_index;
-
-                log!(
-                  trace,
-                  "Recomputed rewards for {:?} with new balance: {} (initial: {})",
-                  account_id,
-                  stake.principal,
-                  stake.initial_balance,
-                );
-              },
-            );
-          });
+    /// Update the reward of a staking period, applied to the existing stakes from the next
+    /// session.
+    ///
+    /// Emits `StakingPeriodRewardUpdated` event when successful.
+    #[pallet::call_index(5)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::update_staking_period_reward())]
+    pub fn update_staking_period_reward(
+      origin: OriginFor<T>,
+      duration: T::BlockNumber,
+      reward: Percent,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
 
+      // 2. Update the reward
+      StakingPeriodRewards::<T>::try_mutate(|periods| -> DispatchResult {
+        let (_, current_reward) = periods
+          .iter_mut()
+          .find(|(iter_duration, _)| *iter_duration == duration)
+          .ok_or(Error::<T>::InvalidDuration)?;
+        *current_reward = reward;
         Ok(())
-      })
-    }
-
-    fn do_session_drain(session_index: SessionIndex) -> DispatchResult {
-      let pending_session =
-        PendingStoredSessions::<T>::take(session_index).ok_or(Error::<T>::InvalidSession)?;
+      })?;
 
-      let mut pool: Vec<(CurrencyId, Balance)> = Default::default();
-      let mut operator: Vec<(CurrencyId, Balance)> = Default::default();
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::StakingPeriodRewardUpdated { duration, reward });
 
-      for (currency_id, distributed_amount) in pending_session {
-        let collected_amount = SessionTotalFees::<T>::take(session_index, currency_id);
-        let operator_amount = collected_amount.saturating_sub(distributed_amount);
-        pool.push((currency_id, distributed_amount));
-        operator.push((currency_id, operator_amount));
+      Ok(().into())
+    }
 
-        // transfer from staking pallet account
-        // to operator account the remaining funds
-        T::CurrencyTidefi::transfer(
-          currency_id,
-          &Self::account_id(),
-          &Self::operator_account(),
-          operator_amount,
-          true,
-        )
-        .map_err(|_| Error::<T>::TransferFeesFailed)?;
+    /// Set the minimum and maximum stake amount of a currency, `None` remove the limits.
+    ///
+    /// Emits `StakingCurrencyMetaUpdated` event when successful.
+    #[pallet::call_index(6)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_staking_currency_meta())]
+    pub fn set_staking_currency_meta(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      meta: Option<StakeCurrencyMeta<Balance>>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Update the limits
+      match meta.clone() {
+        Some(currency_meta) => {
+          ensure!(
+            currency_meta.minimum_amount <= currency_meta.maximum_amount,
+            Error::<T>::InvalidStakingCurrencyMeta
+          );
+          StakingCurrencyMeta::<T>::insert(currency_id, currency_meta);
+        }
+        None => StakingCurrencyMeta::<T>::remove(currency_id),
       }
 
-      Self::deposit_event(Event::<T>::SessionFinished {
-        session_index,
-        pool,
-        operator,
-      });
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::StakingCurrencyMetaUpdated { currency_id, meta });
 
-      Ok(())
+      Ok(().into())
     }
 
-    #[inline]
-    pub fn do_on_idle_compound(remaining_weight: Weight) -> Weight {
-      // any weight that is unaccounted for
-      let mut unaccounted_weight = Weight::from_ref_time(0);
-      let next_batch_size = QueueCompound::<T>::count().min(T::BatchSize::get());
+    /// Set the percentage of fee taken when unstake is forced before the end of the period.
+    ///
+    /// Emits `UnstakeFeeUpdated` event when successful.
+    #[pallet::call_index(7)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_unstake_fee())]
+    pub fn set_unstake_fee(origin: OriginFor<T>, fee: Percent) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
 
-      // determine the number of accounts to check. This is based on both `ErasToCheckPerBlock`
-      // and `remaining_weight` passed on to us from the runtime executive.
-      let max_weight = |b| {
-        <T as Config>::WeightInfo::on_idle_compound(b)
-          .saturating_add(T::DbWeight::get().reads(next_batch_size.into()))
-      };
+      // 2. Update the fee
+      UnstakeFee::<T>::put(fee);
 
-      let accounts: BoundedVec<_, T::BatchSize> = QueueCompound::<T>::drain()
-        .take(T::BatchSize::get() as usize)
-        .map(|(a, _)| a)
-        .collect::<Vec<_>>()
-        .try_into()
-        .expect("take ensures bound is met; qed");
+      // 3. Emit event on chain
+      Self::deposit_event(Event::<T>::UnstakeFeeUpdated { fee });
 
-      let pre_length = accounts.len() as u32;
+      Ok(().into())
+    }
 
-      if max_weight(pre_length).any_gt(remaining_weight) {
-        log!(debug, "early exit because max weight is reached");
-        return T::DbWeight::get()
-          .reads(3)
-          .saturating_add(unaccounted_weight);
+    /// Unstake part of a stake
+    ///
+    /// - `stake_id`: Unique Stake ID
+    /// - `amount`: The amount to unstake, the stake must keep a balance
+    ///
+    /// If the stake is not expired, the unstake fee is applied on the part of the initial balance
+    /// unstaked and deducted from the amount transferred.
+    ///
+    /// Emits `PartiallyUnstaked` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::call_index(8)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::unstake_partial())]
+    pub fn unstake_partial(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+      amount: Balance,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Get Staking request for this user
+      let stake =
+        Self::get_account_stake(&account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+      ensure!(
+        matches!(stake.status, StakeStatus::Staked),
+        Error::<T>::StakeIsPendingUnlock
+      );
+
+      // 3. Make sure the stake keeps a balance, and at least the minimum amount of the currency
+      let stake_principal = Self::stake_principal(&stake);
+      ensure!(!amount.is_zero(), Error::<T>::AmountTooSmall);
+      ensure!(amount < stake_principal, Error::<T>::AmountTooLarge);
+      if let Some(currency_meta) = StakingCurrencyMeta::<T>::get(stake.currency_id) {
+        ensure!(
+          stake_principal.saturating_sub(amount) >= currency_meta.minimum_amount,
+          Error::<T>::AmountTooSmall
+        );
+      }
+
+      // 4. Burn the shares, rounded up so the remaining stakes of the pool are not diluted
+      let shares = StakeShares::<T>::get(stake_id);
+      let burned_shares =
+        multiply_by_rational_with_rounding(amount, shares, stake_principal, Rounding::Up)
+          .ok_or(ArithmeticError::Overflow)?
+          .min(shares);
+      SharesPools::<T>::try_mutate(
+        stake.currency_id,
+        stake.duration,
+        |shares_pool| -> DispatchResult {
+          shares_pool.total_shares = shares_pool
+            .total_shares
+            .checked_sub(burned_shares)
+            .ok_or(ArithmeticError::Underflow)?;
+          shares_pool.total_balance = shares_pool
+            .total_balance
+            .checked_sub(amount)
+            .ok_or(ArithmeticError::Underflow)?;
+          Ok(())
+        },
+      )?;
+      StakeShares::<T>::insert(stake_id, shares.saturating_sub(burned_shares));
+      StakingPool::<T>::try_mutate(stake.currency_id, |balance| -> DispatchResult {
+        if let Some(b) = balance {
+          *balance = Some(b.checked_sub(amount).ok_or(ArithmeticError::Underflow)?)
+        }
+        Ok(())
+      })
+      .map_err(|_| Error::<T>::NotEnoughInPoolToUnstake)?;
+
+      // 5. Reduce the initial balance pro rata, the principal keeps the remaining balance
+      let unstaked_initial_balance = multiply_by_rational_with_rounding(
+        amount,
+        stake.initial_balance,
+        stake_principal,
+        Rounding::Down,
+      )
+      .ok_or(ArithmeticError::Overflow)?;
+      AccountStakes::<T>::try_mutate(account_id.clone(), |stakes| -> DispatchResult {
+        let stake = stakes
+          .iter_mut()
+          .find(|stake| stake.unique_id == stake_id)
+          .ok_or(Error::<T>::InvalidStakeId)?;
+        stake.initial_balance = stake
+          .initial_balance
+          .saturating_sub(unstaked_initial_balance);
+        stake.principal = stake_principal.saturating_sub(amount);
+        Ok(())
+      })?;
+
+      // 6. Pay the unstaking fees to operator account if the stake is not expired
+      let expected_block_expiration = stake.initial_block.saturating_add(stake.duration);
+      let fee = if T::Security::get_current_block_count() >= expected_block_expiration {
+        Zero::zero()
+      } else {
+        Self::unstake_fee() * unstaked_initial_balance
+      };
+      if !fee.is_zero() {
+        T::CurrencyTidefi::transfer(
+          stake.currency_id,
+          &Self::account_id(),
+          &Self::operator_account(),
+          fee,
+          false,
+        )
+        .map_err(|_| Error::<T>::TransferFeesFailed)?;
+      }
+
+      // 7. Transfer the funds to the account
+      T::CurrencyTidefi::transfer(
+        stake.currency_id,
+        &Self::account_id(),
+        &account_id,
+        amount.saturating_sub(fee),
+        false,
+      )
+      .map_err(|_| Error::<T>::TransferFailed)?;
+
+      // 8. Emit event on chain
+      Self::deposit_event(Event::<T>::PartiallyUnstaked {
+        request_id: stake_id,
+        account_id,
+        currency_id: stake.currency_id,
+        amount,
+        fee,
+      });
+
+      Ok(().into())
+    }
+
+    /// Add funds to an existing stake, the staking period is not extended
+    ///
+    /// - `stake_id`: Unique Stake ID
+    /// - `amount`: The amount to add
+    ///
+    /// Emits `StakeIncreased` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::call_index(9)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::add_to_stake())]
+    pub fn add_to_stake(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+      amount: Balance,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Get Staking request for this user, it must be active
+      let stake =
+        Self::get_account_stake(&account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+      ensure!(
+        matches!(stake.status, StakeStatus::Staked),
+        Error::<T>::StakeIsPendingUnlock
+      );
+      ensure!(
+        T::Security::get_current_block_count() < stake.initial_block.saturating_add(stake.duration),
+        Error::<T>::StakeIsExpired
+      );
+
+      // 3. If we have the metadata of this currency, make sure the stake isn't too high
+      ensure!(!amount.is_zero(), Error::<T>::AmountTooSmall);
+      if let Some(currency_meta) = StakingCurrencyMeta::<T>::get(stake.currency_id) {
+        ensure!(
+          Self::stake_principal(&stake).saturating_add(amount) <= currency_meta.maximum_amount,
+          Error::<T>::AmountTooLarge
+        );
+      }
+
+      // 4. Transfer the funds into the staking pool
+      T::CurrencyTidefi::can_withdraw(stake.currency_id, &account_id, amount)
+        .into_result()
+        .map_err(|_| Error::<T>::InsufficientBalance)?;
+      T::CurrencyTidefi::transfer(
+        stake.currency_id,
+        &account_id,
+        &Self::account_id(),
+        amount,
+        false,
+      )?;
+
+      // 5. Mint the shares and update the stake
+      let shares = Self::mint_shares(stake.currency_id, stake.duration, amount)?;
+      StakeShares::<T>::try_mutate(stake_id, |stake_shares| -> DispatchResult {
+        *stake_shares = stake_shares
+          .checked_add(shares)
+          .ok_or(ArithmeticError::Overflow)?;
+        Ok(())
+      })?;
+      AccountStakes::<T>::try_mutate(account_id.clone(), |stakes| -> DispatchResult {
+        let stake = stakes
+          .iter_mut()
+          .find(|stake| stake.unique_id == stake_id)
+          .ok_or(Error::<T>::InvalidStakeId)?;
+        stake.initial_balance = stake.initial_balance.saturating_add(amount);
+        stake.principal = stake.principal.saturating_add(amount);
+        Ok(())
+      })?;
+
+      // 6. Emit event on chain
+      Self::deposit_event(Event::<T>::StakeIncreased {
+        request_id: stake_id,
+        account_id,
+        currency_id: stake.currency_id,
+        amount,
+      });
+
+      Ok(().into())
+    }
+
+    /// Renew the stake for a new term when it expires, or pay it out
+    ///
+    /// - `stake_id`: Unique Stake ID
+    /// - `auto_renew`: Renew the stake when it expires
+    ///
+    /// Emits `AutoRenewUpdated` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::call_index(10)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_auto_renew())]
+    pub fn set_auto_renew(
+      origin: OriginFor<T>,
+      stake_id: Hash,
+      auto_renew: bool,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Get Staking request for this user, it must be active
+      let stake =
+        Self::get_account_stake(&account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+      ensure!(
+        matches!(stake.status, StakeStatus::Staked),
+        Error::<T>::StakeIsPendingUnlock
+      );
+
+      // 3. Update the flag
+      if auto_renew {
+        AutoRenewStakes::<T>::insert(stake_id, true);
+      } else {
+        AutoRenewStakes::<T>::remove(stake_id);
+      }
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::AutoRenewUpdated {
+        request_id: stake_id,
+        account_id,
+        auto_renew,
+      });
+
+      Ok(().into())
+    }
+
+    /// Enable, update or disable the liquid staking of a currency
+    ///
+    /// - `currency_id`: The staked currency
+    /// - `meta`: The receipt currency and the reward of the liquid stakes, `None` to disable the
+    ///   liquid staking
+    ///
+    /// The receipt currency must be registered in the asset registry and can't be updated while
+    /// receipt tokens are in circulation, the reward can.
+    ///
+    /// Emits `LiquidStakingUpdated` event when successful.
+    #[pallet::call_index(11)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::set_liquid_staking())]
+    pub fn set_liquid_staking(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      meta: Option<LiquidStakingMeta>,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the origin is allowed
+      T::ForceOrigin::ensure_origin(origin)?;
+
+      // 2. Make sure there is no receipt token of the current setup in circulation, unless only
+      //    the reward is updated
+      let current_receipt_currency_id =
+        LiquidStaking::<T>::get(currency_id).map(|current_meta| current_meta.receipt_currency_id);
+      let receipt_currency_is_unchanged = current_receipt_currency_id.is_some()
+        && current_receipt_currency_id
+          == meta
+            .as_ref()
+            .map(|liquid_staking| liquid_staking.receipt_currency_id);
+      if let Some(current_receipt_currency_id) = current_receipt_currency_id {
+        ensure!(
+          receipt_currency_is_unchanged
+            || (T::CurrencyTidefi::total_issuance(current_receipt_currency_id).is_zero()
+              && Self::liquid_shares_pool(currency_id).total_shares.is_zero()),
+          Error::<T>::LiquidStakingInUse
+        );
+      }
+
+      // 3. Update the liquid staking, the receipt currency must be a dedicated asset
+      match meta.clone() {
+        Some(liquid_staking) => {
+          ensure!(
+            receipt_currency_is_unchanged
+              || (matches!(liquid_staking.receipt_currency_id, CurrencyId::Wrapped(_))
+                && liquid_staking.receipt_currency_id != currency_id
+                && T::AssetRegistry::is_currency_enabled(liquid_staking.receipt_currency_id)
+                && T::CurrencyTidefi::total_issuance(liquid_staking.receipt_currency_id).is_zero()
+                && !LiquidStaking::<T>::iter().any(|(iter_currency_id, iter_liquid_staking)| {
+                  iter_currency_id == liquid_staking.receipt_currency_id
+                    || iter_liquid_staking.receipt_currency_id == currency_id
+                    || (iter_currency_id != currency_id
+                      && iter_liquid_staking.receipt_currency_id
+                        == liquid_staking.receipt_currency_id)
+                })),
+            Error::<T>::InvalidReceiptCurrency
+          );
+          LiquidStaking::<T>::insert(currency_id, liquid_staking);
+        }
+        None => LiquidStaking::<T>::remove(currency_id),
+      }
+
+      // 4. Emit event on chain
+      Self::deposit_event(Event::<T>::LiquidStakingUpdated { currency_id, meta });
+
+      Ok(().into())
+    }
+
+    /// Stake currency and receive transferable receipt tokens
+    ///
+    /// - `currency_id`: The currency to stake
+    /// - `amount`: The amount to stake
+    ///
+    /// The stake is added to the liquid shares pool of the currency, rewarded at the liquid staking
+    /// reward. One receipt token is minted for each share, so the receipt tokens are redeemed at
+    /// the exchange rate of the pool, including the rewards.
+    ///
+    /// Emits `LiquidStaked` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::call_index(12)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::stake_liquid())]
+    pub fn stake_liquid(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      amount: Balance,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the liquid staking is enabled
+      let liquid_staking =
+        LiquidStaking::<T>::get(currency_id).ok_or(Error::<T>::LiquidStakingNotEnabled)?;
+
+      // 3. If we have the metadata of this currency, make sure the amount isn't too low or too high
+      if let Some(currency_meta) = StakingCurrencyMeta::<T>::get(currency_id) {
+        ensure!(
+          amount >= currency_meta.minimum_amount,
+          Error::<T>::AmountTooSmall
+        );
+        ensure!(
+          amount <= currency_meta.maximum_amount,
+          Error::<T>::AmountTooLarge
+        );
+      }
+
+      // 4. Transfer the funds into the staking pool
+      T::CurrencyTidefi::can_withdraw(currency_id, &account_id, amount)
+        .into_result()
+        .map_err(|_| Error::<T>::InsufficientBalance)?;
+      T::CurrencyTidefi::transfer(currency_id, &account_id, &Self::account_id(), amount, false)?;
+
+      // 5. Mint the shares and the receipt tokens
+      let receipts = LiquidSharesPools::<T>::try_mutate(currency_id, |shares_pool| {
+        Self::mint_pool_shares(shares_pool, amount)
+      })?;
+      Self::increase_staking_pool(currency_id, amount)?;
+      T::CurrencyTidefi::mint_into(liquid_staking.receipt_currency_id, &account_id, receipts)?;
+
+      // 6. Emit event on chain
+      Self::deposit_event(Event::<T>::LiquidStaked {
+        account_id,
+        currency_id,
+        amount,
+        receipts,
+      });
+
+      Ok(().into())
+    }
+
+    /// Burn receipt tokens and unstake their value
+    ///
+    /// - `currency_id`: The staked currency
+    /// - `receipts`: The amount of receipt tokens to burn
+    ///
+    /// The liquid stakes have no staking period to wait for, the unstake fee is deducted from the
+    /// amount transferred.
+    ///
+    /// Emits `LiquidUnstaked` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::call_index(13)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::unstake_liquid())]
+    pub fn unstake_liquid(
+      origin: OriginFor<T>,
+      currency_id: CurrencyId,
+      receipts: Balance,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the liquid staking is enabled
+      let liquid_staking =
+        LiquidStaking::<T>::get(currency_id).ok_or(Error::<T>::LiquidStakingNotEnabled)?;
+      ensure!(!receipts.is_zero(), Error::<T>::AmountTooSmall);
+
+      // 3. Burn the receipt tokens
+      T::CurrencyTidefi::can_withdraw(liquid_staking.receipt_currency_id, &account_id, receipts)
+        .into_result()
+        .map_err(|_| Error::<T>::InsufficientBalance)?;
+      T::CurrencyTidefi::burn_from(liquid_staking.receipt_currency_id, &account_id, receipts)?;
+
+      // 4. Burn the shares at the exchange rate of the pool
+      let amount = LiquidSharesPools::<T>::try_mutate_exists(
+        currency_id,
+        |maybe_shares_pool| -> Result<Balance, DispatchError> {
+          let shares_pool = maybe_shares_pool
+            .as_mut()
+            .ok_or(Error::<T>::NotEnoughInPoolToUnstake)?;
+          let amount = shares_pool.balance_of(receipts);
+          shares_pool.total_shares = shares_pool
+            .total_shares
+            .checked_sub(receipts)
+            .ok_or(ArithmeticError::Underflow)?;
+          shares_pool.total_balance = shares_pool
+            .total_balance
+            .checked_sub(amount)
+            .ok_or(ArithmeticError::Underflow)?;
+          if shares_pool.total_shares.is_zero() {
+            // the rounding dust stays in the pallet account
+            *maybe_shares_pool = None;
+          }
+          Ok(amount)
+        },
+      )?;
+      ensure!(!amount.is_zero(), Error::<T>::AmountTooSmall);
+      StakingPool::<T>::try_mutate(currency_id, |balance| -> DispatchResult {
+        if let Some(b) = balance {
+          *balance = Some(b.checked_sub(amount).ok_or(ArithmeticError::Underflow)?)
+        }
+        Ok(())
+      })
+      .map_err(|_| Error::<T>::NotEnoughInPoolToUnstake)?;
+
+      // 5. Pay the unstaking fees to operator account
+      let fee = Self::unstake_fee() * amount;
+      if !fee.is_zero() {
+        T::CurrencyTidefi::transfer(
+          currency_id,
+          &Self::account_id(),
+          &Self::operator_account(),
+          fee,
+          false,
+        )
+        .map_err(|_| Error::<T>::TransferFeesFailed)?;
+      }
+
+      // 6. Transfer the funds to the account
+      T::CurrencyTidefi::transfer(
+        currency_id,
+        &Self::account_id(),
+        &account_id,
+        amount.saturating_sub(fee),
+        false,
+      )
+      .map_err(|_| Error::<T>::TransferFailed)?;
+
+      // 7. Emit event on chain
+      Self::deposit_event(Event::<T>::LiquidUnstaked {
+        account_id,
+        currency_id,
+        receipts,
+        amount,
+        fee,
+      });
+
+      Ok(().into())
+    }
+  }
+
+  // helper functions (not dispatchable)
+  impl<T: Config> Pallet<T> {
+    pub fn account_id() -> T::AccountId {
+      <T as pallet::Config>::StakePalletId::get().into_account_truncating()
+    }
+
+    /// Reward of the staking period, retired periods keep their reward for the existing stakes.
+    pub fn staking_period_reward(duration: T::BlockNumber) -> Option<Percent> {
+      StakingPeriodRewards::<T>::get()
+        .into_iter()
+        .find(|(iter_duration, _)| *iter_duration == duration)
+        .map(|(_, reward)| reward)
+        .or_else(|| RetiredStakingPeriodRewards::<T>::get(duration))
+    }
+
+    /// Currency and amount of the fee to unstake `initial_balance` before the end of the staking
+    /// period, in TDFY if requested and the oracle has a rate for the staked currency.
+    pub fn unstake_fee_for(
+      currency_id: CurrencyId,
+      initial_balance: Balance,
+      pay_fee_in_tdfy: bool,
+    ) -> (CurrencyId, Balance) {
+      let unstaking_fee = Self::unstake_fee() * initial_balance;
+      if pay_fee_in_tdfy && currency_id != CurrencyId::Tdfy {
+        match T::Sunrise::try_get_tdfy_value(currency_id, unstaking_fee) {
+          Ok(tdfy_fee) if !tdfy_fee.is_zero() => return (CurrencyId::Tdfy, tdfy_fee),
+          _ => log!(
+            debug,
+            "no TDFY rate for {:?}, unstake fee paid in the staked currency",
+            currency_id
+          ),
+        }
+      }
+      (currency_id, unstaking_fee)
+    }
+
+    pub fn operator_account() -> T::AccountId {
+      match Self::operator_account_id() {
+        Some(account_id) => account_id,
+        None => Self::account_id(),
+      }
+    }
+
+    pub fn add_account_stake(
+      account_id: &T::AccountId,
+      currency_id: CurrencyId,
+      amount: Balance,
+      duration: T::BlockNumber,
+      auto_renew: bool,
+    ) -> Result<Hash, DispatchError> {
+      // create unique hash
+      let unique_stake_request_id = <T as Config>::Security::get_unique_id(account_id.clone());
+
+      // 3. Transfer the funds into the staking pool
+      T::CurrencyTidefi::can_withdraw(currency_id, account_id, amount)
+        .into_result()
+        .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+      T::CurrencyTidefi::transfer(currency_id, account_id, &Self::account_id(), amount, false)?;
+
+      // 4. Mint the shares of the stake and update our `StakingPool` storage
+      let shares = Self::mint_shares(currency_id, duration, amount)?;
+      StakeShares::<T>::insert(unique_stake_request_id, shares);
+
+      // 5. Insert the new staking
+      let initial_block = T::Security::get_current_block_count();
+      AccountStakes::<T>::mutate(account_id.clone(), |stake| -> DispatchResult {
+        stake
+          .try_push(Stake {
+            currency_id,
+            unique_id: unique_stake_request_id,
+            last_session_index_compound: InterestCompoundLastSession::<T>::get(),
+            initial_block,
+            initial_balance: amount,
+            principal: amount,
+            duration,
+            status: StakeStatus::Staked,
+          })
+          .map_err(|_| DispatchError::Other("Invalid stake; eqd"))
+      })?;
+
+      // 6. Queue the expiration of the stake
+      Self::queue_expiration(
+        account_id,
+        unique_stake_request_id,
+        initial_block.saturating_add(duration),
+      );
+      if auto_renew {
+        AutoRenewStakes::<T>::insert(unique_stake_request_id, true);
       }
 
-      unaccounted_weight
-        .saturating_accrue(T::DbWeight::get().reads_writes(pre_length as u64, pre_length as u64));
+      Ok(unique_stake_request_id)
+    }
 
-      log!(
-        debug,
-        "checking {:?} accounts, next_batch_size = {:?}, remaining_weight = {:?}",
-        pre_length,
-        next_batch_size,
-        remaining_weight,
-      );
+    /// Mint the shares for `amount` in the shares pool of the staking period and add `amount`
+    /// to the `StakingPool`.
+    fn mint_shares(
+      currency_id: CurrencyId,
+      duration: T::BlockNumber,
+      amount: Balance,
+    ) -> Result<Balance, DispatchError> {
+      let shares = SharesPools::<T>::try_mutate(currency_id, duration, |shares_pool| {
+        Self::mint_pool_shares(shares_pool, amount)
+      })?;
+      Self::increase_staking_pool(currency_id, amount)?;
 
-      let unchecked_sessions_to_check: Vec<SessionIndex> = PendingStoredSessions::<T>::iter()
-        .map(|(key, _)| key)
-        .collect();
+      Ok(shares)
+    }
 
-      let collected_fees_by_session: Vec<(SessionIndex, Vec<(CurrencyId, Balance)>)> =
-        unchecked_sessions_to_check
-          .iter()
-          .map(|session| {
-            (
-              *session,
-              SessionTotalFees::<T>::iter_prefix(*session).collect(),
-            )
+    /// Add `amount` to the shares pool, returns the shares minted.
+    fn mint_pool_shares(
+      shares_pool: &mut SharesPool,
+      amount: Balance,
+    ) -> Result<Balance, DispatchError> {
+      let shares = shares_pool
+        .shares_for(amount)
+        .ok_or(ArithmeticError::Overflow)?;
+      ensure!(!shares.is_zero(), Error::<T>::AmountTooSmall);
+      shares_pool.total_shares = shares_pool
+        .total_shares
+        .checked_add(shares)
+        .ok_or(ArithmeticError::Overflow)?;
+      shares_pool.total_balance = shares_pool
+        .total_balance
+        .checked_add(amount)
+        .ok_or(ArithmeticError::Overflow)?;
+      Ok(shares)
+    }
+
+    fn increase_staking_pool(currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+      StakingPool::<T>::try_mutate(currency_id, |balance| -> DispatchResult {
+        if let Some(b) = balance {
+          *balance = Some(b.checked_add(amount).ok_or(ArithmeticError::Overflow)?);
+        } else {
+          *balance = Some(amount)
+        }
+        Ok(())
+      })
+    }
+
+    /// Whether the currency is the receipt currency of a liquid staking, only this pallet mints
+    /// and burns it.
+    pub fn is_receipt_currency(currency_id: CurrencyId) -> bool {
+      LiquidStaking::<T>::iter_values()
+        .any(|liquid_staking| liquid_staking.receipt_currency_id == currency_id)
+    }
+
+    fn get_account_stake(
+      account_id: &T::AccountId,
+      stake_id: Hash,
+    ) -> Option<Stake<Balance, T::BlockNumber>> {
+      AccountStakes::<T>::get(account_id)
+        .into_iter()
+        .find(|stake| stake.unique_id == stake_id)
+    }
+
+    /// Current value of the stake, including the rewards of its shares.
+    pub fn stake_principal(stake: &Stake<Balance, T::BlockNumber>) -> Balance {
+      SharesPools::<T>::get(stake.currency_id, stake.duration)
+        .balance_of(StakeShares::<T>::get(stake.unique_id))
+    }
+
+    fn do_process_unstake(
+      account_id: &T::AccountId,
+      stake_id: Hash,
+    ) -> Result<Balance, DispatchError> {
+      let current_stake =
+        Self::get_account_stake(account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+      let final_balance = Self::stake_principal(&current_stake);
+
+      AccountStakes::<T>::try_mutate_exists(account_id, |account_stakes| match account_stakes {
+        None => Err(Error::<T>::InvalidStakeId),
+        Some(stakes) => {
+          T::CurrencyTidefi::can_withdraw(
+            current_stake.currency_id,
+            &Self::account_id(),
+            final_balance,
+          )
+          .into_result()
+          .map_err(|_| Error::<T>::InsufficientBalance)?;
+
+          StakingPool::<T>::try_mutate(current_stake.currency_id, |balance| -> DispatchResult {
+            if let Some(b) = balance {
+              *balance = Some(
+                b.checked_sub(final_balance)
+                  .ok_or(ArithmeticError::Underflow)?,
+              )
+            }
+            Ok(())
           })
-          .collect();
+          .map_err(|_| Error::<T>::NotEnoughInPoolToUnstake)?;
 
-      // try to drain a session
-      let do_drain_session = |session: SessionIndex| {
-        let result = Self::do_session_drain(session);
-        log!(info, "session drained {}, outcome: {:?}", session, result);
-      };
+          QueueUnstake::<T>::remove(stake_id);
+          Self::dequeue_expiration(&current_stake);
+          AutoRenewStakes::<T>::remove(stake_id);
 
-      // try to compound an account id
-      let do_compound = |account_id: &T::AccountId| {
-        let result = Self::do_account_compound(account_id, &collected_fees_by_session);
-        log!(
-          info,
-          "account compound {:?}, outcome: {:?}",
-          account_id,
-          result
-        );
-      };
+          // burn the shares of the stake
+          let shares = StakeShares::<T>::take(stake_id);
+          SharesPools::<T>::mutate_exists(
+            current_stake.currency_id,
+            current_stake.duration,
+            |maybe_shares_pool| {
+              if let Some(shares_pool) = maybe_shares_pool {
+                shares_pool.total_shares = shares_pool.total_shares.saturating_sub(shares);
+                shares_pool.total_balance = shares_pool.total_balance.saturating_sub(final_balance);
+                if shares_pool.total_shares.is_zero() {
+                  // the rounding dust stays in the pallet account
+                  *maybe_shares_pool = None;
+                  SessionRewardsHistory::<T>::remove(
+                    current_stake.currency_id,
+                    current_stake.duration,
+                  );
+                }
+              }
+            },
+          );
 
-      // if there is no accounts remaining, that mean we can drain the pending sessions
-      if accounts.is_empty() {
-        let size = unchecked_sessions_to_check.len() as u32;
-        unchecked_sessions_to_check
-          .iter()
-          .for_each(|session| do_drain_session(*session));
+          T::CurrencyTidefi::transfer(
+            current_stake.currency_id,
+            &Self::account_id(),
+            account_id,
+            final_balance,
+            false,
+          )
+          .map_err(|_| Error::<T>::TransferFailed)?;
 
-        Self::deposit_event(Event::<T>::BatchFinished {
-          size,
-          kind: BatchType::Compound,
-        });
+          if stakes.len() > 1 {
+            stakes.retain(|stake| stake.unique_id != stake_id);
+          } else {
+            *account_stakes = None;
+          }
 
-        <T as Config>::WeightInfo::on_idle_compound_finalize(size)
-          .saturating_add(unaccounted_weight)
-      } else {
-        accounts
-          .iter()
-          .for_each(|account_id| do_compound(account_id));
+          Ok(())
+        }
+      })?;
 
-        Self::deposit_event(Event::<T>::BatchCompound { size: pre_length });
+      Ok(final_balance)
+    }
+
+    /// Distribute the session fees of a currency to its shares pools, each staking period and the
+    /// liquid stakes receive their reward proportionally to their part of the staking pool.
+    ///
+    /// Returns the amount distributed.
+    fn distribute_session_fees(currency_id: CurrencyId, fees: Balance) -> Balance {
+      let staking_pool = StakingPool::<T>::get(currency_id).unwrap_or_default();
+      if staking_pool.is_zero() {
+        return Zero::zero();
+      }
 
-        <T as Config>::WeightInfo::on_idle_compound(pre_length).saturating_add(unaccounted_weight)
+      let mut distributed: Balance = Zero::zero();
+      let current_block = T::Security::get_current_block_count();
+      let durations: Vec<T::BlockNumber> = SharesPools::<T>::iter_key_prefix(currency_id).collect();
+      for duration in durations {
+        SharesPools::<T>::mutate(currency_id, duration, |shares_pool| {
+          let available_reward =
+            Self::staking_period_reward(duration).unwrap_or_else(Percent::zero) * fees;
+          let reward =
+            Perquintill::from_rational(shares_pool.total_balance, staking_pool) * available_reward;
+
+          // keep the reward rate of the session for the staking yield
+          if !shares_pool.total_balance.is_zero() {
+            SessionRewardsHistory::<T>::mutate(currency_id, duration, |history| {
+              if history.is_full() {
+                history.remove(0);
+              }
+              let _ = history.try_push((
+                current_block,
+                Perquintill::from_rational(reward, shares_pool.total_balance),
+              ));
+            });
+          }
+
+          shares_pool.total_balance = shares_pool.total_balance.saturating_add(reward);
+          distributed = distributed.saturating_add(reward);
+        });
       }
+      if let Some(liquid_staking) = LiquidStaking::<T>::get(currency_id) {
+        LiquidSharesPools::<T>::mutate_exists(currency_id, |maybe_shares_pool| {
+          if let Some(shares_pool) = maybe_shares_pool {
+            let reward = Perquintill::from_rational(shares_pool.total_balance, staking_pool)
+              * (liquid_staking.reward * fees);
+            shares_pool.total_balance = shares_pool.total_balance.saturating_add(reward);
+            distributed = distributed.saturating_add(reward);
+          }
+        });
+      }
+
+      StakingPool::<T>::mutate(currency_id, |balance| {
+        *balance = Some(balance.unwrap_or_default().saturating_add(distributed));
+      });
+
+      distributed
     }
 
     #[inline]
@@ -935,6 +1740,173 @@ pub mod pallet {
       <T as Config>::WeightInfo::on_idle_unstake(next_batch_size).saturating_add(unaccounted_weight)
     }
 
+    /// Renew the expired stake for a new term if auto-renew is enabled and its staking period is
+    /// still active, otherwise pay it out.
+    fn do_stake_expiration(
+      account_id: &T::AccountId,
+      stake_id: Hash,
+      expiration: T::BlockNumber,
+    ) -> DispatchResult {
+      if QueueExpiration::<T>::take(expiration, stake_id).is_some() {
+        CounterForQueueExpiration::<T>::mutate(|counter| counter.saturating_dec());
+      }
+
+      let stake =
+        Self::get_account_stake(account_id, stake_id).ok_or(Error::<T>::InvalidStakeId)?;
+      // the unstake queue pays out the stake
+      if !matches!(stake.status, StakeStatus::Staked) {
+        return Ok(());
+      }
+
+      let period_is_active = StakingPeriodRewards::<T>::get()
+        .into_iter()
+        .any(|(duration, _)| duration == stake.duration);
+
+      if Self::auto_renew(stake_id) && period_is_active {
+        // the new term starts at the expiration of the previous one, the rewards become the
+        // initial balance of the stake
+        let initial_block = stake.initial_block.saturating_add(stake.duration);
+        let principal = Self::stake_principal(&stake);
+        AccountStakes::<T>::try_mutate(account_id, |stakes| -> DispatchResult {
+          let stake = stakes
+            .iter_mut()
+            .find(|stake| stake.unique_id == stake_id)
+            .ok_or(Error::<T>::InvalidStakeId)?;
+          stake.initial_block = initial_block;
+          stake.initial_balance = principal;
+          stake.principal = principal;
+          stake.last_session_index_compound = InterestCompoundLastSession::<T>::get();
+          Ok(())
+        })?;
+        Self::queue_expiration(
+          account_id,
+          stake_id,
+          initial_block.saturating_add(stake.duration),
+        );
+
+        Self::deposit_event(Event::<T>::StakeRenewed {
+          request_id: stake_id,
+          account_id: account_id.clone(),
+          currency_id: stake.currency_id,
+          principal,
+          duration: stake.duration,
+        });
+      } else {
+        let final_balance = Self::do_process_unstake(account_id, stake_id)?;
+        Self::deposit_event(Event::<T>::Unstaked {
+          request_id: stake_id,
+          account_id: account_id.clone(),
+          currency_id: stake.currency_id,
+          initial_balance: stake.initial_balance,
+          final_balance,
+        });
+      }
+
+      Ok(())
+    }
+
+    /// Queue the expiration of the stake, `on_idle` moves back to the expiration block if it is
+    /// already past it.
+    pub(crate) fn queue_expiration(
+      account_id: &T::AccountId,
+      stake_id: Hash,
+      expiration: T::BlockNumber,
+    ) {
+      let queue_size = CounterForQueueExpiration::<T>::get();
+      NextExpirationBlock::<T>::mutate(|next_block| {
+        *next_block = if queue_size.is_zero() {
+          expiration
+        } else {
+          (*next_block).min(expiration)
+        };
+      });
+      if !QueueExpiration::<T>::contains_key(expiration, stake_id) {
+        CounterForQueueExpiration::<T>::put(queue_size.saturating_add(1));
+      }
+      QueueExpiration::<T>::insert(expiration, stake_id, account_id);
+    }
+
+    /// Remove the stake from the expiration queue.
+    fn dequeue_expiration(stake: &Stake<Balance, T::BlockNumber>) {
+      let expiration = stake.initial_block.saturating_add(stake.duration);
+      if QueueExpiration::<T>::take(expiration, stake.unique_id).is_some() {
+        CounterForQueueExpiration::<T>::mutate(|counter| counter.saturating_dec());
+      }
+    }
+
+    #[inline]
+    pub fn do_on_idle_expiration(remaining_weight: Weight) -> Weight {
+      let current_block = T::Security::get_current_block_count();
+      let batch_size = T::BatchSize::get();
+      let max_weight = <T as Config>::WeightInfo::on_idle_expiration(batch_size)
+        .saturating_add(T::DbWeight::get().reads_writes(batch_size.into(), batch_size.into()));
+
+      if max_weight.any_gt(remaining_weight) {
+        log!(debug, "early exit because max weight is reached");
+        return T::DbWeight::get().reads(3);
+      }
+
+      // each block of the queue costs a read, scan the blocks from the next expiration block as
+      // long as the remaining weight allows and stop at the first block not fully drained
+      let max_scanned_blocks = remaining_weight
+        .saturating_sub(max_weight)
+        .ref_time()
+        .checked_div(T::DbWeight::get().read)
+        .unwrap_or(u64::MAX);
+      let mut next_block = Self::next_expiration_block();
+      let mut scanned_blocks: u64 = 0;
+      let mut stakes: Vec<(T::BlockNumber, Hash, T::AccountId)> = Vec::new();
+      while next_block <= current_block && scanned_blocks < max_scanned_blocks {
+        let remaining = (batch_size as usize).saturating_sub(stakes.len());
+        if remaining.is_zero() {
+          break;
+        }
+        let expired = QueueExpiration::<T>::iter_prefix(next_block)
+          .take(remaining)
+          .map(|(stake_id, account_id)| (next_block, stake_id, account_id))
+          .collect::<Vec<_>>();
+        let block_is_drained = expired.len() < remaining;
+        stakes.extend(expired);
+        scanned_blocks.saturating_inc();
+        if !block_is_drained {
+          break;
+        }
+        next_block.saturating_inc();
+      }
+      // the renewed stakes expire after the next block
+      NextExpirationBlock::<T>::put(next_block);
+
+      let next_batch_size = stakes.len() as u32;
+      log!(
+        debug,
+        "next_batch_size = {:?}, scanned_blocks = {:?}, remaining_weight = {:?}",
+        next_batch_size,
+        scanned_blocks,
+        remaining_weight,
+      );
+
+      stakes
+        .into_iter()
+        .for_each(|(expiration, stake_id, account_id)| {
+          let result = Self::do_stake_expiration(&account_id, stake_id, expiration);
+          log!(info, "expired {:?}, outcome: {:?}", stake_id, result);
+        });
+
+      if next_batch_size > 0 {
+        Self::deposit_event(Event::<T>::BatchFinished {
+          size: next_batch_size,
+          kind: BatchType::Expiration,
+        });
+      }
+
+      <T as Config>::WeightInfo::on_idle_expiration(next_batch_size).saturating_add(
+        T::DbWeight::get().reads_writes(
+          scanned_blocks.saturating_add(next_batch_size.into()),
+          u64::from(next_batch_size).saturating_add(1),
+        ),
+      )
+    }
+
     // Get all stakes for the account, serialized for quick RPC call
     pub fn get_account_stakes(
       account_id: &T::AccountId,
@@ -951,7 +1923,7 @@ pub mod pallet {
             unique_id: account_stake.unique_id,
             initial_block: account_stake.initial_block,
             principal: BalanceInfo {
-              amount: account_stake.principal,
+              amount: Self::stake_principal(&account_stake),
             },
             initial_balance: BalanceInfo {
               amount: account_stake.initial_balance,
@@ -964,6 +1936,82 @@ pub mod pallet {
 
       final_stakes
     }
+
+    /// Annualised yield of the shares pool of a staking period, at the reward rate of the
+    /// sessions kept in `SessionRewardsHistory`.
+    pub fn staking_yield(currency_id: CurrencyId, duration: T::BlockNumber) -> Perquintill {
+      let history = SessionRewardsHistory::<T>::get(currency_id, duration);
+      let (first_block, last_block) = match (history.first(), history.last()) {
+        (Some((first_block, _)), Some((last_block, _))) if last_block > first_block => {
+          (*first_block, *last_block)
+        }
+        _ => return Perquintill::zero(),
+      };
+
+      // the reward of the first session has been earned before its block
+      let rewards_rate: u128 = history
+        .iter()
+        .skip(1)
+        .map(|(_, rate)| u128::from(rate.deconstruct()))
+        .sum();
+      multiply_by_rational_with_rounding(
+        rewards_rate,
+        T::BlocksPerYear::get().saturated_into::<u128>(),
+        last_block
+          .saturating_sub(first_block)
+          .saturated_into::<u128>(),
+        Rounding::Down,
+      )
+      .map(|parts| {
+        Perquintill::from_parts(parts.min(u128::from(Perquintill::one().deconstruct())) as u64)
+      })
+      .unwrap_or_else(Perquintill::one)
+    }
+
+    /// Annualised yield of each currency and staking period with stakes.
+    pub fn get_staking_yields() -> Vec<StakingYield<T::BlockNumber>> {
+      SharesPools::<T>::iter()
+        .map(|(currency_id, duration, shares_pool)| StakingYield {
+          currency_id,
+          duration,
+          reward: Self::staking_period_reward(duration).unwrap_or_else(Percent::zero),
+          staked: shares_pool.total_balance,
+          annual_yield: Self::staking_yield(currency_id, duration),
+        })
+        .collect()
+    }
+
+    /// Projected value of the stakes of the account at the end of their staking period.
+    pub fn get_account_stakes_projection(
+      account_id: &T::AccountId,
+    ) -> Vec<StakeProjection<T::BlockNumber>> {
+      let current_block = T::Security::get_current_block_count();
+
+      Self::account_stakes(account_id)
+        .into_iter()
+        .map(|stake| {
+          let balance = Self::stake_principal(&stake);
+          let expiration = stake.initial_block.saturating_add(stake.duration);
+          let projected_rewards = multiply_by_rational_with_rounding(
+            Self::staking_yield(stake.currency_id, stake.duration) * balance,
+            expiration
+              .saturating_sub(current_block)
+              .saturated_into::<u128>(),
+            T::BlocksPerYear::get().saturated_into::<u128>(),
+            Rounding::Down,
+          )
+          .unwrap_or_default();
+
+          StakeProjection {
+            stake_id: stake.unique_id,
+            currency_id: stake.currency_id,
+            balance,
+            projected_balance: balance.saturating_add(projected_rewards),
+            expiration,
+          }
+        })
+        .collect()
+    }
   }
 
   // implement the `StakingExt` functions
@@ -982,54 +2030,95 @@ pub mod pallet {
       session_trade_values: Vec<(CurrencyId, Balance)>,
       fees_account_id: T::AccountId,
     ) -> Result<(), DispatchError> {
-      let prepare_session = |currency_id: CurrencyId, balance: Balance| {
-        let destination = if currency_id == CurrencyId::Tdfy {
-          Self::operator_account()
-        } else {
-          Self::account_id()
-        };
+      Self::do_session_end(session_index, session_trade_values, fees_account_id);
+      Ok(())
+    }
+  }
+
+  impl<T: Config> Pallet<T> {
+    // Distribute the stakers share of the session fees, returns the amount distributed to the
+    // pools by currency
+    pub(crate) fn do_session_end(
+      session_index: SessionIndex,
+      session_trade_values: Vec<(CurrencyId, Balance)>,
+      fees_account_id: T::AccountId,
+    ) -> Vec<(CurrencyId, Balance)> {
+      let mut pool: Vec<(CurrencyId, Balance)> = Default::default();
+      let mut operator: Vec<(CurrencyId, Balance)> = Default::default();
 
+      // 1. Distribute the stakers share of the session fees, we transfer the fees from
+      //    `fees_account_id` to Pallet account, add the rewards to the shares pools and transfer
+      //    the remaining to the Operator
+      for (currency_id, balance) in session_trade_values {
         // Transfer all fees collected by `Fees` pallet to `Staking` pallet for the redistribution.
-        let result =
-          T::CurrencyTidefi::transfer(currency_id, &fees_account_id, &destination, balance, false);
+        let result = T::CurrencyTidefi::transfer(
+          currency_id,
+          &fees_account_id,
+          &Self::account_id(),
+          balance,
+          false,
+        );
+        if result.is_err() {
+          log!(
+            error,
+            "session {} fees transfer failed for {:?}, outcome: {:?}",
+            session_index,
+            currency_id,
+            result
+          );
+          continue;
+        }
+
+        let distributed_amount = Self::distribute_session_fees(currency_id, balance);
+        let operator_amount = balance.saturating_sub(distributed_amount);
+
+        // transfer from staking pallet account
+        // to operator account the remaining funds
+        let result = T::CurrencyTidefi::transfer(
+          currency_id,
+          &Self::account_id(),
+          &Self::operator_account(),
+          operator_amount,
+          true,
+        );
         log!(
           info,
-          "session {} prepared for {:?}, outcome: {:?}",
+          "session {} distributed for {:?}, outcome: {:?}",
           session_index,
           currency_id,
           result
         );
 
-        if result.is_ok() && currency_id != CurrencyId::Tdfy {
-          SessionTotalFees::<T>::insert(session_index, currency_id, balance);
-        }
-
-        result.is_ok()
-      };
-
-      // 1. Prepare session
-      //   - If currency is TDFY, Operator take all the profits, as there is no staking pool
-      //   - If currency is Wrapped, we transfer the fees from `fees_account_id` to Pallet account
-      //   - If currency is Wrapped, we add the `SessionTotalFees` to the fees map for staking pool redistribution
-      let sessions: Vec<(CurrencyId, Balance)> = session_trade_values
-        .into_iter()
-        .filter(|(currency_id, balance)| prepare_session(*currency_id, *balance))
-        .collect();
+        pool.push((currency_id, distributed_amount));
+        operator.push((currency_id, operator_amount));
+      }
 
-      if !sessions.is_empty() {
+      if !pool.is_empty() || !operator.is_empty() {
         // 2. Mark the session index has last session finished
         InterestCompoundLastSession::<T>::put(session_index);
-        // 3. Add the session into the queue (required for `on_idle`)
-        PendingStoredSessions::<T>::insert::<
-          u64,
-          BoundedVec<(CurrencyId, Balance), T::StakingRewardCap>,
-        >(session_index, Default::default());
-        // 4. Grab all current account (keys) and add them into the `QueueCompound` who will be drained by `on_idle`
-        AccountStakes::<T>::iter_keys()
-          .for_each(|account| QueueCompound::<T>::insert(account, session_index));
+
+        Self::deposit_event(Event::<T>::SessionFinished {
+          session_index,
+          pool: pool.clone(),
+          operator,
+        });
       }
 
-      Ok(())
+      pool
+    }
+  }
+
+  impl<T: Config> StakingOperator<T::AccountId> for Pallet<T> {
+    fn operator_account() -> T::AccountId {
+      Self::operator_account()
+    }
+
+    fn distribute_session_rewards(
+      session_index: SessionIndex,
+      session_fees: Vec<(CurrencyId, Balance)>,
+      fees_account_id: T::AccountId,
+    ) -> Vec<(CurrencyId, Balance)> {
+      Self::do_session_end(session_index, session_fees, fees_account_id)
     }
   }
 }
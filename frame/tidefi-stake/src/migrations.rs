@@ -0,0 +1,317 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_support::{
+  log,
+  pallet_prelude::*,
+  storage_alias,
+  traits::{tokens::fungibles::Transfer, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::{traits::Saturating, Percent, Perquintill};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+use tidefi_primitives::{Balance, CurrencyId, SessionIndex, StakeStatus};
+
+pub mod v3 {
+  use super::*;
+
+  #[storage_alias]
+  type PendingStoredSessions<T: Config> = StorageMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    SessionIndex,
+    BoundedVec<(CurrencyId, Balance), <T as Config>::StakingRewardCap>,
+  >;
+
+  #[storage_alias]
+  type CounterForPendingStoredSessions<T: Config> = StorageValue<Pallet<T>, u32>;
+
+  #[storage_alias]
+  type SessionTotalFees<T: Config> = StorageDoubleMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    SessionIndex,
+    Blake2_128Concat,
+    CurrencyId,
+    Balance,
+    ValueQuery,
+  >;
+
+  #[storage_alias]
+  type QueueCompound<T: Config> =
+    StorageMap<Pallet<T>, Blake2_128Concat, <T as frame_system::Config>::AccountId, SessionIndex>;
+
+  #[storage_alias]
+  type CounterForQueueCompound<T: Config> = StorageValue<Pallet<T>, u32>;
+
+  /// Migrate the stakes from the compounded principal to the shares pools.
+  ///
+  /// - The stakes of the compound queue receive their rewards of the pending sessions
+  /// - The pending sessions are drained, the fees not distributed go to the operator
+  /// - Each stake receives one share per unit of its current principal
+  /// - The expiration of the active stakes is queued
+  ///
+  /// The migration runs in a single block, its weight is linear in the number of stakes and
+  /// pending sessions. It is skipped, keeping the storage version 2, when its upper bound
+  /// exceeds the maximum block weight.
+  pub struct MigrateToV3<T>(sp_std::marker::PhantomData<T>);
+
+  impl<T: Config> MigrateToV3<T> {
+    /// Upper bound of the migration weight, based on the storage counters.
+    pub fn max_weight() -> Weight {
+      let pending_sessions: u64 = CounterForPendingStoredSessions::<T>::get()
+        .unwrap_or_default()
+        .into();
+      let queued_accounts: u64 = CounterForQueueCompound::<T>::get()
+        .unwrap_or_default()
+        .into();
+      let accounts: u64 = AccountStakes::<T>::count().into();
+      let stakes = accounts.saturating_mul(T::StakeAccountCap::get().into());
+      let currencies: u64 = T::StakingRewardCap::get().into();
+      let session_fees = pending_sessions.saturating_mul(currencies);
+
+      // every queued stake compounds every pending session
+      let compound = queued_accounts
+        .saturating_mul(T::StakeAccountCap::get().into())
+        .saturating_mul(pending_sessions);
+
+      let reads = pending_sessions
+        .saturating_add(session_fees)
+        .saturating_add(queued_accounts.saturating_mul(2))
+        .saturating_add(compound)
+        .saturating_add(accounts)
+        .saturating_add(stakes.saturating_mul(3))
+        .saturating_add(currencies)
+        .saturating_add(1);
+      let writes = pending_sessions
+        .saturating_add(session_fees.saturating_mul(3))
+        .saturating_add(queued_accounts.saturating_mul(2))
+        .saturating_add(compound)
+        .saturating_add(stakes.saturating_mul(5))
+        .saturating_add(currencies.saturating_mul(2))
+        .saturating_add(3);
+
+      T::DbWeight::get().reads_writes(reads, writes)
+    }
+  }
+
+  impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+    fn on_runtime_upgrade() -> Weight {
+      let onchain_version = Pallet::<T>::on_chain_storage_version();
+      if onchain_version != 2 {
+        log::warn!(
+          target: LOG_TARGET,
+          "skipping v3 migration, on-chain storage version is {:?}",
+          onchain_version
+        );
+        return T::DbWeight::get().reads(1);
+      }
+
+      // 1. Make sure the migration fits in a block
+      let max_weight = Self::max_weight();
+      if max_weight.any_gt(T::BlockWeights::get().max_block) {
+        log::error!(
+          target: LOG_TARGET,
+          "skipping v3 migration, its weight {:?} exceeds the maximum block weight",
+          max_weight
+        );
+        return T::DbWeight::get().reads(4);
+      }
+
+      let mut reads: u64 = 4;
+      let mut writes: u64 = 0;
+
+      // 2. Load the fees of the pending sessions
+      let mut pending_sessions: Vec<(SessionIndex, Vec<(CurrencyId, Balance)>)> =
+        PendingStoredSessions::<T>::iter_keys()
+          .map(|session_index| {
+            (
+              session_index,
+              SessionTotalFees::<T>::iter_prefix(session_index).collect(),
+            )
+          })
+          .collect();
+      pending_sessions.sort_by_key(|(session_index, _)| *session_index);
+      for (_, fees) in pending_sessions.iter() {
+        reads.saturating_accrue(fees.len().saturating_add(1) as u64);
+      }
+
+      // 3. Compound the pending sessions rewards of the queued stakes, as `on_idle` would have
+      let mut distributed: BTreeMap<(SessionIndex, CurrencyId), Balance> = BTreeMap::new();
+      for (account_id, _) in QueueCompound::<T>::drain() {
+        reads.saturating_accrue(2);
+        writes.saturating_inc();
+        if !AccountStakes::<T>::contains_key(&account_id) {
+          continue;
+        }
+        writes.saturating_inc();
+        AccountStakes::<T>::mutate(&account_id, |stakes| {
+          for stake in stakes.iter_mut() {
+            for (session_index, fees) in pending_sessions
+              .iter()
+              .filter(|(session_index, _)| *session_index > stake.last_session_index_compound)
+            {
+              let session_fee_for_currency = fees
+                .iter()
+                .find(|(currency_id, _)| *currency_id == stake.currency_id)
+                .map(|(_, balance)| *balance)
+                .unwrap_or_default();
+              let available_reward = Pallet::<T>::staking_period_reward(stake.duration)
+                .unwrap_or_else(Percent::zero)
+                * session_fee_for_currency;
+              let staking_pool_percentage = Perquintill::from_rational(
+                stake.principal,
+                StakingPool::<T>::get(stake.currency_id).unwrap_or_default(),
+              );
+              let proportional_reward = staking_pool_percentage * available_reward;
+
+              stake.principal = stake.principal.saturating_add(proportional_reward);
+              stake.last_session_index_compound = *session_index;
+              StakingPool::<T>::mutate(stake.currency_id, |balance| {
+                *balance = Some(
+                  balance
+                    .unwrap_or_default()
+                    .saturating_add(proportional_reward),
+                );
+              });
+              let session_distributed = distributed
+                .entry((*session_index, stake.currency_id))
+                .or_default();
+              *session_distributed = session_distributed.saturating_add(proportional_reward);
+              reads.saturating_inc();
+              writes.saturating_inc();
+            }
+          }
+        });
+      }
+      CounterForQueueCompound::<T>::kill();
+      writes.saturating_inc();
+
+      // 4. Drain the pending sessions, the fees not distributed go to the operator
+      for (session_index, already_distributed) in PendingStoredSessions::<T>::drain() {
+        writes.saturating_inc();
+        for (currency_id, collected_amount) in SessionTotalFees::<T>::drain_prefix(session_index) {
+          writes.saturating_accrue(3);
+          let distributed_amount = already_distributed
+            .iter()
+            .find(|(iter_currency_id, _)| *iter_currency_id == currency_id)
+            .map(|(_, balance)| *balance)
+            .unwrap_or_default()
+            .saturating_add(
+              distributed
+                .get(&(session_index, currency_id))
+                .copied()
+                .unwrap_or_default(),
+            );
+          let operator_amount = collected_amount.saturating_sub(distributed_amount);
+          if let Err(err) = T::CurrencyTidefi::transfer(
+            currency_id,
+            &Pallet::<T>::account_id(),
+            &Pallet::<T>::operator_account(),
+            operator_amount,
+            true,
+          ) {
+            log::error!(
+              target: LOG_TARGET,
+              "unable to transfer session {} fees to the operator: {:?}",
+              session_index,
+              err
+            );
+          }
+        }
+      }
+      let _ = SessionTotalFees::<T>::clear(u32::MAX, None);
+      CounterForPendingStoredSessions::<T>::kill();
+      writes.saturating_inc();
+
+      // 5. Convert the stakes principal into shares
+      let mut staking_pool: BTreeMap<CurrencyId, Balance> = BTreeMap::new();
+      for (account_id, stakes) in AccountStakes::<T>::iter() {
+        reads.saturating_inc();
+        for stake in stakes {
+          // the unstake queue pays out the stakes pending unlock
+          if matches!(stake.status, StakeStatus::Staked) {
+            Pallet::<T>::queue_expiration(
+              &account_id,
+              stake.unique_id,
+              stake.initial_block.saturating_add(stake.duration),
+            );
+            reads.saturating_accrue(2);
+            writes.saturating_accrue(3);
+          }
+          SharesPools::<T>::mutate(stake.currency_id, stake.duration, |shares_pool| {
+            shares_pool.total_shares = shares_pool.total_shares.saturating_add(stake.principal);
+            shares_pool.total_balance = shares_pool.total_balance.saturating_add(stake.principal);
+          });
+          StakeShares::<T>::insert(stake.unique_id, stake.principal);
+          let pool_balance = staking_pool.entry(stake.currency_id).or_default();
+          *pool_balance = pool_balance.saturating_add(stake.principal);
+          reads.saturating_inc();
+          writes.saturating_accrue(2);
+        }
+      }
+
+      // 6. Rebuild the staking pool from the stakes
+      let _ = StakingPool::<T>::clear(u32::MAX, None);
+      for (currency_id, balance) in staking_pool {
+        StakingPool::<T>::insert(currency_id, balance);
+        writes.saturating_inc();
+      }
+
+      StorageVersion::new(3).put::<Pallet<T>>();
+      writes.saturating_inc();
+
+      log::info!(target: LOG_TARGET, "migrated tidefi-stake to v3");
+
+      T::DbWeight::get().reads_writes(reads, writes)
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+      ensure!(
+        Self::max_weight().all_lte(T::BlockWeights::get().max_block),
+        "the migration does not fit in a block"
+      );
+      let stakes: u32 = AccountStakes::<T>::iter_values()
+        .map(|stakes| stakes.len() as u32)
+        .sum();
+      Ok(stakes.encode())
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+      let stakes: u32 =
+        Decode::decode(&mut &state[..]).map_err(|_| "unable to decode the stakes count")?;
+      ensure!(
+        StakeShares::<T>::iter_keys().count() as u32 == stakes,
+        "every stake must have shares"
+      );
+      ensure!(
+        PendingStoredSessions::<T>::iter_keys().next().is_none(),
+        "pending sessions must be drained"
+      );
+      ensure!(
+        QueueCompound::<T>::iter_keys().next().is_none(),
+        "compound queue must be drained"
+      );
+      ensure!(
+        Pallet::<T>::on_chain_storage_version() == 3,
+        "storage version must be 3"
+      );
+      Ok(())
+    }
+  }
+}
@@ -23,7 +23,7 @@ use frame_utils::construct_mock_runtime;
 use sp_runtime::traits::AccountIdConversion;
 use system::EnsureRoot;
 use tidefi_primitives::{
-  assets, BlockNumber, CurrencyId, MarketPair, SessionIndex, StakeCurrencyMeta,
+  assets, BlockNumber, CurrencyId, EraIndex, MarketPair, SessionIndex, StakeCurrencyMeta,
 };
 
 pub struct EnsureRootOrAssetRegistry;
@@ -70,6 +70,7 @@ construct_mock_runtime!({
   AssetRegistry: pallet_asset_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
 }, {
   pub const AssetDeposit: u64 = 1;
+  pub const PauseDuration: u64 = 100;
   pub const ApprovalDeposit: u64 = 1;
   pub const StringLimit: u32 = 50;
   pub const MetadataDepositBase: u64 = 1;
@@ -80,12 +81,18 @@ construct_mock_runtime!({
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
   pub const FeesPalletId: PalletId = PalletId(*b"fees*pal");
   pub const SunrisePalletId: PalletId = PalletId(*b"sunr*pal");
+  pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
   pub const SessionsPerEra: SessionIndex = 10;
   pub const SessionsArchive: SessionIndex = 2;
+  pub const ErasArchive: EraIndex = 2;
+  pub const MaximumSessionsCatchUp: u32 = 5;
   pub const BlocksPerSession: BlockNumber = 50;
+  pub const ExpectedBlockTime: u64 = 6_000;
   pub const BlocksForceUnstake: BlockNumber = 256;
   pub const StakeAccountCap: u32 = 10;
   pub const StakingRewardCap: u32 = 100;
+  pub const SessionRewardsHistoryCap: u32 = 10;
+  pub const BlocksPerYear: BlockNumber = 14400 * 365;
   // 20 basis point
   pub const FeeAmount: Permill = Permill::from_perthousand(20);
   // 10 basis point
@@ -102,11 +109,16 @@ construct_mock_runtime!({
   pub const WatchListLimit: u32 = 10000;
   // The maximum number of pubkey each asset can have, should alway be more more than the current quorum active member set
   pub const PubkeyLimitPerAsset: u32 = 10;
+  pub const CompletedWithdrawalsLimit: u32 = 10;
   // The number of swap each account can have in queue
   pub const SwapLimitByAccount: u32 = 100;
   // The maximum number of supported market pairs
   pub const SupportedMarketPairsLimit: u8 = 255;
   pub const Cooldown: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ClaimWindow: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ReferralShare: Permill = Permill::from_percent(10);
+  // max 1k referral rewards per referee
+  pub const MaximumReferralRewardPerReferee: Balance = 1_000_000_000_000_000;
   // max 10k rewards
   pub const MaximumRewardPerSwap: Balance = 10_000_000_000_000_000;
   // 50%
@@ -139,6 +151,13 @@ impl pallet_assets::Config for Test {
 
 impl pallet_security::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type PauseOrigin = system::EnsureRoot<AccountId>;
+  type ResumeOrigin = system::EnsureRoot<AccountId>;
+  type PauseDuration = PauseDuration;
+  type MaxPauseRenewals = ConstU32<2>;
+  type UnpausablePallets = ();
+  type MaxNameLength = ConstU32<32>;
+  type MaxReasonLength = ConstU32<64>;
   type WeightInfo = pallet_security::weights::SubstrateWeight<Test>;
 }
 
@@ -152,6 +171,8 @@ impl pallet_tidefi::Config for Test {
   type Sunrise = Sunrise;
   type Security = Security;
   type AssetRegistry = AssetRegistry;
+  type MaxClaimedEras = ConstU32<100>;
+  type AddressValidator = ();
 }
 
 impl pallet_quorum::Config for Test {
@@ -162,6 +183,9 @@ impl pallet_quorum::Config for Test {
   type Security = Security;
   type Sunrise = Sunrise;
   type AssetRegistry = AssetRegistry;
+  type InclusionProof = ();
+  type InclusionVerifier = ();
+  type ForceOrigin = EnsureRoot<AccountId>;
   type ProposalsCap = ProposalsCap;
   type BurnedCap = BurnedCap;
   type ProposalLifetime = ProposalLifetime;
@@ -169,6 +193,7 @@ impl pallet_quorum::Config for Test {
   type VotesLimit = VotesLimit;
   type WatchListLimit = WatchListLimit;
   type PubkeyLimitPerAsset = PubkeyLimitPerAsset;
+  type CompletedWithdrawalsLimit = CompletedWithdrawalsLimit;
 }
 
 impl pallet_oracle::Config for Test {
@@ -179,12 +204,15 @@ impl pallet_oracle::Config for Test {
   type Security = Security;
   type SwapLimitByAccount = SwapLimitByAccount;
   type SupportedMarketPairsLimit = SupportedMarketPairsLimit;
+  type ForceOrigin = EnsureRoot<AccountId>;
+  type AssetRegistry = AssetRegistry;
   type Fees = Fees;
   type Sunrise = Sunrise;
 }
 
 impl pallet_fees::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type TreasuryPalletId = TreasuryPalletId;
   type Security = Security;
   type FeesPalletId = FeesPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
@@ -192,10 +220,14 @@ impl pallet_fees::Config for Test {
   type UnixTime = Timestamp;
   type SessionsPerEra = SessionsPerEra;
   type SessionsArchive = SessionsArchive;
+  type ErasArchive = ErasArchive;
+  type MaximumSessionsCatchUp = MaximumSessionsCatchUp;
+  type BatchSize = BatchSize;
   type FeeAmount = FeeAmount;
   type MarketMakerFeeAmount = MarketMakerFeeAmount;
   type MarketMakerLimitFeeAmount = MarketMakerLimitFeeAmount;
   type BlocksPerSession = BlocksPerSession;
+  type ExpectedBlockTime = ExpectedBlockTime;
   type Staking = TidefiStaking;
   type Sunrise = Sunrise;
   type WeightInfo = pallet_fees::weights::SubstrateWeight<Test>;
@@ -204,11 +236,19 @@ impl pallet_fees::Config for Test {
 impl pallet_sunrise::Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type Security = Security;
+  type AssetRegistry = AssetRegistry;
   type SunrisePalletId = SunrisePalletId;
   type CurrencyTidefi = Adapter<AccountId>;
   type Cooldown = Cooldown;
+  type ClaimWindow = ClaimWindow;
+  type ReferralShare = ReferralShare;
+  type MaximumReferralRewardPerReferee = MaximumReferralRewardPerReferee;
+  type BatchSize = BatchSize;
   type MaximumRewardPerSwap = MaximumRewardPerSwap;
   type LeftoverSwapRebates = LeftoverSwapRebates;
+  type TreasuryPalletId = TreasuryPalletId;
+  type ForceOrigin = system::EnsureRoot<AccountId>;
+  type WeightInfo = pallet_sunrise::weights::SubstrateWeight<Test>;
 }
 
 impl pallet_tidefi_stake::Config for Test {
@@ -219,9 +259,13 @@ impl pallet_tidefi_stake::Config for Test {
   type StakeAccountCap = StakeAccountCap;
   type BlocksForceUnstake = BlocksForceUnstake;
   type StakingRewardCap = StakingRewardCap;
+  type SessionRewardsHistoryCap = SessionRewardsHistoryCap;
+  type BlocksPerYear = BlocksPerYear;
   type AssetRegistry = AssetRegistry;
   type Security = Security;
+  type Sunrise = Sunrise;
   type BatchSize = BatchSize;
+  type ForceOrigin = EnsureRoot<AccountId>;
 }
 
 impl pallet_asset_registry::Config for Test {
@@ -321,14 +365,24 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
   .unwrap();
 
   pallet_asset_registry::GenesisConfig::<Test> {
-    assets: vec![(
-      CurrencyId::Wrapped(2),
-      "Test".into(),
-      "TEST".into(),
-      8,
-      vec![],
-    )],
+    assets: vec![
+      (
+        CurrencyId::Wrapped(2),
+        "Test".into(),
+        "TEST".into(),
+        8,
+        vec![],
+      ),
+      (
+        CurrencyId::Wrapped(3),
+        "Staked Test".into(),
+        "stTEST".into(),
+        8,
+        vec![],
+      ),
+    ],
     account: 0_u64.into(),
+    infos: Vec::new(),
   }
   .assimilate_storage(&mut t)
   .unwrap();
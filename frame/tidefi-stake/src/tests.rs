@@ -17,15 +17,16 @@
 use crate::{
   mock::{
     new_test_ext, AccountId, Adapter, Fees, Oracle, RuntimeEvent as MockEvent, RuntimeOrigin,
-    Security, StakeAccountCap, System, Test, Tidefi, TidefiStaking,
+    Security, StakeAccountCap, Sunrise, System, Test, Tidefi, TidefiStaking,
   },
-  pallet as pallet_tidefi_stake, AccountStakes, Error, PendingStoredSessions, QueueUnstake,
-  SessionTotalFees, StakingPool,
+  pallet as pallet_tidefi_stake, AccountStakes, BatchType, CounterForQueueExpiration, Error,
+  LiquidSharesPools, LiquidStakingMeta, QueueExpiration, QueueUnstake, SessionRewardsHistory,
+  SharesPool, SharesPools, StakeShares, StakingPool,
 };
 use frame_support::{
   assert_noop, assert_ok,
   traits::{
-    fungibles::{Inspect, InspectHold, Mutate},
+    fungibles::{Inspect, InspectHold, Mutate, Transfer},
     Get, Hooks,
   },
   BoundedVec,
@@ -37,14 +38,17 @@ use sp_runtime::{
 };
 use std::str::FromStr;
 use tidefi_primitives::{
-  pallet::{FeesExt, OracleExt, StakingExt},
-  Balance, CurrencyId, Hash, Stake, StakeStatus, SwapConfirmation, SwapStatus, SwapType,
+  pallet::{FeesExt, OracleExt, StakingExt, SunriseExt},
+  Balance, CurrencyId, Hash, Stake, StakeCurrencyMeta, StakeStatus, SwapConfirmation, SwapStatus,
+  SwapType,
 };
 
 type BlockNumber = u64;
 
 const TEST_TOKEN: u32 = 2;
 const TEST_TOKEN_CURRENCY_ID: CurrencyId = CurrencyId::Wrapped(TEST_TOKEN);
+const RECEIPT_TOKEN: u32 = 3;
+const RECEIPT_TOKEN_CURRENCY_ID: CurrencyId = CurrencyId::Wrapped(RECEIPT_TOKEN);
 const FIFTEEN_DAYS: BlockNumber = 14400 * 15;
 const BLOCKS_FORCE_UNLOCK: BlockNumber = 256;
 
@@ -147,6 +151,18 @@ impl Context {
     self
   }
 
+  fn enable_liquid_staking(self) -> Self {
+    assert_ok!(TidefiStaking::set_liquid_staking(
+      RuntimeOrigin::root(),
+      TEST_TOKEN_CURRENCY_ID,
+      Some(LiquidStakingMeta {
+        receipt_currency_id: RECEIPT_TOKEN_CURRENCY_ID,
+        reward: Percent::from_percent(2),
+      })
+    ));
+    self
+  }
+
   fn mint_asset_for_accounts(accounts: Vec<AccountId>, asset: CurrencyId, amount: u128) {
     for account in accounts {
       assert_ok!(Adapter::mint_into(asset, &account, amount));
@@ -163,7 +179,8 @@ impl Context {
       RuntimeOrigin::signed(self.staker),
       CurrencyId::Tdfy,
       self.tdfy_amount,
-      self.duration
+      self.duration,
+      false
     ));
 
     assert_eq!(
@@ -184,7 +201,8 @@ impl Context {
       RuntimeOrigin::signed(self.staker),
       TEST_TOKEN_CURRENCY_ID,
       self.test_token_amount,
-      self.duration
+      self.duration,
+      false
     ));
 
     assert_eq!(
@@ -390,6 +408,10 @@ fn run_on_idle_hook<T: pallet_tidefi_stake::Config>() {
   TidefiStaking::on_idle(0, remaining_weight);
 }
 
+fn first_stake_principal(account_id: AccountId) -> Balance {
+  TidefiStaking::stake_principal(TidefiStaking::account_stakes(account_id).first().unwrap())
+}
+
 #[test]
 pub fn check_genesis_config() {
   new_test_ext().execute_with(|| {
@@ -420,7 +442,8 @@ mod stake {
           RuntimeOrigin::signed(context.staker),
           CurrencyId::Tdfy,
           context.tdfy_amount,
-          context.duration
+          context.duration,
+          false
         ));
 
         assert_eq!(
@@ -459,7 +482,8 @@ mod stake {
           RuntimeOrigin::signed(context.staker),
           TEST_TOKEN_CURRENCY_ID,
           context.test_token_amount,
-          context.duration
+          context.duration,
+          false
         ));
 
         assert_eq!(
@@ -500,7 +524,8 @@ mod stake {
             RuntimeOrigin::none(),
             CurrencyId::Tdfy,
             context.tdfy_amount,
-            context.duration
+            context.duration,
+            false
           ),
           BadOrigin
         );
@@ -518,7 +543,8 @@ mod stake {
             RuntimeOrigin::signed(context.staker),
             CurrencyId::Tdfy,
             context.tdfy_amount,
-            1
+            1,
+            false
           ),
           Error::<Test>::InvalidDuration
         );
@@ -540,7 +566,8 @@ mod stake {
             RuntimeOrigin::signed(context.staker),
             TEST_TOKEN_CURRENCY_ID,
             minimum_stake_amount - 1,
-            context.duration
+            context.duration,
+            false
           ),
           Error::<Test>::AmountTooSmall
         );
@@ -559,7 +586,8 @@ mod stake {
             RuntimeOrigin::signed(context.staker),
             TEST_TOKEN_CURRENCY_ID,
             u128::MAX,
-            context.duration
+            context.duration,
+            false
           ),
           Error::<Test>::AmountTooLarge
         );
@@ -580,7 +608,8 @@ mod stake {
             RuntimeOrigin::signed(context.staker),
             TEST_TOKEN_CURRENCY_ID,
             context.test_token_amount,
-            context.duration
+            context.duration,
+            false
           ),
           ArithmeticError::Overflow
         );
@@ -600,7 +629,8 @@ mod stake {
             RuntimeOrigin::signed(context.staker),
             CurrencyId::Tdfy,
             context.tdfy_amount,
-            context.duration
+            context.duration,
+            false
           ),
           DispatchError::Other("Invalid stake; eqd")
         );
@@ -723,7 +753,7 @@ mod unstake {
             Adapter::balance(TEST_TOKEN_CURRENCY_ID, &context.fees_pallet_account)
           );
 
-          // End the session in an advanced block, so the session total fees is transferred from fees pallet to operator account in stake pallet
+          // End the session in an advanced block, so the session total fees is transferred from fees pallet to the stakers in stake pallet
           set_current_block(2);
           assert_ok!(TidefiStaking::on_session_end(
             2,
@@ -750,11 +780,12 @@ mod unstake {
             .into_iter()
             .find(|stake| stake.unique_id == context.stake_id)
             .unwrap();
-          let latest_principal_with_rewards = stake.principal;
+          let latest_principal_with_rewards = TidefiStaking::stake_principal(&stake);
 
           assert_ok!(TidefiStaking::unstake(
             RuntimeOrigin::signed(context.staker),
             context.stake_id,
+            false,
             false
           ));
           System::assert_has_event(MockEvent::TidefiStaking(
@@ -930,10 +961,6 @@ mod unstake {
             context.fees_pallet_account,
           ));
 
-          // run 2x on_idle to clean the Queue
-          run_on_idle_hook::<Test>();
-          run_on_idle_hook::<Test>();
-
           // Finish staking period
           set_current_block(FIFTEEN_DAYS + 1);
 
@@ -942,12 +969,13 @@ mod unstake {
             .into_iter()
             .find(|stake| stake.unique_id == context.stake_id)
             .unwrap();
-          let latest_principal_with_rewards = stake.principal;
+          let latest_principal_with_rewards = TidefiStaking::stake_principal(&stake);
 
           // Unstake
           assert_ok!(TidefiStaking::unstake(
             RuntimeOrigin::signed(context.staker),
             context.stake_id,
+            false,
             false
           ));
           System::assert_has_event(MockEvent::TidefiStaking(
@@ -1021,7 +1049,8 @@ mod unstake {
           assert_ok!(TidefiStaking::unstake(
             RuntimeOrigin::signed(context.staker),
             context.stake_id,
-            true
+            true,
+            false
           ));
 
           assert_eq!(
@@ -1053,7 +1082,8 @@ mod unstake {
           assert_ok!(TidefiStaking::unstake(
             RuntimeOrigin::signed(context.staker),
             context.stake_id,
-            true
+            true,
+            false
           ));
 
           assert_eq!(
@@ -1069,6 +1099,109 @@ mod unstake {
             staker_balance_before - unstaking_fee,
             Adapter::balance(TEST_TOKEN_CURRENCY_ID, &context.staker)
           );
+          System::assert_has_event(MockEvent::TidefiStaking(
+            pallet_tidefi_stake::Event::UnstakeQueued {
+              request_id: context.stake_id,
+              account_id: context.staker,
+              fee_currency_id: TEST_TOKEN_CURRENCY_ID,
+              fee: unstaking_fee,
+            },
+          ));
+        });
+      }
+
+      #[test]
+      fn for_wrapped_asset_with_fee_in_tdfy() {
+        new_test_ext().execute_with(|| {
+          let context = Context::default()
+            .set_operator_account()
+            .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+            .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+            .stake_test_tokens();
+
+          // 1 test token = 1000 TDFY
+          assert_ok!(Sunrise::register_exchange_rate(vec![(
+            TEST_TOKEN,
+            1_000 * ONE_TDFY
+          )]));
+
+          let staker_test_token_balance_before =
+            Adapter::balance(TEST_TOKEN_CURRENCY_ID, &context.staker);
+
+          assert_ok!(TidefiStaking::unstake(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            true,
+            true
+          ));
+
+          // 1% of 1 test token = 10 TDFY
+          let unstaking_fee = 10 * ONE_TDFY;
+          assert_eq!(
+            Sunrise::try_get_tdfy_value(
+              TEST_TOKEN_CURRENCY_ID,
+              TidefiStaking::unstake_fee() * context.test_token_amount
+            )
+            .unwrap(),
+            unstaking_fee
+          );
+          assert_eq!(
+            ALICE_INITIAL_ONE_THOUSAND_TDFYS - unstaking_fee,
+            Adapter::balance(CurrencyId::Tdfy, &context.staker)
+          );
+          assert_eq!(
+            staker_test_token_balance_before,
+            Adapter::balance(TEST_TOKEN_CURRENCY_ID, &context.staker)
+          );
+          assert_eq!(
+            ONE_TDFY + unstaking_fee,
+            Adapter::balance(CurrencyId::Tdfy, &OPERATOR_ACCOUNT_ID)
+          );
+          System::assert_has_event(MockEvent::TidefiStaking(
+            pallet_tidefi_stake::Event::UnstakeQueued {
+              request_id: context.stake_id,
+              account_id: context.staker,
+              fee_currency_id: CurrencyId::Tdfy,
+              fee: unstaking_fee,
+            },
+          ));
+        });
+      }
+
+      #[test]
+      fn for_wrapped_asset_with_fee_in_staked_currency_without_tdfy_rate() {
+        new_test_ext().execute_with(|| {
+          let context = Context::default()
+            .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+            .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+            .stake_test_tokens();
+
+          let staker_balance_before = Adapter::balance(TEST_TOKEN_CURRENCY_ID, &context.staker);
+
+          assert_ok!(TidefiStaking::unstake(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            true,
+            true
+          ));
+
+          let unstaking_fee = TidefiStaking::unstake_fee() * context.test_token_amount;
+          assert_eq!(
+            staker_balance_before - unstaking_fee,
+            Adapter::balance(TEST_TOKEN_CURRENCY_ID, &context.staker)
+          );
+          assert_eq!(
+            ALICE_INITIAL_ONE_THOUSAND_TDFYS,
+            Adapter::balance(CurrencyId::Tdfy, &context.staker)
+          );
+          System::assert_has_event(MockEvent::TidefiStaking(
+            pallet_tidefi_stake::Event::UnstakeQueued {
+              request_id: context.stake_id,
+              account_id: context.staker,
+              fee_currency_id: TEST_TOKEN_CURRENCY_ID,
+              fee: unstaking_fee,
+            },
+          ));
         });
       }
     }
@@ -1085,7 +1218,7 @@ mod unstake {
           .stake_tdfy();
 
         assert_noop!(
-          TidefiStaking::unstake(RuntimeOrigin::none(), Hash::zero(), true),
+          TidefiStaking::unstake(RuntimeOrigin::none(), Hash::zero(), true, false),
           BadOrigin
         );
       });
@@ -1099,7 +1232,12 @@ mod unstake {
           .stake_tdfy();
 
         assert_noop!(
-          TidefiStaking::unstake(RuntimeOrigin::signed(context.staker), Hash::zero(), true),
+          TidefiStaking::unstake(
+            RuntimeOrigin::signed(context.staker),
+            Hash::zero(),
+            true,
+            false
+          ),
           Error::<Test>::InvalidStakeId
         );
       });
@@ -1116,6 +1254,7 @@ mod unstake {
           TidefiStaking::unstake(
             RuntimeOrigin::signed(context.staker),
             context.stake_id,
+            false,
             false
           ),
           Error::<Test>::UnstakingNotReady
@@ -1134,7 +1273,8 @@ mod unstake {
           TidefiStaking::unstake(
             RuntimeOrigin::signed(context.staker),
             context.stake_id,
-            true
+            true,
+            false
           ),
           Error::<Test>::InsufficientBalance
         );
@@ -1153,12 +1293,39 @@ mod unstake {
           TidefiStaking::unstake(
             RuntimeOrigin::signed(context.staker),
             context.stake_id,
-            true
+            true,
+            false
           ),
           Error::<Test>::InsufficientBalance
         );
       });
     }
+
+    #[test]
+    fn stake_is_already_queued() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_ok!(TidefiStaking::unstake(
+          RuntimeOrigin::signed(context.staker),
+          context.stake_id,
+          true,
+          false
+        ));
+
+        assert_noop!(
+          TidefiStaking::unstake(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            true,
+            false
+          ),
+          Error::<Test>::StakeIsPendingUnlock
+        );
+      });
+    }
   }
 }
 
@@ -1171,7 +1338,8 @@ pub fn should_stake_and_unstake() {
       RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
       CurrencyId::Tdfy,
       ALICE_STAKE_ONE_TDFY,
-      FIFTEEN_DAYS
+      FIFTEEN_DAYS,
+      false
     ));
 
     assert_eq!(
@@ -1206,6 +1374,7 @@ pub fn should_stake_and_unstake() {
     assert_ok!(TidefiStaking::unstake(
       RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
       stake_id,
+      false,
       false
     ));
     assert!(TidefiStaking::account_stakes(ALICE_ACCOUNT_ID).len() == 0);
@@ -1226,7 +1395,8 @@ pub fn should_stake_and_unstake_queue() {
       RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
       CurrencyId::Tdfy,
       ALICE_STAKE_ONE_TDFY,
-      FIFTEEN_DAYS
+      FIFTEEN_DAYS,
+      false
     ));
 
     assert_eq!(
@@ -1261,7 +1431,8 @@ pub fn should_stake_and_unstake_queue() {
     assert_ok!(TidefiStaking::unstake(
       RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
       stake_id,
-      true
+      true,
+      false
     ));
 
     assert!(TidefiStaking::account_stakes(ALICE_ACCOUNT_ID).len() == 1);
@@ -1301,7 +1472,8 @@ pub fn should_stake_multiple_and_unstake_queue() {
       RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
       CurrencyId::Tdfy,
       ALICE_STAKE_ONE_TDFY,
-      FIFTEEN_DAYS
+      FIFTEEN_DAYS,
+      false
     ));
 
     assert_eq!(
@@ -1337,7 +1509,8 @@ pub fn should_stake_multiple_and_unstake_queue() {
       RuntimeOrigin::signed(BOB_ACCOUNT_ID),
       CurrencyId::Tdfy,
       BOB_STAKE_QUARTER_TDFY,
-      FIFTEEN_DAYS
+      FIFTEEN_DAYS,
+      false
     ));
 
     let bob_stake_id = TidefiStaking::account_stakes(BOB_ACCOUNT_ID)
@@ -1349,7 +1522,8 @@ pub fn should_stake_multiple_and_unstake_queue() {
       RuntimeOrigin::signed(BOB_ACCOUNT_ID),
       CurrencyId::Tdfy,
       BOB_STAKE_QUARTER_TDFY,
-      FIFTEEN_DAYS * 2
+      FIFTEEN_DAYS * 2,
+      false
     ));
 
     set_current_block(FIFTEEN_DAYS - 2_000);
@@ -1358,7 +1532,8 @@ pub fn should_stake_multiple_and_unstake_queue() {
     assert_ok!(TidefiStaking::unstake(
       RuntimeOrigin::signed(BOB_ACCOUNT_ID),
       bob_stake_id,
-      true
+      true,
+      false
     ));
 
     set_current_block(FIFTEEN_DAYS - 2_000 + (BLOCKS_FORCE_UNLOCK / 2));
@@ -1371,7 +1546,8 @@ pub fn should_stake_multiple_and_unstake_queue() {
     assert_ok!(TidefiStaking::unstake(
       RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
       stake_id,
-      true
+      true,
+      false
     ));
     assert_eq!(QueueUnstake::<Test>::count(), 2);
 
@@ -1430,12 +1606,13 @@ pub fn should_stake_multiple_and_unstake_queue() {
 pub fn should_calculate_rewards() {
   new_test_ext().execute_with(|| {
     const ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
-    const BOB_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
-    const CHARLIE_STAKE_FOUR_HUNDRED_TEST_TOKENS: Balance = 400 * ONE_TEST_TOKEN;
+    const BOB_STAKE_ONE_HUNDRED_TWO_TEST_TOKENS: Balance = 102 * ONE_TEST_TOKEN;
+    const CHARLIE_STAKE_TWO_HUNDRED_SIX_TEST_TOKENS: Balance = 206 * ONE_TEST_TOKEN;
     let fees_pallet_account: AccountId =
       <Test as pallet_fees::Config>::FeesPalletId::get().into_account_truncating();
 
     Context::default()
+      .set_operator_account()
       .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
       .mint_test_token(BOB_ACCOUNT_ID, BOB_INITIAL_ONE_THOUSAND_TEST_TOKENS)
       .mint_test_token(CHARLIE_ACCOUNT_ID, CHARLIE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
@@ -1453,7 +1630,8 @@ pub fn should_calculate_rewards() {
       RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
       TEST_TOKEN_CURRENCY_ID,
       ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
-      FIFTEEN_DAYS
+      FIFTEEN_DAYS,
+      false
     ));
 
     // make sure the staking pool has been updated
@@ -1473,7 +1651,7 @@ pub fn should_calculate_rewards() {
     );
 
     // 100 for TDFY in fees for session 1
-    // 15 days should get 2%, so 2 tides (100% of the pool)
+    // 15 days should get 2%, so 2 test tokens (100% of the pool)
     let expected_max_rewards = 2 * ONE_TEST_TOKEN;
 
     assert_ok!(TidefiStaking::on_session_end(
@@ -1485,32 +1663,51 @@ pub fn should_calculate_rewards() {
       fees_pallet_account
     ));
 
-    run_on_idle_hook::<Test>();
-
-    // 100% of the pool (2 TDFY as reward)
+    // 100% of the pool (2 test tokens as reward), the rewards are available without `on_idle`
     let alice_staked_principal_after_session_1 =
       ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS.saturating_add(expected_max_rewards);
-
     assert_eq!(
-      TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
-        .first()
-        .unwrap()
-        .principal,
+      first_stake_principal(ALICE_ACCOUNT_ID),
       alice_staked_principal_after_session_1
     );
+    assert_eq!(
+      TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
+      Some(alice_staked_principal_after_session_1)
+    );
+
+    // the operator keeps the remaining fees
+    assert_eq!(
+      Adapter::balance(TEST_TOKEN_CURRENCY_ID, &OPERATOR_ACCOUNT_ID),
+      SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS - expected_max_rewards
+    );
 
+    // one share is worth 1.02 test token, so bob receives as many shares as alice
     assert_ok!(TidefiStaking::stake(
       RuntimeOrigin::signed(BOB_ACCOUNT_ID),
       TEST_TOKEN_CURRENCY_ID,
-      BOB_STAKE_ONE_HUNDRED_TEST_TOKENS,
-      FIFTEEN_DAYS
+      BOB_STAKE_ONE_HUNDRED_TWO_TEST_TOKENS,
+      FIFTEEN_DAYS,
+      false
     ));
+    let alice_stake_id = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+      .first()
+      .unwrap()
+      .unique_id;
+    let bob_stake_id = TidefiStaking::account_stakes(BOB_ACCOUNT_ID)
+      .first()
+      .unwrap()
+      .unique_id;
+    assert_eq!(
+      StakeShares::<Test>::get(bob_stake_id),
+      StakeShares::<Test>::get(alice_stake_id)
+    );
 
     // make sure the staking pool has been updated after bob stake
     assert_eq!(
       TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
       Some(
-        alice_staked_principal_after_session_1.saturating_add(BOB_STAKE_ONE_HUNDRED_TEST_TOKENS)
+        alice_staked_principal_after_session_1
+          .saturating_add(BOB_STAKE_ONE_HUNDRED_TWO_TEST_TOKENS)
       )
     );
 
@@ -1523,41 +1720,15 @@ pub fn should_calculate_rewards() {
       fees_pallet_account
     ));
 
-    let staking_pool_before_compound_session_1 =
-      TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID).unwrap_or(0);
-    run_on_idle_hook::<Test>();
-
-    let alice_pool_percentage_after_session_2 = Perquintill::from_rational(
-      alice_staked_principal_after_session_1,
-      staking_pool_before_compound_session_1,
-    );
-
-    let expected_alice_staked_principal_after_session_2 = alice_staked_principal_after_session_1
-      .saturating_add(alice_pool_percentage_after_session_2 * expected_max_rewards);
-
-    let bob_pool_after_session_2 = Perquintill::from_rational(
-      BOB_STAKE_ONE_HUNDRED_TEST_TOKENS,
-      expected_alice_staked_principal_after_session_2
-        .saturating_add(BOB_STAKE_ONE_HUNDRED_TEST_TOKENS),
-    );
-
-    let expected_bob_staked_principal_after_session_2 = BOB_STAKE_ONE_HUNDRED_TEST_TOKENS
-      .saturating_add(bob_pool_after_session_2 * expected_max_rewards);
-
+    // alice and bob own 50% of the shares each
+    let expected_staked_principal_after_session_2 = 103 * ONE_TEST_TOKEN;
     assert_eq!(
-      TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
-        .first()
-        .unwrap()
-        .principal,
-      expected_alice_staked_principal_after_session_2
+      first_stake_principal(ALICE_ACCOUNT_ID),
+      expected_staked_principal_after_session_2
     );
-
     assert_eq!(
-      TidefiStaking::account_stakes(BOB_ACCOUNT_ID)
-        .first()
-        .unwrap()
-        .principal,
-      expected_bob_staked_principal_after_session_2
+      first_stake_principal(BOB_ACCOUNT_ID),
+      expected_staked_principal_after_session_2
     );
 
     // 2 empty sessions
@@ -1572,30 +1743,23 @@ pub fn should_calculate_rewards() {
       fees_pallet_account
     ));
 
-    run_on_idle_hook::<Test>();
-
     // balance should be the same
     assert_eq!(
-      TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
-        .first()
-        .unwrap()
-        .principal,
-      expected_alice_staked_principal_after_session_2
+      first_stake_principal(ALICE_ACCOUNT_ID),
+      expected_staked_principal_after_session_2
     );
-
     assert_eq!(
-      TidefiStaking::account_stakes(BOB_ACCOUNT_ID)
-        .first()
-        .unwrap()
-        .principal,
-      expected_bob_staked_principal_after_session_2
+      first_stake_principal(BOB_ACCOUNT_ID),
+      expected_staked_principal_after_session_2
     );
 
+    // charlie owns 50% of the shares after his stake
     assert_ok!(TidefiStaking::stake(
       RuntimeOrigin::signed(CHARLIE_ACCOUNT_ID),
       TEST_TOKEN_CURRENCY_ID,
-      CHARLIE_STAKE_FOUR_HUNDRED_TEST_TOKENS,
-      FIFTEEN_DAYS
+      CHARLIE_STAKE_TWO_HUNDRED_SIX_TEST_TOKENS,
+      FIFTEEN_DAYS,
+      false
     ));
 
     assert_ok!(TidefiStaking::on_session_end(
@@ -1607,78 +1771,2064 @@ pub fn should_calculate_rewards() {
       fees_pallet_account
     ));
 
-    let staking_pool_before_compound_session_5 =
-      TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID).unwrap_or(0);
-    run_on_idle_hook::<Test>();
-
-    // charlie is processed first
-    let charlie_pool_percentage_after_session_5 = Perquintill::from_rational(
-      CHARLIE_STAKE_FOUR_HUNDRED_TEST_TOKENS,
-      staking_pool_before_compound_session_5,
+    assert_eq!(
+      first_stake_principal(CHARLIE_ACCOUNT_ID),
+      CHARLIE_STAKE_TWO_HUNDRED_SIX_TEST_TOKENS + expected_max_rewards / 2
     );
-
-    let expected_charlie_staked_principal_after_session_5 = CHARLIE_STAKE_FOUR_HUNDRED_TEST_TOKENS
-      .saturating_add(charlie_pool_percentage_after_session_5 * expected_max_rewards);
-
     assert_eq!(
-      TidefiStaking::account_stakes(CHARLIE_ACCOUNT_ID)
-        .first()
-        .unwrap()
-        .principal,
-      expected_charlie_staked_principal_after_session_5
+      first_stake_principal(ALICE_ACCOUNT_ID),
+      expected_staked_principal_after_session_2 + expected_max_rewards / 4
     );
-
-    let alice_pool_percentage_after_session_5 = Perquintill::from_rational(
-      expected_alice_staked_principal_after_session_2,
-      staking_pool_before_compound_session_5.saturating_add(
-        // get only the rewards from charlie to add them to the staking pool variable
-        expected_charlie_staked_principal_after_session_5
-          .saturating_sub(CHARLIE_STAKE_FOUR_HUNDRED_TEST_TOKENS),
-      ),
+    assert_eq!(
+      first_stake_principal(BOB_ACCOUNT_ID),
+      expected_staked_principal_after_session_2 + expected_max_rewards / 4
     );
-
-    let expected_alice_staked_principal_after_session_5 =
-      expected_alice_staked_principal_after_session_2
-        .saturating_add(alice_pool_percentage_after_session_5 * expected_max_rewards);
-
     assert_eq!(
-      TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
-        .first()
-        .unwrap()
-        .principal,
-      expected_alice_staked_principal_after_session_5
+      TidefiStaking::shares_pool(TEST_TOKEN_CURRENCY_ID, FIFTEEN_DAYS),
+      SharesPool {
+        total_shares: 4 * ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+        total_balance: TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID).unwrap(),
+      }
     );
+  });
+}
 
-    let bob_pool_percentage_after_session_5 = Perquintill::from_rational(
-      expected_bob_staked_principal_after_session_2,
-      staking_pool_before_compound_session_5
-        .saturating_add(
-          // get only the rewards from charlie to add them to the staking pool variable
-          // as its a new stake
-          expected_charlie_staked_principal_after_session_5
-            .saturating_sub(CHARLIE_STAKE_FOUR_HUNDRED_TEST_TOKENS),
-        )
-        // add alice rewards
-        .saturating_add(alice_pool_percentage_after_session_5 * expected_max_rewards),
-    );
+#[test]
+pub fn should_weight_rewards_by_staking_period() {
+  new_test_ext().execute_with(|| {
+    const ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
+    let fees_pallet_account: AccountId =
+      <Test as pallet_fees::Config>::FeesPalletId::get().into_account_truncating();
+
+    Context::default()
+      .set_operator_account()
+      .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+      .mint_test_token(BOB_ACCOUNT_ID, BOB_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+
+    assert_ok!(Tidefi::transfer(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      fees_pallet_account,
+      TEST_TOKEN_CURRENCY_ID,
+      SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+    ));
+
+    set_current_block(1);
+
+    assert_ok!(TidefiStaking::stake(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      TEST_TOKEN_CURRENCY_ID,
+      ONE_HUNDRED_TEST_TOKENS,
+      FIFTEEN_DAYS,
+      false
+    ));
+    assert_ok!(TidefiStaking::stake(
+      RuntimeOrigin::signed(BOB_ACCOUNT_ID),
+      TEST_TOKEN_CURRENCY_ID,
+      ONE_HUNDRED_TEST_TOKENS,
+      FIFTEEN_DAYS * 2,
+      false
+    ));
+
+    assert_ok!(TidefiStaking::on_session_end(
+      1,
+      vec![(
+        TEST_TOKEN_CURRENCY_ID,
+        SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+      )],
+      fees_pallet_account
+    ));
 
-    let expected_bob_staked_principal_after_session_5 =
-      expected_bob_staked_principal_after_session_2
-        .saturating_add(bob_pool_percentage_after_session_5 * expected_max_rewards);
+    // 15 days get 2% of the fees for 50% of the pool
+    let alice_rewards = ONE_TEST_TOKEN;
+    // 30 days get 3% of the fees for 50% of the pool
+    let bob_rewards = 3 * ONE_TEST_TOKEN / 2;
 
     assert_eq!(
-      TidefiStaking::account_stakes(BOB_ACCOUNT_ID)
-        .first()
-        .unwrap()
-        .principal,
-      expected_bob_staked_principal_after_session_5
+      first_stake_principal(ALICE_ACCOUNT_ID),
+      ONE_HUNDRED_TEST_TOKENS + alice_rewards
     );
+    assert_eq!(
+      first_stake_principal(BOB_ACCOUNT_ID),
+      ONE_HUNDRED_TEST_TOKENS + bob_rewards
+    );
+    assert_eq!(
+      TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
+      Some(2 * ONE_HUNDRED_TEST_TOKENS + alice_rewards + bob_rewards)
+    );
+    assert_eq!(
+      Adapter::balance(TEST_TOKEN_CURRENCY_ID, &OPERATOR_ACCOUNT_ID),
+      SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS - alice_rewards - bob_rewards
+    );
+    System::assert_has_event(MockEvent::TidefiStaking(
+      pallet_tidefi_stake::Event::SessionFinished {
+        session_index: 1,
+        pool: vec![(TEST_TOKEN_CURRENCY_ID, alice_rewards + bob_rewards)],
+        operator: vec![(
+          TEST_TOKEN_CURRENCY_ID,
+          SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS - alice_rewards - bob_rewards,
+        )],
+      },
+    ));
 
-    // make sure we can drain all sessions
-    assert_eq!(PendingStoredSessions::<Test>::count(), 1);
-    assert_eq!(SessionTotalFees::<Test>::iter().count(), 1);
-    run_on_idle_hook::<Test>();
-    assert_eq!(PendingStoredSessions::<Test>::count(), 0);
-    assert_eq!(SessionTotalFees::<Test>::iter().count(), 0);
-  });
+    // unstaking burns the shares and pays the rewards
+    set_current_block(FIFTEEN_DAYS + 1);
+    let alice_stake_id = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+      .first()
+      .unwrap()
+      .unique_id;
+    assert_ok!(TidefiStaking::unstake(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      alice_stake_id,
+      false,
+      false
+    ));
+    assert_eq!(
+      Adapter::balance(TEST_TOKEN_CURRENCY_ID, &ALICE_ACCOUNT_ID),
+      ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS - SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+        + alice_rewards
+    );
+    assert_eq!(StakeShares::<Test>::get(alice_stake_id), 0);
+    assert!(!SharesPools::<Test>::contains_key(
+      TEST_TOKEN_CURRENCY_ID,
+      FIFTEEN_DAYS
+    ));
+    assert_eq!(
+      TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
+      Some(ONE_HUNDRED_TEST_TOKENS + bob_rewards)
+    );
+  });
+}
+
+mod add_staking_period {
+  use super::*;
+
+  const SEVEN_DAYS: BlockNumber = 14400 * 7;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(TidefiStaking::add_staking_period(
+        RuntimeOrigin::root(),
+        SEVEN_DAYS,
+        Percent::from_parts(1)
+      ));
+
+      assert!(TidefiStaking::staking_rewards().contains(&(SEVEN_DAYS, Percent::from_parts(1))));
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::StakingPeriodAdded {
+          duration: SEVEN_DAYS,
+          reward: Percent::from_parts(1),
+        },
+      ));
+    });
+  }
+
+  #[test]
+  fn reactivates_a_retired_period() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(TidefiStaking::retire_staking_period(
+        RuntimeOrigin::root(),
+        FIFTEEN_DAYS
+      ));
+      assert_ok!(TidefiStaking::add_staking_period(
+        RuntimeOrigin::root(),
+        FIFTEEN_DAYS,
+        Percent::from_parts(3)
+      ));
+
+      assert_eq!(TidefiStaking::retired_staking_rewards(FIFTEEN_DAYS), None);
+      assert_eq!(
+        TidefiStaking::staking_period_reward(FIFTEEN_DAYS),
+        Some(Percent::from_parts(3))
+      );
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_root() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::add_staking_period(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            SEVEN_DAYS,
+            Percent::from_parts(1)
+          ),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn duration_is_zero() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::add_staking_period(RuntimeOrigin::root(), 0, Percent::from_parts(1)),
+          Error::<Test>::InvalidDuration
+        );
+      });
+    }
+
+    #[test]
+    fn period_already_exists() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::add_staking_period(
+            RuntimeOrigin::root(),
+            FIFTEEN_DAYS,
+            Percent::from_parts(1)
+          ),
+          Error::<Test>::StakingPeriodAlreadyExists
+        );
+      });
+    }
+  }
+}
+
+mod retire_staking_period {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      let context =
+        Context::default().mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS);
+
+      assert_ok!(TidefiStaking::retire_staking_period(
+        RuntimeOrigin::root(),
+        FIFTEEN_DAYS
+      ));
+
+      assert!(!TidefiStaking::staking_rewards()
+        .iter()
+        .any(|(duration, _)| *duration == FIFTEEN_DAYS));
+      assert_eq!(
+        TidefiStaking::retired_staking_rewards(FIFTEEN_DAYS),
+        Some(Percent::from_parts(2))
+      );
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::StakingPeriodRetired {
+          duration: FIFTEEN_DAYS,
+        },
+      ));
+
+      // no new stake can be created with the retired period
+      assert_noop!(
+        TidefiStaking::stake(
+          RuntimeOrigin::signed(context.staker),
+          CurrencyId::Tdfy,
+          context.tdfy_amount,
+          FIFTEEN_DAYS,
+          false
+        ),
+        Error::<Test>::InvalidDuration
+      );
+    });
+  }
+
+  #[test]
+  fn existing_stakes_are_still_rewarded() {
+    new_test_ext().execute_with(|| {
+      const ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
+      let context = Context::default()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+
+      assert_ok!(Tidefi::transfer(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        context.fees_pallet_account,
+        TEST_TOKEN_CURRENCY_ID,
+        500 * ONE_TEST_TOKEN
+      ));
+
+      set_current_block(1);
+
+      assert_ok!(TidefiStaking::stake(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+        FIFTEEN_DAYS,
+        false
+      ));
+
+      assert_ok!(TidefiStaking::retire_staking_period(
+        RuntimeOrigin::root(),
+        FIFTEEN_DAYS
+      ));
+
+      assert_ok!(TidefiStaking::on_session_end(
+        1,
+        vec![(
+          TEST_TOKEN_CURRENCY_ID,
+          SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+        )],
+        context.fees_pallet_account
+      ));
+
+      // 15 days retired with 2%, so 2 test tokens (100% of the pool)
+      assert_eq!(
+        first_stake_principal(ALICE_ACCOUNT_ID),
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS + 2 * ONE_TEST_TOKEN
+      );
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_root() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::retire_staking_period(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            FIFTEEN_DAYS
+          ),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn duration_is_invalid() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::retire_staking_period(RuntimeOrigin::root(), 1),
+          Error::<Test>::InvalidDuration
+        );
+      });
+    }
+  }
+}
+
+mod update_staking_period_reward {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(TidefiStaking::update_staking_period_reward(
+        RuntimeOrigin::root(),
+        FIFTEEN_DAYS,
+        Percent::from_parts(7)
+      ));
+
+      assert_eq!(
+        TidefiStaking::staking_period_reward(FIFTEEN_DAYS),
+        Some(Percent::from_parts(7))
+      );
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::StakingPeriodRewardUpdated {
+          duration: FIFTEEN_DAYS,
+          reward: Percent::from_parts(7),
+        },
+      ));
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_root() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::update_staking_period_reward(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            FIFTEEN_DAYS,
+            Percent::from_parts(7)
+          ),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn duration_is_invalid() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::update_staking_period_reward(
+            RuntimeOrigin::root(),
+            1,
+            Percent::from_parts(7)
+          ),
+          Error::<Test>::InvalidDuration
+        );
+      });
+    }
+  }
+}
+
+mod set_staking_currency_meta {
+  use super::*;
+
+  const META: StakeCurrencyMeta<Balance> = StakeCurrencyMeta {
+    minimum_amount: ONE_TEST_TOKEN,
+    maximum_amount: 10 * ONE_TEST_TOKEN,
+  };
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(TidefiStaking::set_staking_currency_meta(
+        RuntimeOrigin::root(),
+        TEST_TOKEN_CURRENCY_ID,
+        Some(META)
+      ));
+
+      assert_eq!(
+        TidefiStaking::staking_meta(TEST_TOKEN_CURRENCY_ID),
+        Some(META)
+      );
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::StakingCurrencyMetaUpdated {
+          currency_id: TEST_TOKEN_CURRENCY_ID,
+          meta: Some(META),
+        },
+      ));
+
+      assert_ok!(TidefiStaking::set_staking_currency_meta(
+        RuntimeOrigin::root(),
+        TEST_TOKEN_CURRENCY_ID,
+        None
+      ));
+      assert_eq!(TidefiStaking::staking_meta(TEST_TOKEN_CURRENCY_ID), None);
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_root() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::set_staking_currency_meta(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            TEST_TOKEN_CURRENCY_ID,
+            Some(META)
+          ),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn minimum_is_above_maximum() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::set_staking_currency_meta(
+            RuntimeOrigin::root(),
+            TEST_TOKEN_CURRENCY_ID,
+            Some(StakeCurrencyMeta {
+              minimum_amount: META.maximum_amount + 1,
+              maximum_amount: META.maximum_amount,
+            })
+          ),
+          Error::<Test>::InvalidStakingCurrencyMeta
+        );
+      });
+    }
+  }
+}
+
+mod set_unstake_fee {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(TidefiStaking::set_unstake_fee(
+        RuntimeOrigin::root(),
+        Percent::from_parts(5)
+      ));
+
+      assert_eq!(TidefiStaking::unstake_fee(), Percent::from_parts(5));
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::UnstakeFeeUpdated {
+          fee: Percent::from_parts(5),
+        },
+      ));
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_root() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::set_unstake_fee(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            Percent::from_parts(5)
+          ),
+          BadOrigin
+        );
+      });
+    }
+  }
+}
+
+mod unstake_partial {
+  use super::*;
+
+  const HALF_TDFY: Balance = ONE_TDFY / 2;
+
+  #[test]
+  fn succeeds_with_pro_rata_fee_before_staking_duration_expires() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .set_operator_account()
+        .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+        .stake_tdfy();
+
+      let staker_balance_before = Adapter::balance(CurrencyId::Tdfy, &context.staker);
+
+      assert_ok!(TidefiStaking::unstake_partial(
+        RuntimeOrigin::signed(context.staker),
+        context.stake_id,
+        HALF_TDFY
+      ));
+
+      // 1% of the half of the initial balance
+      let unstaking_fee = TidefiStaking::unstake_fee() * HALF_TDFY;
+      assert_eq!(
+        Adapter::balance(CurrencyId::Tdfy, &context.staker),
+        staker_balance_before + HALF_TDFY - unstaking_fee
+      );
+      assert_eq!(
+        Adapter::balance(CurrencyId::Tdfy, &OPERATOR_ACCOUNT_ID),
+        ONE_TDFY + unstaking_fee
+      );
+
+      let stake = TidefiStaking::account_stakes(context.staker)
+        .first()
+        .unwrap()
+        .clone();
+      assert_eq!(stake.initial_balance, HALF_TDFY);
+      assert_eq!(stake.principal, HALF_TDFY);
+      assert_eq!(stake.status, StakeStatus::Staked);
+      assert_eq!(TidefiStaking::stake_principal(&stake), HALF_TDFY);
+      assert_eq!(
+        TidefiStaking::staking_pool(CurrencyId::Tdfy),
+        Some(HALF_TDFY)
+      );
+      assert_eq!(
+        Adapter::balance(CurrencyId::Tdfy, &context.staking_pallet_account),
+        HALF_TDFY
+      );
+
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::PartiallyUnstaked {
+          request_id: context.stake_id,
+          account_id: context.staker,
+          currency_id: CurrencyId::Tdfy,
+          amount: HALF_TDFY,
+          fee: unstaking_fee,
+        },
+      ));
+    });
+  }
+
+  #[test]
+  fn succeeds_without_fee_when_staking_duration_expired() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .set_operator_account()
+        .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+        .stake_tdfy();
+
+      set_current_block(FIFTEEN_DAYS + 1);
+      let staker_balance_before = Adapter::balance(CurrencyId::Tdfy, &context.staker);
+
+      assert_ok!(TidefiStaking::unstake_partial(
+        RuntimeOrigin::signed(context.staker),
+        context.stake_id,
+        HALF_TDFY
+      ));
+
+      assert_eq!(
+        Adapter::balance(CurrencyId::Tdfy, &context.staker),
+        staker_balance_before + HALF_TDFY
+      );
+      assert_eq!(
+        Adapter::balance(CurrencyId::Tdfy, &OPERATOR_ACCOUNT_ID),
+        ONE_TDFY
+      );
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::PartiallyUnstaked {
+          request_id: context.stake_id,
+          account_id: context.staker,
+          currency_id: CurrencyId::Tdfy,
+          amount: HALF_TDFY,
+          fee: 0,
+        },
+      ));
+    });
+  }
+
+  #[test]
+  fn keeps_the_rewards_of_the_remaining_stake() {
+    new_test_ext().execute_with(|| {
+      const ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
+      let context = Context::default()
+        .set_operator_account()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+
+      assert_ok!(Tidefi::transfer(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        context.fees_pallet_account,
+        TEST_TOKEN_CURRENCY_ID,
+        SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+      ));
+
+      set_current_block(1);
+      assert_ok!(TidefiStaking::stake(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+        FIFTEEN_DAYS,
+        false
+      ));
+      assert_ok!(TidefiStaking::on_session_end(
+        1,
+        vec![(
+          TEST_TOKEN_CURRENCY_ID,
+          SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+        )],
+        context.fees_pallet_account
+      ));
+
+      // 2 test tokens of rewards, half of the stake is unstaked
+      let stake_id = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+        .first()
+        .unwrap()
+        .unique_id;
+      set_current_block(FIFTEEN_DAYS + 1);
+      assert_ok!(TidefiStaking::unstake_partial(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        stake_id,
+        51 * ONE_TEST_TOKEN
+      ));
+
+      let stake = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+        .first()
+        .unwrap()
+        .clone();
+      assert_eq!(stake.initial_balance, 50 * ONE_TEST_TOKEN);
+      assert_eq!(TidefiStaking::stake_principal(&stake), 51 * ONE_TEST_TOKEN);
+      assert_eq!(StakeShares::<Test>::get(stake_id), 50 * ONE_TEST_TOKEN);
+      assert_eq!(
+        TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
+        Some(51 * ONE_TEST_TOKEN)
+      );
+    });
+  }
+
+  #[test]
+  fn keeps_the_principal_of_the_remaining_stake_after_two_partial_unstakes() {
+    new_test_ext().execute_with(|| {
+      const ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
+      let context = Context::default()
+        .set_operator_account()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+
+      assert_ok!(Tidefi::transfer(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        context.fees_pallet_account,
+        TEST_TOKEN_CURRENCY_ID,
+        SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+      ));
+
+      set_current_block(1);
+      assert_ok!(TidefiStaking::stake(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+        FIFTEEN_DAYS,
+        false
+      ));
+      assert_ok!(TidefiStaking::on_session_end(
+        1,
+        vec![(
+          TEST_TOKEN_CURRENCY_ID,
+          SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+        )],
+        context.fees_pallet_account
+      ));
+
+      // 2 test tokens of rewards, the stake is unstaked twice
+      let stake_id = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+        .first()
+        .unwrap()
+        .unique_id;
+      set_current_block(FIFTEEN_DAYS + 1);
+      assert_ok!(TidefiStaking::unstake_partial(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        stake_id,
+        51 * ONE_TEST_TOKEN
+      ));
+      assert_ok!(TidefiStaking::unstake_partial(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        stake_id,
+        30 * ONE_TEST_TOKEN
+      ));
+
+      let stake = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+        .first()
+        .unwrap()
+        .clone();
+      assert_eq!(TidefiStaking::stake_principal(&stake), 21 * ONE_TEST_TOKEN);
+      assert_eq!(stake.principal, 21 * ONE_TEST_TOKEN);
+      assert!(stake.principal >= stake.initial_balance);
+      assert_eq!(
+        TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
+        Some(21 * ONE_TEST_TOKEN)
+      );
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_signed() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::unstake_partial(RuntimeOrigin::none(), context.stake_id, HALF_TDFY),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn stake_id_is_invalid() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::unstake_partial(
+            RuntimeOrigin::signed(context.staker),
+            Hash::zero(),
+            HALF_TDFY
+          ),
+          Error::<Test>::InvalidStakeId
+        );
+      });
+    }
+
+    #[test]
+    fn amount_is_zero() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::unstake_partial(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            0
+          ),
+          Error::<Test>::AmountTooSmall
+        );
+      });
+    }
+
+    #[test]
+    fn amount_is_the_whole_stake() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::unstake_partial(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            context.tdfy_amount
+          ),
+          Error::<Test>::AmountTooLarge
+        );
+      });
+    }
+
+    #[test]
+    fn remaining_stake_is_below_the_minimum_amount() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+          .stake_test_tokens();
+
+        assert_noop!(
+          TidefiStaking::unstake_partial(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            context.test_token_amount - 50
+          ),
+          Error::<Test>::AmountTooSmall
+        );
+      });
+    }
+
+    #[test]
+    fn stake_is_pending_unlock() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_ok!(TidefiStaking::unstake(
+          RuntimeOrigin::signed(context.staker),
+          context.stake_id,
+          true,
+          false
+        ));
+
+        assert_noop!(
+          TidefiStaking::unstake_partial(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            HALF_TDFY
+          ),
+          Error::<Test>::StakeIsPendingUnlock
+        );
+      });
+    }
+  }
+}
+
+mod add_to_stake {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+        .stake_tdfy();
+
+      assert_ok!(TidefiStaking::add_to_stake(
+        RuntimeOrigin::signed(context.staker),
+        context.stake_id,
+        context.tdfy_amount
+      ));
+
+      let stake = TidefiStaking::account_stakes(context.staker)
+        .first()
+        .unwrap()
+        .clone();
+      assert_eq!(TidefiStaking::account_stakes(context.staker).len(), 1);
+      assert_eq!(stake.initial_balance, 2 * context.tdfy_amount);
+      assert_eq!(stake.principal, 2 * context.tdfy_amount);
+      assert_eq!(stake.initial_block, BLOCK_NUMBER_ZERO);
+      assert_eq!(
+        TidefiStaking::stake_principal(&stake),
+        2 * context.tdfy_amount
+      );
+      assert_eq!(
+        TidefiStaking::staking_pool(CurrencyId::Tdfy),
+        Some(2 * context.tdfy_amount)
+      );
+      assert_eq!(
+        Adapter::balance(CurrencyId::Tdfy, &context.staker),
+        ALICE_INITIAL_ONE_THOUSAND_TDFYS - 2 * context.tdfy_amount
+      );
+      assert_eq!(
+        Adapter::balance(CurrencyId::Tdfy, &context.staking_pallet_account),
+        2 * context.tdfy_amount
+      );
+
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::StakeIncreased {
+          request_id: context.stake_id,
+          account_id: context.staker,
+          currency_id: CurrencyId::Tdfy,
+          amount: context.tdfy_amount,
+        },
+      ));
+    });
+  }
+
+  #[test]
+  fn mints_shares_at_the_current_value() {
+    new_test_ext().execute_with(|| {
+      const ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
+      let context = Context::default()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+
+      assert_ok!(Tidefi::transfer(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        context.fees_pallet_account,
+        TEST_TOKEN_CURRENCY_ID,
+        SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+      ));
+
+      set_current_block(1);
+      assert_ok!(TidefiStaking::stake(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+        FIFTEEN_DAYS,
+        false
+      ));
+      assert_ok!(TidefiStaking::on_session_end(
+        1,
+        vec![(
+          TEST_TOKEN_CURRENCY_ID,
+          SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+        )],
+        context.fees_pallet_account
+      ));
+
+      // one share is worth 1.02 test token
+      let stake_id = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+        .first()
+        .unwrap()
+        .unique_id;
+      assert_ok!(TidefiStaking::add_to_stake(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        stake_id,
+        51 * ONE_TEST_TOKEN
+      ));
+
+      let stake = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+        .first()
+        .unwrap()
+        .clone();
+      assert_eq!(stake.initial_balance, 151 * ONE_TEST_TOKEN);
+      assert_eq!(StakeShares::<Test>::get(stake_id), 150 * ONE_TEST_TOKEN);
+      assert_eq!(TidefiStaking::stake_principal(&stake), 153 * ONE_TEST_TOKEN);
+      assert_eq!(
+        TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
+        Some(153 * ONE_TEST_TOKEN)
+      );
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_signed() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::add_to_stake(RuntimeOrigin::none(), context.stake_id, ONE_TDFY),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn stake_id_is_invalid() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::add_to_stake(
+            RuntimeOrigin::signed(context.staker),
+            Hash::zero(),
+            ONE_TDFY
+          ),
+          Error::<Test>::InvalidStakeId
+        );
+      });
+    }
+
+    #[test]
+    fn amount_is_zero() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::add_to_stake(RuntimeOrigin::signed(context.staker), context.stake_id, 0),
+          Error::<Test>::AmountTooSmall
+        );
+      });
+    }
+
+    #[test]
+    fn stake_amount_is_too_large() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+          .stake_test_tokens();
+
+        // the maximum stake amount is 1000 test tokens
+        assert_noop!(
+          TidefiStaking::add_to_stake(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            1_000 * ONE_TEST_TOKEN
+          ),
+          Error::<Test>::AmountTooLarge
+        );
+      });
+    }
+
+    #[test]
+    fn staking_is_expired() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        set_current_block(FIFTEEN_DAYS);
+
+        assert_noop!(
+          TidefiStaking::add_to_stake(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            ONE_TDFY
+          ),
+          Error::<Test>::StakeIsExpired
+        );
+      });
+    }
+
+    #[test]
+    fn stake_is_pending_unlock() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_ok!(TidefiStaking::unstake(
+          RuntimeOrigin::signed(context.staker),
+          context.stake_id,
+          true,
+          false
+        ));
+
+        assert_noop!(
+          TidefiStaking::add_to_stake(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            ONE_TDFY
+          ),
+          Error::<Test>::StakeIsPendingUnlock
+        );
+      });
+    }
+
+    #[test]
+    fn has_insufficient_balance() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::add_to_stake(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            ALICE_INITIAL_ONE_THOUSAND_TDFYS
+          ),
+          Error::<Test>::InsufficientBalance
+        );
+      });
+    }
+  }
+}
+
+mod set_auto_renew {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+        .stake_tdfy();
+
+      assert!(!TidefiStaking::auto_renew(context.stake_id));
+
+      assert_ok!(TidefiStaking::set_auto_renew(
+        RuntimeOrigin::signed(context.staker),
+        context.stake_id,
+        true
+      ));
+      assert!(TidefiStaking::auto_renew(context.stake_id));
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::AutoRenewUpdated {
+          request_id: context.stake_id,
+          account_id: context.staker,
+          auto_renew: true,
+        },
+      ));
+
+      assert_ok!(TidefiStaking::set_auto_renew(
+        RuntimeOrigin::signed(context.staker),
+        context.stake_id,
+        false
+      ));
+      assert!(!TidefiStaking::auto_renew(context.stake_id));
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_signed() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::set_auto_renew(RuntimeOrigin::none(), context.stake_id, true),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn stake_id_is_invalid() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_noop!(
+          TidefiStaking::set_auto_renew(RuntimeOrigin::signed(context.staker), Hash::zero(), true),
+          Error::<Test>::InvalidStakeId
+        );
+      });
+    }
+
+    #[test]
+    fn stake_is_pending_unlock() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+          .stake_tdfy();
+
+        assert_ok!(TidefiStaking::unstake(
+          RuntimeOrigin::signed(context.staker),
+          context.stake_id,
+          true,
+          false
+        ));
+
+        assert_noop!(
+          TidefiStaking::set_auto_renew(
+            RuntimeOrigin::signed(context.staker),
+            context.stake_id,
+            true
+          ),
+          Error::<Test>::StakeIsPendingUnlock
+        );
+      });
+    }
+  }
+}
+
+mod stake_expiration {
+  use super::*;
+
+  const ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
+
+  fn stake_test_tokens_with_rewards(context: &Context, auto_renew: bool) -> Hash {
+    assert_ok!(Tidefi::transfer(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      context.fees_pallet_account,
+      TEST_TOKEN_CURRENCY_ID,
+      SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+    ));
+
+    set_current_block(1);
+    assert_ok!(TidefiStaking::stake(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      TEST_TOKEN_CURRENCY_ID,
+      ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+      FIFTEEN_DAYS,
+      auto_renew
+    ));
+
+    // 2 test tokens of rewards
+    assert_ok!(TidefiStaking::on_session_end(
+      1,
+      vec![(
+        TEST_TOKEN_CURRENCY_ID,
+        SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+      )],
+      context.fees_pallet_account
+    ));
+
+    TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+      .first()
+      .unwrap()
+      .unique_id
+  }
+
+  #[test]
+  fn pays_out_the_stake_without_auto_renew() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+      let stake_id = stake_test_tokens_with_rewards(&context, false);
+
+      assert_eq!(CounterForQueueExpiration::<Test>::get(), 1);
+      set_current_block(FIFTEEN_DAYS + 1);
+      run_on_idle_hook::<Test>();
+
+      assert!(TidefiStaking::account_stakes(ALICE_ACCOUNT_ID).is_empty());
+      assert_eq!(CounterForQueueExpiration::<Test>::get(), 0);
+      assert_eq!(
+        Adapter::balance(TEST_TOKEN_CURRENCY_ID, &ALICE_ACCOUNT_ID),
+        ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS - SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+          + 2 * ONE_TEST_TOKEN
+      );
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::Unstaked {
+          request_id: stake_id,
+          account_id: ALICE_ACCOUNT_ID,
+          currency_id: TEST_TOKEN_CURRENCY_ID,
+          initial_balance: ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+          final_balance: ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS + 2 * ONE_TEST_TOKEN,
+        },
+      ));
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::BatchFinished {
+          size: 1,
+          kind: BatchType::Expiration,
+        },
+      ));
+    });
+  }
+
+  #[test]
+  fn renews_the_stake_with_auto_renew() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+      let stake_id = stake_test_tokens_with_rewards(&context, true);
+
+      // the sweep waits for the expiration
+      set_current_block(FIFTEEN_DAYS);
+      run_on_idle_hook::<Test>();
+      assert_eq!(
+        TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+          .first()
+          .unwrap()
+          .initial_block,
+        1
+      );
+
+      set_current_block(FIFTEEN_DAYS + 10);
+      run_on_idle_hook::<Test>();
+
+      // the new term starts at the expiration of the previous one
+      let stake = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+        .first()
+        .unwrap()
+        .clone();
+      assert_eq!(stake.initial_block, FIFTEEN_DAYS + 1);
+      assert_eq!(
+        stake.initial_balance,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS + 2 * ONE_TEST_TOKEN
+      );
+      assert_eq!(
+        TidefiStaking::stake_principal(&stake),
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS + 2 * ONE_TEST_TOKEN
+      );
+      assert_eq!(
+        QueueExpiration::<Test>::get(2 * FIFTEEN_DAYS + 1, stake_id),
+        Some(ALICE_ACCOUNT_ID)
+      );
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::StakeRenewed {
+          request_id: stake_id,
+          account_id: ALICE_ACCOUNT_ID,
+          currency_id: TEST_TOKEN_CURRENCY_ID,
+          principal: ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS + 2 * ONE_TEST_TOKEN,
+          duration: FIFTEEN_DAYS,
+        },
+      ));
+    });
+  }
+
+  #[test]
+  fn pays_out_the_stake_when_its_staking_period_is_retired() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+      stake_test_tokens_with_rewards(&context, true);
+
+      assert_ok!(TidefiStaking::retire_staking_period(
+        RuntimeOrigin::root(),
+        FIFTEEN_DAYS
+      ));
+
+      set_current_block(FIFTEEN_DAYS + 1);
+      run_on_idle_hook::<Test>();
+
+      assert!(TidefiStaking::account_stakes(ALICE_ACCOUNT_ID).is_empty());
+      assert_eq!(CounterForQueueExpiration::<Test>::get(), 0);
+    });
+  }
+
+  #[test]
+  fn sweeps_the_queue_by_expiration_block() {
+    new_test_ext().execute_with(|| {
+      const FIFTEEN_MINUTES: BlockNumber = 150;
+      Context::default().mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+
+      set_current_block(1);
+      for duration in [FIFTEEN_DAYS, FIFTEEN_MINUTES] {
+        assert_ok!(TidefiStaking::stake(
+          RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+          TEST_TOKEN_CURRENCY_ID,
+          ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+          duration,
+          false
+        ));
+      }
+      assert_eq!(CounterForQueueExpiration::<Test>::get(), 2);
+      assert_eq!(TidefiStaking::next_expiration_block(), FIFTEEN_MINUTES + 1);
+
+      // nothing expired yet
+      set_current_block(FIFTEEN_MINUTES);
+      run_on_idle_hook::<Test>();
+      assert_eq!(TidefiStaking::account_stakes(ALICE_ACCOUNT_ID).len(), 2);
+      assert_eq!(TidefiStaking::next_expiration_block(), FIFTEEN_MINUTES + 1);
+
+      set_current_block(FIFTEEN_MINUTES + 10);
+      run_on_idle_hook::<Test>();
+      let stakes = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID);
+      assert_eq!(stakes.len(), 1);
+      assert_eq!(stakes.first().unwrap().duration, FIFTEEN_DAYS);
+      assert_eq!(CounterForQueueExpiration::<Test>::get(), 1);
+      assert_eq!(TidefiStaking::next_expiration_block(), FIFTEEN_MINUTES + 11);
+      assert!(QueueExpiration::<Test>::contains_key(
+        FIFTEEN_DAYS + 1,
+        stakes.first().unwrap().unique_id
+      ));
+    });
+  }
+
+  #[test]
+  fn skips_the_stakes_pending_unlock() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_tdfy(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TDFYS)
+        .stake_tdfy();
+
+      assert_eq!(CounterForQueueExpiration::<Test>::get(), 1);
+      assert_ok!(TidefiStaking::unstake(
+        RuntimeOrigin::signed(context.staker),
+        context.stake_id,
+        true,
+        false
+      ));
+
+      assert_eq!(CounterForQueueExpiration::<Test>::get(), 0);
+      assert_eq!(QueueUnstake::<Test>::count(), 1);
+    });
+  }
+}
+
+mod set_liquid_staking {
+  use super::*;
+
+  const LIQUID_STAKING: LiquidStakingMeta = LiquidStakingMeta {
+    receipt_currency_id: RECEIPT_TOKEN_CURRENCY_ID,
+    reward: Percent::from_percent(2),
+  };
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      assert_ok!(TidefiStaking::set_liquid_staking(
+        RuntimeOrigin::root(),
+        TEST_TOKEN_CURRENCY_ID,
+        Some(LIQUID_STAKING)
+      ));
+
+      assert_eq!(
+        TidefiStaking::liquid_staking(TEST_TOKEN_CURRENCY_ID),
+        Some(LIQUID_STAKING)
+      );
+      assert!(TidefiStaking::is_receipt_currency(
+        RECEIPT_TOKEN_CURRENCY_ID
+      ));
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::LiquidStakingUpdated {
+          currency_id: TEST_TOKEN_CURRENCY_ID,
+          meta: Some(LIQUID_STAKING),
+        },
+      ));
+
+      assert_ok!(TidefiStaking::set_liquid_staking(
+        RuntimeOrigin::root(),
+        TEST_TOKEN_CURRENCY_ID,
+        None
+      ));
+      assert_eq!(TidefiStaking::liquid_staking(TEST_TOKEN_CURRENCY_ID), None);
+      assert!(!TidefiStaking::is_receipt_currency(
+        RECEIPT_TOKEN_CURRENCY_ID
+      ));
+    });
+  }
+
+  #[test]
+  fn updates_the_reward_while_receipts_are_in_circulation() {
+    new_test_ext().execute_with(|| {
+      Context::default()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+        .enable_liquid_staking();
+      assert_ok!(TidefiStaking::stake_liquid(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ONE_TEST_TOKEN
+      ));
+
+      let meta = LiquidStakingMeta {
+        reward: Percent::from_percent(1),
+        ..LIQUID_STAKING
+      };
+      assert_ok!(TidefiStaking::set_liquid_staking(
+        RuntimeOrigin::root(),
+        TEST_TOKEN_CURRENCY_ID,
+        Some(meta.clone())
+      ));
+      assert_eq!(
+        TidefiStaking::liquid_staking(TEST_TOKEN_CURRENCY_ID),
+        Some(meta)
+      );
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_root() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::set_liquid_staking(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            TEST_TOKEN_CURRENCY_ID,
+            Some(LIQUID_STAKING)
+          ),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn receipt_currency_is_tdfy() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::set_liquid_staking(
+            RuntimeOrigin::root(),
+            TEST_TOKEN_CURRENCY_ID,
+            Some(LiquidStakingMeta {
+              receipt_currency_id: CurrencyId::Tdfy,
+              ..LIQUID_STAKING
+            })
+          ),
+          Error::<Test>::InvalidReceiptCurrency
+        );
+      });
+    }
+
+    #[test]
+    fn receipt_currency_is_not_registered() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::set_liquid_staking(
+            RuntimeOrigin::root(),
+            TEST_TOKEN_CURRENCY_ID,
+            Some(LiquidStakingMeta {
+              receipt_currency_id: CurrencyId::Wrapped(99),
+              ..LIQUID_STAKING
+            })
+          ),
+          Error::<Test>::InvalidReceiptCurrency
+        );
+      });
+    }
+
+    #[test]
+    fn receipt_currency_is_already_used() {
+      new_test_ext().execute_with(|| {
+        assert_ok!(TidefiStaking::set_liquid_staking(
+          RuntimeOrigin::root(),
+          TEST_TOKEN_CURRENCY_ID,
+          Some(LIQUID_STAKING)
+        ));
+
+        assert_noop!(
+          TidefiStaking::set_liquid_staking(
+            RuntimeOrigin::root(),
+            CurrencyId::Tdfy,
+            Some(LIQUID_STAKING)
+          ),
+          Error::<Test>::InvalidReceiptCurrency
+        );
+      });
+    }
+
+    #[test]
+    fn receipts_are_in_circulation() {
+      new_test_ext().execute_with(|| {
+        Context::default()
+          .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+          .enable_liquid_staking();
+        assert_ok!(TidefiStaking::stake_liquid(
+          RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+          TEST_TOKEN_CURRENCY_ID,
+          ONE_TEST_TOKEN
+        ));
+
+        assert_noop!(
+          TidefiStaking::set_liquid_staking(RuntimeOrigin::root(), TEST_TOKEN_CURRENCY_ID, None),
+          Error::<Test>::LiquidStakingInUse
+        );
+        assert_noop!(
+          TidefiStaking::set_liquid_staking(
+            RuntimeOrigin::root(),
+            TEST_TOKEN_CURRENCY_ID,
+            Some(LiquidStakingMeta {
+              receipt_currency_id: CurrencyId::Wrapped(99),
+              ..LIQUID_STAKING
+            })
+          ),
+          Error::<Test>::LiquidStakingInUse
+        );
+      });
+    }
+  }
+}
+
+mod stake_liquid {
+  use super::*;
+
+  const ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+        .enable_liquid_staking();
+
+      assert_ok!(TidefiStaking::stake_liquid(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+      ));
+
+      assert_eq!(
+        Adapter::balance(RECEIPT_TOKEN_CURRENCY_ID, &ALICE_ACCOUNT_ID),
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+      );
+      assert_eq!(
+        Adapter::balance(TEST_TOKEN_CURRENCY_ID, &context.staking_pallet_account),
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+      );
+      assert_eq!(
+        TidefiStaking::liquid_shares_pool(TEST_TOKEN_CURRENCY_ID),
+        SharesPool {
+          total_shares: ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+          total_balance: ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+        }
+      );
+      assert!(!SharesPools::<Test>::contains_key(
+        TEST_TOKEN_CURRENCY_ID,
+        FIFTEEN_DAYS
+      ));
+      assert_eq!(
+        TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
+        Some(ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS)
+      );
+      assert!(TidefiStaking::account_stakes(ALICE_ACCOUNT_ID).is_empty());
+
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::LiquidStaked {
+          account_id: ALICE_ACCOUNT_ID,
+          currency_id: TEST_TOKEN_CURRENCY_ID,
+          amount: ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+          receipts: ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+        },
+      ));
+    });
+  }
+
+  #[test]
+  fn mints_receipts_at_the_pool_exchange_rate() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+        .mint_test_token(BOB_ACCOUNT_ID, BOB_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+        .enable_liquid_staking();
+
+      assert_ok!(Tidefi::transfer(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        context.fees_pallet_account,
+        TEST_TOKEN_CURRENCY_ID,
+        SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+      ));
+
+      assert_ok!(TidefiStaking::stake_liquid(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+      ));
+      assert_ok!(TidefiStaking::on_session_end(
+        1,
+        vec![(
+          TEST_TOKEN_CURRENCY_ID,
+          SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+        )],
+        context.fees_pallet_account
+      ));
+
+      // one receipt is worth 1.02 test token
+      assert_ok!(TidefiStaking::stake_liquid(
+        RuntimeOrigin::signed(BOB_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        51 * ONE_TEST_TOKEN
+      ));
+      assert_eq!(
+        Adapter::balance(RECEIPT_TOKEN_CURRENCY_ID, &BOB_ACCOUNT_ID),
+        50 * ONE_TEST_TOKEN
+      );
+      assert_eq!(
+        TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
+        Some(153 * ONE_TEST_TOKEN)
+      );
+    });
+  }
+
+  #[test]
+  fn keeps_the_liquid_stakes_apart_from_the_staking_periods() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+        .mint_test_token(BOB_ACCOUNT_ID, BOB_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+        .enable_liquid_staking();
+      assert_ok!(TidefiStaking::set_liquid_staking(
+        RuntimeOrigin::root(),
+        TEST_TOKEN_CURRENCY_ID,
+        Some(LiquidStakingMeta {
+          receipt_currency_id: RECEIPT_TOKEN_CURRENCY_ID,
+          reward: Percent::from_percent(1),
+        })
+      ));
+
+      assert_ok!(Tidefi::transfer(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        context.fees_pallet_account,
+        TEST_TOKEN_CURRENCY_ID,
+        SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+      ));
+      assert_ok!(TidefiStaking::stake(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+        FIFTEEN_DAYS,
+        false
+      ));
+      assert_ok!(TidefiStaking::stake_liquid(
+        RuntimeOrigin::signed(BOB_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+      ));
+      assert_ok!(TidefiStaking::on_session_end(
+        1,
+        vec![(
+          TEST_TOKEN_CURRENCY_ID,
+          SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+        )],
+        context.fees_pallet_account
+      ));
+
+      // each pool holds half of the staking pool, the staking period is rewarded 2% of the fees
+      // and the liquid stakes 1%
+      assert_eq!(
+        SharesPools::<Test>::get(TEST_TOKEN_CURRENCY_ID, FIFTEEN_DAYS).total_balance,
+        101 * ONE_TEST_TOKEN
+      );
+      assert_eq!(
+        TidefiStaking::liquid_shares_pool(TEST_TOKEN_CURRENCY_ID).total_balance,
+        100 * ONE_TEST_TOKEN + ONE_TEST_TOKEN / 2
+      );
+      assert_eq!(
+        TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID),
+        Some(201 * ONE_TEST_TOKEN + ONE_TEST_TOKEN / 2)
+      );
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn liquid_staking_is_not_enabled() {
+      new_test_ext().execute_with(|| {
+        Context::default()
+          .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+
+        assert_noop!(
+          TidefiStaking::stake_liquid(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            TEST_TOKEN_CURRENCY_ID,
+            ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+          ),
+          Error::<Test>::LiquidStakingNotEnabled
+        );
+      });
+    }
+
+    #[test]
+    fn amount_is_too_small() {
+      new_test_ext().execute_with(|| {
+        Context::default()
+          .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+          .enable_liquid_staking();
+
+        assert_noop!(
+          TidefiStaking::stake_liquid(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            TEST_TOKEN_CURRENCY_ID,
+            1
+          ),
+          Error::<Test>::AmountTooSmall
+        );
+      });
+    }
+
+    #[test]
+    fn insufficient_balance() {
+      new_test_ext().execute_with(|| {
+        Context::default().enable_liquid_staking();
+
+        assert_noop!(
+          TidefiStaking::stake_liquid(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            TEST_TOKEN_CURRENCY_ID,
+            ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+          ),
+          Error::<Test>::InsufficientBalance
+        );
+      });
+    }
+  }
+}
+
+mod unstake_liquid {
+  use super::*;
+
+  const ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
+
+  #[test]
+  fn succeeds_with_rewards_and_fee() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .set_operator_account()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+        .enable_liquid_staking();
+
+      assert_ok!(Tidefi::transfer(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        context.fees_pallet_account,
+        TEST_TOKEN_CURRENCY_ID,
+        SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+      ));
+      assert_ok!(TidefiStaking::stake_liquid(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+      ));
+      assert_ok!(TidefiStaking::on_session_end(
+        1,
+        vec![(
+          TEST_TOKEN_CURRENCY_ID,
+          SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS
+        )],
+        context.fees_pallet_account
+      ));
+
+      // the receipts are transferable, BOB redeems the stake of ALICE with its rewards
+      assert_ok!(Adapter::transfer(
+        RECEIPT_TOKEN_CURRENCY_ID,
+        &ALICE_ACCOUNT_ID,
+        &BOB_ACCOUNT_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+        false
+      ));
+      assert_ok!(TidefiStaking::unstake_liquid(
+        RuntimeOrigin::signed(BOB_ACCOUNT_ID),
+        TEST_TOKEN_CURRENCY_ID,
+        ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+      ));
+
+      let amount = 102 * ONE_TEST_TOKEN;
+      let fee = TidefiStaking::unstake_fee() * amount;
+      assert_eq!(
+        Adapter::balance(TEST_TOKEN_CURRENCY_ID, &BOB_ACCOUNT_ID),
+        amount - fee
+      );
+      assert_eq!(
+        Adapter::balance(TEST_TOKEN_CURRENCY_ID, &OPERATOR_ACCOUNT_ID),
+        SESSION_TOTAL_FEES_ONE_HUNDRED_TEST_TOKENS - 2 * ONE_TEST_TOKEN + fee
+      );
+      assert_eq!(Adapter::total_issuance(RECEIPT_TOKEN_CURRENCY_ID), 0);
+      assert!(!LiquidSharesPools::<Test>::contains_key(
+        TEST_TOKEN_CURRENCY_ID
+      ));
+      assert_eq!(TidefiStaking::staking_pool(TEST_TOKEN_CURRENCY_ID), Some(0));
+
+      System::assert_has_event(MockEvent::TidefiStaking(
+        pallet_tidefi_stake::Event::LiquidUnstaked {
+          account_id: BOB_ACCOUNT_ID,
+          currency_id: TEST_TOKEN_CURRENCY_ID,
+          receipts: ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+          amount,
+          fee,
+        },
+      ));
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn liquid_staking_is_not_enabled() {
+      new_test_ext().execute_with(|| {
+        assert_noop!(
+          TidefiStaking::unstake_liquid(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            TEST_TOKEN_CURRENCY_ID,
+            ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+          ),
+          Error::<Test>::LiquidStakingNotEnabled
+        );
+      });
+    }
+
+    #[test]
+    fn insufficient_receipts() {
+      new_test_ext().execute_with(|| {
+        Context::default()
+          .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS)
+          .enable_liquid_staking();
+        assert_ok!(TidefiStaking::stake_liquid(
+          RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+          TEST_TOKEN_CURRENCY_ID,
+          ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS
+        ));
+
+        assert_noop!(
+          TidefiStaking::unstake_liquid(
+            RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+            TEST_TOKEN_CURRENCY_ID,
+            ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS + 1
+          ),
+          Error::<Test>::InsufficientBalance
+        );
+      });
+    }
+  }
+}
+
+mod staking_yield {
+  use super::*;
+
+  const ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS: Balance = 100 * ONE_TEST_TOKEN;
+  const ONE_DAY: BlockNumber = 14400;
+
+  fn stake_and_end_sessions(context: &Context, session_blocks: Vec<BlockNumber>) {
+    assert_ok!(Tidefi::transfer(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      context.fees_pallet_account,
+      TEST_TOKEN_CURRENCY_ID,
+      session_blocks.len() as Balance * ONE_TEST_TOKEN
+    ));
+
+    set_current_block(1);
+    assert_ok!(TidefiStaking::stake(
+      RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+      TEST_TOKEN_CURRENCY_ID,
+      ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS,
+      FIFTEEN_DAYS,
+      false
+    ));
+
+    for (session_index, block) in session_blocks.into_iter().enumerate() {
+      set_current_block(block);
+      assert_ok!(TidefiStaking::on_session_end(
+        session_index as u32,
+        vec![(TEST_TOKEN_CURRENCY_ID, ONE_TEST_TOKEN)],
+        context.fees_pallet_account
+      ));
+    }
+  }
+
+  #[test]
+  fn is_annualised_from_the_session_rewards() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .set_operator_account()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+      stake_and_end_sessions(&context, vec![1, 1 + ONE_DAY]);
+
+      // 2% of the fees are distributed to the stakes of 15 days each session
+      let session_reward = Percent::from_parts(2) * ONE_TEST_TOKEN;
+      assert_eq!(
+        SessionRewardsHistory::<Test>::get(TEST_TOKEN_CURRENCY_ID, FIFTEEN_DAYS).into_inner(),
+        vec![
+          (
+            1,
+            Perquintill::from_rational(session_reward, ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS)
+          ),
+          (
+            1 + ONE_DAY,
+            Perquintill::from_rational(
+              session_reward,
+              ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS + session_reward
+            )
+          ),
+        ]
+      );
+
+      // one session a day
+      let annual_yield = Perquintill::from_parts(
+        Perquintill::from_rational(
+          session_reward,
+          ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS + session_reward,
+        )
+        .deconstruct()
+          * 365,
+      );
+      assert_eq!(
+        TidefiStaking::staking_yield(TEST_TOKEN_CURRENCY_ID, FIFTEEN_DAYS),
+        annual_yield
+      );
+      assert_eq!(
+        TidefiStaking::get_staking_yields(),
+        vec![pallet_tidefi_stake::StakingYield {
+          currency_id: TEST_TOKEN_CURRENCY_ID,
+          duration: FIFTEEN_DAYS,
+          reward: Percent::from_parts(2),
+          staked: ALICE_STAKE_ONE_HUNDRED_TEST_TOKENS + 2 * session_reward,
+          annual_yield,
+        }]
+      );
+    });
+  }
+
+  #[test]
+  fn is_zero_without_enough_sessions() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .set_operator_account()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+      stake_and_end_sessions(&context, vec![1]);
+
+      assert_eq!(
+        TidefiStaking::staking_yield(TEST_TOKEN_CURRENCY_ID, FIFTEEN_DAYS),
+        Perquintill::zero()
+      );
+    });
+  }
+
+  #[test]
+  fn keeps_the_last_sessions() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .set_operator_account()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+      let history_cap: u32 = <Test as pallet_tidefi_stake::Config>::SessionRewardsHistoryCap::get();
+      stake_and_end_sessions(
+        &context,
+        (1..=history_cap as BlockNumber + 1)
+          .map(|day| day * ONE_DAY)
+          .collect(),
+      );
+
+      let history = SessionRewardsHistory::<Test>::get(TEST_TOKEN_CURRENCY_ID, FIFTEEN_DAYS);
+      assert_eq!(history.len() as u32, history_cap);
+      assert_eq!(history.first().unwrap().0, 2 * ONE_DAY);
+    });
+  }
+
+  #[test]
+  fn is_cleared_with_the_shares_pool() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .set_operator_account()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+      stake_and_end_sessions(&context, vec![1, 1 + ONE_DAY]);
+
+      set_current_block(1 + FIFTEEN_DAYS);
+      assert_ok!(TidefiStaking::unstake(
+        RuntimeOrigin::signed(ALICE_ACCOUNT_ID),
+        TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+          .first()
+          .unwrap()
+          .unique_id,
+        false,
+        false
+      ));
+
+      assert!(!SessionRewardsHistory::<Test>::contains_key(
+        TEST_TOKEN_CURRENCY_ID,
+        FIFTEEN_DAYS
+      ));
+      assert!(TidefiStaking::get_staking_yields().is_empty());
+    });
+  }
+
+  #[test]
+  fn projects_the_stakes_until_they_expire() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .set_operator_account()
+        .mint_test_token(ALICE_ACCOUNT_ID, ALICE_INITIAL_ONE_THOUSAND_TEST_TOKENS);
+      stake_and_end_sessions(&context, vec![1, 1 + ONE_DAY]);
+
+      let stake = TidefiStaking::account_stakes(ALICE_ACCOUNT_ID)
+        .first()
+        .unwrap()
+        .clone();
+      let balance = TidefiStaking::stake_principal(&stake);
+      let annual_yield = TidefiStaking::staking_yield(TEST_TOKEN_CURRENCY_ID, FIFTEEN_DAYS);
+      let remaining_blocks = (1 + FIFTEEN_DAYS - (1 + ONE_DAY)) as Balance;
+      let blocks_per_year: BlockNumber =
+        <Test as pallet_tidefi_stake::Config>::BlocksPerYear::get();
+
+      assert_eq!(
+        TidefiStaking::get_account_stakes_projection(&ALICE_ACCOUNT_ID),
+        vec![pallet_tidefi_stake::StakeProjection {
+          stake_id: stake.unique_id,
+          currency_id: TEST_TOKEN_CURRENCY_ID,
+          balance,
+          projected_balance: balance
+            + annual_yield * balance * remaining_blocks / blocks_per_year as Balance,
+          expiration: 1 + FIFTEEN_DAYS,
+        }]
+      );
+    });
+  }
 }
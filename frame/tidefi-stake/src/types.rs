@@ -0,0 +1,52 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Types returned by the staking queries (runtime API).
+
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{Percent, Perquintill};
+use tidefi_primitives::{Balance, CurrencyId, Hash};
+
+/// Annualised yield of the stakes of a currency for a staking period.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct StakingYield<BlockNumber> {
+  pub currency_id: CurrencyId,
+  pub duration: BlockNumber,
+  /// Percentage of the session fees distributed to the staking period
+  pub reward: Percent,
+  /// Value of the stakes of the staking period, including the rewards
+  pub staked: Balance,
+  /// Yield over a year at the rate of the recent sessions, capped to 100%
+  pub annual_yield: Perquintill,
+}
+
+/// Projected value of a stake when its staking period ends.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct StakeProjection<BlockNumber> {
+  pub stake_id: Hash,
+  pub currency_id: CurrencyId,
+  /// Current value of the stake, including the rewards
+  pub balance: Balance,
+  /// Value of the stake at `expiration` if the yield of its staking period stays the same
+  pub projected_balance: Balance,
+  pub expiration: BlockNumber,
+}
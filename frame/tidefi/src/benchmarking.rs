@@ -16,11 +16,9 @@
 
 use crate::*;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::{
-  inherent::Vec,
-  traits::{fungibles::Mutate, Hooks},
-};
+use frame_support::traits::{fungibles::Mutate, Hooks};
 use frame_system::RawOrigin;
+use sp_runtime::traits::UniqueSaturatedInto;
 use tidefi_primitives::{pallet::SunriseExt, ActiveEraInfo, CurrencyId, SwapType};
 const INITIAL_AMOUNT: u128 = 500_000_000_000_000;
 const IA_MULTIPLIER: u32 = 2;
@@ -33,7 +31,8 @@ benchmarks! {
       let caller: T::AccountId = whitelisted_caller();
       let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
       <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Wrapped(TEST_TOKEN), &caller, balance as u128).expect("Unable to mint token");
-   }: _(RawOrigin::Signed(caller), CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT.into(), Vec::new())
+      let external_address = <T as Config>::AddressValidator::valid_address(CurrencyId::Wrapped(TEST_TOKEN));
+   }: _(RawOrigin::Signed(caller), CurrencyId::Wrapped(TEST_TOKEN), INITIAL_AMOUNT.into(), external_address)
    swap {
       let caller: T::AccountId = whitelisted_caller();
       let balance = INITIAL_AMOUNT.saturating_mul(IA_MULTIPLIER.into());
@@ -67,6 +66,68 @@ benchmarks! {
       pallet_security::CurrentBlockCount::<T>::put(frame_system::Pallet::<T>::block_number());
 
    }: _(RawOrigin::Signed(caller), 1)
+   claim_all_sunrise_rewards {
+      let e in 1 .. T::MaxClaimedEras::get();
+      let caller: T::AccountId = whitelisted_caller();
+      // init first era
+      pallet_fees::Pallet::<T>::start_era();
+      pallet_fees::Pallet::<T>::on_finalize(T::BlockNumber::from(1_u32));
+
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Tdfy, &<T as Config>::Sunrise::account_id(), 1_000_000_000_000_000_000).unwrap();
+      for era_index in 1..=e {
+         <pallet_sunrise::Rewards<T>>::insert(&caller, era_index, 1_000_000_000_000);
+      }
+
+      frame_system::Pallet::<T>::on_finalize(frame_system::Pallet::<T>::block_number());
+      frame_system::Pallet::<T>::set_block_number(T::BlockNumber::from(1_500_000_u32));
+      pallet_fees::ActiveEra::<T>::put(ActiveEraInfo::<T::BlockNumber> {
+         index: 25_000,
+         start_block: Some(T::BlockNumber::from(1_500_000_u32)),
+         start_session_index: None,
+         last_session_block: None,
+         start: None,
+       });
+
+      frame_system::Pallet::<T>::on_initialize(frame_system::Pallet::<T>::block_number());
+      frame_system::Pallet::<T>::on_finalize(frame_system::Pallet::<T>::block_number());
+      pallet_security::CurrentBlockCount::<T>::put(frame_system::Pallet::<T>::block_number());
+
+      // the rewards of the eras in cooldown are read but not claimed
+      let eras_in_cooldown: u32 = Pallet::<T>::eras_in_cooldown().unwrap().unique_saturated_into();
+      for era_index in 25_000_u32.saturating_sub(eras_in_cooldown)..=25_000 {
+         <pallet_sunrise::Rewards<T>>::insert(&caller, era_index, 1_000_000_000_000);
+      }
+   }: _(RawOrigin::Signed(caller.clone()), e)
+   verify {
+      assert_eq!(<pallet_sunrise::Rewards<T>>::iter_prefix(&caller).count() as u32, eras_in_cooldown + 1);
+   }
+   claim_referral_rewards {
+      let caller: T::AccountId = whitelisted_caller();
+      // init first era
+      pallet_fees::Pallet::<T>::start_era();
+      pallet_fees::Pallet::<T>::on_finalize(T::BlockNumber::from(1_u32));
+
+      <T as Config>::CurrencyTidefi::mint_into(CurrencyId::Tdfy, &<T as Config>::Sunrise::account_id(), 1_000_000_000_000_000_000).unwrap();
+      <pallet_sunrise::ReferralRewards<T>>::insert(&caller, 1, 1_000_000_000_000);
+
+      frame_system::Pallet::<T>::on_finalize(frame_system::Pallet::<T>::block_number());
+      frame_system::Pallet::<T>::set_block_number(T::BlockNumber::from(1_500_000_u32));
+      pallet_fees::ActiveEra::<T>::put(ActiveEraInfo::<T::BlockNumber> {
+         index: 25_000,
+         start_block: Some(T::BlockNumber::from(1_500_000_u32)),
+         start_session_index: None,
+         last_session_block: None,
+         start: None,
+       });
+
+      frame_system::Pallet::<T>::on_initialize(frame_system::Pallet::<T>::block_number());
+      frame_system::Pallet::<T>::on_finalize(frame_system::Pallet::<T>::block_number());
+      pallet_security::CurrentBlockCount::<T>::put(frame_system::Pallet::<T>::block_number());
+
+   }: _(RawOrigin::Signed(caller.clone()), 1)
+   verify {
+      assert_eq!(<pallet_sunrise::ReferralRewards<T>>::get(&caller, 1), 0);
+   }
 }
 
 impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
@@ -31,10 +31,45 @@ pub use weights::*;
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+use tidefi_primitives::CurrencyId;
+
+/// Reason why an external address has been rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressError {
+  /// The address (or its decoded payload) doesn't have the expected length
+  InvalidLength,
+  /// The address contains characters or a prefix not allowed by the chain format
+  InvalidEncoding,
+  /// The address checksum doesn't match
+  InvalidChecksum,
+  /// The asset base chain has no known address format, the address can't be validated
+  UnsupportedChain,
+}
+
+/// Validate the external address provided on withdrawal for a given currency.
+pub trait AddressValidator {
+  /// Returns `Ok(())` when `address` is a valid destination for `currency_id`.
+  fn validate(currency_id: CurrencyId, address: &[u8]) -> Result<(), AddressError>;
+
+  /// Returns a valid address for `currency_id`, used by the benchmarks.
+  #[cfg(feature = "runtime-benchmarks")]
+  fn valid_address(_currency_id: CurrencyId) -> sp_std::vec::Vec<u8> {
+    sp_std::vec::Vec::new()
+  }
+}
+
+/// Accept all addresses, in case no validation is required.
+impl AddressValidator for () {
+  fn validate(_currency_id: CurrencyId, _address: &[u8]) -> Result<(), AddressError> {
+    Ok(())
+  }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
   use super::*;
   use frame_support::{
+    dispatch::PostDispatchInfo,
     inherent::Vec,
     pallet_prelude::*,
     traits::tokens::{
@@ -43,9 +78,10 @@ pub mod pallet {
     },
   };
   use frame_system::pallet_prelude::*;
+  use pallet_sunrise::{SunriseEraReward, SunriseRewards};
   use sp_io::hashing::blake2_256;
   use sp_runtime::{
-    traits::{CheckedDiv, Saturating},
+    traits::{CheckedDiv, Saturating, UniqueSaturatedInto},
     Permill,
   };
   use tidefi_primitives::{
@@ -75,17 +111,26 @@ pub mod pallet {
     type Fees: FeesExt<Self::AccountId, Self::BlockNumber>;
 
     /// Tidefi sunrise traits
-    type Sunrise: SunriseExt<Self::AccountId, Self::BlockNumber>;
+    type Sunrise: SunriseExt<Self::AccountId, Self::BlockNumber>
+      + pallet_sunrise::SunriseRewards<Self::AccountId, Self::BlockNumber>
+      + pallet_sunrise::SunriseReferral<Self::AccountId>;
 
     /// Security traits
     type Security: SecurityExt<Self::AccountId, Self::BlockNumber>;
 
+    /// External address validator used on withdrawal
+    type AddressValidator: AddressValidator;
+
     /// Tidechain currency wrapper
     type CurrencyTidefi: Inspect<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + Mutate<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + Transfer<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + InspectHold<Self::AccountId, AssetId = CurrencyId, Balance = Balance>
       + MutateHold<Self::AccountId, AssetId = CurrencyId, Balance = Balance>;
+
+    /// Maximum number of eras claimed by a single `claim_all_sunrise_rewards`
+    #[pallet::constant]
+    type MaxClaimedEras: Get<u32>;
   }
 
   #[pallet::pallet]
@@ -159,6 +204,25 @@ pub mod pallet {
     EraNotReady,
     /// Invalid era
     InvalidEra,
+    /// External address length is invalid for this asset
+    InvalidExternalAddressLength,
+    /// External address encoding is invalid for this asset
+    InvalidExternalAddressEncoding,
+    /// External address checksum is invalid
+    InvalidExternalAddressChecksum,
+    /// External address can't be validated for this asset
+    UnsupportedExternalAddressChain,
+  }
+
+  impl<T> From<AddressError> for Error<T> {
+    fn from(error: AddressError) -> Self {
+      match error {
+        AddressError::InvalidLength => Error::<T>::InvalidExternalAddressLength,
+        AddressError::InvalidEncoding => Error::<T>::InvalidExternalAddressEncoding,
+        AddressError::InvalidChecksum => Error::<T>::InvalidExternalAddressChecksum,
+        AddressError::UnsupportedChain => Error::<T>::UnsupportedExternalAddressChain,
+      }
+    }
   }
 
   #[pallet::call]
@@ -231,7 +295,10 @@ pub mod pallet {
         Error::<T>::AssetDisabled
       );
 
-      // 4. Make sure the account have enough funds
+      // 4. Make sure the external address is valid for this asset
+      T::AddressValidator::validate(currency_id, &external_address).map_err(Error::<T>::from)?;
+
+      // 5. Make sure the account have enough funds
       match T::CurrencyTidefi::can_withdraw(currency_id, &account_id, amount) {
         WithdrawConsequence::Success | WithdrawConsequence::ReducedToZero(_) => {
           // Add withdrawal in queue
@@ -419,13 +486,7 @@ pub mod pallet {
         return Err(Error::<T>::InvalidEra.into());
       }
 
-      let era_blocks_count = T::Fees::era_blocks_count();
-      let cooldown_blocks_count = T::Sunrise::cooldown_blocks_count();
-      let eras_in_cooldown = cooldown_blocks_count
-        .checked_div(&era_blocks_count)
-        .ok_or(Error::<T>::EraNotReady)?;
-      let minimum_era_index_as_blocknumber =
-        T::BlockNumber::from(current_era.index).saturating_sub(eras_in_cooldown);
+      let minimum_era_index_as_blocknumber = Self::minimum_era_in_cooldown(current_era.index)?;
 
       // Unable to claim previous era's if the `T::Cooldown` cooldown isnt cleared
       if T::BlockNumber::from(era_index) >= minimum_era_index_as_blocknumber {
@@ -438,5 +499,144 @@ pub mod pallet {
       // Don't take tx fees on success
       Ok(Pays::No.into())
     }
+
+    /// Claim available sunrise rewards of all eras with cleared cooldown, in a single transfer
+    ///
+    /// - `max_eras`: Maximum number of eras to claim, capped by `MaxClaimedEras`, the rewards of
+    ///   the eras still in cooldown are skipped
+    ///
+    /// Emits `SunriseClaimedAll` event when successful.
+    ///
+    /// Weight: `O(E)` where `E` is `max_eras`
+    #[pallet::call_index(5)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::claim_all_sunrise_rewards(
+      (*max_eras).min(T::MaxClaimedEras::get())
+    ))]
+    pub fn claim_all_sunrise_rewards(
+      origin: OriginFor<T>,
+      max_eras: u32,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Only eras with cleared `T::Cooldown` cooldown can be claimed
+      let current_era = T::Fees::current_era().ok_or(Error::<T>::NoActiveEra)?;
+      let before_era_index: EraIndex =
+        Self::minimum_era_in_cooldown(current_era.index)?.unique_saturated_into();
+
+      // 3. Claim rewards
+      let (eras_claimed, _) = T::Sunrise::try_claim_all_sunrise_rewards(
+        &account_id,
+        before_era_index,
+        max_eras.min(T::MaxClaimedEras::get()),
+      )?;
+
+      // Don't take tx fees on success and refund the weight of the eras not claimed
+      Ok(PostDispatchInfo {
+        actual_weight: Some(
+          <T as pallet::Config>::WeightInfo::claim_all_sunrise_rewards(eras_claimed),
+        ),
+        pays_fee: Pays::No,
+      })
+    }
+
+    /// Claim available referral rewards
+    ///
+    /// - `era_index`: Era to claim rewards
+    ///
+    /// Emits `ReferralClaimed` event when successful.
+    ///
+    /// Weight: `O(1)`
+    #[pallet::call_index(6)]
+    #[pallet::weight(<T as pallet::Config>::WeightInfo::claim_referral_rewards())]
+    pub fn claim_referral_rewards(
+      origin: OriginFor<T>,
+      era_index: EraIndex,
+    ) -> DispatchResultWithPostInfo {
+      // 1. Make sure the transaction is signed
+      let account_id = ensure_signed(origin)?;
+
+      // 2. Make sure the era Index provided is ready to be claimed
+      let current_era = T::Fees::current_era().ok_or(Error::<T>::NoActiveEra)?;
+
+      // Unable to claim current Era
+      if era_index >= current_era.index {
+        return Err(Error::<T>::InvalidEra.into());
+      }
+
+      // Unable to claim previous era's if the `T::Cooldown` cooldown isnt cleared
+      if T::BlockNumber::from(era_index) >= Self::minimum_era_in_cooldown(current_era.index)? {
+        return Err(Error::<T>::EraNotReady.into());
+      }
+
+      // 3. Claim rewards
+      T::Sunrise::try_claim_referral_rewards(&account_id, era_index)?;
+
+      // Don't take tx fees on success
+      Ok(Pays::No.into())
+    }
+  }
+
+  impl<T: Config> Pallet<T> {
+    // Number of eras to wait after an era is completed, before claiming its rewards
+    pub(crate) fn eras_in_cooldown() -> Result<T::BlockNumber, DispatchError> {
+      let era_blocks_count = T::Fees::era_blocks_count();
+      let cooldown_blocks_count = T::Sunrise::cooldown_blocks_count();
+      cooldown_blocks_count
+        .checked_div(&era_blocks_count)
+        .ok_or_else(|| Error::<T>::EraNotReady.into())
+    }
+
+    // First era index which can't be claimed yet, as the `T::Cooldown` isn't cleared
+    fn minimum_era_in_cooldown(
+      current_era_index: EraIndex,
+    ) -> Result<T::BlockNumber, DispatchError> {
+      Ok(T::BlockNumber::from(current_era_index).saturating_sub(Self::eras_in_cooldown()?))
+    }
+
+    // Get the unclaimed sunrise rewards of the account with the block from which they can be
+    // claimed, serialized for quick RPC call
+    pub fn get_account_sunrise_rewards(
+      account_id: &T::AccountId,
+    ) -> Result<Vec<SunriseEraReward<T::BlockNumber>>, DispatchError> {
+      let current_era = T::Fees::current_era().ok_or(Error::<T>::NoActiveEra)?;
+      let current_era_index = T::BlockNumber::from(current_era.index);
+      let current_era_start_block = current_era
+        .start_block
+        .unwrap_or_else(T::Security::get_current_block_count);
+      let era_blocks_count = T::Fees::era_blocks_count();
+      let eras_in_cooldown = Self::eras_in_cooldown()?;
+
+      Ok(
+        T::Sunrise::account_rewards(account_id)
+          .into_iter()
+          .map(|(era_index, reward, expire_at)| {
+            // the era can be claimed once the `T::Cooldown` is cleared after the era is completed
+            let claimable_era_index = T::BlockNumber::from(era_index)
+              .saturating_add(eras_in_cooldown)
+              .saturating_add(1_u32.into());
+            let claimable_at = if claimable_era_index >= current_era_index {
+              current_era_start_block.saturating_add(
+                claimable_era_index
+                  .saturating_sub(current_era_index)
+                  .saturating_mul(era_blocks_count),
+              )
+            } else {
+              current_era_start_block.saturating_sub(
+                current_era_index
+                  .saturating_sub(claimable_era_index)
+                  .saturating_mul(era_blocks_count),
+              )
+            };
+            SunriseEraReward {
+              era_index,
+              reward,
+              claimable_at,
+              expire_at,
+            }
+          })
+          .collect(),
+      )
+    }
   }
 }
@@ -21,9 +21,9 @@ use frame_system as system;
 use frame_utils::construct_mock_runtime;
 use sp_runtime::traits::AccountIdConversion;
 use system::EnsureRoot;
-use tidefi_primitives::{assets, BlockNumber, CurrencyId, MarketPair, SessionIndex};
+use tidefi_primitives::{assets, BlockNumber, CurrencyId, EraIndex, MarketPair, SessionIndex};
 
-use crate::pallet as pallet_tidefi;
+use crate::{pallet as pallet_tidefi, AddressError, AddressValidator};
 
 pub struct EnsureRootOrAssetRegistry;
 impl EnsureOrigin<RuntimeOrigin> for EnsureRootOrAssetRegistry {
@@ -57,6 +57,23 @@ impl EnsureOrigin<RuntimeOrigin> for EnsureRootOrAssetRegistry {
   }
 }
 
+/// Reject empty addresses and addresses starting with `0xff`, accept everything else.
+pub struct MockAddressValidator;
+impl AddressValidator for MockAddressValidator {
+  fn validate(_currency_id: CurrencyId, address: &[u8]) -> Result<(), AddressError> {
+    match address.first() {
+      None => Err(AddressError::InvalidLength),
+      Some(0xff) => Err(AddressError::InvalidChecksum),
+      Some(_) => Ok(()),
+    }
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn valid_address(_currency_id: CurrencyId) -> Vec<u8> {
+    vec![0; 32]
+  }
+}
+
 construct_mock_runtime!({
   Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
   Assets: pallet_assets::{Pallet, Call, Storage, Event<T>},
@@ -70,6 +87,7 @@ construct_mock_runtime!({
   AssetRegistry: pallet_asset_registry::{Pallet, Call, Config<T>, Storage, Event<T>},
 }, {
   pub const AssetDeposit: u64 = 1;
+  pub const PauseDuration: u64 = 100;
   pub const ApprovalDeposit: u64 = 1;
   pub const MetadataDepositBase: u64 = 1;
   pub const MetadataDepositPerByte: u64 = 1;
@@ -78,10 +96,14 @@ construct_mock_runtime!({
   pub const AssetRegistryPalletId: PalletId = PalletId(*b"asst*pal");
   pub const FeesPalletId: PalletId = PalletId(*b"fees*pal");
   pub const SunrisePalletId: PalletId = PalletId(*b"sunr*pal");
+  pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
   pub const StakePalletId: PalletId = PalletId(*b"stak*pal");
   pub const SessionsPerEra: SessionIndex = 10;
   pub const SessionsArchive: SessionIndex = 2;
+  pub const ErasArchive: EraIndex = 2;
+  pub const MaximumSessionsCatchUp: u32 = 5;
   pub const BlocksPerSession: BlockNumber = 50;
+  pub const ExpectedBlockTime: u64 = 6_000;
   pub const BlocksForceUnstake: BlockNumber = 10;
   pub const StakeAccountCap: u32 = 10;
   // 20 basis point
@@ -104,13 +126,20 @@ construct_mock_runtime!({
   pub const WatchListLimit: u32 = 10000;
   // The maximum number of pubkey each asset can have, should alway be more more than the current quorum active member set
   pub const PubkeyLimitPerAsset: u32 = 10;
+  pub const CompletedWithdrawalsLimit: u32 = 10;
   // The number of swap each account can have in queue
   pub const SwapLimitByAccount: u32 = 100;
   // The maximum number of supported market pairs
   pub const SupportedMarketPairsLimit: u8 = 255;
   // Maximum number of staking period the chain can support
   pub const StakingRewardCap: u32 = 10;
+  pub const SessionRewardsHistoryCap: u32 = 10;
+  pub const BlocksPerYear: BlockNumber = 14400 * 365;
   pub const Cooldown: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ClaimWindow: BlockNumber = 1_296_000; // 90 DAYS
+  pub const ReferralShare: Permill = Permill::from_percent(10);
+  // max 1k referral rewards per referee
+  pub const MaximumReferralRewardPerReferee: Balance = 1_000_000_000_000_000;
   // max 10k rewards
   pub const MaximumRewardPerSwap: Balance = 10_000_000_000_000_000;
   // 50%
@@ -151,6 +180,8 @@ impl pallet_tidefi::Config for Test {
   type Sunrise = Sunrise;
   type Security = Security;
   type AssetRegistry = AssetRegistry;
+  type MaxClaimedEras = ConstU32<100>;
+  type AddressValidator = MockAddressValidator;
 }
 
 impl pallet_quorum::Config for Test {
@@ -161,6 +192,9 @@ impl pallet_quorum::Config for Test {
   type Security = Security;
   type Sunrise = Sunrise;
   type AssetRegistry = AssetRegistry;
+  type InclusionProof = ();
+  type InclusionVerifier = ();
+  type ForceOrigin = EnsureRoot<AccountId>;
   type ProposalsCap = ProposalsCap;
   type BurnedCap = BurnedCap;
   type ProposalLifetime = ProposalLifetime;
@@ -168,6 +202,7 @@ impl pallet_quorum::Config for Test {
   type VotesLimit = VotesLimit;
   type WatchListLimit = WatchListLimit;
   type PubkeyLimitPerAsset = PubkeyLimitPerAsset;
+  type CompletedWithdrawalsLimit = CompletedWithdrawalsLimit;
 }
 
 impl pallet_oracle::Config for Test {
@@ -178,12 +213,21 @@ impl pallet_oracle::Config for Test {
   type Security = Security;
   type SwapLimitByAccount = SwapLimitByAccount;
   type SupportedMarketPairsLimit = SupportedMarketPairsLimit;
+  type ForceOrigin = EnsureRoot<AccountId>;
+  type AssetRegistry = AssetRegistry;
   type Fees = Fees;
   type Sunrise = Sunrise;
 }
 
 impl pallet_security::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type PauseOrigin = system::EnsureRoot<AccountId>;
+  type ResumeOrigin = system::EnsureRoot<AccountId>;
+  type PauseDuration = PauseDuration;
+  type MaxPauseRenewals = ConstU32<2>;
+  type UnpausablePallets = ();
+  type MaxNameLength = ConstU32<32>;
+  type MaxReasonLength = ConstU32<64>;
   type WeightInfo = pallet_security::weights::SubstrateWeight<Test>;
 }
 
@@ -201,6 +245,7 @@ impl pallet_sudo::Config for Test {
 
 impl pallet_fees::Config for Test {
   type RuntimeEvent = RuntimeEvent;
+  type TreasuryPalletId = TreasuryPalletId;
   type Security = Security;
   type FeesPalletId = FeesPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
@@ -208,10 +253,14 @@ impl pallet_fees::Config for Test {
   type UnixTime = Timestamp;
   type SessionsPerEra = SessionsPerEra;
   type SessionsArchive = SessionsArchive;
+  type ErasArchive = ErasArchive;
+  type MaximumSessionsCatchUp = MaximumSessionsCatchUp;
+  type BatchSize = BatchSize;
   type FeeAmount = FeeAmount;
   type MarketMakerFeeAmount = MarketMakerFeeAmount;
   type MarketMakerLimitFeeAmount = MarketMakerLimitFeeAmount;
   type BlocksPerSession = BlocksPerSession;
+  type ExpectedBlockTime = ExpectedBlockTime;
   type Staking = TidefiStaking;
   type Sunrise = Sunrise;
   type WeightInfo = pallet_fees::weights::SubstrateWeight<Test>;
@@ -220,11 +269,19 @@ impl pallet_fees::Config for Test {
 impl pallet_sunrise::Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type Security = Security;
+  type AssetRegistry = AssetRegistry;
   type SunrisePalletId = SunrisePalletId;
   type CurrencyTidefi = Adapter<AccountId>;
   type Cooldown = Cooldown;
+  type ClaimWindow = ClaimWindow;
+  type ReferralShare = ReferralShare;
+  type MaximumReferralRewardPerReferee = MaximumReferralRewardPerReferee;
+  type BatchSize = BatchSize;
   type MaximumRewardPerSwap = MaximumRewardPerSwap;
   type LeftoverSwapRebates = LeftoverSwapRebates;
+  type TreasuryPalletId = TreasuryPalletId;
+  type ForceOrigin = system::EnsureRoot<AccountId>;
+  type WeightInfo = pallet_sunrise::weights::SubstrateWeight<Test>;
 }
 
 impl pallet_tidefi_stake::Config for Test {
@@ -236,8 +293,12 @@ impl pallet_tidefi_stake::Config for Test {
   type BlocksForceUnstake = BlocksForceUnstake;
   type AssetRegistry = AssetRegistry;
   type StakingRewardCap = StakingRewardCap;
+  type SessionRewardsHistoryCap = SessionRewardsHistoryCap;
+  type BlocksPerYear = BlocksPerYear;
   type Security = Security;
+  type Sunrise = Sunrise;
   type BatchSize = BatchSize;
+  type ForceOrigin = EnsureRoot<AccountId>;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -321,6 +382,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
       vec![],
     )],
     account: 0_u64.into(),
+    infos: Vec::new(),
   }
   .assimilate_storage(&mut storage)
   .unwrap();
@@ -23,12 +23,14 @@ use crate::{
 };
 use frame_support::{
   assert_noop, assert_ok,
+  dispatch::Pays,
   traits::fungibles::{Inspect, Mutate},
   BoundedVec,
 };
 use pallet_assets::{Account, Error as AssetsError};
 use pallet_balances::Error as BalancesError;
 use pallet_oracle::{AccountSwaps, Error as OracleError};
+use pallet_quorum::{Error as QuorumError, WithdrawalFee, WithdrawalFees};
 use pallet_sunrise::Error as SunriseError;
 use sp_runtime::{traits::BadOrigin, Permill};
 use std::str::FromStr;
@@ -225,6 +227,20 @@ impl Context {
     assert_eq!(Sunrise::sunrise_rewards(account, era_index), rewards_amount);
     self
   }
+
+  fn set_referral_rewards(
+    self,
+    account: AccountId,
+    era_index: u32,
+    rewards_amount: Balance,
+  ) -> Self {
+    pallet_sunrise::ReferralRewards::<Test>::insert(account, era_index, rewards_amount);
+    assert_eq!(
+      Sunrise::referral_rewards(account, era_index),
+      rewards_amount
+    );
+    self
+  }
 }
 
 fn get_alice_balance(currency_id: CurrencyId) -> Balance {
@@ -680,6 +696,74 @@ mod withdrawal {
         );
       });
     }
+
+    #[test]
+    fn external_address_is_empty() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, 10 * ONE_TDFY)
+          .create_temp_asset_and_metadata()
+          .mint_temp(ALICE_ACCOUNT_ID, 10 * ONE_TEMP);
+
+        assert_noop!(
+          Tidefi::withdrawal(
+            RuntimeOrigin::signed(context.sender),
+            TEMP_CURRENCY_ID,
+            context.amount,
+            Vec::new(),
+          ),
+          Error::<Test>::InvalidExternalAddressLength
+        );
+      });
+    }
+
+    #[test]
+    fn amount_is_below_withdrawal_fee() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, 10 * ONE_TDFY)
+          .create_temp_asset_and_metadata()
+          .mint_temp(ALICE_ACCOUNT_ID, 10 * ONE_TEMP);
+
+        WithdrawalFees::<Test>::insert(
+          TEMP_CURRENCY_ID,
+          WithdrawalFee {
+            flat: context.amount,
+            percentage: Permill::zero(),
+          },
+        );
+
+        assert_noop!(
+          Tidefi::withdrawal(
+            RuntimeOrigin::signed(context.sender),
+            TEMP_CURRENCY_ID,
+            context.amount,
+            context.external_address,
+          ),
+          QuorumError::<Test>::WithdrawalAmountBelowFee
+        );
+      });
+    }
+
+    #[test]
+    fn external_address_checksum_is_invalid() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(ALICE_ACCOUNT_ID, 10 * ONE_TDFY)
+          .create_temp_asset_and_metadata()
+          .mint_temp(ALICE_ACCOUNT_ID, 10 * ONE_TEMP);
+
+        assert_noop!(
+          Tidefi::withdrawal(
+            RuntimeOrigin::signed(context.sender),
+            TEMP_CURRENCY_ID,
+            context.amount,
+            vec![0xff; 32],
+          ),
+          Error::<Test>::InvalidExternalAddressChecksum
+        );
+      });
+    }
   }
 }
 
@@ -1584,3 +1668,340 @@ mod claim_sunrise_rewards {
     }
   }
 }
+
+mod claim_all_sunrise_rewards {
+  use super::*;
+
+  const REWARDS_CLAIMER: AccountId = CHARLIE_ACCOUNT_ID;
+
+  fn context_with_rewards() -> Context {
+    Context::default()
+      .mint_tdfy(Sunrise::account_id(), 1_000 * ONE_TDFY)
+      .mint_tdfy(REWARDS_CLAIMER, 1_000 * ONE_TDFY)
+      .set_active_era(25_000, 1_400_000)
+      .set_sunrise_rewards(REWARDS_CLAIMER, 20_000, ONE_TDFY)
+      .set_sunrise_rewards(REWARDS_CLAIMER, 20_001, 2 * ONE_TDFY)
+      .set_sunrise_rewards(REWARDS_CLAIMER, 20_002, 3 * ONE_TDFY)
+      // cooldown isn't cleared
+      .set_sunrise_rewards(REWARDS_CLAIMER, 24_999, 4 * ONE_TDFY)
+  }
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      let context = context_with_rewards();
+      let rewards_claimer_balance_before = Adapter::balance(CurrencyId::Tdfy, &REWARDS_CLAIMER);
+
+      assert_ok!(Pallet::<Test>::claim_all_sunrise_rewards(
+        context.rewards_claimer,
+        10,
+      ));
+
+      // Rewards are received in a single transfer
+      assert_eq!(
+        Adapter::balance(CurrencyId::Tdfy, &REWARDS_CLAIMER),
+        rewards_claimer_balance_before.saturating_add(6 * ONE_TDFY)
+      );
+
+      // SunriseClaimedAll event is emitted
+      System::assert_has_event(MockEvent::Sunrise(
+        pallet_sunrise::Event::SunriseClaimedAll {
+          account_id: REWARDS_CLAIMER,
+          eras: 3,
+          reward: 6 * ONE_TDFY,
+        },
+      ));
+
+      // Rewards book keeping is updated in storage, the era in cooldown is kept
+      assert_eq!(Sunrise::sunrise_rewards(REWARDS_CLAIMER, 20_000), 0);
+      assert_eq!(Sunrise::sunrise_rewards(REWARDS_CLAIMER, 20_001), 0);
+      assert_eq!(Sunrise::sunrise_rewards(REWARDS_CLAIMER, 20_002), 0);
+      assert_eq!(
+        Sunrise::sunrise_rewards(REWARDS_CLAIMER, 24_999),
+        4 * ONE_TDFY
+      );
+    });
+  }
+
+  #[test]
+  fn succeeds_up_to_max_eras() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_tdfy(Sunrise::account_id(), 1_000 * ONE_TDFY)
+        .mint_tdfy(REWARDS_CLAIMER, 1_000 * ONE_TDFY)
+        .set_active_era(25_000, 1_400_000)
+        .set_sunrise_rewards(REWARDS_CLAIMER, 20_000, ONE_TDFY)
+        .set_sunrise_rewards(REWARDS_CLAIMER, 20_001, 2 * ONE_TDFY)
+        .set_sunrise_rewards(REWARDS_CLAIMER, 20_002, 3 * ONE_TDFY);
+
+      assert_ok!(Pallet::<Test>::claim_all_sunrise_rewards(
+        context.rewards_claimer.clone(),
+        2,
+      ));
+      assert_eq!(
+        pallet_sunrise::Rewards::<Test>::iter_prefix(REWARDS_CLAIMER).count(),
+        1
+      );
+
+      assert_ok!(Pallet::<Test>::claim_all_sunrise_rewards(
+        context.rewards_claimer,
+        2,
+      ));
+      assert_eq!(
+        pallet_sunrise::Rewards::<Test>::iter_prefix(REWARDS_CLAIMER).count(),
+        0
+      );
+    });
+  }
+
+  #[test]
+  fn skips_the_eras_in_cooldown() {
+    new_test_ext().execute_with(|| {
+      let context =
+        context_with_rewards().set_sunrise_rewards(REWARDS_CLAIMER, 25_000, 5 * ONE_TDFY);
+
+      // the eras in cooldown don't count against `max_eras`
+      assert_ok!(Pallet::<Test>::claim_all_sunrise_rewards(
+        context.rewards_claimer,
+        3,
+      ));
+      System::assert_has_event(MockEvent::Sunrise(
+        pallet_sunrise::Event::SunriseClaimedAll {
+          account_id: REWARDS_CLAIMER,
+          eras: 3,
+          reward: 6 * ONE_TDFY,
+        },
+      ));
+      assert_eq!(
+        pallet_sunrise::Rewards::<Test>::iter_prefix(REWARDS_CLAIMER).count(),
+        2
+      );
+    });
+  }
+
+  #[test]
+  fn charges_the_eras_claimed() {
+    use crate::weights::{SubstrateWeight, WeightInfo};
+
+    new_test_ext().execute_with(|| {
+      let context = context_with_rewards();
+
+      let post_info =
+        Pallet::<Test>::claim_all_sunrise_rewards(context.rewards_claimer, 10).unwrap();
+      assert_eq!(
+        post_info.actual_weight,
+        Some(SubstrateWeight::<Test>::claim_all_sunrise_rewards(3))
+      );
+      assert_eq!(post_info.pays_fee, Pays::No);
+    });
+  }
+
+  #[test]
+  fn caps_the_eras_claimed() {
+    use crate::weights::{SubstrateWeight, WeightInfo};
+    use frame_support::{dispatch::GetDispatchInfo, traits::Get};
+
+    let call = crate::Call::<Test>::claim_all_sunrise_rewards { max_eras: u32::MAX };
+    assert_eq!(
+      call.get_dispatch_info().weight,
+      SubstrateWeight::<Test>::claim_all_sunrise_rewards(
+        <Test as crate::Config>::MaxClaimedEras::get()
+      )
+    );
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn not_signed() {
+      new_test_ext().execute_with(|| {
+        context_with_rewards();
+
+        assert_noop!(
+          Pallet::<Test>::claim_all_sunrise_rewards(RuntimeOrigin::none(), 10),
+          BadOrigin
+        );
+      });
+    }
+
+    #[test]
+    fn no_active_era() {
+      new_test_ext().execute_with(|| {
+        let context = context_with_rewards();
+
+        pallet_fees::ActiveEra::<Test>::kill();
+
+        assert_noop!(
+          Pallet::<Test>::claim_all_sunrise_rewards(context.rewards_claimer, 10),
+          Error::<Test>::NoActiveEra
+        );
+      });
+    }
+
+    #[test]
+    fn only_eras_in_cooldown() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(Sunrise::account_id(), 1_000 * ONE_TDFY)
+          .mint_tdfy(REWARDS_CLAIMER, 1_000 * ONE_TDFY)
+          .set_active_era(25_000, 1_400_000)
+          .set_sunrise_rewards(REWARDS_CLAIMER, 24_999, ONE_TDFY);
+
+        assert_noop!(
+          Pallet::<Test>::claim_all_sunrise_rewards(context.rewards_claimer, 10),
+          SunriseError::<Test>::NoRewardsAvailable
+        );
+      });
+    }
+
+    #[test]
+    fn no_rewards_available() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(Sunrise::account_id(), 1_000 * ONE_TDFY)
+          .set_active_era(25_000, 1_400_000);
+
+        assert_noop!(
+          Pallet::<Test>::claim_all_sunrise_rewards(context.rewards_claimer, 10),
+          SunriseError::<Test>::NoRewardsAvailable
+        );
+      });
+    }
+
+    #[test]
+    fn sunrise_account_has_insufficient_balance_to_pay_rewards() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(REWARDS_CLAIMER, 1_000 * ONE_TDFY)
+          .set_active_era(25_000, 1_400_000)
+          .set_sunrise_rewards(REWARDS_CLAIMER, 20_000, ONE_TDFY);
+
+        assert_noop!(
+          Pallet::<Test>::claim_all_sunrise_rewards(context.rewards_claimer, 10),
+          BalancesError::<Test>::InsufficientBalance
+        );
+      });
+    }
+  }
+}
+
+mod get_account_sunrise_rewards {
+  use super::*;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      Context::default()
+        .set_active_era(25_000, 1_400_000)
+        .set_sunrise_rewards(CHARLIE_ACCOUNT_ID, 24_999, 2 * ONE_TDFY)
+        .set_sunrise_rewards(CHARLIE_ACCOUNT_ID, 20_000, ONE_TDFY);
+
+      let rewards = Pallet::<Test>::get_account_sunrise_rewards(&CHARLIE_ACCOUNT_ID).unwrap();
+
+      // era length is 500 blocks and cooldown is 2_592 eras
+      assert_eq!(
+        rewards,
+        vec![
+          pallet_sunrise::SunriseEraReward {
+            era_index: 20_000,
+            reward: ONE_TDFY,
+            claimable_at: 1_400_000 - (25_000 - 22_593) * 500,
+            expire_at: None,
+          },
+          pallet_sunrise::SunriseEraReward {
+            era_index: 24_999,
+            reward: 2 * ONE_TDFY,
+            claimable_at: 1_400_000 + 2_592 * 500,
+            expire_at: None,
+          },
+        ]
+      );
+    });
+  }
+
+  #[test]
+  fn fails_without_active_era() {
+    new_test_ext().execute_with(|| {
+      Context::default().set_sunrise_rewards(CHARLIE_ACCOUNT_ID, 1, ONE_TDFY);
+      pallet_fees::ActiveEra::<Test>::kill();
+
+      assert_noop!(
+        Pallet::<Test>::get_account_sunrise_rewards(&CHARLIE_ACCOUNT_ID),
+        Error::<Test>::NoActiveEra
+      );
+    });
+  }
+}
+
+mod claim_referral_rewards {
+  use super::*;
+
+  const REWARDS_CLAIMER: AccountId = CHARLIE_ACCOUNT_ID;
+
+  #[test]
+  fn succeeds() {
+    new_test_ext().execute_with(|| {
+      let context = Context::default()
+        .mint_tdfy(Sunrise::account_id(), 1_000 * ONE_TDFY)
+        .mint_tdfy(REWARDS_CLAIMER, 1_000 * ONE_TDFY)
+        .set_active_era(25_000, 1_400_000)
+        .set_referral_rewards(REWARDS_CLAIMER, 20_000, ONE_TDFY);
+      let rewards_claimer_balance_before = Adapter::balance(CurrencyId::Tdfy, &REWARDS_CLAIMER);
+
+      assert_ok!(Pallet::<Test>::claim_referral_rewards(
+        context.rewards_claimer,
+        20_000,
+      ));
+
+      // Rewards are received
+      assert_eq!(
+        Adapter::balance(CurrencyId::Tdfy, &REWARDS_CLAIMER),
+        rewards_claimer_balance_before.saturating_add(ONE_TDFY)
+      );
+
+      // ReferralClaimed event is emitted
+      System::assert_has_event(MockEvent::Sunrise(pallet_sunrise::Event::ReferralClaimed {
+        era_index: 20_000,
+        account_id: REWARDS_CLAIMER,
+        reward: ONE_TDFY,
+      }));
+
+      // Rewards book keeping is updated in storage
+      assert_eq!(Sunrise::referral_rewards(REWARDS_CLAIMER, 20_000), 0);
+    });
+  }
+
+  mod fails_when {
+    use super::*;
+
+    #[test]
+    fn era_is_in_cooldown() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(Sunrise::account_id(), 1_000 * ONE_TDFY)
+          .set_active_era(25_000, 1_400_000)
+          .set_referral_rewards(REWARDS_CLAIMER, 24_999, ONE_TDFY);
+
+        assert_noop!(
+          Pallet::<Test>::claim_referral_rewards(context.rewards_claimer, 24_999),
+          Error::<Test>::EraNotReady
+        );
+      });
+    }
+
+    #[test]
+    fn no_rewards_available() {
+      new_test_ext().execute_with(|| {
+        let context = Context::default()
+          .mint_tdfy(Sunrise::account_id(), 1_000 * ONE_TDFY)
+          .set_active_era(25_000, 1_400_000);
+
+        assert_noop!(
+          Pallet::<Test>::claim_referral_rewards(context.rewards_claimer, 20_000),
+          pallet_sunrise::Error::<Test>::NoRewardsAvailable
+        );
+      });
+    }
+  }
+}
@@ -26,6 +26,8 @@ pub trait WeightInfo {
    fn swap() -> Weight;
    fn transfer() -> Weight;
    fn claim_sunrise_rewards() -> Weight;
+   fn claim_all_sunrise_rewards(e: u32) -> Weight;
+   fn claim_referral_rewards() -> Weight;
 }
 
 /// Weights for `pallet_tidefi` using the Substrate node and recommended hardware.
@@ -51,4 +53,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
       .saturating_add(T::DbWeight::get().reads(6_u64))
       .saturating_add(T::DbWeight::get().writes(5_u64))
    }
+   fn claim_all_sunrise_rewards(e: u32) -> Weight {
+      Weight::from_ref_time(25_200_000)
+      .saturating_add(Weight::from_ref_time(3_100_000).saturating_mul(e.into()))
+      .saturating_add(T::DbWeight::get().reads(5_u64))
+      .saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(e.into())))
+      .saturating_add(T::DbWeight::get().writes(4_u64))
+      .saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+   }
+   fn claim_referral_rewards() -> Weight {
+      Weight::from_ref_time(24_700_000)
+      .saturating_add(T::DbWeight::get().reads(6_u64))
+      .saturating_add(T::DbWeight::get().writes(4_u64))
+   }
 }
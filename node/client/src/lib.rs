@@ -80,6 +80,7 @@ pub trait RuntimeApiCollection:
   + sp_session::SessionKeys<Block>
   + sp_authority_discovery::AuthorityDiscoveryApi<Block>
   + pallet_tidefi_rpc::TidefiRuntimeApi<Block, AccountId>
+  + pallet_quorum_rpc::QuorumRuntimeApi<Block, AccountId, BlockNumber>
 where
   <Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -98,7 +99,8 @@ where
     + sp_offchain::OffchainWorkerApi<Block>
     + sp_session::SessionKeys<Block>
     + sp_authority_discovery::AuthorityDiscoveryApi<Block>
-    + pallet_tidefi_rpc::TidefiRuntimeApi<Block, AccountId>,
+    + pallet_tidefi_rpc::TidefiRuntimeApi<Block, AccountId>
+    + pallet_quorum_rpc::QuorumRuntimeApi<Block, AccountId, BlockNumber>,
   <Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
 }
@@ -100,11 +100,13 @@ where
   C::Api: BlockBuilder<Block>,
   // Tidechain API
   C::Api: pallet_tidefi_rpc::TidefiRuntimeApi<Block, AccountId>,
+  C::Api: pallet_quorum_rpc::QuorumRuntimeApi<Block, AccountId, BlockNumber>,
   P: TransactionPool + 'static,
   SC: SelectChain<Block> + 'static,
   B: sc_client_api::Backend<Block> + Send + Sync + 'static,
   B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 {
+  use pallet_quorum_rpc::{QuorumApiServer, QuorumRpc};
   use pallet_tidefi_rpc::{TidefiApiServer, TidefiRpc};
   use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
   use substrate_frame_rpc_system::{System, SystemApiServer};
@@ -170,7 +172,8 @@ where
   )?;
 
   // Tidechain Custom traits
-  io.merge(TidefiRpc::new(client).into_rpc())?;
+  io.merge(TidefiRpc::new(client.clone()).into_rpc())?;
+  io.merge(QuorumRpc::new(client).into_rpc())?;
 
   Ok(io)
 }
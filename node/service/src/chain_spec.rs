@@ -16,6 +16,7 @@
 
 use hex_literal::hex;
 use itertools::Itertools;
+use pallet_asset_registry::AssetInfo;
 use pallet_im_online::sr25519::AuthorityId as ImOnlineId;
 use sc_service::ChainType;
 use sc_telemetry::TelemetryEndpoints;
@@ -267,6 +268,10 @@ fn lagoon_testnet_genesis(
       members: vec![],
       phantom: Default::default(),
     },
+    guardian_committee: lagoon_runtime::GuardianCommitteeConfig {
+      members: vec![],
+      phantom: Default::default(),
+    },
 
     sudo: lagoon_runtime::SudoConfig {
       key: Some(root.clone()),
@@ -280,6 +285,7 @@ fn lagoon_testnet_genesis(
     authority_discovery: lagoon_runtime::AuthorityDiscoveryConfig { keys: vec![] },
     grandpa: Default::default(),
     technical_membership: Default::default(),
+    guardian_membership: Default::default(),
     treasury: Default::default(),
     // tidefi custom genesis
     quorum: lagoon_runtime::QuorumConfig {
@@ -352,6 +358,7 @@ fn lagoon_testnet_genesis(
       // this is the owner of the wrapped asset on chain and have full authority on them
       // this account can also create new wrapped asset on chain
       account: root,
+      infos: helpers::get_all_asset_infos(),
     },
     security: Default::default(),
     fees: Default::default(),
@@ -568,6 +575,11 @@ fn tidechain_testnet_genesis(
       phantom: Default::default(),
     },
 
+    guardian_committee: tidechain_runtime::GuardianCommitteeConfig {
+      members: vec![],
+      phantom: Default::default(),
+    },
+
     // FIXME: Remove sudo once the staging is completed
     sudo: tidechain_runtime::SudoConfig { key: Some(root) },
 
@@ -579,6 +591,7 @@ fn tidechain_testnet_genesis(
     authority_discovery: tidechain_runtime::AuthorityDiscoveryConfig { keys: vec![] },
     grandpa: Default::default(),
     technical_membership: Default::default(),
+    guardian_membership: Default::default(),
     treasury: Default::default(),
     // tidefi custom genesis
     quorum: tidechain_runtime::QuorumConfig {
@@ -597,6 +610,7 @@ fn tidechain_testnet_genesis(
       assets: helpers::get_assets_with_stakeholders(stakeholders, assets),
       // FIXME: Not sure if the owner should be the asset registry pallet itself?
       account: asset_registry,
+      infos: helpers::get_all_asset_infos(),
     },
     security: Default::default(),
     fees: Default::default(),
@@ -1254,6 +1268,27 @@ mod helpers {
       .collect()
   }
 
+  pub fn get_all_asset_infos() -> Vec<(CurrencyId, AssetInfo<Vec<u8>>)> {
+    assets::Asset::iter()
+      .map(|asset| {
+        (
+          asset.currency_id(),
+          AssetInfo {
+            base_chain: asset
+              .base_chain()
+              .map(|base_chain| base_chain.currency_id()),
+            contract_address: None,
+            required_confirmations: 0,
+            deposit_minimum: 0,
+            withdrawal_minimum: 0,
+            // only the assets deposited from an external chain
+            gas_refund: asset.currency_id() != CurrencyId::Tdfy,
+          },
+        )
+      })
+      .collect()
+  }
+
   pub(crate) fn adjust_treasury_balance_for_initial_validators_and_quorums(
     initial_validators: usize,
     initial_quorums: usize,
@@ -0,0 +1,547 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! External address validation for the withdrawals, selected by the asset base chain.
+
+use frame_support::traits::Get;
+use pallet_asset_registry::AssetInfoProvider;
+use pallet_btc_relay::Network;
+use pallet_tidefi::{AddressError, AddressValidator};
+use sp_std::marker::PhantomData;
+use tidefi_primitives::{assets::Asset, CurrencyId};
+
+/// Validate the withdrawal external address with the format of the asset base chain.
+///
+/// Bitcoin addresses are only accepted for the `BitcoinNetwork` followed by the runtime.
+/// The base chain is read from the `AssetRegistry` records, the assets without record fall back
+/// to the compiled assets. Assets without a known base chain format are rejected, as the address
+/// can't be validated.
+pub struct ExternalAddressValidator<BitcoinNetwork, AssetRegistry>(
+  PhantomData<(BitcoinNetwork, AssetRegistry)>,
+);
+
+impl<BitcoinNetwork, AssetRegistry: AssetInfoProvider>
+  ExternalAddressValidator<BitcoinNetwork, AssetRegistry>
+{
+  fn base_chain(currency_id: CurrencyId) -> Option<Asset> {
+    match AssetRegistry::asset_info(currency_id) {
+      // the assets without base chain are the native currency of their chain
+      Some(asset_info) => asset_info.base_chain.unwrap_or(currency_id).try_into().ok(),
+      None => {
+        let asset: Asset = currency_id.try_into().ok()?;
+        Some(asset.base_chain().unwrap_or(asset))
+      }
+    }
+  }
+}
+
+impl<BitcoinNetwork: Get<Network>, AssetRegistry: AssetInfoProvider> AddressValidator
+  for ExternalAddressValidator<BitcoinNetwork, AssetRegistry>
+{
+  fn validate(currency_id: CurrencyId, address: &[u8]) -> Result<(), AddressError> {
+    match Self::base_chain(currency_id) {
+      Some(Asset::Bitcoin) => bitcoin::validate(BitcoinNetwork::get(), address),
+      Some(Asset::Ethereum) => ethereum::validate(address),
+      _ => Err(AddressError::UnsupportedChain),
+    }
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn valid_address(currency_id: CurrencyId) -> sp_std::vec::Vec<u8> {
+    match (Self::base_chain(currency_id), BitcoinNetwork::get()) {
+      (Some(Asset::Bitcoin), Network::Mainnet) => {
+        b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_vec()
+      }
+      (Some(Asset::Bitcoin), _) => b"mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn".to_vec(),
+      (Some(Asset::Ethereum), _) => b"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_vec(),
+      _ => sp_std::vec::Vec::new(),
+    }
+  }
+}
+
+mod bitcoin {
+  use super::{AddressError, Network};
+  use sp_io::hashing::sha2_256;
+
+  const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+  /// Version byte (1) + hash160 (20) + checksum (4)
+  const BASE58_PAYLOAD_LENGTH: usize = 25;
+  /// P2PKH and P2SH version bytes for mainnet
+  const MAINNET_BASE58_VERSIONS: &[u8] = &[0x00, 0x05];
+  /// P2PKH and P2SH version bytes for testnet and regtest
+  const TESTNET_BASE58_VERSIONS: &[u8] = &[0x6f, 0xc4];
+
+  const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+  const BECH32_MAX_LENGTH: usize = 90;
+  /// Human readable parts for mainnet, testnet and regtest
+  const BECH32_HRPS: [&[u8]; 3] = [b"bc", b"tb", b"bcrt"];
+  /// BIP-173 checksum constant, used by witness version 0
+  const BECH32_CONST: u32 = 1;
+  /// BIP-350 checksum constant, used by witness version 1 and above
+  const BECH32M_CONST: u32 = 0x2bc8_30a3;
+  const CHECKSUM_LENGTH: usize = 6;
+
+  pub fn validate(network: Network, address: &[u8]) -> Result<(), AddressError> {
+    let (base58_versions, bech32_hrp): (&[u8], &[u8]) = match network {
+      Network::Mainnet => (MAINNET_BASE58_VERSIONS, BECH32_HRPS[0]),
+      Network::Testnet => (TESTNET_BASE58_VERSIONS, BECH32_HRPS[1]),
+      Network::Regtest => (TESTNET_BASE58_VERSIONS, BECH32_HRPS[2]),
+    };
+
+    // detect segwit addresses from any network, the human readable part is checked once decoded
+    let is_segwit = BECH32_HRPS.iter().any(|hrp| {
+      address.len() > hrp.len()
+        && address[..hrp.len()].eq_ignore_ascii_case(hrp)
+        && address[hrp.len()] == b'1'
+    });
+
+    if is_segwit {
+      validate_segwit(bech32_hrp, address)
+    } else {
+      validate_base58check(base58_versions, address)
+    }
+  }
+
+  fn validate_base58check(versions: &[u8], address: &[u8]) -> Result<(), AddressError> {
+    if !(26..=35).contains(&address.len()) {
+      return Err(AddressError::InvalidLength);
+    }
+
+    // big-endian base58 decoding into the fixed payload
+    let mut payload = [0_u8; BASE58_PAYLOAD_LENGTH];
+    for character in address {
+      let mut carry = BASE58_ALPHABET
+        .iter()
+        .position(|c| c == character)
+        .ok_or(AddressError::InvalidEncoding)? as u32;
+      for byte in payload.iter_mut().rev() {
+        carry += (*byte as u32) * 58;
+        *byte = carry as u8;
+        carry >>= 8;
+      }
+      if carry != 0 {
+        return Err(AddressError::InvalidLength);
+      }
+    }
+
+    // every leading `1` encode a leading zero byte, anything else means the payload is too short
+    let leading_ones = address.iter().take_while(|c| **c == b'1').count();
+    let leading_zeros = payload.iter().take_while(|b| **b == 0).count();
+    if leading_ones != leading_zeros {
+      return Err(AddressError::InvalidLength);
+    }
+
+    let (data, checksum) = payload.split_at(BASE58_PAYLOAD_LENGTH - 4);
+    if sha2_256(&sha2_256(data))[..4] != *checksum {
+      return Err(AddressError::InvalidChecksum);
+    }
+
+    if !versions.contains(&data[0]) {
+      return Err(AddressError::InvalidEncoding);
+    }
+
+    Ok(())
+  }
+
+  fn validate_segwit(network_hrp: &[u8], address: &[u8]) -> Result<(), AddressError> {
+    if address.len() > BECH32_MAX_LENGTH {
+      return Err(AddressError::InvalidLength);
+    }
+
+    // mixed case is not allowed
+    if address.iter().any(|c| c.is_ascii_lowercase())
+      && address.iter().any(|c| c.is_ascii_uppercase())
+    {
+      return Err(AddressError::InvalidEncoding);
+    }
+
+    let separator = address
+      .iter()
+      .rposition(|c| *c == b'1')
+      .ok_or(AddressError::InvalidEncoding)?;
+    let (hrp, data) = (&address[..separator], &address[separator + 1..]);
+
+    // address from another network
+    if !hrp.eq_ignore_ascii_case(network_hrp) {
+      return Err(AddressError::InvalidEncoding);
+    }
+
+    // witness version + checksum
+    if data.len() < 1 + CHECKSUM_LENGTH {
+      return Err(AddressError::InvalidLength);
+    }
+
+    let mut values = [0_u8; BECH32_MAX_LENGTH];
+    for (value, character) in values.iter_mut().zip(data) {
+      *value = BECH32_CHARSET
+        .iter()
+        .position(|c| *c == character.to_ascii_lowercase())
+        .ok_or(AddressError::InvalidEncoding)? as u8;
+    }
+    let values = &values[..data.len()];
+
+    let witness_version = values[0];
+    let expected_checksum = match witness_version {
+      0 => BECH32_CONST,
+      1..=16 => BECH32M_CONST,
+      _ => return Err(AddressError::InvalidEncoding),
+    };
+    if polymod(hrp, values) != expected_checksum {
+      return Err(AddressError::InvalidChecksum);
+    }
+
+    // convert the 5-bit groups of the witness program into bytes
+    let mut accumulator = 0_u32;
+    let mut bits = 0_u32;
+    let mut program_length = 0_usize;
+    for value in &values[1..values.len() - CHECKSUM_LENGTH] {
+      accumulator = (accumulator << 5) | *value as u32;
+      bits += 5;
+      if bits >= 8 {
+        bits -= 8;
+        program_length += 1;
+      }
+      accumulator &= (1 << bits) - 1;
+    }
+    // padding can't exceed 4 bits and must be zero
+    if bits >= 5 || accumulator != 0 {
+      return Err(AddressError::InvalidEncoding);
+    }
+
+    match (witness_version, program_length) {
+      (0, 20) | (0, 32) => Ok(()),
+      (0, _) => Err(AddressError::InvalidLength),
+      (_, 2..=40) => Ok(()),
+      _ => Err(AddressError::InvalidLength),
+    }
+  }
+
+  fn polymod(hrp: &[u8], values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [
+      0x3b6a_57b2,
+      0x2650_8e6d,
+      0x1ea1_19fa,
+      0x3d42_33dd,
+      0x2a14_62b3,
+    ];
+
+    let expanded_hrp = hrp
+      .iter()
+      .map(|c| c.to_ascii_lowercase() >> 5)
+      .chain(Some(0))
+      .chain(hrp.iter().map(|c| c.to_ascii_lowercase() & 0x1f));
+
+    let mut checksum = 1_u32;
+    for value in expanded_hrp.chain(values.iter().copied()) {
+      let top = checksum >> 25;
+      checksum = ((checksum & 0x01ff_ffff) << 5) ^ value as u32;
+      for (i, generator) in GENERATOR.iter().enumerate() {
+        if (top >> i) & 1 == 1 {
+          checksum ^= generator;
+        }
+      }
+    }
+    checksum
+  }
+}
+
+mod ethereum {
+  use super::AddressError;
+  use sp_io::hashing::keccak_256;
+
+  const HEX_ADDRESS_LENGTH: usize = 40;
+
+  pub fn validate(address: &[u8]) -> Result<(), AddressError> {
+    let hex = address
+      .strip_prefix(b"0x")
+      .ok_or(AddressError::InvalidEncoding)?;
+
+    if hex.len() != HEX_ADDRESS_LENGTH {
+      return Err(AddressError::InvalidLength);
+    }
+
+    if !hex.iter().all(|c| c.is_ascii_hexdigit()) {
+      return Err(AddressError::InvalidEncoding);
+    }
+
+    // all lowercase or all uppercase addresses do not carry an EIP-55 checksum
+    let has_lowercase = hex.iter().any(|c| c.is_ascii_lowercase());
+    let has_uppercase = hex.iter().any(|c| c.is_ascii_uppercase());
+    if !(has_lowercase && has_uppercase) {
+      return Ok(());
+    }
+
+    let mut lowercase_hex = [0_u8; HEX_ADDRESS_LENGTH];
+    lowercase_hex.copy_from_slice(hex);
+    lowercase_hex.make_ascii_lowercase();
+    let hash = keccak_256(&lowercase_hex);
+
+    for (i, character) in hex.iter().enumerate() {
+      if character.is_ascii_alphabetic() {
+        let nibble = if i % 2 == 0 {
+          hash[i / 2] >> 4
+        } else {
+          hash[i / 2] & 0x0f
+        };
+        if (nibble >= 8) != character.is_ascii_uppercase() {
+          return Err(AddressError::InvalidChecksum);
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use pallet_asset_registry::AssetInfo;
+
+  frame_support::parameter_types! {
+    pub const Mainnet: Network = Network::Mainnet;
+    pub const Testnet: Network = Network::Testnet;
+    pub const Regtest: Network = Network::Regtest;
+  }
+
+  /// Token listed on Ethereum with `set_asset_info`, without compiled asset
+  const LISTED_TOKEN: CurrencyId = CurrencyId::Wrapped(u32::MAX - 1);
+
+  /// Asset records of the tests, only the listed token has a record
+  pub struct AssetRegistry;
+  impl AssetInfoProvider for AssetRegistry {
+    fn asset_info(currency_id: CurrencyId) -> Option<AssetInfo<sp_std::vec::Vec<u8>>> {
+      (currency_id == LISTED_TOKEN).then(|| AssetInfo {
+        base_chain: Some(Asset::Ethereum.currency_id()),
+        contract_address: None,
+        required_confirmations: 12,
+        deposit_minimum: 0,
+        withdrawal_minimum: 0,
+        gas_refund: false,
+      })
+    }
+
+    fn decimals(_currency_id: CurrencyId) -> Option<u8> {
+      None
+    }
+  }
+
+  fn validate(asset: Asset, address: &str) -> Result<(), AddressError> {
+    ExternalAddressValidator::<Mainnet, AssetRegistry>::validate(
+      asset.currency_id(),
+      address.as_bytes(),
+    )
+  }
+
+  fn validate_testnet(asset: Asset, address: &str) -> Result<(), AddressError> {
+    ExternalAddressValidator::<Testnet, AssetRegistry>::validate(
+      asset.currency_id(),
+      address.as_bytes(),
+    )
+  }
+
+  #[test]
+  fn bitcoin_base58_addresses() {
+    // P2PKH
+    assert_eq!(
+      validate(Asset::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+      Ok(())
+    );
+    // P2SH
+    assert_eq!(
+      validate(Asset::Bitcoin, "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"),
+      Ok(())
+    );
+
+    assert_eq!(
+      validate(Asset::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"),
+      Err(AddressError::InvalidChecksum)
+    );
+    assert_eq!(
+      validate(Asset::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfN0"),
+      Err(AddressError::InvalidEncoding)
+    );
+    assert_eq!(
+      validate(Asset::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SL"),
+      Err(AddressError::InvalidLength)
+    );
+    assert_eq!(
+      validate(Asset::Bitcoin, ""),
+      Err(AddressError::InvalidLength)
+    );
+  }
+
+  #[test]
+  fn bitcoin_bech32_addresses() {
+    // P2WPKH
+    assert_eq!(
+      validate(Asset::Bitcoin, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+      Ok(())
+    );
+    assert_eq!(
+      validate(Asset::Bitcoin, "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"),
+      Ok(())
+    );
+    // P2TR (bech32m)
+    assert_eq!(
+      validate(
+        Asset::Bitcoin,
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0"
+      ),
+      Ok(())
+    );
+
+    assert_eq!(
+      validate(Asset::Bitcoin, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"),
+      Err(AddressError::InvalidChecksum)
+    );
+    assert_eq!(
+      validate(Asset::Bitcoin, "bc1qw508d6qejxtdg4y5r3zArvary0c5xw7kv8f3t4"),
+      Err(AddressError::InvalidEncoding)
+    );
+    assert_eq!(
+      validate(Asset::Bitcoin, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3tb"),
+      Err(AddressError::InvalidEncoding)
+    );
+  }
+
+  #[test]
+  fn bitcoin_addresses_are_validated_against_the_network() {
+    // testnet P2PKH
+    assert_eq!(
+      validate_testnet(Asset::Bitcoin, "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn"),
+      Ok(())
+    );
+    assert_eq!(
+      validate(Asset::Bitcoin, "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn"),
+      Err(AddressError::InvalidEncoding)
+    );
+    // P2WSH on testnet
+    assert_eq!(
+      validate_testnet(
+        Asset::Bitcoin,
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7"
+      ),
+      Ok(())
+    );
+    assert_eq!(
+      validate(
+        Asset::Bitcoin,
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7"
+      ),
+      Err(AddressError::InvalidEncoding)
+    );
+    // mainnet addresses on testnet
+    assert_eq!(
+      validate_testnet(Asset::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+      Err(AddressError::InvalidEncoding)
+    );
+    assert_eq!(
+      validate_testnet(Asset::Bitcoin, "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"),
+      Err(AddressError::InvalidEncoding)
+    );
+    // regtest share the testnet version bytes but not the human readable part
+    assert_eq!(
+      ExternalAddressValidator::<Regtest, AssetRegistry>::validate(
+        Asset::Bitcoin.currency_id(),
+        b"mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn"
+      ),
+      Ok(())
+    );
+    assert_eq!(
+      ExternalAddressValidator::<Regtest, AssetRegistry>::validate(
+        Asset::Bitcoin.currency_id(),
+        b"tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7"
+      ),
+      Err(AddressError::InvalidEncoding)
+    );
+  }
+
+  #[test]
+  fn ethereum_addresses() {
+    for asset in [Asset::Ethereum, Asset::USDCoin, Asset::Tether] {
+      assert_eq!(
+        validate(asset, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+        Ok(())
+      );
+      assert_eq!(
+        validate(asset, "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"),
+        Ok(())
+      );
+      // without checksum
+      assert_eq!(
+        validate(asset, "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"),
+        Ok(())
+      );
+      assert_eq!(
+        validate(asset, "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"),
+        Ok(())
+      );
+
+      assert_eq!(
+        validate(asset, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"),
+        Err(AddressError::InvalidChecksum)
+      );
+      assert_eq!(
+        validate(asset, "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+        Err(AddressError::InvalidEncoding)
+      );
+      assert_eq!(
+        validate(asset, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg"),
+        Err(AddressError::InvalidEncoding)
+      );
+      assert_eq!(
+        validate(asset, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"),
+        Err(AddressError::InvalidLength)
+      );
+    }
+  }
+
+  #[test]
+  fn addresses_are_validated_against_the_base_chain() {
+    assert_eq!(
+      validate(Asset::Tether, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+      Err(AddressError::InvalidEncoding)
+    );
+    assert_eq!(
+      validate(Asset::Bitcoin, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+      Err(AddressError::InvalidLength)
+    );
+    // the listed tokens are validated against the base chain of their record
+    assert_eq!(
+      ExternalAddressValidator::<Mainnet, AssetRegistry>::validate(
+        LISTED_TOKEN,
+        b"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+      ),
+      Ok(())
+    );
+    assert_eq!(
+      ExternalAddressValidator::<Mainnet, AssetRegistry>::validate(
+        LISTED_TOKEN,
+        b"1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"
+      ),
+      Err(AddressError::InvalidEncoding)
+    );
+    // unknown assets can't be validated
+    assert_eq!(
+      ExternalAddressValidator::<Mainnet, AssetRegistry>::validate(
+        CurrencyId::Wrapped(u32::MAX),
+        b"anything"
+      ),
+      Err(AddressError::UnsupportedChain)
+    );
+  }
+}
@@ -19,8 +19,9 @@ use crate::{
     AccountId, AuthorityDiscoveryId, Balance, Block, CurrencyId, EpochDuration, GrandpaId, Index,
     BABE_GENESIS_EPOCH_CONFIG,
   },
-  AssetRegistry, AuthorityDiscovery, Babe, Executive, Grandpa, Historical, InherentDataExt,
-  Runtime, SessionKeys, System, TidefiStaking, TransactionPayment, VERSION,
+  AssetRegistry, AuthorityDiscovery, Babe, Executive, Fees, Grandpa, Historical, InherentDataExt,
+  Quorum, Runtime, Security, SessionKeys, Sunrise, System, Tidefi, TidefiStaking,
+  TransactionPayment, VERSION,
 };
 use frame_support::{pallet_prelude::Weight, traits::KeyOwnerProofSystem};
 use pallet_grandpa::{fg_primitives, AuthorityList as GrandpaAuthorityList};
@@ -36,7 +37,10 @@ use sp_runtime::{
 };
 use sp_std::prelude::*;
 use sp_version::RuntimeVersion;
-use tidefi_primitives::{BalanceInfo, BlockNumber, CurrencyBalance, CurrencyMetadata, Stake};
+use tidefi_primitives::{
+  AssetId, BalanceInfo, BlockNumber, CurrencyBalance, CurrencyMetadata, Hash, Stake, WatchList,
+  Withdrawal,
+};
 
 // Work around the issue that RUNTIME_API_VERSIONS is not public.
 pub(crate) const PRUNTIME_API_VERSIONS: ApisVec = RUNTIME_API_VERSIONS;
@@ -232,6 +236,9 @@ impl_runtime_apis! {
      fn get_assets() -> Result<Vec<(CurrencyId, CurrencyMetadata<Vec<u8>>)>, DispatchError> {
        AssetRegistry::get_assets()
      }
+     fn get_asset_infos() -> Result<Vec<(CurrencyId, pallet_asset_registry::AssetInfo<Vec<u8>>)>, DispatchError> {
+       Ok(AssetRegistry::get_asset_infos())
+     }
      fn get_account_balance(account_id: AccountId, asset_id: CurrencyId) -> Result<CurrencyBalance<BalanceInfo>, DispatchError> {
        AssetRegistry::get_account_balance(&account_id, asset_id)
      }
@@ -241,6 +248,53 @@ impl_runtime_apis! {
      fn get_account_stakes(account_id: AccountId) -> Result<Vec<(CurrencyId, Stake<BalanceInfo, BlockNumber>)>, DispatchError> {
        Ok(TidefiStaking::get_account_stakes(&account_id))
      }
+     fn get_staking_yields() -> Result<Vec<pallet_tidefi_stake::StakingYield<BlockNumber>>, DispatchError> {
+       Ok(TidefiStaking::get_staking_yields())
+     }
+     fn get_account_stakes_projection(account_id: AccountId) -> Result<Vec<pallet_tidefi_stake::StakeProjection<BlockNumber>>, DispatchError> {
+       Ok(TidefiStaking::get_account_stakes_projection(&account_id))
+     }
+     fn get_account_sunrise_rewards(account_id: AccountId) -> Result<Vec<pallet_sunrise::SunriseEraReward<BlockNumber>>, DispatchError> {
+       Tidefi::get_account_sunrise_rewards(&account_id)
+     }
+     fn get_sunrise_pools() -> Result<pallet_sunrise::SunrisePools, DispatchError> {
+       Ok(Sunrise::get_sunrise_pools())
+     }
+     fn get_onboarding_rebates() -> Result<pallet_sunrise::OnboardingRebatesProgress, DispatchError> {
+       Ok(Sunrise::get_onboarding_rebates_progress())
+     }
+     fn get_account_fee_statements(account_id: AccountId) -> Result<Vec<pallet_fees::AccountFeeStatement>, DispatchError> {
+       Ok(Fees::get_account_fee_statements(&account_id))
+     }
+     fn get_paused_calls() -> Result<Vec<pallet_security::PausedCall<Vec<u8>>>, DispatchError> {
+       Ok(Security::get_paused_calls())
+     }
+
+     fn get_scheduled_maintenance() -> Result<Option<pallet_security::MaintenanceWindow<BlockNumber, Vec<u8>>>, DispatchError> {
+       Ok(Security::get_scheduled_maintenance())
+     }
+   }
+
+   // Quorum API
+   impl pallet_quorum_rpc_runtime_api::QuorumApi<Block, AccountId, BlockNumber> for Runtime {
+     fn get_proposals(offset: u32, limit: u32) -> pallet_quorum::Page<pallet_quorum::ProposalInfo<AccountId, BlockNumber>> {
+       Quorum::get_proposals_info(offset, limit)
+     }
+     fn get_proposal(proposal_id: Hash) -> Option<pallet_quorum::ProposalInfo<AccountId, BlockNumber>> {
+       Quorum::get_proposal_info(proposal_id)
+     }
+     fn get_burned_queue(offset: u32, limit: u32) -> pallet_quorum::Page<(Hash, Withdrawal<AccountId, BlockNumber, Vec<u8>>)> {
+       Quorum::get_burned_queue(offset, limit)
+     }
+     fn get_public_keys(asset_id: AssetId, offset: u32, limit: u32) -> pallet_quorum::Page<(AccountId, Vec<u8>)> {
+       Quorum::get_public_keys(asset_id, offset, limit)
+     }
+     fn get_account_watch_list(account_id: AccountId, offset: u32, limit: u32) -> pallet_quorum::Page<WatchList<BlockNumber, Vec<u8>>> {
+       Quorum::get_account_watch_list(account_id, offset, limit)
+     }
+     fn get_completed_withdrawals(account_id: AccountId, offset: u32, limit: u32) -> pallet_quorum::Page<pallet_quorum::CompletedWithdrawal<BlockNumber, Vec<u8>>> {
+       Quorum::get_completed_withdrawals(account_id, offset, limit)
+     }
    }
 
    #[cfg(feature = "try-runtime")]
@@ -305,11 +359,13 @@ impl_runtime_apis! {
        list_benchmark!(list, extra, pallet_utility, crate::Utility);
        list_benchmark!(list, extra, pallet_tidefi, crate::Tidefi);
        list_benchmark!(list, extra, pallet_quorum, crate::Quorum);
+       list_benchmark!(list, extra, pallet_btc_relay, crate::BtcRelay);
        list_benchmark!(list, extra, pallet_tidefi_stake, crate::TidefiStaking);
        list_benchmark!(list, extra, pallet_oracle, crate::Oracle);
        list_benchmark!(list, extra, pallet_asset_registry, crate::AssetRegistry);
        list_benchmark!(list, extra, frame_election_provider_support, ElectionProviderBench::<Runtime>);
        list_benchmark!(list, extra, pallet_fees, crate::Fees);
+       list_benchmark!(list, extra, pallet_sunrise, crate::Sunrise);
 
        let storage_info = crate::AllPalletsWithSystem::storage_info();
 
@@ -382,11 +438,13 @@ impl_runtime_apis! {
            add_benchmark!(params, batches, pallet_tidefi, crate::Tidefi);
            add_benchmark!(params, batches, pallet_tidefi_stake, crate::TidefiStaking);
            add_benchmark!(params, batches, pallet_quorum, crate::Quorum);
+           add_benchmark!(params, batches, pallet_btc_relay, crate::BtcRelay);
            add_benchmark!(params, batches, pallet_oracle, crate::Oracle);
            add_benchmark!(params, batches, pallet_asset_registry, crate::AssetRegistry);
            add_benchmark!(params, batches, pallet_preimage, crate::Preimage);
            add_benchmark!(params, batches, frame_election_provider_support, ElectionProviderBench::<Runtime>);
            add_benchmark!(params, batches, pallet_fees, crate::Fees);
+           add_benchmark!(params, batches, pallet_sunrise, crate::Sunrise);
 
            if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
            Ok(batches)
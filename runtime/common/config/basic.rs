@@ -20,7 +20,7 @@ use crate::{
     fee::WeightToFee,
     time::SLOT_DURATION,
   },
-  impls::DealWithFees,
+  impls::{DealWithFees, PausedCallsFilter},
   types::{
     AccountId, AccountIndex, Balance, BlakeTwo256, BlockHashCount, BlockNumber,
     EnsureRootOrHalfCouncil, Hash, Nonce, RocksDbWeight, RuntimeBlockLength, RuntimeBlockWeights,
@@ -30,7 +30,7 @@ use crate::{
 };
 use frame_support::{
   parameter_types,
-  traits::{ConstU32, Everything, PrivilegeCmp},
+  traits::{ConstU32, PrivilegeCmp},
   weights::{ConstantMultiplier, Weight},
 };
 use frame_system::EnsureRoot;
@@ -45,7 +45,8 @@ parameter_types! {
 }
 
 impl frame_system::Config for Runtime {
-  type BaseCallFilter = Everything;
+  // Reject the calls paused in the security pallet
+  type BaseCallFilter = PausedCallsFilter;
   type BlockWeights = RuntimeBlockWeights;
   type BlockLength = RuntimeBlockLength;
   type DbWeight = RocksDbWeight;
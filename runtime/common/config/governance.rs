@@ -20,9 +20,10 @@ use crate::{
     time::{DAYS, HOURS},
   },
   types::{AccountId, Balance, BlockNumber, EnsureRootOrHalfCouncil},
-  Balances, Bounties, Council, CouncilCollectiveInstance, OriginCaller, Preimage, Runtime,
-  RuntimeCall, RuntimeEvent, RuntimeOrigin, Scheduler, TechnicalCollectiveInstance,
-  TechnicalCommittee, Treasury, TreasuryPalletId,
+  Balances, Bounties, Council, CouncilCollectiveInstance, GuardianCollectiveInstance,
+  GuardianCommittee, GuardianMembershipInstance, OriginCaller, Preimage, Runtime, RuntimeCall,
+  RuntimeEvent, RuntimeOrigin, Scheduler, TechnicalCollectiveInstance, TechnicalCommittee,
+  Treasury, TreasuryPalletId,
 };
 use frame_support::{
   parameter_types,
@@ -122,6 +123,38 @@ impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
   type WeightInfo = crate::weights::pallet_membership::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+   // Guardians only pause the chain, their motions have to be voted quickly
+   pub const GuardianMotionDuration: BlockNumber = 6 * HOURS;
+   pub const GuardianMaxProposals: u32 = 10;
+   pub const GuardianMaxMembers: u32 = 16;
+}
+
+impl pallet_collective::Config<GuardianCollectiveInstance> for Runtime {
+  type RuntimeOrigin = RuntimeOrigin;
+  type Proposal = RuntimeCall;
+  type RuntimeEvent = RuntimeEvent;
+  type MotionDuration = GuardianMotionDuration;
+  type MaxProposals = GuardianMaxProposals;
+  type MaxMembers = GuardianMaxMembers;
+  type DefaultVote = pallet_collective::PrimeDefaultVote;
+  type SetMembersOrigin = EnsureRoot<AccountId>;
+  type WeightInfo = crate::weights::pallet_collective::WeightInfo<Runtime>;
+}
+
+impl pallet_membership::Config<GuardianMembershipInstance> for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type AddOrigin = EnsureRootOrHalfCouncil;
+  type RemoveOrigin = EnsureRootOrHalfCouncil;
+  type SwapOrigin = EnsureRootOrHalfCouncil;
+  type ResetOrigin = EnsureRootOrHalfCouncil;
+  type PrimeOrigin = EnsureRootOrHalfCouncil;
+  type MembershipInitialized = GuardianCommittee;
+  type MembershipChanged = GuardianCommittee;
+  type MaxMembers = GuardianMaxMembers;
+  type WeightInfo = crate::weights::pallet_membership::WeightInfo<Runtime>;
+}
+
 parameter_types! {
    pub const ProposalBond: Permill = Permill::from_percent(5);
    pub const ProposalBondMinimum: Balance = 100 * DOLLARS;
@@ -43,6 +43,12 @@ pub enum ProxyType {
   NonTransfer,
   Governance,
   Staking,
+  /// Swaps on tidefi, without custody of the funds
+  Trading,
+  /// Tidefi assets staking
+  AssetStaking,
+  /// Claims of the sunrise and referral rewards
+  SunriseClaim,
 }
 
 impl Default for ProxyType {
@@ -54,10 +60,15 @@ impl Default for ProxyType {
 impl InstanceFilter<RuntimeCall> for ProxyType {
   fn filter(&self, c: &RuntimeCall) -> bool {
     match self {
-      ProxyType::Any => false,
+      ProxyType::Any => true,
       ProxyType::NonTransfer => !matches!(
         c,
-        RuntimeCall::Balances(..) | RuntimeCall::Indices(pallet_indices::Call::transfer { .. })
+        RuntimeCall::Balances(..)
+          | RuntimeCall::Indices(pallet_indices::Call::transfer { .. })
+          | RuntimeCall::Assets(..)
+          | RuntimeCall::Vesting(pallet_vesting::Call::vested_transfer { .. })
+          | RuntimeCall::Tidefi(pallet_tidefi::Call::transfer { .. })
+          | RuntimeCall::Tidefi(pallet_tidefi::Call::withdrawal { .. })
       ),
       ProxyType::Governance => matches!(
         c,
@@ -67,6 +78,18 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
           | RuntimeCall::Treasury(..)
       ),
       ProxyType::Staking => matches!(c, RuntimeCall::Staking(..)),
+      ProxyType::Trading => matches!(
+        c,
+        RuntimeCall::Tidefi(pallet_tidefi::Call::swap { .. })
+          | RuntimeCall::Tidefi(pallet_tidefi::Call::cancel_swap { .. })
+      ),
+      ProxyType::AssetStaking => matches!(c, RuntimeCall::TidefiStaking(..)),
+      ProxyType::SunriseClaim => matches!(
+        c,
+        RuntimeCall::Tidefi(pallet_tidefi::Call::claim_sunrise_rewards { .. })
+          | RuntimeCall::Tidefi(pallet_tidefi::Call::claim_all_sunrise_rewards { .. })
+          | RuntimeCall::Tidefi(pallet_tidefi::Call::claim_referral_rewards { .. })
+      ),
     }
   }
   fn is_superset(&self, o: &Self) -> bool {
@@ -94,3 +117,168 @@ impl pallet_proxy::Config for Runtime {
   type AnnouncementDepositFactor = AnnouncementDepositFactor;
   type WeightInfo = crate::weights::pallet_proxy::WeightInfo<Runtime>;
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::types::AccountId;
+  use sp_runtime::Permill;
+  use tidefi_primitives::{CurrencyId, Hash, SwapType};
+
+  fn transfer_call() -> RuntimeCall {
+    RuntimeCall::Tidefi(pallet_tidefi::Call::transfer {
+      destination_id: AccountId::new([1; 32]),
+      currency_id: CurrencyId::Tdfy,
+      amount: 1,
+    })
+  }
+
+  fn withdrawal_call() -> RuntimeCall {
+    RuntimeCall::Tidefi(pallet_tidefi::Call::withdrawal {
+      currency_id: CurrencyId::Wrapped(1),
+      amount: 1,
+      external_address: Vec::new(),
+    })
+  }
+
+  fn swap_call() -> RuntimeCall {
+    RuntimeCall::Tidefi(pallet_tidefi::Call::swap {
+      currency_id_from: CurrencyId::Tdfy,
+      amount_from: 1,
+      currency_id_to: CurrencyId::Wrapped(1),
+      amount_to: 1,
+      swap_type: SwapType::Limit,
+      slippage_tolerance: Some(Permill::zero()),
+    })
+  }
+
+  fn cancel_swap_call() -> RuntimeCall {
+    RuntimeCall::Tidefi(pallet_tidefi::Call::cancel_swap {
+      request_id: Hash::zero(),
+    })
+  }
+
+  fn claim_sunrise_rewards_call() -> RuntimeCall {
+    RuntimeCall::Tidefi(pallet_tidefi::Call::claim_sunrise_rewards { era_index: 1 })
+  }
+
+  fn claim_all_sunrise_rewards_call() -> RuntimeCall {
+    RuntimeCall::Tidefi(pallet_tidefi::Call::claim_all_sunrise_rewards { max_eras: 10 })
+  }
+
+  fn claim_referral_rewards_call() -> RuntimeCall {
+    RuntimeCall::Tidefi(pallet_tidefi::Call::claim_referral_rewards { era_index: 1 })
+  }
+
+  fn stake_call() -> RuntimeCall {
+    RuntimeCall::TidefiStaking(pallet_tidefi_stake::Call::stake {
+      currency_id: CurrencyId::Tdfy,
+      amount: 1,
+      duration: 1,
+      auto_renew: false,
+    })
+  }
+
+  fn balances_transfer_call() -> RuntimeCall {
+    RuntimeCall::Balances(pallet_balances::Call::transfer {
+      dest: AccountId::new([1; 32]).into(),
+      value: 1,
+    })
+  }
+
+  fn remark_call() -> RuntimeCall {
+    RuntimeCall::System(frame_system::Call::remark { remark: Vec::new() })
+  }
+
+  #[test]
+  fn any_allows_all_calls() {
+    assert!(ProxyType::Any.filter(&transfer_call()));
+    assert!(ProxyType::Any.filter(&balances_transfer_call()));
+    assert!(ProxyType::Any.filter(&swap_call()));
+    assert!(ProxyType::Any.filter(&remark_call()));
+  }
+
+  #[test]
+  fn non_transfer_denies_transfers_and_withdrawals() {
+    assert!(!ProxyType::NonTransfer.filter(&balances_transfer_call()));
+    assert!(!ProxyType::NonTransfer.filter(&transfer_call()));
+    assert!(!ProxyType::NonTransfer.filter(&withdrawal_call()));
+    assert!(
+      !ProxyType::NonTransfer.filter(&RuntimeCall::Assets(pallet_assets::Call::transfer {
+        id: 1,
+        target: AccountId::new([1; 32]).into(),
+        amount: 1,
+      }))
+    );
+
+    assert!(ProxyType::NonTransfer.filter(&swap_call()));
+    assert!(ProxyType::NonTransfer.filter(&stake_call()));
+    assert!(ProxyType::NonTransfer.filter(&remark_call()));
+  }
+
+  #[test]
+  fn trading_allows_only_swaps() {
+    assert!(ProxyType::Trading.filter(&swap_call()));
+    assert!(ProxyType::Trading.filter(&cancel_swap_call()));
+
+    assert!(!ProxyType::Trading.filter(&transfer_call()));
+    assert!(!ProxyType::Trading.filter(&withdrawal_call()));
+    assert!(!ProxyType::Trading.filter(&claim_sunrise_rewards_call()));
+    assert!(!ProxyType::Trading.filter(&claim_all_sunrise_rewards_call()));
+    assert!(!ProxyType::Trading.filter(&stake_call()));
+    assert!(!ProxyType::Trading.filter(&balances_transfer_call()));
+  }
+
+  #[test]
+  fn asset_staking_allows_only_tidefi_staking() {
+    assert!(ProxyType::AssetStaking.filter(&stake_call()));
+    assert!(ProxyType::AssetStaking.filter(&RuntimeCall::TidefiStaking(
+      pallet_tidefi_stake::Call::unstake {
+        stake_id: Hash::zero(),
+        force_unstake: true,
+        pay_fee_in_tdfy: false,
+      }
+    )));
+
+    assert!(!ProxyType::AssetStaking.filter(&swap_call()));
+    assert!(!ProxyType::AssetStaking.filter(&transfer_call()));
+    assert!(!ProxyType::AssetStaking.filter(&balances_transfer_call()));
+  }
+
+  #[test]
+  fn sunrise_claim_allows_only_the_claims() {
+    assert!(ProxyType::SunriseClaim.filter(&claim_sunrise_rewards_call()));
+    assert!(ProxyType::SunriseClaim.filter(&claim_all_sunrise_rewards_call()));
+    assert!(ProxyType::SunriseClaim.filter(&claim_referral_rewards_call()));
+
+    assert!(!ProxyType::SunriseClaim.filter(&swap_call()));
+    assert!(!ProxyType::SunriseClaim.filter(&transfer_call()));
+    assert!(!ProxyType::SunriseClaim.filter(&stake_call()));
+  }
+
+  #[test]
+  fn governance_and_staking_are_unchanged() {
+    assert!(!ProxyType::Governance.filter(&swap_call()));
+    assert!(!ProxyType::Governance.filter(&balances_transfer_call()));
+    assert!(!ProxyType::Staking.filter(&stake_call()));
+    assert!(!ProxyType::Staking.filter(&swap_call()));
+  }
+
+  #[test]
+  fn superset() {
+    for proxy_type in [
+      ProxyType::NonTransfer,
+      ProxyType::Governance,
+      ProxyType::Staking,
+      ProxyType::Trading,
+      ProxyType::AssetStaking,
+      ProxyType::SunriseClaim,
+    ] {
+      assert!(ProxyType::Any.is_superset(&proxy_type));
+      assert!(ProxyType::NonTransfer.is_superset(&proxy_type));
+      assert!(!proxy_type.is_superset(&ProxyType::Any));
+    }
+    assert!(!ProxyType::Trading.is_superset(&ProxyType::AssetStaking));
+    assert!(!ProxyType::SunriseClaim.is_superset(&ProxyType::Trading));
+  }
+}
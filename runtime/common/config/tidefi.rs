@@ -15,16 +15,24 @@
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-  constants::currency::{deposit, Adapter, TDFY},
-  types::{AccountId, AssetId, Balance, BlockNumber, SessionIndex},
-  AssetRegistry, AssetRegistryPalletId, Balances, CouncilCollectiveInstance, Fees, FeesPalletId,
-  Oracle, OraclePalletId, Quorum, QuorumPalletId, Runtime, RuntimeEvent, RuntimeOrigin, Security,
-  Sunrise, SunriseCooldown, SunrisePalletId, TidefiStaking, TidefiStakingPalletId, Timestamp,
+  constants::{
+    currency::{deposit, Adapter, TDFY},
+    time::{DAYS, MILLISECS_PER_BLOCK},
+  },
+  types::{
+    AccountId, AssetId, Balance, BlockNumber, EnsureRootOrHalfCouncil,
+    EnsureRootOrHalfCouncilOrGuardians, EnsureRootOrTwoThirdsCouncilOrTechnical, EraIndex,
+    SessionIndex,
+  },
+  AssetRegistry, AssetRegistryPalletId, Balances, BitcoinNetwork, CouncilCollectiveInstance, Fees,
+  FeesPalletId, Oracle, OraclePalletId, Quorum, QuorumPalletId, Runtime, RuntimeEvent,
+  RuntimeOrigin, Security, Sunrise, SunriseClaimWindow, SunriseCooldown, SunrisePalletId,
+  TidefiStaking, TidefiStakingPalletId, Timestamp, TreasuryPalletId,
 };
 
 use frame_support::{
   parameter_types,
-  traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, EitherOfDiverse, EnsureOrigin},
+  traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, EitherOfDiverse, EnsureOrigin},
 };
 use frame_system::{EnsureRoot, RawOrigin};
 use sp_runtime::{traits::AccountIdConversion, FixedU128, Permill};
@@ -44,9 +52,14 @@ parameter_types! {
   pub const SessionsPerEra: SessionIndex = 12;
   // Staking: Number of sessions to keep in archive
   pub const SessionsArchive: SessionIndex = 5;
+  // Fees: Number of past eras to keep the account fees of
+  // ~ 90 days
+  pub const ErasArchive: EraIndex = 2_160;
   // Staking: Number of block per sessions
   // ~ 5 mins
   pub const BlocksPerSession: BlockNumber = 50;
+  // Fees: Maximum number of sessions ended in a single block when catching up with the wall clock
+  pub const MaximumSessionsCatchUp: u32 = 12;
   // Staking: Number of blocks to wait before unstaking when we force-unstake.
   pub const BlocksForceUnstake: BlockNumber = 14_400;
   // 0.25%
@@ -63,6 +76,11 @@ parameter_types! {
   pub const ProposalLifetime: BlockNumber = 100;
   // Maximum number of staking period the chain can support
   pub const StakingRewardCap: u32 = 10;
+  // Number of session rewards kept to compute the staking yield
+  // ~ 1 day
+  pub const SessionRewardsHistoryCap: u32 = 288;
+  // Number of blocks in a year, to annualise the staking yield
+  pub const BlocksPerYear: BlockNumber = 365 * DAYS;
   // The maximum size of a string
   pub const StringLimit: u32 = 255;
   // The number of votes maximum per proposal, should alway be higher than the proposals threshold
@@ -71,6 +89,8 @@ parameter_types! {
   pub const WatchListLimit: u32 = 10_000;
   // The maximum number of pubkey each asset can have, should alway be more more than the current quorum active member set
   pub const PubkeyLimitPerAsset: u32 = 10;
+  // The number of completed withdrawals kept in the history of each account
+  pub const CompletedWithdrawalsLimit: u32 = 50;
   // The number of swap each account can have in queue
   pub const SwapLimitByAccount: u32 = 10_000;
   // The maximum number of supported market pairs
@@ -79,8 +99,49 @@ parameter_types! {
   pub const MaximumRewardPerSwap: Balance = 100_000_000_000_000_000;
   // Rebates applied to left-over pool
   pub const LeftoverSwapRebates: FixedU128 = FixedU128::from_inner(500_000_000_000_000_000);
+  // Maximum number of eras claimed by a single sunrise claim-all
+  pub const MaxClaimedEras: u32 = 100;
+  // Share of the swap fees of the referees accrued to their referrer
+  pub const ReferralShare: Permill = Permill::from_percent(10);
+  // Maximum referral rewards accrued from a single referee (in TDFY's)
+  pub const MaximumReferralRewardPerReferee: Balance = 10_000 * TDFY;
   // The number of accounts to proceed by compound and unstake batch
   pub const BatchSize: u32 = 500;
+  // Bitcoin relay: Number of blocks (included) on top of a deposit before it can be minted
+  pub const BitcoinConfirmationDepth: u32 = 6;
+  // Bitcoin relay: Maximum number of blocks reverted by a reorganization
+  pub const BitcoinMaxReorgDepth: u32 = 100;
+  // Bitcoin relay: Maximum number of headers in a single submission
+  pub const BitcoinMaxHeadersPerSubmission: u32 = 50;
+  // Bitcoin relay: Number of block headers kept below the best block (~1 week), only the merkle root
+  // of the deeper main chain blocks is kept to verify the late deposits
+  pub const BitcoinPruningDepth: u32 = 1_008;
+  // Number of blocks before the chain maintenance and the paused calls are resumed, unless renewed
+  pub const PauseDuration: BlockNumber = 2 * DAYS;
+  // Number of times the guardians can renew a pause, before the council or the technical committee
+  // has to renew or resume it
+  pub const MaxPauseRenewals: u32 = 3;
+  // Pallets which can't be paused, the inherents and the consensus keep the chain producing blocks
+  // and the governance and the memberships resume the paused calls or remove the guardians
+  pub const UnpausablePallets: &'static [&'static str] = &[
+    "System",
+    "Timestamp",
+    "Babe",
+    "Grandpa",
+    "Authorship",
+    "ImOnline",
+    "Session",
+    "ElectionProviderMultiPhase",
+    "Democracy",
+    "Council",
+    "TechnicalCommittee",
+    "Elections",
+    "TechnicalMembership",
+    "GuardianCommittee",
+    "GuardianMembership",
+    "Preimage",
+    "Sudo",
+  ];
 }
 
 pub struct EnsureRootOrAssetRegistry;
@@ -113,6 +174,26 @@ impl EnsureOrigin<RuntimeOrigin> for EnsureRootOrAssetRegistry {
   }
 }
 
+/// Quorum members relay the bitcoin block headers.
+pub struct EnsureQuorumMember;
+impl EnsureOrigin<RuntimeOrigin> for EnsureQuorumMember {
+  type Success = AccountId;
+
+  fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+    Into::<Result<RawOrigin<AccountId>, RuntimeOrigin>>::into(o).and_then(|o| match o {
+      RawOrigin::Signed(caller) if Quorum::members(&caller).unwrap_or(false) => Ok(caller),
+      r => Err(RuntimeOrigin::from(r)),
+    })
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+    let caller: AccountId = QuorumPalletId::get().into_account_truncating();
+    pallet_quorum::Members::<Runtime>::insert(&caller, true);
+    Ok(RuntimeOrigin::from(RawOrigin::Signed(caller)))
+  }
+}
+
 impl pallet_assets::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type Balance = Balance;
@@ -151,6 +232,8 @@ impl pallet_tidefi::Config for Runtime {
   type CurrencyTidefi = Adapter<AccountId>;
   // Asset registry
   type AssetRegistry = AssetRegistry;
+  type AddressValidator = crate::address::ExternalAddressValidator<BitcoinNetwork, AssetRegistry>;
+  type MaxClaimedEras = MaxClaimedEras;
   type WeightInfo = crate::weights::pallet_tidefi::WeightInfo<Runtime>;
 }
 
@@ -164,8 +247,15 @@ impl pallet_tidefi_stake::Config for Runtime {
   // Asset registry
   type AssetRegistry = AssetRegistry;
   type Security = Security;
+  type Sunrise = Sunrise;
   type StakingRewardCap = StakingRewardCap;
+  type SessionRewardsHistoryCap = SessionRewardsHistoryCap;
+  type BlocksPerYear = BlocksPerYear;
   type BatchSize = BatchSize;
+  type ForceOrigin = EitherOfDiverse<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
   type WeightInfo = crate::weights::pallet_tidefi_stake::WeightInfo<Runtime>;
 }
 
@@ -186,6 +276,14 @@ impl pallet_quorum::Config for Runtime {
   type VotesLimit = VotesLimit;
   type WatchListLimit = WatchListLimit;
   type PubkeyLimitPerAsset = PubkeyLimitPerAsset;
+  type CompletedWithdrawalsLimit = CompletedWithdrawalsLimit;
+  // Bitcoin deposits are verified against the header relay
+  type InclusionProof = pallet_btc_relay::DepositProof;
+  type InclusionVerifier = crate::impls::BitcoinInclusionVerifier<Runtime>;
+  type ForceOrigin = EitherOfDiverse<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
   type WeightInfo = crate::weights::pallet_quorum::WeightInfo<Runtime>;
 }
 
@@ -202,11 +300,27 @@ impl pallet_oracle::Config for Runtime {
   type Sunrise = Sunrise;
   type SwapLimitByAccount = SwapLimitByAccount;
   type SupportedMarketPairsLimit = SupportedMarketPairsLimit;
+  type ForceOrigin = EitherOfDiverse<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
+  type AssetRegistry = AssetRegistry;
   type WeightInfo = crate::weights::pallet_oracle::WeightInfo<Runtime>;
 }
 
 impl pallet_security::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
+  // The guardians can only pause, resuming requires a supermajority of the council or the
+  // technical committee
+  type PauseOrigin = EnsureRootOrHalfCouncilOrGuardians;
+  type ResumeOrigin = EnsureRootOrTwoThirdsCouncilOrTechnical;
+  type PauseDuration = PauseDuration;
+  type MaxPauseRenewals = MaxPauseRenewals;
+  type UnpausablePallets = UnpausablePallets;
+  // Longest pallet and call names of the runtime, for example `claim_all_sunrise_rewards`
+  type MaxNameLength = ConstU32<32>;
+  // Reason of the scheduled maintenance displayed by the wallets
+  type MaxReasonLength = ConstU32<256>;
   type WeightInfo = crate::weights::pallet_security::WeightInfo<Runtime>;
 }
 
@@ -221,11 +335,16 @@ impl pallet_asset_registry::Config for Runtime {
 impl pallet_fees::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type FeesPalletId = FeesPalletId;
+  type TreasuryPalletId = TreasuryPalletId;
   type CurrencyTidefi = Adapter<AccountId>;
   type UnixTime = Timestamp;
   type SessionsPerEra = SessionsPerEra;
   type SessionsArchive = SessionsArchive;
+  type ErasArchive = ErasArchive;
+  type MaximumSessionsCatchUp = MaximumSessionsCatchUp;
+  type BatchSize = BatchSize;
   type BlocksPerSession = BlocksPerSession;
+  type ExpectedBlockTime = ConstU64<MILLISECS_PER_BLOCK>;
   type Staking = TidefiStaking;
   // Swap fees
   type FeeAmount = FeeAmount;
@@ -245,9 +364,36 @@ impl pallet_fees::Config for Runtime {
 impl pallet_sunrise::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type Security = Security;
+  type AssetRegistry = AssetRegistry;
   type SunrisePalletId = SunrisePalletId;
   type CurrencyTidefi = Adapter<AccountId>;
   type Cooldown = SunriseCooldown;
+  type ClaimWindow = SunriseClaimWindow;
+  type BatchSize = BatchSize;
   type MaximumRewardPerSwap = MaximumRewardPerSwap;
   type LeftoverSwapRebates = LeftoverSwapRebates;
+  type ReferralShare = ReferralShare;
+  type MaximumReferralRewardPerReferee = MaximumReferralRewardPerReferee;
+  type TreasuryPalletId = TreasuryPalletId;
+  type ForceOrigin = EitherOfDiverse<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
+  type WeightInfo = crate::weights::pallet_sunrise::WeightInfo<Runtime>;
+}
+
+impl pallet_btc_relay::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type Network = BitcoinNetwork;
+  type ConfirmationDepth = BitcoinConfirmationDepth;
+  type MaxReorgDepth = BitcoinMaxReorgDepth;
+  type MaxHeadersPerSubmission = BitcoinMaxHeadersPerSubmission;
+  type PruningDepth = BitcoinPruningDepth;
+  type UnixTime = Timestamp;
+  type ForceOrigin = EitherOfDiverse<
+    EnsureRoot<AccountId>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+  >;
+  type RelayerOrigin = EnsureQuorumMember;
+  type WeightInfo = crate::weights::pallet_btc_relay::WeightInfo<Runtime>;
 }
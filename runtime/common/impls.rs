@@ -16,9 +16,15 @@
 
 //! Some configurable implementations as associated type for the substrate runtime.
 
-use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+use frame_support::{
+  dispatch::{CallMetadata, DispatchResult, GetCallMetadata},
+  traits::{Contains, Currency, Imbalance, OnUnbalanced},
+};
+use sp_runtime::DispatchError;
+use sp_std::{vec, vec::Vec};
+use tidefi_primitives::{assets::Asset, Balance, CurrencyId};
 
-use crate::types::NegativeImbalance;
+use crate::{types::NegativeImbalance, RuntimeCall, Security, TidefiStaking};
 
 /// Logic for the author to get a portion of fees.
 pub struct ToAuthor<R>(sp_std::marker::PhantomData<R>);
@@ -58,6 +64,106 @@ where
   }
 }
 
+/// Bitcoin deposits need to be included in the main chain followed by the relay
+/// before being minted by the quorum, the deposit output must pay the minted amount
+/// to the account deposit script.
+pub struct BitcoinInclusionVerifier<R>(sp_std::marker::PhantomData<R>);
+impl<R> pallet_quorum::InclusionVerifier<pallet_btc_relay::DepositProof>
+  for BitcoinInclusionVerifier<R>
+where
+  R: pallet_btc_relay::Config,
+{
+  fn is_proof_required(currency_id: CurrencyId) -> bool {
+    // proofs are only required once the relay is following the chain
+    currency_id == Asset::Bitcoin.currency_id() && pallet_btc_relay::Pallet::<R>::is_initialized()
+  }
+
+  fn verify(
+    _currency_id: CurrencyId,
+    transaction_id: &[u8],
+    amount: Balance,
+    deposit_script: &[u8],
+    required_confirmations: u32,
+    proof: &pallet_btc_relay::DepositProof,
+  ) -> DispatchResult {
+    // bitcoin has 8 decimals, the amount minted is in satoshis
+    let amount: u64 = amount
+      .try_into()
+      .map_err(|_| DispatchError::Other("Deposit amount overflow"))?;
+    pallet_btc_relay::Pallet::<R>::verify_deposit(
+      transaction_id,
+      proof,
+      amount,
+      deposit_script,
+      required_confirmations,
+    )
+  }
+}
+
+/// Calls filter of the runtime, the calls paused in the security pallet are rejected, as well
+/// as the new swaps and withdrawals during the notice of a scheduled maintenance and the
+/// privileged assets calls on the liquid staking receipt currencies.
+pub struct PausedCallsFilter;
+impl Contains<RuntimeCall> for PausedCallsFilter {
+  fn contains(call: &RuntimeCall) -> bool {
+    let CallMetadata {
+      pallet_name,
+      function_name,
+    } = call.get_call_metadata();
+    !Security::is_call_paused(
+      pallet_name.as_bytes(),
+      function_name.as_bytes(),
+      &call_currencies(call),
+    ) && !(is_swap_or_withdrawal(call) && Security::is_maintenance_notice())
+      && !is_receipt_currency_management(call)
+  }
+}
+
+/// Assets calls minting, burning or moving the receipt tokens of a liquid staking, or handing
+/// over the management of their asset, only the staking pallet issues them.
+fn is_receipt_currency_management(call: &RuntimeCall) -> bool {
+  match call {
+    RuntimeCall::Assets(pallet_assets::Call::mint { id, .. })
+    | RuntimeCall::Assets(pallet_assets::Call::burn { id, .. })
+    | RuntimeCall::Assets(pallet_assets::Call::force_transfer { id, .. })
+    | RuntimeCall::Assets(pallet_assets::Call::transfer_ownership { id, .. })
+    | RuntimeCall::Assets(pallet_assets::Call::set_team { id, .. })
+    | RuntimeCall::Assets(pallet_assets::Call::force_asset_status { id, .. })
+    | RuntimeCall::Assets(pallet_assets::Call::start_destroy { id }) => {
+      TidefiStaking::is_receipt_currency(CurrencyId::Wrapped(*id))
+    }
+    _ => false,
+  }
+}
+
+/// New swaps and withdrawals, rejected during the notice of a scheduled maintenance.
+fn is_swap_or_withdrawal(call: &RuntimeCall) -> bool {
+  matches!(
+    call,
+    RuntimeCall::Tidefi(pallet_tidefi::Call::swap { .. })
+      | RuntimeCall::Tidefi(pallet_tidefi::Call::withdrawal { .. })
+  )
+}
+
+/// Currencies of the call, a call paused for a currency is rejected only for this currency.
+fn call_currencies(call: &RuntimeCall) -> Vec<CurrencyId> {
+  match call {
+    RuntimeCall::Tidefi(pallet_tidefi::Call::transfer { currency_id, .. })
+    | RuntimeCall::Tidefi(pallet_tidefi::Call::withdrawal { currency_id, .. })
+    | RuntimeCall::TidefiStaking(pallet_tidefi_stake::Call::stake { currency_id, .. })
+    | RuntimeCall::TidefiStaking(pallet_tidefi_stake::Call::stake_liquid { currency_id, .. })
+    | RuntimeCall::TidefiStaking(pallet_tidefi_stake::Call::unstake_liquid {
+      currency_id, ..
+    }) => vec![*currency_id],
+    RuntimeCall::Tidefi(pallet_tidefi::Call::swap {
+      currency_id_from,
+      currency_id_to,
+      ..
+    }) => vec![*currency_id_from, *currency_id_to],
+    _ => Vec::new(),
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
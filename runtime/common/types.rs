@@ -15,8 +15,8 @@
 // along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-  BlockLength, BlockWeights, CouncilCollectiveInstance, Runtime, RuntimeCall, RuntimeVersion,
-  VERSION,
+  BlockLength, BlockWeights, CouncilCollectiveInstance, GuardianCollectiveInstance, Runtime,
+  RuntimeCall, RuntimeVersion, TechnicalCollectiveInstance, VERSION,
 };
 use frame_support::{
   dispatch::DispatchClass,
@@ -37,8 +37,8 @@ pub use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 pub use sp_runtime::traits::BlakeTwo256;
 
 pub use tidefi_primitives::{
-  AccountId, AccountIndex, AssetId, Balance, BlockNumber, CurrencyId, Hash, Index, Moment,
-  SessionIndex, Signature,
+  AccountId, AccountIndex, AssetId, Balance, BlockNumber, CurrencyId, EraIndex, Hash, Index,
+  Moment, SessionIndex, Signature,
 };
 
 pub type NegativeImbalance<T> = <pallet_balances::Pallet<T> as Currency<
@@ -95,6 +95,21 @@ pub type EnsureRootOrHalfCouncil = EitherOfDiverse<
   pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollectiveInstance, 1, 2>,
 >;
 
+/// Root, two thirds of the council or two thirds of the technical committee.
+pub type EnsureRootOrTwoThirdsCouncilOrTechnical = EitherOfDiverse<
+  EnsureRoot<AccountId>,
+  EitherOfDiverse<
+    pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollectiveInstance, 2, 3>,
+    pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollectiveInstance, 2, 3>,
+  >,
+>;
+
+/// Root, half of the council or one third of the guardians.
+pub type EnsureRootOrHalfCouncilOrGuardians = EitherOfDiverse<
+  EnsureRootOrHalfCouncil,
+  pallet_collective::EnsureProportionAtLeast<AccountId, GuardianCollectiveInstance, 1, 3>,
+>;
+
 pub type CurrencyToVote = frame_support::traits::U128CurrencyToVote;
 pub type Nonce = u32;
 
@@ -27,6 +27,7 @@ pub mod pallet_asset_registry;
 pub mod pallet_assets;
 pub mod pallet_balances;
 pub mod pallet_bounties;
+pub mod pallet_btc_relay;
 pub mod pallet_collective;
 pub mod pallet_democracy;
 pub mod pallet_election_provider_multi_phase;
@@ -46,6 +47,7 @@ pub mod pallet_scheduler;
 pub mod pallet_security;
 pub mod pallet_session;
 pub mod pallet_staking;
+pub mod pallet_sunrise;
 pub mod pallet_tidefi;
 pub mod pallet_tidefi_stake;
 pub mod pallet_timestamp;
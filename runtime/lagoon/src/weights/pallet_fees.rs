@@ -55,28 +55,24 @@ impl<T: frame_system::Config> pallet_fees::WeightInfo for WeightInfo<T> {
 	/// Proof: Fees CurrentSession (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
 	/// Storage: Fees SessionTotalFees (r:7 w:0)
 	/// Proof: Fees SessionTotalFees (max_values: None, max_size: Some(93), added: 2568, mode: MaxEncodedLen)
+	/// Storage: Fees FeeDistributions (r:6 w:0)
+	/// Proof: Fees FeeDistributions (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
 	/// Storage: Assets Asset (r:6 w:6)
 	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
 	/// Storage: Assets Account (r:12 w:12)
 	/// Proof: Assets Account (max_values: Some(300000), max_size: Some(118), added: 2593, mode: MaxEncodedLen)
 	/// Storage: System Account (r:2 w:2)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
-	/// Storage: TidefiStaking PendingStoredSessions (r:1 w:1)
-	/// Proof: TidefiStaking PendingStoredSessions (max_values: None, max_size: Some(235), added: 2710, mode: MaxEncodedLen)
-	/// Storage: TidefiStaking CounterForPendingStoredSessions (r:1 w:1)
-	/// Proof: TidefiStaking CounterForPendingStoredSessions (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: TidefiStaking AccountStakes (r:10001 w:0)
 	/// Proof: TidefiStaking AccountStakes (max_values: None, max_size: Some(949), added: 3424, mode: MaxEncodedLen)
-	/// Storage: TidefiStaking QueueCompound (r:10000 w:10000)
-	/// Proof: TidefiStaking QueueCompound (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
-	/// Storage: TidefiStaking CounterForQueueCompound (r:1 w:1)
-	/// Proof: TidefiStaking CounterForQueueCompound (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: TidefiStaking StakingPool (r:6 w:6)
+	/// Proof: TidefiStaking StakingPool (max_values: None, max_size: Some(37), added: 2512, mode: MaxEncodedLen)
+	/// Storage: TidefiStaking SharesPools (r:6 w:6)
+	/// Proof: TidefiStaking SharesPools (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
 	/// Storage: Fees StoredSessions (r:2 w:1)
 	/// Proof: Fees StoredSessions (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
 	/// Storage: TidefiStaking InterestCompoundLastSession (r:0 w:1)
 	/// Proof: TidefiStaking InterestCompoundLastSession (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
-	/// Storage: TidefiStaking SessionTotalFees (r:0 w:6)
-	/// Proof: TidefiStaking SessionTotalFees (max_values: None, max_size: Some(61), added: 2536, mode: MaxEncodedLen)
 	/// The range of component `a` is `[1, 10000]`.
 	/// The range of component `b` is `[1, 6]`.
 	fn on_finalize(a: u32, b: u32, ) -> Weight {
@@ -89,11 +85,60 @@ impl<T: frame_system::Config> pallet_fees::WeightInfo for WeightInfo<T> {
 			.saturating_add(Weight::from_ref_time(12_535_319).saturating_mul(a.into()))
 			.saturating_add(T::DbWeight::get().reads(12))
 			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(a.into())))
-			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(b.into())))
 			.saturating_add(T::DbWeight::get().writes(9))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
-			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(b.into())))
 			.saturating_add(Weight::from_proof_size(10439).saturating_mul(b.into()))
 			.saturating_add(Weight::from_proof_size(5955).saturating_mul(a.into()))
 	}
+	/// Storage: Fees FeeDistributions (r:0 w:1)
+	/// Proof: Fees FeeDistributions (max_values: None, max_size: Some(34), added: 2509, mode: MaxEncodedLen)
+	fn set_fee_distribution() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 15_412 nanoseconds.
+		Weight::from_ref_time(16_288_000)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Fees ActiveEra (r:1 w:0)
+	/// Proof: Fees ActiveEra (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: Fees OldestAccountFeesEra (r:1 w:1)
+	/// Proof: Fees OldestAccountFeesEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Fees AccountFees (r:500 w:500)
+	/// Proof: Fees AccountFees (max_values: None, max_size: Some(53070), added: 55545, mode: MaxEncodedLen)
+	/// The range of component `b` is `[1, 500]`.
+	fn on_idle_account_fees_pruning(b: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `141 + b * (121 ±0)`
+		//  Estimated: `2022 + b * (55545 ±0)`
+		// Minimum execution time: 11_853 nanoseconds.
+		Weight::from_parts(12_392_000, 2022)
+			// Standard Error: 2_713
+			.saturating_add(Weight::from_ref_time(3_021_566).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(b.into())))
+			.saturating_add(Weight::from_proof_size(55545).saturating_mul(b.into()))
+	}
+	/// Storage: Fees ActiveEra (r:1 w:1)
+	/// Proof: Fees ActiveEra (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: Security CurrentBlockCount (r:1 w:0)
+	/// Proof: Security CurrentBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Fees SessionDuration (r:0 w:1)
+	/// Proof: Fees SessionDuration (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Fees SessionEnd (r:0 w:1)
+	/// Proof: Fees SessionEnd (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	/// Storage: Fees SessionsToEnd (r:0 w:1)
+	/// Proof: Fees SessionsToEnd (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_session_duration() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `148`
+		//  Estimated: `3026`
+		// Minimum execution time: 17_941 nanoseconds.
+		Weight::from_parts(18_633_000, 3026)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
 }
@@ -97,14 +97,22 @@ impl<T: frame_system::Config> pallet_quorum::WeightInfo for WeightInfo<T> {
 	/// Proof: Quorum Members (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
 	/// Storage: Quorum BurnedQueue (r:1 w:1)
 	/// Proof: Quorum BurnedQueue (max_values: Some(1), max_size: Some(346002), added: 346497, mode: MaxEncodedLen)
+	/// Storage: Quorum BurnedAcknowledgements (r:1 w:1)
+	/// Proof: Quorum BurnedAcknowledgements (max_values: None, max_size: Some(2939), added: 5414, mode: MaxEncodedLen)
+	/// Storage: Quorum Threshold (r:1 w:0)
+	/// Proof: Quorum Threshold (max_values: Some(1), max_size: Some(2), added: 497, mode: MaxEncodedLen)
+	/// Storage: Quorum CompletedWithdrawals (r:1 w:1)
+	/// Proof: Quorum CompletedWithdrawals (max_values: None, max_size: Some(28799), added: 31274, mode: MaxEncodedLen)
+	/// Storage: Security CurrentBlockCount (r:1 w:0)
+	/// Proof: Security CurrentBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	fn acknowledge_burned() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `513`
-		//  Estimated: `359793`
-		// Minimum execution time: 35_692 nanoseconds.
-		Weight::from_parts(40_108_000, 359793)
-			.saturating_add(T::DbWeight::get().reads(4))
-			.saturating_add(T::DbWeight::get().writes(1))
+		//  Measured:  `584`
+		//  Estimated: `397477`
+		// Minimum execution time: 52_871 nanoseconds.
+		Weight::from_parts(57_436_000, 397477)
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().writes(3))
 	}
 	/// Storage: Quorum PublicKeys (r:2 w:0)
 	/// Proof: Quorum PublicKeys (max_values: None, max_size: Some(2911), added: 5386, mode: MaxEncodedLen)
@@ -164,4 +172,46 @@ impl<T: frame_system::Config> pallet_quorum::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(k.into())))
 			.saturating_add(Weight::from_proof_size(5380).saturating_mul(k.into()))
 	}
+	/// Storage: Quorum Members (r:1 w:0)
+	/// Proof: Quorum Members (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// Storage: Quorum PublicKeys (r:1 w:0)
+	/// Proof: Quorum PublicKeys (max_values: None, max_size: Some(2911), added: 5386, mode: MaxEncodedLen)
+	/// Storage: Quorum Proposals (r:1 w:0)
+	/// Proof: Quorum Proposals (max_values: Some(1), max_size: Some(365002), added: 365497, mode: MaxEncodedLen)
+	/// Storage: Quorum InclusionProofs (r:0 w:1)
+	/// Proof: Quorum InclusionProofs (max_values: None, max_size: Some(1108), added: 3583, mode: MaxEncodedLen)
+	fn submit_inclusion_proof() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `476`
+		//  Estimated: `373407`
+		// Minimum execution time: 31_220 nanoseconds.
+		Weight::from_parts(33_104_000, 373407)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Quorum WithdrawalFees (r:0 w:1)
+	/// Proof: Quorum WithdrawalFees (max_values: None, max_size: Some(53), added: 2528, mode: MaxEncodedLen)
+	fn set_withdrawal_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 17_402 nanoseconds.
+		Weight::from_parts(18_230_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Quorum Members (r:1 w:0)
+	/// Proof: Quorum Members (max_values: None, max_size: Some(49), added: 2524, mode: MaxEncodedLen)
+	/// Storage: Quorum PublicKeys (r:1 w:0)
+	/// Proof: Quorum PublicKeys (max_values: None, max_size: Some(2911), added: 5386, mode: MaxEncodedLen)
+	/// Storage: Quorum DepositScripts (r:0 w:1)
+	/// Proof: Quorum DepositScripts (max_values: None, max_size: Some(343), added: 2818, mode: MaxEncodedLen)
+	fn submit_deposit_script() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `331`
+		//  Estimated: `8325`
+		// Estimated, not measured by the benchmark CLI.
+		Weight::from_parts(30_000_000, 8325)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
@@ -55,6 +55,10 @@ mod config;
 #[path = "../../common/impls.rs"]
 mod impls;
 
+/// External address validation used on withdrawals.
+#[path = "../../common/address.rs"]
+mod address;
+
 /// Constant values used within the runtime.
 #[path = "../../common/constants.rs"]
 pub mod constants;
@@ -126,6 +130,8 @@ impl_opaque_keys! {
 pub type CouncilCollectiveInstance = pallet_collective::Instance1;
 pub type TechnicalCollectiveInstance = pallet_collective::Instance2;
 pub type TechnicalMembershipInstance = pallet_membership::Instance1;
+pub type GuardianCollectiveInstance = pallet_collective::Instance3;
+pub type GuardianMembershipInstance = pallet_membership::Instance2;
 
 parameter_types! {
   // Pallet Id's
@@ -139,6 +145,10 @@ parameter_types! {
   // Sunrise Pool: Number of blocks to wait before they can claim the last era reward.
   // current_era.start_block + Cooldown < current_block to be able to claim last era sunrise reward
   pub const SunriseCooldown: BlockNumber = 1_296_000; // 90 days
+  // Sunrise Pool: Number of blocks the era rewards can be claimed once the cooldown is cleared.
+  pub const SunriseClaimWindow: BlockNumber = 1_296_000; // 90 days
+  // Bitcoin network followed by the header relay
+  pub const BitcoinNetwork: pallet_btc_relay::Network = pallet_btc_relay::Network::Mainnet;
 }
 
 // FIXME: Should be removed once we'll give control to the community (governance)
@@ -218,6 +228,10 @@ construct_runtime!(
         // Sudo module
         Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 31,
 
+        // Guardians allowed to pause the chain
+        GuardianCommittee: pallet_collective::<Instance3>::{Pallet, Call, Storage, Origin<T>, Event<T>, Config<T>} = 32,
+        GuardianMembership: pallet_membership::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>} = 33,
+
         // Tidefi public calls
         Tidefi: pallet_tidefi::{Pallet, Call, Storage, Event<T>} = 50,
 
@@ -234,16 +248,19 @@ construct_runtime!(
         Security: pallet_security::{Pallet, Call, Config, Storage, Event<T>} = 54,
 
         // Fees module
-        Fees: pallet_fees::{Pallet, Config<T>, Storage, Event<T>} = 55,
+        Fees: pallet_fees::{Pallet, Call, Config<T>, Storage, Event<T>} = 55,
 
         // Asset registry module
         AssetRegistry: pallet_asset_registry::{Pallet, Call, Config<T>, Storage, Event<T>} = 56,
 
         // Sunrise module
-        Sunrise: pallet_sunrise::{Pallet, Config<T>, Storage, Event<T>} = 57,
+        Sunrise: pallet_sunrise::{Pallet, Call, Config<T>, Storage, Event<T>} = 57,
 
         // Vesting module
         Vesting: pallet_vesting::{Pallet, Storage, Call, Event<T>, Config<T>} = 58,
+
+        // Bitcoin header relay
+        BtcRelay: pallet_btc_relay::{Pallet, Call, Storage, Event<T>} = 59,
     }
 );
 
@@ -276,6 +293,12 @@ pub type Executive = frame_executive::Executive<
     MigrateBountyToV4<Runtime>,
     // Migration for moving preimage from V0 to V1 storage.
     pallet_preimage::migration::v1::Migration<Runtime>,
+    // Migrate the stakes to the shares pools
+    pallet_tidefi_stake::migrations::v3::MigrateToV3<Runtime>,
+    // Index the sunrise rewards by era for their expiration
+    pallet_sunrise::migrations::v2::MigrateToV2<Runtime>,
+    // Seed the asset infos from the compiled assets
+    pallet_asset_registry::migrations::v1::MigrateToV1<Runtime>,
   ),
 >;
 
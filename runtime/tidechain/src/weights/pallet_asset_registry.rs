@@ -57,4 +57,19 @@ impl<T: frame_system::Config> pallet_asset_registry::WeightInfo for WeightInfo<T
 		Weight::from_parts(18_557_000, 527)
 			.saturating_add(T::DbWeight::get().reads(1))
 	}
+	/// Storage: AssetRegistry AssetRegistryAccountId (r:1 w:0)
+	/// Proof: AssetRegistry AssetRegistryAccountId (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: Assets Asset (r:1 w:0)
+	/// Proof: Assets Asset (max_values: None, max_size: Some(210), added: 2685, mode: MaxEncodedLen)
+	/// Storage: AssetRegistry AssetInfos (r:0 w:1)
+	/// Proof: AssetRegistry AssetInfos (max_values: None, max_size: Some(116), added: 2591, mode: MaxEncodedLen)
+	fn set_asset_info() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3212`
+		// Minimum execution time: 17_912 nanoseconds.
+		Weight::from_parts(19_204_000, 3212)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
 }
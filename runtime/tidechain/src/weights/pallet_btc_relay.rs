@@ -0,0 +1,94 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Autogenerated weights for `pallet_btc_relay`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2023-02-05, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `ns3206536`, CPU: `Intel(R) Xeon(R) D-2141I CPU @ 2.20GHz`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("tidechain-dev"), DB CACHE: 1024
+
+// Executed Command:
+// target/production/tidechain
+// benchmark
+// pallet
+// --chain=tidechain-dev
+// --steps=50
+// --repeat=20
+// --pallet=*
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./FILE_TEMPLATE
+// --output=./runtime/tidechain/src/weights/
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_btc_relay`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_btc_relay::WeightInfo for WeightInfo<T> {
+	/// Storage: BtcRelay BestBlock (r:1 w:1)
+	/// Proof: BtcRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: BtcRelay BlockHeaders (r:0 w:1)
+	/// Proof: BtcRelay BlockHeaders (max_values: None, max_size: Some(152), added: 2627, mode: MaxEncodedLen)
+	/// Storage: BtcRelay MainChain (r:0 w:1)
+	/// Proof: BtcRelay MainChain (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+	/// Storage: BtcRelay BestBlockHeight (r:0 w:1)
+	/// Proof: BtcRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BtcRelay HeadersByHeight (r:0 w:1)
+	/// Proof: BtcRelay HeadersByHeight (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	/// Storage: BtcRelay LowestHeight (r:0 w:1)
+	/// Proof: BtcRelay LowestHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn initialize() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `4`
+		//  Estimated: `527`
+		// Estimated, not measured by the benchmark CLI.
+		Weight::from_parts(47_000_000, 527)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	/// Storage: BtcRelay BestBlock (r:1 w:1)
+	/// Proof: BtcRelay BestBlock (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: BtcRelay BlockHeaders (r:14 w:2)
+	/// Proof: BtcRelay BlockHeaders (max_values: None, max_size: Some(152), added: 2627, mode: MaxEncodedLen)
+	/// Storage: BtcRelay MainChain (r:1 w:2)
+	/// Proof: BtcRelay MainChain (max_values: None, max_size: Some(44), added: 2519, mode: MaxEncodedLen)
+	/// Storage: BtcRelay BestBlockHeight (r:1 w:1)
+	/// Proof: BtcRelay BestBlockHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: BtcRelay HeadersByHeight (r:1 w:2)
+	/// Proof: BtcRelay HeadersByHeight (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	/// Storage: BtcRelay LowestHeight (r:1 w:1)
+	/// Proof: BtcRelay LowestHeight (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Proof: Timestamp Now (max_values: Some(1), max_size: Some(8), added: 503, mode: MaxEncodedLen)
+	fn submit_block_header() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `44577`
+		// Estimated, not measured by the benchmark CLI.
+		Weight::from_parts(95_000_000, 44577)
+			.saturating_add(T::DbWeight::get().reads(20))
+			.saturating_add(T::DbWeight::get().writes(9))
+	}
+}
@@ -50,17 +50,15 @@ impl<T: frame_system::Config> pallet_fees::WeightInfo for WeightInfo<T> {
 	// Storage: Security CurrentBlockCount (r:1 w:0)
 	// Storage: Fees CurrentSession (r:1 w:1)
 	// Storage: Fees SessionTotalFees (r:7 w:0)
+	// Storage: Fees FeeDistributions (r:6 w:0)
 	// Storage: Assets Asset (r:6 w:6)
 	// Storage: Assets Account (r:12 w:12)
 	// Storage: System Account (r:2 w:2)
-	// Storage: TidefiStaking PendingStoredSessions (r:1 w:1)
-	// Storage: TidefiStaking CounterForPendingStoredSessions (r:1 w:1)
 	// Storage: TidefiStaking AccountStakes (r:2 w:0)
-	// Storage: TidefiStaking QueueCompound (r:1 w:1)
-	// Storage: TidefiStaking CounterForQueueCompound (r:1 w:1)
+	// Storage: TidefiStaking StakingPool (r:6 w:6)
+	// Storage: TidefiStaking SharesPools (r:6 w:6)
 	// Storage: Fees StoredSessions (r:2 w:1)
 	// Storage: TidefiStaking InterestCompoundLastSession (r:0 w:1)
-	// Storage: TidefiStaking SessionTotalFees (r:0 w:6)
 	/// The range of component `a` is `[1, 10000]`.
 	/// The range of component `b` is `[2, 6]`.
 	/// The range of component `a` is `[1, 10000]`.
@@ -73,9 +71,36 @@ impl<T: frame_system::Config> pallet_fees::WeightInfo for WeightInfo<T> {
 			.saturating_add(Weight::from_ref_time(45_319_000).saturating_mul(b.into()))
 			.saturating_add(T::DbWeight::get().reads(12_u64))
 			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(a.into())))
-			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(b.into())))
 			.saturating_add(T::DbWeight::get().writes(9_u64))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(a.into())))
-			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(b.into())))
+	}
+	// Storage: Fees FeeDistributions (r:0 w:1)
+	fn set_fee_distribution() -> Weight {
+		Weight::from_ref_time(16_903_000)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Fees ActiveEra (r:1 w:0)
+	// Storage: Fees OldestAccountFeesEra (r:1 w:1)
+	// Storage: Fees AccountFees (r:500 w:500)
+	/// The range of component `b` is `[1, 500]`.
+	fn on_idle_account_fees_pruning(b: u32, ) -> Weight {
+		Weight::from_ref_time(12_614_000)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_ref_time(3_148_000).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(b.into())))
+	}
+	// Storage: Fees ActiveEra (r:1 w:1)
+	// Storage: Security CurrentBlockCount (r:1 w:0)
+	// Storage: Fees SessionDuration (r:0 w:1)
+	// Storage: Fees SessionEnd (r:0 w:1)
+	// Storage: Fees SessionsToEnd (r:0 w:1)
+	fn set_session_duration() -> Weight {
+		Weight::from_ref_time(19_072_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 }
@@ -135,14 +135,16 @@ impl<T: frame_system::Config> pallet_oracle::WeightInfo for WeightInfo<T> {
 	}
 	/// Storage: Oracle SupportedMarketPairs (r:1 w:1)
 	/// Proof: Oracle SupportedMarketPairs (max_values: Some(1), max_size: Some(1002), added: 1497, mode: MaxEncodedLen)
+	/// Storage: Assets Metadata (r:2 w:0)
+	/// Proof: Assets Metadata (max_values: Some(300000), max_size: Some(140), added: 2615, mode: MaxEncodedLen)
 	fn add_market_pair() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `138`
-		//  Estimated: `2487`
-		// Minimum execution time: 19_104 nanoseconds.
-		Weight::from_ref_time(21_250_000)
-			.saturating_add(Weight::from_proof_size(2487))
-			.saturating_add(T::DbWeight::get().reads(1))
+		//  Measured:  `414`
+		//  Estimated: `7717`
+		// Minimum execution time: 23_318 nanoseconds.
+		Weight::from_ref_time(25_962_000)
+			.saturating_add(Weight::from_proof_size(7717))
+			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
 	/// Storage: Oracle SupportedMarketPairs (r:1 w:1)
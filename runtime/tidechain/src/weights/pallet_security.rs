@@ -47,14 +47,67 @@ use sp_std::marker::PhantomData;
 /// Weight functions for `pallet_security`.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_security::WeightInfo for WeightInfo<T> {
+	/// Storage: Security ScheduledMaintenance (r:1 w:1)
+	/// Proof: Security ScheduledMaintenance (max_values: Some(1), max_size: Some(267), added: 762, mode: MaxEncodedLen)
+	/// Storage: Security MaintenanceExpiry (r:0 w:1)
+	/// Proof: Security MaintenanceExpiry (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Security ChainStatus (r:0 w:1)
 	/// Proof: Security ChainStatus (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
 	fn set_status() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
-		//  Estimated: `0`
+		//  Estimated: `762`
 		// Minimum execution time: 10_570 nanoseconds.
-		Weight::from_ref_time(19_335_000)
+		Weight::from_parts(19_335_000, 762)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: Security PausedCalls (r:1 w:1)
+	/// Proof: Security PausedCalls (max_values: None, max_size: Some(93), added: 2568, mode: MaxEncodedLen)
+	/// Storage: Security PauseRenewals (r:1 w:1)
+	/// Proof: Security PauseRenewals (max_values: None, max_size: Some(93), added: 2568, mode: MaxEncodedLen)
+	fn pause() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `135`
+		//  Estimated: `5136`
+		// Estimated, not measured by the benchmark CLI.
+		Weight::from_parts(27_000_000, 5136)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Security PausedCalls (r:1 w:1)
+	/// Proof: Security PausedCalls (max_values: None, max_size: Some(93), added: 2568, mode: MaxEncodedLen)
+	/// Storage: Security PauseRenewals (r:0 w:1)
+	/// Proof: Security PauseRenewals (max_values: None, max_size: Some(93), added: 2568, mode: MaxEncodedLen)
+	fn resume() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `135`
+		//  Estimated: `2568`
+		// Estimated, not measured by the benchmark CLI.
+		Weight::from_parts(20_962_000, 2568)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Security ScheduledMaintenance (r:1 w:1)
+	/// Proof: Security ScheduledMaintenance (max_values: Some(1), max_size: Some(267), added: 762, mode: MaxEncodedLen)
+	fn schedule_maintenance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `762`
+		// Minimum execution time: 23_405 nanoseconds.
+		Weight::from_parts(24_718_000, 762)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Security ScheduledMaintenance (r:1 w:1)
+	/// Proof: Security ScheduledMaintenance (max_values: Some(1), max_size: Some(267), added: 762, mode: MaxEncodedLen)
+	fn cancel_maintenance() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `274`
+		//  Estimated: `762`
+		// Minimum execution time: 19_012 nanoseconds.
+		Weight::from_parts(20_136_000, 762)
+			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
 }
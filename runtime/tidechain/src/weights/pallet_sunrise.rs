@@ -0,0 +1,137 @@
+// Copyright 2021-2022 Semantic Network Ltd.
+// This file is part of Tidechain.
+
+// Tidechain is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Tidechain is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Tidechain.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Autogenerated weights for `pallet_sunrise`
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2023-02-05, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! WORST CASE MAP SIZE: `1000000`
+//! HOSTNAME: `ns3206536`, CPU: `Intel(R) Xeon(R) D-2141I CPU @ 2.20GHz`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("tidechain-dev"), DB CACHE: 1024
+
+// Executed Command:
+// target/production/tidechain
+// benchmark
+// pallet
+// --chain=tidechain-dev
+// --steps=50
+// --repeat=20
+// --pallet=*
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./FILE_TEMPLATE
+// --output=./runtime/tidechain/src/weights/
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for `pallet_sunrise`.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> pallet_sunrise::WeightInfo for WeightInfo<T> {
+	/// Storage: Sunrise Pools (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:2 w:1)
+	/// Storage: Sunrise PoolsLeftOverBalance (r:1 w:0)
+	/// Storage: Sunrise Onboarding (r:1 w:0)
+	fn add_pool() -> Weight {
+		// Minimum execution time: 41_130 nanoseconds.
+		Weight::from_ref_time(58_312_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: Sunrise Pools (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:2 w:1)
+	/// Storage: Sunrise PoolsLeftOverBalance (r:1 w:0)
+	/// Storage: Sunrise Onboarding (r:1 w:0)
+	fn replenish_pool() -> Weight {
+		// Minimum execution time: 40_212 nanoseconds.
+		Weight::from_ref_time(56_947_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: Sunrise Pools (r:1 w:1)
+	fn update_pool() -> Weight {
+		// Minimum execution time: 15_083 nanoseconds.
+		Weight::from_ref_time(21_406_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Storage: Sunrise Pools (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:2 w:1)
+	/// Storage: Sunrise PoolsLeftOverBalance (r:1 w:0)
+	/// Storage: Sunrise Onboarding (r:1 w:0)
+	fn close_pool() -> Weight {
+		// Minimum execution time: 40_947 nanoseconds.
+		Weight::from_ref_time(57_830_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: Sunrise Onboarding (r:1 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:2 w:1)
+	/// Storage: Sunrise Pools (r:1 w:0)
+	/// Storage: Sunrise PoolsLeftOverBalance (r:1 w:0)
+	fn top_up_onboarding() -> Weight {
+		// Minimum execution time: 39_021 nanoseconds.
+		Weight::from_ref_time(55_114_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	/// Storage: Security CurrentBlockCount (r:1 w:0)
+	/// Storage: Sunrise EraRewardsExpiration (r:2 w:1)
+	/// Storage: Sunrise EraRewardedAccounts (r:b w:b)
+	/// Storage: Sunrise Rewards (r:b w:b)
+	/// Storage: Sunrise PoolsLeftOverBalance (r:1 w:1)
+	/// The range of component `b` is `[1, 500]`.
+	fn on_idle_rewards_expiration(b: u32, ) -> Weight {
+		// Minimum execution time: 17_903 nanoseconds.
+		Weight::from_ref_time(19_214_000)
+			// Standard Error: 11_000
+			.saturating_add(Weight::from_ref_time(22_108_000).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(b.into())))
+	}
+	/// Storage: Sunrise Referrers (r:1 w:1)
+	/// Storage: Sunrise RefereesCount (r:2 w:1)
+	fn register_referrer() -> Weight {
+		// Minimum execution time: 17_204 nanoseconds.
+		Weight::from_ref_time(24_518_000)
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	/// Storage: Sunrise ReferralBudget (r:2 w:1)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Storage: System Account (r:2 w:1)
+	/// Storage: Sunrise Pools (r:1 w:0)
+	/// Storage: Sunrise PoolsLeftOverBalance (r:1 w:0)
+	/// Storage: Sunrise Onboarding (r:1 w:0)
+	fn top_up_referral_budget() -> Weight {
+		// Minimum execution time: 39_688 nanoseconds.
+		Weight::from_ref_time(55_873_000)
+			.saturating_add(T::DbWeight::get().reads(6))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+}
@@ -125,4 +125,44 @@ impl<T: frame_system::Config> pallet_tidefi::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(4))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	/// Storage: Fees ActiveEra (r:1 w:0)
+	/// Proof: Fees ActiveEra (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: Sunrise Rewards (r:e w:e)
+	/// Proof: Sunrise Rewards (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: Security CurrentBlockCount (r:1 w:0)
+	/// Proof: Security CurrentBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `e` is `[1, 100]`.
+	fn claim_all_sunrise_rewards(e: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `683 + e * (84 ±0)`
+		//  Estimated: `8791 + e * (2559 ±0)`
+		// Minimum execution time: 64_218 nanoseconds.
+		Weight::from_parts(71_843_000, 8791)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_ref_time(3_214_000).saturating_mul(e.into()))
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(e.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(e.into())))
+			.saturating_add(Weight::from_proof_size(2559).saturating_mul(e.into()))
+	}
+	/// Storage: Fees ActiveEra (r:1 w:0)
+	/// Proof: Fees ActiveEra (max_values: Some(1), max_size: Some(32), added: 527, mode: MaxEncodedLen)
+	/// Storage: Security CurrentBlockCount (r:1 w:0)
+	/// Proof: Security CurrentBlockCount (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Sunrise ReferralRewards (r:1 w:1)
+	/// Proof: Sunrise ReferralRewards (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn claim_referral_rewards() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `683`
+		//  Estimated: `6188`
+		// Minimum execution time: 67_912 nanoseconds.
+		Weight::from_parts(72_518_000, 6188)
+			.saturating_add(T::DbWeight::get().reads(4))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
 }
@@ -49,36 +49,16 @@ pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> pallet_tidefi_stake::WeightInfo for WeightInfo<T> {
 	fn stake() -> Weight {
 		Weight::from_ref_time(106_968_000)
-			.saturating_add(T::DbWeight::get().reads(11))
-			.saturating_add(T::DbWeight::get().writes(6))
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(10))
 	}
 
 	fn unstake() -> Weight {
 		Weight::from_ref_time(87_151_000)
-			.saturating_add(T::DbWeight::get().reads(9 ))
+			.saturating_add(T::DbWeight::get().reads(11 ))
 			.saturating_add(T::DbWeight::get().writes(6 ))
 	}
 
-	fn on_idle_compound_finalize(b: u32, ) -> Weight {
-		Weight::from_ref_time(0)
-			// Standard Error: 32_000
-			.saturating_add(Weight::from_ref_time(30_468_000).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads(8_u64))
-			.saturating_add(T::DbWeight::get().reads(1_u64.saturating_mul(b.into())))
-			.saturating_add(T::DbWeight::get().writes(2_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64.saturating_mul(b.into())))
-	}
-
-	fn on_idle_compound(b: u32, ) -> Weight {
-		Weight::from_ref_time(0)
-			// Standard Error: 28_000
-			.saturating_add(Weight::from_ref_time(27_581_000).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads(10_u64))
-			.saturating_add(T::DbWeight::get().reads(2_u64.saturating_mul(b.into())))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
-			.saturating_add(T::DbWeight::get().writes(2_u64.saturating_mul(b.into())))
-	}
-
 	fn on_idle_unstake(b: u32, ) -> Weight {
 		Weight::from_ref_time(0)
 			// Standard Error: 39_000
@@ -88,4 +68,78 @@ impl<T: frame_system::Config> pallet_tidefi_stake::WeightInfo for WeightInfo<T>
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(b.into())))
 	}
+
+	fn on_idle_expiration(b: u32, ) -> Weight {
+		Weight::from_ref_time(0)
+			// Standard Error: 41_000
+			.saturating_add(Weight::from_ref_time(64_117_000).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(b.into())))
+	}
+
+	fn add_staking_period() -> Weight {
+		Weight::from_ref_time(19_412_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+
+	fn retire_staking_period() -> Weight {
+		Weight::from_ref_time(18_906_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+
+	fn update_staking_period_reward() -> Weight {
+		Weight::from_ref_time(17_530_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	fn set_staking_currency_meta() -> Weight {
+		Weight::from_ref_time(15_877_000)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	fn set_unstake_fee() -> Weight {
+		Weight::from_ref_time(13_204_000)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	fn unstake_partial() -> Weight {
+		Weight::from_ref_time(91_466_000)
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
+
+	fn add_to_stake() -> Weight {
+		Weight::from_ref_time(98_213_000)
+			.saturating_add(T::DbWeight::get().reads(11))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+
+	fn set_auto_renew() -> Weight {
+		Weight::from_ref_time(24_652_000)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	fn set_liquid_staking() -> Weight {
+		Weight::from_ref_time(27_318_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+
+	fn stake_liquid() -> Weight {
+		Weight::from_ref_time(101_742_000)
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+
+	fn unstake_liquid() -> Weight {
+		Weight::from_ref_time(104_589_000)
+			.saturating_add(T::DbWeight::get().reads(10))
+			.saturating_add(T::DbWeight::get().writes(8))
+	}
 }